@@ -6,6 +6,7 @@ BINDIR="${PREFIX}/bin"
 ICONDIR="${PREFIX}/share/icons/hicolor/256x256/apps"
 DESKTOPDIR="${PREFIX}/share/applications"
 PIXMAPDIR="${PREFIX}/share/pixmaps"
+POLKITDIR="/usr/share/polkit-1/actions"
 
 # Find the script's directory
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
@@ -77,6 +78,53 @@ DESKTOP
 chmod 644 "$DESKTOPDIR/procular.desktop"
 echo "Installed desktop entry to $DESKTOPDIR/procular.desktop"
 
+# Install polkit policy for privileged process actions (system-wide installs only)
+if [ "$EUID" -eq 0 ] && [ -d "$POLKITDIR" ]; then
+    KILL_PATH="$(type -P kill || echo /usr/bin/kill)"
+    TASKSET_PATH="$(type -P taskset || echo /usr/bin/taskset)"
+    RENICE_PATH="$(type -P renice || echo /usr/bin/renice)"
+    cat > "$POLKITDIR/org.procular.ProcessMonitor.policy" << POLICY
+<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE policyconfig PUBLIC "-//freedesktop//DTD PolicyKit Policy Configuration 1.0//EN"
+ "http://www.freedesktop.org/standards/PolicyKit/1/policyconfig.dtd">
+<policyconfig>
+  <vendor>Procular</vendor>
+  <action id="org.procular.ProcessMonitor.kill">
+    <description>Send a signal to another user's process</description>
+    <message>Authentication is required to signal a process owned by another user</message>
+    <defaults>
+      <allow_any>auth_admin</allow_any>
+      <allow_inactive>auth_admin</allow_inactive>
+      <allow_active>auth_admin_keep</allow_active>
+    </defaults>
+    <annotate key="org.freedesktop.policykit.exec.path">$KILL_PATH</annotate>
+  </action>
+  <action id="org.procular.ProcessMonitor.affinity">
+    <description>Change the CPU affinity of another user's process</description>
+    <message>Authentication is required to change the CPU affinity of a process</message>
+    <defaults>
+      <allow_any>auth_admin</allow_any>
+      <allow_inactive>auth_admin</allow_inactive>
+      <allow_active>auth_admin_keep</allow_active>
+    </defaults>
+    <annotate key="org.freedesktop.policykit.exec.path">$TASKSET_PATH</annotate>
+  </action>
+  <action id="org.procular.ProcessMonitor.priority">
+    <description>Change the priority of a process</description>
+    <message>Authentication is required to change the priority of a process</message>
+    <defaults>
+      <allow_any>auth_admin</allow_any>
+      <allow_inactive>auth_admin</allow_inactive>
+      <allow_active>auth_admin_keep</allow_active>
+    </defaults>
+    <annotate key="org.freedesktop.policykit.exec.path">$RENICE_PATH</annotate>
+  </action>
+</policyconfig>
+POLICY
+    chmod 644 "$POLKITDIR/org.procular.ProcessMonitor.policy"
+    echo "Installed polkit policy to $POLKITDIR/org.procular.ProcessMonitor.policy"
+fi
+
 # Update icon cache if available
 if command -v gtk-update-icon-cache &> /dev/null; then
     gtk-update-icon-cache -f -t "${PREFIX}/share/icons/hicolor" 2>/dev/null || true
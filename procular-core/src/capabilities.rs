@@ -0,0 +1,264 @@
+//! Detection of optional system capabilities and how to enable the missing ones
+//!
+//! Distribution packages can't set up polkit policies, capabilities or group
+//! membership for the user, so the GUI offers these checks on first run.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::process_actions::find_in_path;
+
+/// Where install.sh puts the polkit policy for privileged process actions
+const POLKIT_POLICY_PATH: &str = "/usr/share/polkit-1/actions/org.procular.ProcessMonitor.policy";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapabilityStatus {
+    Available,
+    Missing,
+    /// Not relevant on this system (e.g. no NVIDIA GPU)
+    NotNeeded,
+}
+
+/// Result of one capability check
+#[derive(Debug, Clone)]
+pub struct Capability {
+    pub title: &'static str,
+    pub status: CapabilityStatus,
+    /// What the capability gives, or why it is missing
+    pub detail: String,
+    /// Shell command(s) that enable a missing capability
+    pub fix: Option<String>,
+}
+
+impl Capability {
+    fn new(title: &'static str, status: CapabilityStatus, detail: impl Into<String>) -> Self {
+        Self {
+            title,
+            status,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+/// Run all capability checks
+pub fn check_capabilities() -> Vec<Capability> {
+    let mut checks = vec![
+        check_other_users_io(),
+        check_proc_visibility(),
+        check_pkexec(),
+        check_polkit_policy(),
+        check_nvml(),
+        check_xprop(),
+        check_cgroup_v2(),
+    ];
+    if cfg!(feature = "perf") {
+        checks.push(check_perf_events());
+    }
+    checks
+}
+
+fn is_root() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+/// Disk I/O counters of other users' processes need ptrace read access
+fn check_other_users_io() -> Capability {
+    const TITLE: &str = "Disk I/O of Other Users' Processes";
+    if is_root() {
+        return Capability::new(TITLE, CapabilityStatus::Available, "Running as root");
+    }
+    // PID 1 belongs to root on any normal system
+    match fs::read_to_string("/proc/1/io") {
+        Ok(_) => Capability::new(TITLE, CapabilityStatus::Available, "Disk I/O is shown for all processes"),
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+            let exe = std::env::current_exe()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|_| "/usr/local/bin/procular".to_string());
+            Capability::new(
+                TITLE,
+                CapabilityStatus::Missing,
+                "Disk columns stay empty for processes of other users and system services. \
+                 Granting CAP_SYS_PTRACE lets Procular read them, and also lets it inspect \
+                 any process on the system",
+            )
+            .with_fix(format!("sudo setcap cap_sys_ptrace+ep '{}'", exe))
+        }
+        Err(e) => Capability::new(TITLE, CapabilityStatus::Missing, e.to_string()),
+    }
+}
+
+/// /proc mounted with hidepid hides other users' processes unless we are in its gid group
+fn check_proc_visibility() -> Capability {
+    const TITLE: &str = "Visibility of Other Users' Processes";
+    let options = fs::read_to_string("/proc/self/mounts")
+        .ok()
+        .and_then(|mounts| {
+            mounts.lines().find_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                (fields.len() >= 4 && fields[1] == "/proc").then(|| fields[3].to_string())
+            })
+        })
+        .unwrap_or_default();
+
+    let option = |name: &str| {
+        options
+            .split(',')
+            .find_map(|opt| opt.strip_prefix(name).and_then(|v| v.strip_prefix('=')))
+            .map(str::to_string)
+    };
+    let hidden = option("hidepid").is_some_and(|v| !matches!(v.as_str(), "0" | "off"));
+    if !hidden || is_root() {
+        return Capability::new(TITLE, CapabilityStatus::NotNeeded, "All processes are visible in /proc");
+    }
+
+    let Some(gid) = option("gid").and_then(|gid| gid.parse::<u32>().ok()) else {
+        return Capability::new(
+            TITLE,
+            CapabilityStatus::Missing,
+            "/proc is mounted with hidepid and no exempt group; only your own processes are listed",
+        )
+        .with_fix("# Add gid=<group> to the proc line in /etc/fstab, then:\nsudo mount -o remount /proc");
+    };
+    if user_in_group(gid) {
+        return Capability::new(TITLE, CapabilityStatus::Available, "You are in the group exempt from hidepid");
+    }
+    let group = group_name(gid).unwrap_or_else(|| gid.to_string());
+    Capability::new(
+        TITLE,
+        CapabilityStatus::Missing,
+        format!(
+            "/proc is mounted with hidepid; members of the \"{}\" group can see all processes. \
+             Log out and back in after joining it",
+            group
+        ),
+    )
+    .with_fix(format!("sudo usermod -aG {} \"$USER\"", group))
+}
+
+fn check_pkexec() -> Capability {
+    const TITLE: &str = "Actions on Other Users' Processes";
+    if find_in_path("pkexec").is_some() {
+        Capability::new(
+            TITLE,
+            CapabilityStatus::Available,
+            "pkexec asks for authorization to end, renice or re-pin processes you don't own",
+        )
+    } else {
+        Capability::new(
+            TITLE,
+            CapabilityStatus::Missing,
+            "Without polkit's pkexec only your own processes can be ended, reniced or re-pinned",
+        )
+        .with_fix("# Debian/Ubuntu\nsudo apt install pkexec\n# Fedora / Arch\nsudo dnf install polkit\nsudo pacman -S polkit")
+    }
+}
+
+fn check_polkit_policy() -> Capability {
+    const TITLE: &str = "Polkit Policy";
+    if find_in_path("pkexec").is_none() {
+        return Capability::new(TITLE, CapabilityStatus::NotNeeded, "Requires pkexec");
+    }
+    if Path::new(POLKIT_POLICY_PATH).exists() {
+        Capability::new(TITLE, CapabilityStatus::Available, "Authorization prompts name the action")
+    } else {
+        Capability::new(
+            TITLE,
+            CapabilityStatus::Missing,
+            "Authorization prompts are generic and ask again for every action. \
+             A system-wide install.sh installs the policy",
+        )
+        .with_fix("sudo ./install.sh   # from the Procular release or source directory")
+    }
+}
+
+fn check_nvml() -> Capability {
+    const TITLE: &str = "NVIDIA GPU Monitoring";
+    if !Path::new("/proc/driver/nvidia/version").exists() {
+        return Capability::new(TITLE, CapabilityStatus::NotNeeded, "No NVIDIA driver loaded");
+    }
+    match nvml_wrapper::Nvml::init() {
+        Ok(_) => Capability::new(TITLE, CapabilityStatus::Available, "GPU usage is shown per process"),
+        Err(e) => Capability::new(
+            TITLE,
+            CapabilityStatus::Missing,
+            format!("The NVIDIA driver is loaded but NVML is unavailable: {}", e),
+        )
+        .with_fix("# Install the NVML library matching your driver (libnvidia-ml.so.1)\n\
+                   sudo apt install libnvidia-ml1   # or nvidia-utils on Arch/Fedora"),
+    }
+}
+
+fn check_xprop() -> Capability {
+    const TITLE: &str = "Focus-Based Automation";
+    if find_in_path("xprop").is_some() {
+        Capability::new(TITLE, CapabilityStatus::Available, "Boost While Focused can detect the active window")
+    } else {
+        Capability::new(
+            TITLE,
+            CapabilityStatus::Missing,
+            "Boost While Focused needs xprop to find the focused window",
+        )
+        .with_fix("# Debian/Ubuntu\nsudo apt install x11-utils\n# Fedora / Arch\nsudo dnf install xprop\nsudo pacman -S xorg-xprop")
+    }
+}
+
+fn check_cgroup_v2() -> Capability {
+    const TITLE: &str = "Startup Impact";
+    if Path::new("/sys/fs/cgroup/cgroup.controllers").exists() {
+        Capability::new(TITLE, CapabilityStatus::Available, "Session units are accounted through cgroup v2")
+    } else {
+        Capability::new(
+            TITLE,
+            CapabilityStatus::Missing,
+            "Startup Impact needs the unified cgroup v2 hierarchy, which this system does not use",
+        )
+        .with_fix("# Add to the kernel command line and reboot:\nsystemd.unified_cgroup_hierarchy=1")
+    }
+}
+
+fn check_perf_events() -> Capability {
+    const TITLE: &str = "Syscall Rates";
+    let paranoid = fs::read_to_string("/proc/sys/kernel/perf_event_paranoid")
+        .ok()
+        .and_then(|value| value.trim().parse::<i32>().ok());
+    if is_root() || paranoid.is_some_and(|level| level <= 1) {
+        Capability::new(TITLE, CapabilityStatus::Available, "Syscall tracepoints can be sampled")
+    } else {
+        Capability::new(
+            TITLE,
+            CapabilityStatus::Missing,
+            "Sampling syscalls needs kernel.perf_event_paranoid at 1 or lower (hardware counters work anyway)",
+        )
+        .with_fix("sudo sysctl kernel.perf_event_paranoid=1")
+    }
+}
+
+/// Whether the current user has `gid` as primary or supplementary group
+fn user_in_group(gid: u32) -> bool {
+    if unsafe { libc::getegid() } == gid {
+        return true;
+    }
+    let count = unsafe { libc::getgroups(0, std::ptr::null_mut()) };
+    if count <= 0 {
+        return false;
+    }
+    let mut groups = vec![0 as libc::gid_t; count as usize];
+    let count = unsafe { libc::getgroups(count, groups.as_mut_ptr()) };
+    count > 0 && groups[..count as usize].contains(&gid)
+}
+
+/// Look up a group name in /etc/group
+fn group_name(gid: u32) -> Option<String> {
+    let content = fs::read_to_string("/etc/group").ok()?;
+    content.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split(':').collect();
+        (fields.len() >= 3 && fields[2].parse::<u32>().ok() == Some(gid)).then(|| fields[0].to_string())
+    })
+}
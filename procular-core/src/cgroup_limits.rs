@@ -0,0 +1,195 @@
+//! CPU, memory and I/O limits and freezing for a process through a cgroup v2 systemd scope
+//!
+//! Limits are set as properties of a systemd scope unit, so systemd keeps the
+//! cgroup consistent. A process that already sits alone in a scope keeps it;
+//! otherwise it is moved into a new transient `procular-limit-<pid>.scope`.
+//! Freezing (`cgroup.freeze`) stops every task of that scope without sending
+//! SIGSTOP, so shells and job control do not notice.
+//! Processes below the user's systemd instance are handled by the user manager
+//! without authorization; others go through the system manager via pkexec.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::systemd_units::{is_user_managed, run_manager_command};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+pub const DEFAULT_IO_WEIGHT: u32 = 100;
+
+/// Limits of a cgroup; `None` means unlimited (or the default I/O weight)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// CPU time as percent of one CPU, so 200 allows two full CPUs
+    pub cpu_percent: Option<u32>,
+    pub memory_max: Option<u64>,
+    /// Relative I/O weight, 1-10000 (default 100)
+    pub io_weight: Option<u32>,
+}
+
+/// The cgroup a process runs in
+#[derive(Debug, Clone)]
+pub struct ProcessCgroup {
+    /// Path below the cgroup root, e.g. "/user.slice/user-1000.slice/.../app.scope"
+    pub path: String,
+    /// The scope or service the cgroup belongs to
+    pub unit: Option<String>,
+    /// Whether the user's systemd instance manages it
+    pub user_manager: bool,
+    pub limits: ResourceLimits,
+    /// The cgroup is frozen
+    pub frozen: bool,
+}
+
+impl ProcessCgroup {
+    fn dir(&self) -> PathBuf {
+        Path::new(CGROUP_ROOT).join(self.path.trim_start_matches('/'))
+    }
+
+    /// Whether `pid` is the only process in the cgroup, so its unit's limits affect nothing else
+    fn holds_only(&self, pid: u32) -> bool {
+        fs::read_to_string(self.dir().join("cgroup.procs"))
+            .map(|procs| procs.split_whitespace().eq([pid.to_string().as_str()]))
+            .unwrap_or(false)
+    }
+
+    /// Run systemctl or busctl against the manager that owns this cgroup
+    fn run_manager_command(&self, program: &str, args: Vec<String>, error_prefix: &str) -> io::Result<()> {
+        run_manager_command(self.user_manager, program, args, error_prefix)
+    }
+
+    /// Name of a scope holding only `pid`, creating one if the current unit is shared
+    fn own_scope(&self, pid: u32) -> io::Result<String> {
+        if let Some(unit) = self.unit.as_ref().filter(|unit| unit.ends_with(".scope") && self.holds_only(pid)) {
+            return Ok(unit.clone());
+        }
+        let unit = format!("procular-limit-{}.scope", pid);
+        let args = [
+            "call",
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+            "StartTransientUnit",
+            "ssa(sv)a(sa(sv))",
+            &unit,
+            "fail",
+            "1",
+            "PIDs",
+            "au",
+            "1",
+            &pid.to_string(),
+            "0",
+        ];
+        self.run_manager_command(
+            "busctl",
+            args.iter().map(|arg| arg.to_string()).collect(),
+            "Failed to create scope",
+        )?;
+        Ok(unit)
+    }
+}
+
+/// Path of the cgroup v2 a process belongs to, e.g. "/user.slice/..."
+pub fn cgroup_path(pid: u32) -> Option<String> {
+    let content = fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    content.lines().find_map(|line| line.strip_prefix("0::")).map(str::to_string)
+}
+
+/// Whether the cgroup at `path` (from `cgroup_path`) is frozen, per its cgroup.events
+pub fn is_cgroup_frozen(path: &str) -> bool {
+    fs::read_to_string(Path::new(CGROUP_ROOT).join(path.trim_start_matches('/')).join("cgroup.events"))
+        .map(|events| events.lines().any(|line| line == "frozen 1"))
+        .unwrap_or(false)
+}
+
+/// Look up the cgroup of a process and its current limits
+pub fn read_process_cgroup(pid: u32) -> io::Result<ProcessCgroup> {
+    if !Path::new(CGROUP_ROOT).join("cgroup.controllers").exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Resource limits need the cgroup v2 (unified) hierarchy",
+        ));
+    }
+    fs::metadata(format!("/proc/{}", pid))?;
+    let path = cgroup_path(pid)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Process is not in a cgroup v2 hierarchy"))?;
+    let unit = path
+        .rsplit('/')
+        .next()
+        .filter(|leaf| leaf.ends_with(".scope") || leaf.ends_with(".service"))
+        .map(str::to_string);
+    let user_manager = is_user_managed(&path);
+
+    let mut cgroup = ProcessCgroup {
+        frozen: is_cgroup_frozen(&path),
+        path,
+        unit,
+        user_manager,
+        limits: ResourceLimits::default(),
+    };
+    cgroup.limits = read_limits(&cgroup.dir());
+    Ok(cgroup)
+}
+
+fn read_limits(dir: &Path) -> ResourceLimits {
+    let read = |file: &str| fs::read_to_string(dir.join(file)).ok();
+    // cpu.max is "<quota> <period>" or "max <period>"
+    let cpu_percent = read("cpu.max").and_then(|content| {
+        let mut fields = content.split_whitespace();
+        let quota: u64 = fields.next()?.parse().ok()?;
+        let period: u64 = fields.next()?.parse().ok()?;
+        (period > 0).then(|| (quota * 100 / period) as u32)
+    });
+    let memory_max = read("memory.max").and_then(|content| content.trim().parse().ok());
+    // io.weight is "default <weight>" followed by per-device overrides
+    let io_weight = read("io.weight")
+        .and_then(|content| content.lines().next()?.strip_prefix("default ")?.trim().parse().ok())
+        .filter(|weight| *weight != DEFAULT_IO_WEIGHT);
+    ResourceLimits {
+        cpu_percent,
+        memory_max,
+        io_weight,
+    }
+}
+
+/// Apply `limits` to a process, moving it into its own scope first if needed.
+/// Returns the name of the scope holding the limits.
+pub fn apply_limits(pid: u32, limits: &ResourceLimits) -> io::Result<String> {
+    let cgroup = read_process_cgroup(pid)?;
+    let unit = cgroup.own_scope(pid)?;
+
+    let cpu_quota = limits.cpu_percent.map_or(String::new(), |percent| format!("{}%", percent));
+    let memory_max = limits.memory_max.map_or("infinity".to_string(), |bytes| bytes.to_string());
+    let io_weight = limits.io_weight.map_or(String::new(), |weight| weight.to_string());
+    cgroup.run_manager_command(
+        "systemctl",
+        vec![
+            "set-property".to_string(),
+            "--runtime".to_string(),
+            unit.clone(),
+            format!("CPUQuota={}", cpu_quota),
+            format!("MemoryMax={}", memory_max),
+            format!("IOWeight={}", io_weight),
+        ],
+        "Failed to set limits",
+    )?;
+    Ok(unit)
+}
+
+/// Freeze a process (with its threads) in its own scope; unlike SIGSTOP this is invisible to the process
+pub fn freeze_process(pid: u32) -> io::Result<()> {
+    let cgroup = read_process_cgroup(pid)?;
+    let unit = cgroup.own_scope(pid)?;
+    cgroup.run_manager_command("systemctl", vec!["freeze".to_string(), unit], "Failed to freeze process")
+}
+
+/// Thaw the frozen scope of a process
+pub fn thaw_process(pid: u32) -> io::Result<()> {
+    let cgroup = read_process_cgroup(pid)?;
+    let unit = cgroup
+        .unit
+        .clone()
+        .filter(|_| cgroup.frozen)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Process is not frozen"))?;
+    cgroup.run_manager_command("systemctl", vec!["thaw".to_string(), unit], "Failed to thaw process")
+}
@@ -0,0 +1,247 @@
+//! Network connections of a process
+//!
+//! Correlates the socket inodes in /proc/<pid>/fd with the socket tables in
+//! /proc/<pid>/net/{tcp,tcp6,udp,udp6} (read through the process so its network
+//! namespace is used).
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use crate::net_usage::{map_socket_inodes_to_pids, parse_socket_inode};
+
+/// Transport protocol of a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+}
+
+impl Protocol {
+    fn table(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Tcp6 => "tcp6",
+            Protocol::Udp => "udp",
+            Protocol::Udp6 => "udp6",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "TCP",
+            Protocol::Tcp6 => "TCPv6",
+            Protocol::Udp => "UDP",
+            Protocol::Udp6 => "UDPv6",
+        }
+    }
+
+    fn is_tcp(&self) -> bool {
+        matches!(self, Protocol::Tcp | Protocol::Tcp6)
+    }
+}
+
+/// A socket of a process with its endpoints
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub protocol: Protocol,
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+    pub state: &'static str,
+    pub inode: u32,
+}
+
+impl Connection {
+    /// Whether this is a listening TCP socket (or an unconnected UDP socket)
+    pub fn is_listening(&self) -> bool {
+        self.state == "LISTEN" || (!self.protocol.is_tcp() && self.remote.port() == 0)
+    }
+}
+
+/// Name of a TCP state number from include/net/tcp_states.h
+fn tcp_state_name(state: u8) -> &'static str {
+    match state {
+        0x01 => "ESTABLISHED",
+        0x02 => "SYN_SENT",
+        0x03 => "SYN_RECV",
+        0x04 => "FIN_WAIT1",
+        0x05 => "FIN_WAIT2",
+        0x06 => "TIME_WAIT",
+        0x07 => "CLOSE",
+        0x08 => "CLOSE_WAIT",
+        0x09 => "LAST_ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Parse a "ADDR:PORT" field of /proc/net/{tcp,udp}[6]
+/// Addresses are printed as 32-bit words in host byte order
+fn parse_address(field: &str) -> Option<SocketAddr> {
+    let (addr, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let ip = match addr.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(u32::from_str_radix(addr, 16).ok()?.to_ne_bytes())),
+        32 => {
+            let mut bytes = [0u8; 16];
+            for (i, chunk) in bytes.chunks_mut(4).enumerate() {
+                let word = u32::from_str_radix(&addr[i * 8..i * 8 + 8], 16).ok()?;
+                chunk.copy_from_slice(&word.to_ne_bytes());
+            }
+            let ip = Ipv6Addr::from(bytes);
+            // Show IPv4-mapped addresses (dual-stack sockets) as plain IPv4
+            ip.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(IpAddr::V6(ip))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/// Parse one line of a /proc/net socket table
+fn parse_socket_line(protocol: Protocol, line: &str) -> Option<Connection> {
+    // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 10 {
+        return None;
+    }
+    let state = u8::from_str_radix(fields[3], 16).ok()?;
+    let state = if protocol.is_tcp() {
+        tcp_state_name(state)
+    } else if state == 0x01 {
+        "CONNECTED"
+    } else {
+        "" // Unconnected UDP socket
+    };
+
+    Some(Connection {
+        protocol,
+        local: parse_address(fields[1])?,
+        remote: parse_address(fields[2])?,
+        state,
+        inode: fields[9].parse().ok()?,
+    })
+}
+
+/// Socket inodes held open by a process
+pub fn read_socket_inodes(pid: u32) -> io::Result<HashSet<u32>> {
+    Ok(fs::read_dir(format!("/proc/{}/fd", pid))?
+        .flatten()
+        .filter_map(|fd| fs::read_link(fd.path()).ok())
+        .filter_map(|target| parse_socket_inode(&target.to_string_lossy()))
+        .collect())
+}
+
+/// List the TCP and UDP sockets of a process
+pub fn read_connections(pid: u32) -> io::Result<Vec<Connection>> {
+    let inodes = read_socket_inodes(pid)?;
+    let mut connections = Vec::new();
+    if inodes.is_empty() {
+        return Ok(connections);
+    }
+
+    for protocol in [Protocol::Tcp, Protocol::Tcp6, Protocol::Udp, Protocol::Udp6] {
+        // The table may be missing (e.g. IPv6 disabled)
+        let Ok(content) = fs::read_to_string(format!("/proc/{}/net/{}", pid, protocol.table())) else {
+            continue;
+        };
+        connections.extend(
+            content
+                .lines()
+                .skip(1)
+                .filter_map(|line| parse_socket_line(protocol, line))
+                .filter(|conn| inodes.contains(&conn.inode)),
+        );
+    }
+
+    Ok(connections)
+}
+
+/// A socket a process is listening on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListeningPort {
+    pub protocol: Protocol,
+    pub local: SocketAddr,
+}
+
+impl ListeningPort {
+    /// Short description like "TCP *:8080"
+    pub fn describe(&self) -> String {
+        format!("{} {}", self.protocol.label(), format_endpoint(&self.local))
+    }
+}
+
+/// Finds listening sockets of all processes in our network namespace
+///
+/// Socket owners are found by scanning every /proc/<pid>/fd, so the mapping is
+/// cached and only rebuilt when a listening socket with an unknown owner appears.
+#[derive(Default)]
+pub struct ListeningPortTracker {
+    /// Socket inode -> owning PID (TGID)
+    inode_owner: HashMap<u32, u32>,
+}
+
+impl ListeningPortTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Listening sockets per PID
+    pub fn sample(&mut self) -> HashMap<u32, Vec<ListeningPort>> {
+        let mut listening: Vec<Connection> = Vec::new();
+        for protocol in [Protocol::Tcp, Protocol::Tcp6, Protocol::Udp, Protocol::Udp6] {
+            let Ok(content) = fs::read_to_string(format!("/proc/net/{}", protocol.table())) else {
+                continue;
+            };
+            listening.extend(
+                content
+                    .lines()
+                    .skip(1)
+                    .filter_map(|line| parse_socket_line(protocol, line))
+                    .filter(|conn| conn.is_listening() && conn.local.port() != 0 && conn.inode != 0),
+            );
+        }
+
+        if listening.iter().any(|conn| !self.inode_owner.contains_key(&conn.inode)) {
+            self.inode_owner = map_socket_inodes_to_pids();
+        }
+        let live: HashSet<u32> = listening.iter().map(|conn| conn.inode).collect();
+        self.inode_owner.retain(|inode, _| live.contains(inode));
+
+        let mut ports: HashMap<u32, Vec<ListeningPort>> = HashMap::new();
+        for conn in listening {
+            if let Some(&pid) = self.inode_owner.get(&conn.inode) {
+                let port = ListeningPort {
+                    protocol: conn.protocol,
+                    local: conn.local,
+                };
+                let entry = ports.entry(pid).or_default();
+                if !entry.contains(&port) {
+                    entry.push(port);
+                }
+            }
+        }
+        for entry in ports.values_mut() {
+            entry.sort_by_key(|port| (port.local.port(), port.protocol.label()));
+        }
+        ports
+    }
+}
+
+/// Format an endpoint, showing "*" for unspecified addresses and ports
+pub fn format_endpoint(addr: &SocketAddr) -> String {
+    let host = if addr.ip().is_unspecified() {
+        "*".to_string()
+    } else {
+        match addr.ip() {
+            IpAddr::V4(ip) => ip.to_string(),
+            IpAddr::V6(ip) => format!("[{}]", ip),
+        }
+    };
+    let port = if addr.port() == 0 { "*".to_string() } else { addr.port().to_string() };
+    format!("{}:{}", host, port)
+}
@@ -0,0 +1,98 @@
+//! Per-process user and kernel CPU time from /proc/<pid>/stat
+//!
+//! User time is spent running the program's own code, system time in the kernel on its
+//! behalf (system calls, page faults). A process spending most of its CPU in the kernel
+//! is usually doing many small reads or writes, polling, or fighting over memory.
+
+use std::collections::HashMap;
+use std::fs;
+use std::time::Instant;
+
+use crate::monitor::ProcessKey;
+
+/// Cumulative CPU time of a process and all its threads since it started
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuTimes {
+    pub user_secs: f64,
+    pub system_secs: f64,
+}
+
+impl CpuTimes {
+    pub fn total_secs(&self) -> f64 {
+        self.user_secs + self.system_secs
+    }
+}
+
+/// CPU use between two refreshes, in percent of the whole machine like `ProcessInfo::cpu_percent`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuSplit {
+    pub user_percent: f64,
+    pub system_percent: f64,
+}
+
+/// CPU time like top's TIME+ column: "4:07.31", or "27:03:10" from an hour on
+pub fn format_cpu_time(secs: f64) -> String {
+    let whole = secs.max(0.0) as u64;
+    if whole >= 3600 {
+        format!("{}:{:02}:{:02}", whole / 3600, (whole % 3600) / 60, whole % 60)
+    } else {
+        format!("{}:{:05.2}", whole / 60, secs.max(0.0) - (whole / 60 * 60) as f64)
+    }
+}
+
+/// User and system time of a process (utime and stime, fields 14 and 15 of /proc/<pid>/stat)
+pub fn read_cpu_times(pid: u32) -> Option<CpuTimes> {
+    let content = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let comm_end = content.rfind(')')?;
+    let mut fields = content[comm_end + 1..].split_whitespace().skip(11);
+    let utime: u64 = fields.next()?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as f64;
+    Some(CpuTimes {
+        user_secs: utime as f64 / ticks_per_sec,
+        system_secs: stime as f64 / ticks_per_sec,
+    })
+}
+
+/// Turns CPU time counters into user and system percentages between refreshes
+pub struct CpuTimeTracker {
+    /// Times from the previous refresh
+    previous: HashMap<ProcessKey, (CpuTimes, Instant)>,
+    /// Times read during the current refresh
+    current: HashMap<ProcessKey, (CpuTimes, Instant)>,
+    cpu_count: usize,
+}
+
+impl CpuTimeTracker {
+    pub fn new(cpu_count: usize) -> Self {
+        Self {
+            previous: HashMap::new(),
+            current: HashMap::new(),
+            cpu_count: cpu_count.max(1),
+        }
+    }
+
+    /// Cumulative times of a process, and its split since the previous refresh
+    /// (None on its first sample)
+    pub fn sample(&mut self, key: ProcessKey) -> (Option<CpuTimes>, Option<CpuSplit>) {
+        let Some(times) = read_cpu_times(key.pid) else {
+            return (None, None);
+        };
+        let now = Instant::now();
+        self.current.insert(key, (times, now));
+        let split = self.previous.get(&key).map(|(last, last_time)| {
+            let elapsed = now.duration_since(*last_time).as_secs_f64().max(0.001);
+            let percent = |secs: f64| secs.max(0.0) * 100.0 / elapsed / self.cpu_count as f64;
+            CpuSplit {
+                user_percent: percent(times.user_secs - last.user_secs),
+                system_percent: percent(times.system_secs - last.system_secs),
+            }
+        });
+        (Some(times), split)
+    }
+
+    /// End a refresh; processes not sampled in it are forgotten
+    pub fn finish_refresh(&mut self) {
+        self.previous = std::mem::take(&mut self.current);
+    }
+}
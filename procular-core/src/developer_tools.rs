@@ -0,0 +1,180 @@
+//! Opening a terminal that runs a tracer or debugger attached to a process
+//!
+//! Attaching needs ptrace access: without root it only works when Yama's ptrace_scope is 0 and
+//! the process belongs to us, so otherwise the tracer runs through sudo in the terminal, where
+//! the password can be typed.
+
+use std::fs;
+use std::io;
+use std::process::Command;
+
+use crate::process_actions::find_in_path;
+
+/// Terminals tried in order when none is configured; `$TERMINAL` comes first
+const KNOWN_TERMINALS: &[&str] = &[
+    "xdg-terminal-exec",
+    "kgx",
+    "ptyxis",
+    "gnome-terminal",
+    "konsole",
+    "xfce4-terminal",
+    "alacritty",
+    "kitty",
+    "foot",
+    "wezterm",
+    "xterm",
+];
+
+/// Debugger command used until one is set in Preferences; `{pid}` is replaced by the process ID
+pub const DEFAULT_DEBUGGER_COMMAND: &str = "gdb -p {pid}";
+
+/// Program attached to a process to show what it is doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tracer {
+    /// System calls, with every thread followed
+    Strace,
+    /// Calls into shared libraries
+    Ltrace,
+}
+
+impl Tracer {
+    pub fn all() -> &'static [Tracer] {
+        &[Tracer::Strace, Tracer::Ltrace]
+    }
+
+    /// Program name, also used as the settings key
+    pub fn key(&self) -> &'static str {
+        match self {
+            Tracer::Strace => "strace",
+            Tracer::Ltrace => "ltrace",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|tracer| tracer.key() == key)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Tracer::Strace => "strace (system calls)",
+            Tracer::Ltrace => "ltrace (library calls)",
+        }
+    }
+
+    fn arguments(&self, pid: u32) -> Vec<String> {
+        let pid = pid.to_string();
+        match self {
+            Tracer::Strace => vec!["strace".into(), "-f".into(), "-p".into(), pid],
+            Tracer::Ltrace => vec!["ltrace".into(), "-p".into(), pid],
+        }
+    }
+}
+
+/// Whether attaching to `pid` needs root: Yama allows unprivileged attaching only with
+/// ptrace_scope 0, and never to another user's process
+fn needs_root(pid: u32) -> bool {
+    if unsafe { libc::geteuid() } == 0 {
+        return false;
+    }
+    let ptrace_scope = fs::read_to_string("/proc/sys/kernel/yama/ptrace_scope")
+        .ok()
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .unwrap_or(0);
+    let own_process = fs::read_to_string(format!("/proc/{}/status", pid))
+        .ok()
+        .and_then(|status| {
+            let uids = status.lines().find_map(|line| line.strip_prefix("Uid:"))?;
+            uids.split_whitespace().next()?.parse::<u32>().ok()
+        })
+        .is_some_and(|uid| uid == unsafe { libc::getuid() });
+    ptrace_scope > 0 || !own_process
+}
+
+/// `configured` if set, else `$TERMINAL` or the first known terminal that is installed
+pub fn find_terminal(configured: &str) -> Option<String> {
+    let configured = configured.trim();
+    if !configured.is_empty() {
+        return Some(configured.to_string());
+    }
+    std::env::var("TERMINAL")
+        .ok()
+        .filter(|terminal| find_in_path(terminal).is_some())
+        .or_else(|| {
+            KNOWN_TERMINALS
+                .iter()
+                .find(|terminal| find_in_path(terminal).is_some())
+                .map(|terminal| terminal.to_string())
+        })
+}
+
+/// Arguments that make `terminal` run a command given after them
+fn terminal_arguments(terminal: &str) -> &'static [&'static str] {
+    let name = terminal.rsplit('/').next().unwrap_or(terminal);
+    match name {
+        "xdg-terminal-exec" | "kitty" | "foot" => &[],
+        "gnome-terminal" | "kgx" | "ptyxis" => &["--"],
+        "wezterm" => &["start", "--"],
+        "xfce4-terminal" => &["-x"],
+        _ => &["-e"],
+    }
+}
+
+/// Start `terminal` (see `find_terminal`) running `command`; the terminal stays open after the
+/// command ends so its last output and errors can be read
+pub fn run_in_terminal(terminal: &str, command: &[String]) -> io::Result<()> {
+    let Some(terminal) = find_terminal(terminal) else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "No terminal emulator found; set one in Preferences",
+        ));
+    };
+    // The terminal may be configured with arguments of its own, such as "konsole --hold"
+    let mut words = terminal.split_whitespace();
+    let program = words.next().unwrap_or_default();
+    // Quoted for sh, which runs the command and then waits for Enter
+    let quoted: Vec<String> = command.iter().map(|word| format!("'{}'", word.replace('\'', "'\\''"))).collect();
+    let script = format!("{}; printf '\\nPress Enter to close '; read _", quoted.join(" "));
+    let mut child = Command::new(program)
+        .args(words)
+        .args(terminal_arguments(program))
+        .args(["sh", "-c", &script])
+        .spawn()?;
+    // Reaped in the background so the terminal does not linger as a zombie
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Open a terminal running `tracer` attached to `pid`, through sudo when that needs root
+pub fn trace_in_terminal(terminal: &str, tracer: Tracer, pid: u32) -> io::Result<()> {
+    if find_in_path(tracer.key()).is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not installed", tracer.key()),
+        ));
+    }
+    let mut command = tracer.arguments(pid);
+    if needs_root(pid) {
+        command.insert(0, "sudo".to_string());
+    }
+    run_in_terminal(terminal, &command)
+}
+
+/// Open a terminal running the debugger `template` (such as `lldb -p {pid}`) with `{pid}` replaced,
+/// through sudo when attaching needs root
+pub fn debug_in_terminal(terminal: &str, template: &str, pid: u32) -> io::Result<()> {
+    let template = if template.trim().is_empty() { DEFAULT_DEBUGGER_COMMAND } else { template };
+    let mut command: Vec<String> = template
+        .split_whitespace()
+        .map(|word| word.replace("{pid}", &pid.to_string()))
+        .collect();
+    if find_in_path(&command[0]).is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not installed", command[0]),
+        ));
+    }
+    if needs_root(pid) {
+        command.insert(0, "sudo".to_string());
+    }
+    run_in_terminal(terminal, &command)
+}
@@ -0,0 +1,373 @@
+//! "Why is my system slow?" diagnosis combining pressure stall information,
+//! CPU time accounting, swap activity, throttling and the busiest processes
+//!
+//! Rates need two snapshots taken a moment apart; `diagnose` compares them and
+//! returns findings ranked from most to least likely cause.
+
+use std::fs;
+use std::time::Instant;
+
+use crate::kernel_wait::read_wchan;
+use crate::monitor::{format_bytes, format_rate, ProcessInfo};
+
+/// Processes listed per finding
+const TOP_PROCESSES: usize = 5;
+
+/// Pressure stall information for one resource, in percent of wall time over 10 seconds
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pressure {
+    /// Some tasks were stalled
+    pub some: f64,
+    /// All non-idle tasks were stalled (not reported for CPU on older kernels)
+    pub full: f64,
+}
+
+/// Counters read at one point in time
+#[derive(Debug, Clone)]
+pub struct SystemSnapshot {
+    taken: Instant,
+    /// Aggregate jiffies from the "cpu" line of /proc/stat: (busy, iowait, total)
+    cpu_busy: u64,
+    cpu_iowait: u64,
+    cpu_total: u64,
+    /// Pages swapped in and out since boot
+    swap_in: u64,
+    swap_out: u64,
+    /// Sum of the per-core thermal throttle counters (Intel only)
+    throttle_count: Option<u64>,
+    /// None when /proc/pressure is missing (kernel without PSI)
+    pub cpu_pressure: Option<Pressure>,
+    pub memory_pressure: Option<Pressure>,
+    pub io_pressure: Option<Pressure>,
+    pub mem_total: u64,
+    pub mem_available: u64,
+    pub swap_total: u64,
+    pub swap_free: u64,
+    /// Average current frequency as a share of the maximum across cores
+    pub frequency_ratio: Option<f64>,
+}
+
+/// Read the current counters
+pub fn take_snapshot() -> SystemSnapshot {
+    let (cpu_busy, cpu_iowait, cpu_total) = read_cpu_times().unwrap_or_default();
+    let vmstat = fs::read_to_string("/proc/vmstat").unwrap_or_default();
+    let meminfo = fs::read_to_string("/proc/meminfo").unwrap_or_default();
+    let kib = |key: &str| read_field(&meminfo, key).unwrap_or(0) * 1024;
+
+    SystemSnapshot {
+        taken: Instant::now(),
+        cpu_busy,
+        cpu_iowait,
+        cpu_total,
+        swap_in: read_field(&vmstat, "pswpin").unwrap_or(0),
+        swap_out: read_field(&vmstat, "pswpout").unwrap_or(0),
+        throttle_count: read_throttle_count(),
+        cpu_pressure: read_pressure("cpu"),
+        memory_pressure: read_pressure("memory"),
+        io_pressure: read_pressure("io"),
+        mem_total: kib("MemTotal:"),
+        mem_available: kib("MemAvailable:"),
+        swap_total: kib("SwapTotal:"),
+        swap_free: kib("SwapFree:"),
+        frequency_ratio: read_frequency_ratio(),
+    }
+}
+
+/// Value of the first line starting with `key` in a "key value" file
+fn read_field(content: &str, key: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix(key))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|value| value.parse().ok())
+}
+
+/// (busy, iowait, total) jiffies from /proc/stat
+fn read_cpu_times() -> Option<(u64, u64, u64)> {
+    let stat = fs::read_to_string("/proc/stat").ok()?;
+    let line = stat.lines().find(|line| line.starts_with("cpu "))?;
+    // user nice system idle iowait irq softirq steal (guest time is included in user)
+    let values: Vec<u64> = line.split_whitespace().skip(1).take(8).filter_map(|v| v.parse().ok()).collect();
+    if values.len() < 5 {
+        return None;
+    }
+    let total: u64 = values.iter().sum();
+    let idle = values[3] + values[4];
+    Some((total - idle, values[4], total))
+}
+
+/// Parse /proc/pressure/<resource>, e.g. "some avg10=1.23 avg60=..."
+fn read_pressure(resource: &str) -> Option<Pressure> {
+    let content = fs::read_to_string(format!("/proc/pressure/{}", resource)).ok()?;
+    let avg10 = |kind: &str| {
+        content
+            .lines()
+            .find(|line| line.starts_with(kind))
+            .and_then(|line| line.split_whitespace().find_map(|field| field.strip_prefix("avg10=")))
+            .and_then(|value| value.parse::<f64>().ok())
+    };
+    Some(Pressure {
+        some: avg10("some")?,
+        full: avg10("full").unwrap_or(0.0),
+    })
+}
+
+fn read_throttle_count() -> Option<u64> {
+    let entries = fs::read_dir("/sys/devices/system/cpu").ok()?;
+    let counts: Vec<u64> = entries
+        .flatten()
+        .filter_map(|entry| fs::read_to_string(entry.path().join("thermal_throttle/core_throttle_count")).ok())
+        .filter_map(|count| count.trim().parse().ok())
+        .collect();
+    (!counts.is_empty()).then(|| counts.iter().sum())
+}
+
+fn read_frequency_ratio() -> Option<f64> {
+    let entries = fs::read_dir("/sys/devices/system/cpu").ok()?;
+    let ratios: Vec<f64> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let read = |file: &str| -> Option<f64> {
+                fs::read_to_string(entry.path().join("cpufreq").join(file)).ok()?.trim().parse().ok()
+            };
+            let max = read("cpuinfo_max_freq")?;
+            (max > 0.0).then_some(read("scaling_cur_freq")? / max)
+        })
+        .collect();
+    (!ratios.is_empty()).then(|| ratios.iter().sum::<f64>() / ratios.len() as f64)
+}
+
+/// How strongly a finding points at the cause of a slowdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// A process worth looking at for a finding
+#[derive(Debug, Clone)]
+pub struct Suspect {
+    pub pid: u32,
+    pub name: String,
+    /// What it contributes, e.g. "45.0% CPU"
+    pub detail: String,
+}
+
+/// One diagnosed cause
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    pub title: String,
+    pub explanation: String,
+    pub suspects: Vec<Suspect>,
+    /// Ranking score; higher means more likely the cause
+    score: f64,
+}
+
+/// Rates over the interval between two snapshots
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rates {
+    /// Busy share of all CPUs in percent
+    pub cpu_busy_percent: f64,
+    /// Share of CPU time idle while waiting for I/O, in percent
+    pub iowait_percent: f64,
+    /// Pages swapped in/out per second
+    pub swap_in_per_sec: f64,
+    pub swap_out_per_sec: f64,
+    /// Thermal throttle events during the interval
+    pub throttle_events: Option<u64>,
+}
+
+impl Rates {
+    pub fn between(before: &SystemSnapshot, after: &SystemSnapshot) -> Self {
+        let seconds = after.taken.duration_since(before.taken).as_secs_f64().max(0.001);
+        let total = after.cpu_total.saturating_sub(before.cpu_total).max(1) as f64;
+        Self {
+            cpu_busy_percent: after.cpu_busy.saturating_sub(before.cpu_busy) as f64 * 100.0 / total,
+            iowait_percent: after.cpu_iowait.saturating_sub(before.cpu_iowait) as f64 * 100.0 / total,
+            swap_in_per_sec: after.swap_in.saturating_sub(before.swap_in) as f64 / seconds,
+            swap_out_per_sec: after.swap_out.saturating_sub(before.swap_out) as f64 / seconds,
+            throttle_events: before
+                .throttle_count
+                .zip(after.throttle_count)
+                .map(|(before, after)| after.saturating_sub(before)),
+        }
+    }
+}
+
+/// Rank the likely causes of a slowdown. `processes` is the monitor's latest
+/// refresh (disk values are bytes since the previous refresh).
+pub fn diagnose(before: &SystemSnapshot, after: &SystemSnapshot, processes: &[ProcessInfo]) -> Vec<Finding> {
+    let rates = Rates::between(before, after);
+    let mut findings = Vec::new();
+
+    // CPU saturation
+    let cpu_some = after.cpu_pressure.map_or(0.0, |p| p.some);
+    if cpu_some >= 10.0 || rates.cpu_busy_percent >= 85.0 {
+        findings.push(Finding {
+            severity: if cpu_some >= 40.0 || rates.cpu_busy_percent >= 95.0 {
+                Severity::Critical
+            } else {
+                Severity::Warning
+            },
+            title: "CPU is saturated".to_string(),
+            explanation: format!(
+                "CPUs are {:.0}% busy and runnable tasks waited for a CPU {:.1}% of the time. \
+                 Lower the priority of or end the busiest processes.",
+                rates.cpu_busy_percent, cpu_some
+            ),
+            suspects: top_suspects(processes, |p| p.total_cpu() as f64, |p| format!("{:.1}% CPU", p.total_cpu())),
+            score: cpu_some.max(rates.cpu_busy_percent - 50.0),
+        });
+    }
+
+    // Memory pressure and swapping
+    let memory = after.memory_pressure.unwrap_or_default();
+    let available_percent = if after.mem_total > 0 {
+        after.mem_available as f64 * 100.0 / after.mem_total as f64
+    } else {
+        100.0
+    };
+    let swap_rate = rates.swap_in_per_sec + rates.swap_out_per_sec;
+    if memory.some >= 5.0 || available_percent < 10.0 || swap_rate >= 100.0 {
+        findings.push(Finding {
+            severity: if memory.full >= 10.0 || available_percent < 5.0 || swap_rate >= 1000.0 {
+                Severity::Critical
+            } else {
+                Severity::Warning
+            },
+            title: "Memory is running low".to_string(),
+            explanation: format!(
+                "{} of {} is available ({:.0}%), {:.0} pages/s are being swapped and tasks \
+                 stalled on memory {:.1}% of the time. Close the largest programs.",
+                format_bytes(after.mem_available),
+                format_bytes(after.mem_total),
+                available_percent,
+                swap_rate,
+                memory.some
+            ),
+            suspects: top_suspects(
+                processes,
+                |p| p.total_memory() as f64,
+                |p| format_bytes(p.total_memory()),
+            ),
+            score: (memory.some * 2.0).max(100.0 - available_percent * 5.0).max(swap_rate / 10.0),
+        });
+    }
+
+    // Disk I/O
+    let io = after.io_pressure.unwrap_or_default();
+    if io.some >= 10.0 || rates.iowait_percent >= 10.0 {
+        findings.push(Finding {
+            severity: if io.full >= 20.0 || rates.iowait_percent >= 30.0 {
+                Severity::Critical
+            } else {
+                Severity::Warning
+            },
+            title: "Disk I/O is the bottleneck".to_string(),
+            explanation: format!(
+                "Tasks stalled on I/O {:.1}% of the time and CPUs waited for disks {:.0}% of the time. \
+                 The processes below read or write the most.",
+                io.some, rates.iowait_percent
+            ),
+            suspects: top_suspects(
+                processes,
+                |p| p.total_disk_rate() as f64,
+                |p| format!("{} read, {} written", format_rate(p.total_disk_read_rate()), format_rate(p.total_disk_write_rate())),
+            ),
+            score: io.some.max(rates.iowait_percent * 2.0),
+        });
+    }
+
+    // Uninterruptible sleep
+    let blocked = read_blocked_processes();
+    if !blocked.is_empty() {
+        findings.push(Finding {
+            severity: if blocked.len() >= 5 { Severity::Warning } else { Severity::Info },
+            title: format!(
+                "{} process{} stuck in uninterruptible sleep",
+                blocked.len(),
+                if blocked.len() == 1 { "" } else { "es" }
+            ),
+            explanation: "Processes in D state wait for the kernel, usually for slow or \
+                          unresponsive storage (including network filesystems), and cannot be \
+                          ended until it answers."
+                .to_string(),
+            suspects: blocked.into_iter().take(TOP_PROCESSES).collect(),
+            score: 20.0,
+        });
+    }
+
+    // Throttling
+    let throttled = rates.throttle_events.is_some_and(|events| events > 0);
+    let slow_clock = after.frequency_ratio.is_some_and(|ratio| ratio < 0.6) && rates.cpu_busy_percent >= 50.0;
+    if throttled || slow_clock {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            title: "CPU is being throttled".to_string(),
+            explanation: match (throttled, after.frequency_ratio) {
+                (true, _) => "The CPU hit its thermal limit and slowed down. Check cooling and \
+                              dust, or use a less aggressive power profile."
+                    .to_string(),
+                (false, Some(ratio)) => format!(
+                    "CPUs are busy but run at {:.0}% of their maximum clock. A power-saving \
+                     profile or thermal limits may be holding them back.",
+                    ratio * 100.0
+                ),
+                (false, None) => String::new(),
+            },
+            suspects: Vec::new(),
+            score: 30.0,
+        });
+    }
+
+    findings.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then(b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    findings
+}
+
+/// The busiest processes by `metric`, skipping those that contribute nothing
+fn top_suspects(
+    processes: &[ProcessInfo],
+    metric: impl Fn(&ProcessInfo) -> f64,
+    detail: impl Fn(&ProcessInfo) -> String,
+) -> Vec<Suspect> {
+    let mut ranked: Vec<&ProcessInfo> = processes.iter().filter(|p| metric(p) > 0.0).collect();
+    ranked.sort_by(|a, b| metric(b).partial_cmp(&metric(a)).unwrap_or(std::cmp::Ordering::Equal));
+    ranked
+        .into_iter()
+        .take(TOP_PROCESSES)
+        .map(|p| Suspect {
+            pid: p.pid,
+            name: p.name.clone(),
+            detail: detail(p),
+        })
+        .collect()
+}
+
+/// Processes in state D, with the kernel function they wait in when visible
+fn read_blocked_processes() -> Vec<Suspect> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_string_lossy().parse::<u32>().ok())
+        .filter_map(|pid| {
+            let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+            let name = stat.get(stat.find('(')? + 1..stat.rfind(')')?)?.to_string();
+            let state = stat[stat.rfind(')')? + 1..].split_whitespace().next()?;
+            (state == "D").then(|| Suspect {
+                pid,
+                name,
+                detail: read_wchan(pid).map_or_else(|| "Waiting in the kernel".to_string(), |wchan| {
+                    format!("Waiting in {}", wchan)
+                }),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,196 @@
+//! Block device activity from /proc/diskstats
+//!
+//! Each line holds cumulative counters after the major, minor and device name: reads completed,
+//! reads merged, sectors read, milliseconds reading, writes completed, writes merged, sectors
+//! written, milliseconds writing, I/Os in progress, milliseconds doing I/O and weighted
+//! milliseconds (see the kernel's iostats documentation). Sectors are always 512 bytes.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+
+const SECTOR_SIZE: f64 = 512.0;
+
+/// Cumulative counters of one device
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskCounters {
+    pub reads: u64,
+    pub read_sectors: u64,
+    pub read_ms: u64,
+    pub writes: u64,
+    pub write_sectors: u64,
+    pub write_ms: u64,
+    /// Time the device had I/O in flight
+    pub busy_ms: u64,
+}
+
+/// Parse one /proc/diskstats line into the device name and its counters
+fn parse_line(line: &str) -> Option<(String, DiskCounters)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let name = fields.get(2)?;
+    let number = |index: usize| fields.get(index).and_then(|field| field.parse::<u64>().ok());
+    Some((
+        name.to_string(),
+        DiskCounters {
+            reads: number(3)?,
+            read_sectors: number(5)?,
+            read_ms: number(6)?,
+            writes: number(7)?,
+            write_sectors: number(9)?,
+            write_ms: number(10)?,
+            busy_ms: number(12)?,
+        },
+    ))
+}
+
+/// Whole disks are listed in /sys/block, their partitions are not. Loop and RAM disks are
+/// left out, as are devices that have never done any I/O (empty card readers and drives)
+fn is_shown(name: &str, counters: &DiskCounters) -> bool {
+    Path::new("/sys/block").join(name).exists()
+        && !name.starts_with("loop")
+        && !name.starts_with("ram")
+        && counters.reads + counters.writes > 0
+}
+
+/// Counters of each shown disk, in /proc/diskstats order
+pub fn read_disk_counters() -> Vec<(String, DiskCounters)> {
+    fs::read_to_string("/proc/diskstats")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(parse_line)
+        .filter(|(name, counters)| is_shown(name, counters))
+        .collect()
+}
+
+/// Model of a drive, or the mapped name of a device-mapper device such as an LVM volume
+pub fn disk_description(name: &str) -> Option<String> {
+    let block = Path::new("/sys/block").join(name);
+    [block.join("device/model"), block.join("dm/name")]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .map(|text| text.trim().to_string())
+        .find(|text| !text.is_empty())
+}
+
+/// Activity of one disk between two readings
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskActivity {
+    /// Kernel name such as `sda` or `nvme0n1`
+    pub name: String,
+    pub description: Option<String>,
+    /// Bytes per second
+    pub read_rate: f64,
+    pub write_rate: f64,
+    /// Completed operations per second
+    pub read_iops: f64,
+    pub write_iops: f64,
+    /// Share of the time the device was busy, 0-100
+    pub utilization: f64,
+    /// Mean time a completed read or write took, in milliseconds; 0 without I/O
+    pub latency_ms: f64,
+}
+
+impl DiskActivity {
+    fn between(
+        name: &str,
+        description: Option<String>,
+        before: &DiskCounters,
+        after: &DiskCounters,
+        elapsed: f64,
+    ) -> Self {
+        let delta = |after: u64, before: u64| after.saturating_sub(before) as f64;
+        let operations = delta(after.reads, before.reads) + delta(after.writes, before.writes);
+        let io_ms = delta(after.read_ms, before.read_ms) + delta(after.write_ms, before.write_ms);
+        Self {
+            name: name.to_string(),
+            description,
+            read_rate: delta(after.read_sectors, before.read_sectors) * SECTOR_SIZE / elapsed,
+            write_rate: delta(after.write_sectors, before.write_sectors) * SECTOR_SIZE / elapsed,
+            read_iops: delta(after.reads, before.reads) / elapsed,
+            write_iops: delta(after.writes, before.writes) / elapsed,
+            utilization: (delta(after.busy_ms, before.busy_ms) / (elapsed * 10.0)).min(100.0),
+            latency_ms: if operations > 0.0 { io_ms / operations } else { 0.0 },
+        }
+    }
+}
+
+/// Turns successive readings of /proc/diskstats into rates
+#[derive(Debug, Default)]
+pub struct DiskTracker {
+    last: HashMap<String, DiskCounters>,
+    /// Descriptions read once per device
+    descriptions: HashMap<String, Option<String>>,
+}
+
+impl DiskTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Activity of each disk since the previous call, `elapsed` seconds ago; a disk shows up
+    /// from its second reading
+    pub fn sample(&mut self, elapsed: f64) -> Vec<DiskActivity> {
+        let counters = read_disk_counters();
+        let activity = counters
+            .iter()
+            .filter_map(|(name, after)| {
+                let before = self.last.get(name)?;
+                let description = self
+                    .descriptions
+                    .entry(name.clone())
+                    .or_insert_with(|| disk_description(name))
+                    .clone();
+                Some(DiskActivity::between(name, description, before, after, elapsed.max(0.001)))
+            })
+            .collect();
+        self.last = counters.into_iter().collect();
+        activity
+    }
+}
+
+/// Recorded activity of one disk, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct DiskHistory {
+    pub read_rate: VecDeque<f64>,
+    pub write_rate: VecDeque<f64>,
+    pub read_iops: VecDeque<f64>,
+    pub write_iops: VecDeque<f64>,
+    pub utilization: VecDeque<f64>,
+    pub latency_ms: VecDeque<f64>,
+}
+
+impl DiskHistory {
+    fn series(&mut self) -> [&mut VecDeque<f64>; 6] {
+        [
+            &mut self.read_rate,
+            &mut self.write_rate,
+            &mut self.read_iops,
+            &mut self.write_iops,
+            &mut self.utilization,
+            &mut self.latency_ms,
+        ]
+    }
+
+    pub fn add_sample(&mut self, activity: &DiskActivity, max_samples: usize) {
+        let values = [
+            activity.read_rate,
+            activity.write_rate,
+            activity.read_iops,
+            activity.write_iops,
+            activity.utilization,
+            activity.latency_ms,
+        ];
+        for (history, value) in self.series().into_iter().zip(values) {
+            history.push_back(value);
+        }
+        self.trim_to(max_samples);
+    }
+
+    pub fn trim_to(&mut self, max_samples: usize) {
+        for history in self.series() {
+            while history.len() > max_samples {
+                history.pop_front();
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+//! Environment of a process from /proc/<pid>/environ
+
+use std::fs;
+use std::io;
+
+/// Read the environment a process was started with as (name, value) pairs, sorted by name
+/// (changes made by the process itself after exec are not visible)
+pub fn read_environment(pid: u32) -> io::Result<Vec<(String, String)>> {
+    let data = fs::read(format!("/proc/{}/environ", pid))?;
+    let mut vars: Vec<(String, String)> = data
+        .split(|&b| b == 0)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let entry = String::from_utf8_lossy(entry);
+            match entry.split_once('=') {
+                Some((name, value)) => (name.to_string(), value.to_string()),
+                None => (entry.to_string(), String::new()),
+            }
+        })
+        .collect();
+
+    vars.sort();
+    Ok(vars)
+}
@@ -0,0 +1,159 @@
+//! Plain text, JSON and CSV export helpers
+
+use crate::monitor::ProcessInfo;
+use crate::process_actions::get_command_line;
+use crate::users::user_name;
+
+/// Format a table as aligned plain text with a header row, columns separated by two spaces
+pub fn format_aligned_table(headers: &[String], rows: &[Vec<String>], left_aligned: &[bool]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: &[String]| {
+        let line = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let width = widths.get(i).copied().unwrap_or(0);
+                if left_aligned.get(i).copied().unwrap_or(false) {
+                    format!("{:<width$}", cell, width = width)
+                } else {
+                    format!("{:>width$}", cell, width = width)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        line.trim_end().to_string()
+    };
+
+    let mut text = format_row(headers);
+    text.push('\n');
+    for row in rows {
+        text.push_str(&format_row(row));
+        text.push('\n');
+    }
+    text
+}
+
+/// Quote a string as a JSON string literal
+pub fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// `value` as JSON, or null
+fn json_or_null<T>(value: Option<T>, format: impl Fn(T) -> String) -> String {
+    value.map_or_else(|| "null".to_string(), format)
+}
+
+/// JSON object with every metric of `process` from the last refresh, its threads, and its child
+/// processes among `processes` (nested the same way), for pasting into bug reports
+pub fn process_json(process: &ProcessInfo, processes: &[ProcessInfo]) -> String {
+    let threads: Vec<String> = process
+        .children
+        .iter()
+        .map(|thread| {
+            format!(
+                "{{\"tid\":{},\"name\":{},\"state\":{},\"cpu_percent\":{:.2}}}",
+                thread.pid,
+                json_string(&thread.name),
+                json_string(thread.state.as_str()),
+                thread.cpu_percent,
+            )
+        })
+        .collect();
+    let children: Vec<String> = processes
+        .iter()
+        .filter(|child| child.parent_pid == Some(process.pid) && child.pid != process.pid)
+        .map(|child| process_json(child, processes))
+        .collect();
+    let listening_ports: Vec<String> =
+        process.listening_ports.iter().map(|port| json_string(&port.describe())).collect();
+    let allowed_cpus = process.allowed_cpus.as_ref().map(|allowed| {
+        let cpus: Vec<String> = allowed
+            .iter()
+            .enumerate()
+            .filter(|(_, allowed)| **allowed)
+            .map(|(cpu, _)| cpu.to_string())
+            .collect();
+        format!("[{}]", cpus.join(","))
+    });
+
+    format!(
+        "{{\"pid\":{},\"start_time\":{},\"name\":{},\"command\":{},\"parent_pid\":{},\"state\":{},\
+         \"uid\":{},\"user\":{},\"cpu_percent\":{:.2},\"cpu_user_percent\":{},\"cpu_system_percent\":{},\
+         \"cpu_user_secs\":{},\"cpu_system_secs\":{},\"memory_bytes\":{},\"pss_bytes\":{},\"uss_bytes\":{},\
+         \"disk_read_bytes_per_sec\":{},\"disk_write_bytes_per_sec\":{},\"gpu_percent\":{},\
+         \"net_rx_bytes\":{},\"net_tx_bytes\":{},\"nice\":{},\"scheduling\":{},\"allowed_cpus\":{},\
+         \"wakeups_per_sec\":{},\"voluntary_switches_per_sec\":{},\"involuntary_switches_per_sec\":{},\
+         \"minor_faults_per_sec\":{},\"major_faults_per_sec\":{},\"listening_ports\":[{}],\"unit\":{},\
+         \"sandbox\":{},\"tracer_pid\":{},\"kernel_thread\":{},\"frozen\":{},\"pinned\":{},\
+         \"threads\":[{}],\"children\":[{}]}}",
+        process.pid,
+        process.start_time,
+        json_string(&process.name),
+        json_or_null(get_command_line(process.pid), |command| json_string(&command)),
+        json_or_null(process.parent_pid, |ppid| ppid.to_string()),
+        json_string(process.state.as_str()),
+        json_or_null(process.uid, |uid| uid.to_string()),
+        json_or_null(process.uid.and_then(user_name), |name| json_string(&name)),
+        process.total_cpu(),
+        json_or_null(process.cpu_split, |split| format!("{:.2}", split.user_percent)),
+        json_or_null(process.cpu_split, |split| format!("{:.2}", split.system_percent)),
+        json_or_null(process.cpu_times, |times| format!("{:.2}", times.user_secs)),
+        json_or_null(process.cpu_times, |times| format!("{:.2}", times.system_secs)),
+        process.total_memory(),
+        json_or_null(process.pss_bytes, |bytes| bytes.to_string()),
+        json_or_null(process.uss_bytes, |bytes| bytes.to_string()),
+        process.total_disk_read_rate(),
+        process.total_disk_write_rate(),
+        json_or_null(process.gpu_percent, |gpu| format!("{:.2}", gpu)),
+        process.total_net_rx(),
+        process.total_net_tx(),
+        json_or_null(process.nice, |nice| nice.to_string()),
+        json_or_null(process.effective_scheduling(), |scheduling| json_string(&scheduling.short())),
+        json_or_null(allowed_cpus, |cpus| cpus),
+        json_or_null(process.wakeups_per_sec, |rate| format!("{:.2}", rate)),
+        json_or_null(process.context_switches, |rates| format!("{:.2}", rates.voluntary_per_sec)),
+        json_or_null(process.context_switches, |rates| format!("{:.2}", rates.involuntary_per_sec)),
+        json_or_null(process.page_faults, |rates| format!("{:.2}", rates.minor_per_sec)),
+        json_or_null(process.page_faults, |rates| format!("{:.2}", rates.major_per_sec)),
+        listening_ports.join(","),
+        json_or_null(process.unit.as_ref(), |unit| json_string(&unit.name)),
+        json_or_null(process.sandbox.as_ref(), |app| {
+            json_string(&format!("{} {}", app.kind.as_str(), app.app_id))
+        }),
+        json_or_null(process.tracer_pid, |pid| pid.to_string()),
+        process.kernel_thread,
+        process.frozen,
+        process.pinned,
+        threads.join(","),
+        children.join(","),
+    )
+}
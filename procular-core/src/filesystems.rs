@@ -0,0 +1,189 @@
+//! Mounted filesystem usage from /proc/self/mounts and statvfs
+
+use std::ffi::CString;
+use std::fs;
+use std::mem::MaybeUninit;
+use std::path::Path;
+
+use crate::monitor::ProcessInfo;
+use crate::open_files::{read_open_files, FdKind};
+
+/// Filesystem types that never hold user data
+const VIRTUAL_FS_TYPES: &[&str] = &[
+    "proc", "sysfs", "devtmpfs", "devpts", "cgroup", "cgroup2", "securityfs", "debugfs",
+    "tracefs", "pstore", "bpf", "configfs", "fusectl", "mqueue", "hugetlbfs", "autofs",
+    "binfmt_misc", "efivarfs", "nsfs", "ramfs", "rpc_pipefs", "squashfs", "overlay",
+];
+
+/// Usage of one mounted filesystem
+#[derive(Debug, Clone)]
+pub struct Filesystem {
+    pub device: String,
+    pub mount_point: String,
+    pub fs_type: String,
+    pub total_bytes: u64,
+    /// Space available to unprivileged users
+    pub available_bytes: u64,
+    pub used_bytes: u64,
+}
+
+impl Filesystem {
+    /// Used share in percent, counting root-reserved blocks as unavailable (like df)
+    pub fn used_percent(&self) -> f64 {
+        let usable = self.used_bytes + self.available_bytes;
+        if usable == 0 {
+            return 0.0;
+        }
+        self.used_bytes as f64 * 100.0 / usable as f64
+    }
+}
+
+/// Decode the octal escapes (\040 for space, ...) used in /proc/self/mounts
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() {
+            let octal = std::str::from_utf8(&bytes[i + 1..i + 4]).ok();
+            if let Some(value) = octal.and_then(|o| u8::from_str_radix(o, 8).ok()) {
+                out.push(value);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn statvfs(path: &str) -> Option<libc::statvfs> {
+    let c_path = CString::new(path).ok()?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+    let result = unsafe { libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) };
+    (result == 0).then(|| unsafe { stat.assume_init() })
+}
+
+/// Real (non-virtual) mounted filesystems, each mount point once
+// statvfs field types are u32 on some 32-bit targets
+#[allow(clippy::unnecessary_cast)]
+pub fn read_filesystems() -> Vec<Filesystem> {
+    let Ok(mounts) = fs::read_to_string("/proc/self/mounts") else {
+        return Vec::new();
+    };
+
+    let mut filesystems: Vec<Filesystem> = Vec::new();
+    for line in mounts.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 3 || VIRTUAL_FS_TYPES.contains(&fields[2]) {
+            continue;
+        }
+        let mount_point = unescape_mount_field(fields[1]);
+        let Some(stat) = statvfs(&mount_point) else {
+            continue;
+        };
+        let block_size = stat.f_frsize as u64;
+        let total_bytes = stat.f_blocks as u64 * block_size;
+        if total_bytes == 0 {
+            continue;
+        }
+
+        let filesystem = Filesystem {
+            device: unescape_mount_field(fields[0]),
+            fs_type: fields[2].to_string(),
+            total_bytes,
+            available_bytes: stat.f_bavail as u64 * block_size,
+            used_bytes: (stat.f_blocks as u64 - stat.f_bfree as u64) * block_size,
+            mount_point,
+        };
+        // Later mounts on the same path hide earlier ones
+        filesystems.retain(|fs| fs.mount_point != filesystem.mount_point);
+        filesystems.push(filesystem);
+    }
+    filesystems
+}
+
+/// Mount point holding `path` (longest matching mount point)
+pub fn mount_point_of<'a>(filesystems: &'a [Filesystem], path: &str) -> Option<&'a str> {
+    filesystems
+        .iter()
+        .map(|fs| fs.mount_point.as_str())
+        .filter(|mount| Path::new(path).starts_with(mount))
+        .max_by_key(|mount| mount.len())
+}
+
+/// A process writing to a filesystem and the files it has open for writing there
+#[derive(Debug, Clone)]
+pub struct MountWriter {
+    pub pid: u32,
+    pub name: String,
+    /// Disk writes in bytes per second since the last refresh
+    pub write_rate: u64,
+    pub files: Vec<String>,
+}
+
+/// Processes that wrote to disk since the last refresh and hold files open for
+/// writing on `mount_point`, heaviest writers first
+pub fn writers_on_mount(
+    filesystems: &[Filesystem],
+    mount_point: &str,
+    processes: &[ProcessInfo],
+) -> Vec<MountWriter> {
+    let mut writers: Vec<MountWriter> = processes
+        .iter()
+        .filter(|p| p.total_disk_write_rate() > 0)
+        .filter_map(|p| {
+            let files: Vec<String> = read_open_files(p.pid)
+                .ok()?
+                .into_iter()
+                .filter(|f| f.kind == FdKind::File)
+                .filter(|f| f.flags.starts_with("WRONLY") || f.flags.starts_with("RDWR"))
+                .filter(|f| mount_point_of(filesystems, &f.target) == Some(mount_point))
+                .map(|f| f.target)
+                .collect();
+            (!files.is_empty()).then(|| MountWriter {
+                pid: p.pid,
+                name: p.name.clone(),
+                write_rate: p.total_disk_write_rate(),
+                files,
+            })
+        })
+        .collect();
+    writers.sort_by_key(|w| std::cmp::Reverse(w.write_rate));
+    writers
+}
+
+/// Percentage points usage must drop below the threshold before a mount can alert again
+const ALERT_HYSTERESIS_PERCENT: f64 = 2.0;
+
+/// Reports filesystems once when they cross a "nearly full" threshold
+#[derive(Default)]
+pub struct DiskFullDetector {
+    /// Mount points currently above the threshold
+    alerted: Vec<String>,
+}
+
+impl DiskFullDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Filesystems that crossed `threshold_percent` since the last check
+    pub fn check<'a>(&mut self, filesystems: &'a [Filesystem], threshold_percent: f64) -> Vec<&'a Filesystem> {
+        self.alerted.retain(|mount| {
+            filesystems.iter().any(|fs| {
+                &fs.mount_point == mount && fs.used_percent() >= threshold_percent - ALERT_HYSTERESIS_PERCENT
+            })
+        });
+
+        let mut crossed = Vec::new();
+        for fs in filesystems {
+            if fs.used_percent() >= threshold_percent && !self.alerted.contains(&fs.mount_point) {
+                self.alerted.push(fs.mount_point.clone());
+                crossed.push(fs);
+            }
+        }
+        crossed
+    }
+}
@@ -0,0 +1,95 @@
+//! Bulk export of everything the monitor has recorded, for offline analysis after an incident
+//!
+//! The document covers the retained history window: per-core CPU, swap activity and
+//! temperatures for the system, and the full history of every top or tracked process.
+//! All arrays are aligned with the newest sample; shorter process arrays belong to
+//! processes that started (or entered the top list) later. Timestamps are the wall
+//! clock times of the refreshes, so they stay exact across gaps (suspend).
+
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::export::json_string;
+use crate::monitor::SystemMonitor;
+
+/// JSON array of the values in a history
+fn json_array<T: Display>(values: &VecDeque<T>) -> String {
+    let items: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    format!("[{}]", items.join(","))
+}
+
+/// Seconds since the Unix epoch
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// One JSON document with the system and process histories of `monitor`
+pub fn export_json(monitor: &SystemMonitor, sample_interval_secs: u64) -> String {
+    let gaps = monitor.sample_gaps();
+    let timestamps: VecDeque<u64> = monitor.sample_times().iter().copied().map(unix_secs).collect();
+
+    let cores: Vec<String> = monitor.core_history().iter().map(json_array).collect();
+    let sensors: Vec<String> = monitor
+        .sensors()
+        .iter()
+        .filter_map(|sensor| {
+            let history = monitor.sensor_history(&sensor.id)?;
+            Some(format!(
+                "{{\"id\":{},\"kind\":{},\"chip\":{},\"label\":{},\"celsius\":{}}}",
+                json_string(&sensor.id),
+                json_string(sensor.kind.as_str()),
+                json_string(&sensor.chip),
+                json_string(&sensor.label),
+                json_array(history)
+            ))
+        })
+        .collect();
+
+    let mut histories = monitor.histories();
+    histories.sort_by_key(|(key, _, _)| key.pid);
+    let processes: Vec<String> = histories
+        .iter()
+        .map(|(key, name, history)| {
+            format!(
+                "{{\"pid\":{},\"start_time\":{},\"name\":{},\"timestamps\":{},\"cpu_percent\":{},\"memory_bytes\":{},\
+                 \"disk_read_bytes_per_sec\":{},\"disk_write_bytes_per_sec\":{},\"gpu_memory_percent\":{},\
+                 \"net_rx_bytes\":{},\"net_tx_bytes\":{},\"minor_faults_per_sec\":{},\"major_faults_per_sec\":{},\
+                 \"voluntary_switches_per_sec\":{},\"involuntary_switches_per_sec\":{},\
+                 \"user_cpu_percent\":{},\"system_cpu_percent\":{}}}",
+                key.pid,
+                key.start_time,
+                json_string(name),
+                json_array(&history.sample_times.iter().copied().map(unix_secs).collect()),
+                json_array(&history.cpu_history),
+                json_array(&history.memory_history),
+                json_array(&history.disk_read_history),
+                json_array(&history.disk_write_history),
+                json_array(&history.gpu_mem_history),
+                json_array(&history.net_rx_history),
+                json_array(&history.net_tx_history),
+                json_array(&history.minor_fault_history),
+                json_array(&history.major_fault_history),
+                json_array(&history.voluntary_switch_history),
+                json_array(&history.involuntary_switch_history),
+                json_array(&history.user_cpu_history),
+                json_array(&history.system_cpu_history)
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\"exported\":{},\"sample_interval_secs\":{},\"timestamps\":{},\"gap_before\":{},\
+         \"system\":{{\"cpu_core_percent\":[{}],\"swap_in_bytes_per_sec\":{},\"swap_out_bytes_per_sec\":{},\
+         \"temperatures\":[{}]}},\"processes\":[{}]}}\n",
+        unix_secs(SystemTime::now()),
+        sample_interval_secs,
+        json_array(&timestamps),
+        json_array(gaps),
+        cores.join(","),
+        json_array(monitor.swap_in_history()),
+        json_array(monitor.swap_out_history()),
+        sensors.join(","),
+        processes.join(",")
+    )
+}
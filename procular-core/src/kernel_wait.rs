@@ -0,0 +1,25 @@
+//! Where a blocked process waits in the kernel, from /proc/<pid>/wchan and /proc/<pid>/stack
+
+use std::fs;
+use std::io;
+
+/// Kernel function the process is sleeping in, if it is blocked and the symbol is visible
+pub fn read_wchan(pid: u32) -> Option<String> {
+    let wchan = fs::read_to_string(format!("/proc/{}/wchan", pid)).ok()?;
+    let wchan = wchan.trim();
+    // "0" when running or when kallsyms are hidden from us
+    (!wchan.is_empty() && wchan != "0").then(|| wchan.to_string())
+}
+
+/// Kernel stack of the process, innermost frame first (needs CAP_SYS_ADMIN)
+pub fn read_kernel_stack(pid: u32) -> io::Result<Vec<String>> {
+    let stack = fs::read_to_string(format!("/proc/{}/stack", pid))?;
+    Ok(stack
+        .lines()
+        // Lines look like "[<0>] do_sys_poll+0x3f1/0x5b0"; the address is hidden without kptr access
+        .map(|line| match line.split_once("] ") {
+            Some((_, frame)) => frame.to_string(),
+            None => line.to_string(),
+        })
+        .collect())
+}
@@ -0,0 +1,42 @@
+//! Process sampling and control for Procular
+//!
+//! Everything here works without GTK so it can be shared by the GUI and other
+//! frontends.
+
+pub mod capabilities;
+pub mod cgroup_limits;
+pub mod connections;
+pub mod cpu_times;
+pub mod developer_tools;
+pub mod diagnosis;
+pub mod disks;
+pub mod environment;
+pub mod export;
+pub mod filesystems;
+pub mod history_export;
+pub mod kernel_wait;
+pub mod mangohud;
+pub mod memory_map;
+pub mod monitor;
+pub mod name_pattern;
+pub mod net_usage;
+pub mod offenders;
+pub mod open_files;
+pub mod page_faults;
+#[cfg(feature = "perf")]
+pub mod perf_events;
+pub mod process_actions;
+pub mod process_properties;
+pub mod process_state;
+pub mod sandbox;
+pub mod self_usage;
+pub mod sensors;
+pub mod snapshot;
+pub mod startup_impact;
+pub mod swap;
+pub mod systemd_units;
+pub mod user_usage;
+pub mod users;
+pub mod vram_leaks;
+pub mod wakeups;
+pub mod window_focus;
@@ -0,0 +1,198 @@
+//! Frame rate of games running with MangoHud, the Vulkan/OpenGL overlay
+//!
+//! MangoHud has no query for live numbers, but while logging (Shift+F2, or the
+//! "logging" command on its control socket) it appends one CSV row with FPS and frame
+//! time per log interval to a file in its output folder. Reading that file works the
+//! same under every window manager and compositor.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::net::{SocketAddr, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::environment::read_environment;
+
+/// Whether a process looks like a game: MangoHud or GameMode loaded into it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GameStatus {
+    pub mangohud: bool,
+    /// The game asked GameMode for performance settings (libgamemode is loaded)
+    pub gamemode: bool,
+}
+
+/// Check the libraries mapped into a process; read once per window since /proc/<pid>/maps
+/// of a game is large
+pub fn game_status(pid: u32) -> GameStatus {
+    let Ok(maps) = fs::read_to_string(format!("/proc/{}/maps", pid)) else {
+        return GameStatus::default();
+    };
+    GameStatus {
+        mangohud: maps.contains("libMangoHud"),
+        gamemode: maps.contains("libgamemode"),
+    }
+}
+
+/// Abstract socket name of MangoHud's control socket in a process, if the `control`
+/// option enabled one. Found by matching the process's socket inodes with /proc/net/unix
+pub fn control_socket(pid: u32) -> Option<String> {
+    let inodes: Vec<String> = fs::read_dir(format!("/proc/{}/fd", pid))
+        .ok()?
+        .flatten()
+        .filter_map(|entry| fs::read_link(entry.path()).ok())
+        .filter_map(|target| {
+            let target = target.to_string_lossy();
+            Some(target.strip_prefix("socket:[")?.strip_suffix(']')?.to_string())
+        })
+        .collect();
+    let table = fs::read_to_string("/proc/net/unix").ok()?;
+    table.lines().skip(1).find_map(|line| {
+        // Num RefCount Protocol Flags Type St Inode Path
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let name = fields.get(7)?.strip_prefix('@')?;
+        (name.starts_with("mangohud") && inodes.iter().any(|inode| inode == fields[6])).then(|| name.to_string())
+    })
+}
+
+/// Start or stop MangoHud's logging through its control socket
+pub fn toggle_logging(socket_name: &str) -> io::Result<()> {
+    let address = SocketAddr::from_abstract_name(socket_name.as_bytes())?;
+    let mut stream = UnixStream::connect_addr(&address)?;
+    stream.write_all(b":logging;")
+}
+
+/// Folder MangoHud writes its logs to: `output_folder` from MANGOHUD_CONFIG or the config
+/// file the process uses, else the home folder
+pub fn output_folder(pid: u32, process_name: &str) -> PathBuf {
+    let environment = read_environment(pid).unwrap_or_default();
+    let var = |name: &str| environment.iter().find(|(n, _)| n == name).map(|(_, value)| value.clone());
+    let home = var("HOME").or_else(|| std::env::var("HOME").ok()).unwrap_or_default();
+
+    // MANGOHUD_CONFIG holds comma-separated options and wins over the config files
+    let inline = var("MANGOHUD_CONFIG").and_then(|config| {
+        config
+            .split(',')
+            .find_map(|option| option.trim().strip_prefix("output_folder=").map(str::to_string))
+    });
+    let config_dir = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(&home).join(".config"))
+        .join("MangoHud");
+    let config_files = [
+        var("MANGOHUD_CONFIGFILE").map(PathBuf::from),
+        Some(config_dir.join(format!("{}.conf", process_name))),
+        Some(config_dir.join("MangoHud.conf")),
+    ];
+    let from_file = || {
+        config_files.iter().flatten().find_map(|path| {
+            let content = fs::read_to_string(path).ok()?;
+            content.lines().find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "output_folder").then(|| value.trim().to_string())
+            })
+        })
+    };
+
+    let folder = inline.or_else(from_file).unwrap_or_else(|| home.clone());
+    match folder.strip_prefix("~/") {
+        Some(rest) => Path::new(&home).join(rest),
+        None => PathBuf::from(folder),
+    }
+}
+
+/// Frames logged by MangoHud since the previous read
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameStats {
+    pub average_fps: f64,
+    /// Slowest frame time among the rows, for stutter
+    pub worst_frame_time_ms: f64,
+}
+
+/// Follows the newest MangoHud log in an output folder. Logs are named after the
+/// executable, which for Wine games is not the process name, so any new log is taken
+pub struct FrameLog {
+    folder: PathBuf,
+    /// Only logs written after this are followed, so an old log is not mistaken for a live one
+    since: SystemTime,
+    path: Option<PathBuf>,
+    offset: u64,
+    /// Unfinished last line of the previous read
+    partial: String,
+    /// Positions of the fps and frametime columns, known once the header was read
+    columns: Option<(usize, usize)>,
+}
+
+impl FrameLog {
+    pub fn new(folder: PathBuf, since: SystemTime) -> Self {
+        Self {
+            folder,
+            since,
+            path: None,
+            offset: 0,
+            partial: String::new(),
+            columns: None,
+        }
+    }
+
+    /// Newest log modified after `since`
+    fn newest_log(&self) -> Option<PathBuf> {
+        fs::read_dir(&self.folder)
+            .ok()?
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".csv"))
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .filter(|(modified, _)| *modified >= self.since)
+            .max_by_key(|(modified, _)| *modified)
+            .map(|(_, path)| path)
+    }
+
+    /// Rows appended since the last call; None when nothing new was logged
+    pub fn read(&mut self) -> Option<FrameStats> {
+        let newest = self.newest_log()?;
+        if self.path.as_ref() != Some(&newest) {
+            // A new logging session starts a new file
+            self.path = Some(newest.clone());
+            self.offset = 0;
+            self.partial.clear();
+            self.columns = None;
+        }
+
+        let mut file = File::open(&newest).ok()?;
+        file.seek(SeekFrom::Start(self.offset)).ok()?;
+        let mut text = String::new();
+        self.offset += file.read_to_string(&mut text).ok()? as u64;
+        self.partial.push_str(&text);
+        let end = self.partial.rfind('\n')?;
+        let complete: String = self.partial.drain(..=end).collect();
+
+        let mut rows: Vec<(f64, f64)> = Vec::new();
+        for line in complete.lines() {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            // Header rows describe the system; the frame columns start at the "fps" header
+            if fields.first() == Some(&"fps") {
+                let frame_time = fields.iter().position(|&field| field == "frametime");
+                self.columns = frame_time.map(|frame_time| (0, frame_time));
+                continue;
+            }
+            let Some((fps, frame_time)) = self.columns else {
+                continue;
+            };
+            if let (Some(Ok(fps)), Some(Ok(frame_time))) = (
+                fields.get(fps).map(|v| v.parse::<f64>()),
+                fields.get(frame_time).map(|v| v.parse::<f64>()),
+            ) {
+                rows.push((fps, frame_time));
+            }
+        }
+        if rows.is_empty() {
+            return None;
+        }
+
+        let count = rows.len() as f64;
+        Some(FrameStats {
+            average_fps: rows.iter().map(|(fps, _)| fps).sum::<f64>() / count,
+            worst_frame_time_ms: rows.iter().map(|(_, frame_time)| *frame_time).fold(0.0, f64::max),
+        })
+    }
+}
@@ -0,0 +1,300 @@
+//! Memory map analysis from /proc/<pid>/maps
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+
+use crate::monitor::ProcessKey;
+use crate::process_actions::{get_descendant_processes, get_process_name};
+
+/// Refreshes between smaps_rollup reads of the same process; the kernel walks
+/// all page tables for it, which is too slow to do for every process every refresh
+const ROLLUP_REFRESH_INTERVAL: u32 = 5;
+
+/// Virtual memory of a process broken down by mapping kind
+#[derive(Debug, Clone, Default)]
+pub struct MemoryMapSummary {
+    /// Executable file mappings (program text, library code, vdso)
+    pub code: u64,
+    pub heap: u64,
+    /// Main thread and thread stacks
+    pub stack: u64,
+    /// Non-executable file mappings (library data, mmapped files)
+    pub mapped_files: u64,
+    /// Anonymous mappings (malloc arenas, JIT, shared memory)
+    pub anonymous: u64,
+    /// Number of distinct shared objects mapped
+    pub shared_libraries: usize,
+    /// Total size of all shared object mappings (code and data)
+    pub shared_library_bytes: u64,
+}
+
+impl MemoryMapSummary {
+    /// Total virtual size of all mappings
+    pub fn total(&self) -> u64 {
+        self.code + self.heap + self.stack + self.mapped_files + self.anonymous
+    }
+}
+
+/// A single line of /proc/<pid>/maps
+#[derive(Debug, Clone)]
+pub struct MapEntry {
+    pub start: u64,
+    pub end: u64,
+    pub perms: String,
+    pub pathname: String,
+}
+
+impl MapEntry {
+    pub fn size(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_executable(&self) -> bool {
+        self.perms.contains('x')
+    }
+
+    /// Whether the mapping is backed by a file on disk
+    pub fn is_file_backed(&self) -> bool {
+        self.pathname.starts_with('/')
+    }
+}
+
+/// Parse a single maps line: "start-end perms offset dev inode    pathname"
+pub fn parse_maps_line(line: &str) -> Option<MapEntry> {
+    // The first five fields are separated by single spaces; the pathname is padded
+    let mut parts = line.splitn(6, ' ');
+    let range = parts.next()?;
+    let perms = parts.next()?.to_string();
+    let _offset = parts.next()?;
+    let _dev = parts.next()?;
+    let _inode = parts.next()?;
+    let pathname = parts.next().unwrap_or("").trim().to_string();
+
+    let (start, end) = range.split_once('-')?;
+    Some(MapEntry {
+        start: u64::from_str_radix(start, 16).ok()?,
+        end: u64::from_str_radix(end, 16).ok()?,
+        perms,
+        pathname,
+    })
+}
+
+/// Read and parse /proc/<pid>/maps
+pub fn read_maps(pid: u32) -> io::Result<Vec<MapEntry>> {
+    let content = fs::read_to_string(format!("/proc/{}/maps", pid))?;
+    Ok(content.lines().filter_map(parse_maps_line).collect())
+}
+
+/// Whether a path looks like a shared object (libfoo.so, libfoo.so.1.2)
+fn is_shared_object(path: &str) -> bool {
+    path.rsplit('/')
+        .next()
+        .is_some_and(|file| file.ends_with(".so") || file.contains(".so."))
+}
+
+/// Summarize the memory map of a process into code/heap/stack/file/anonymous totals
+pub fn read_memory_map_summary(pid: u32) -> io::Result<MemoryMapSummary> {
+    let entries = read_maps(pid)?;
+    let mut summary = MemoryMapSummary::default();
+    let mut libraries: HashSet<&str> = HashSet::new();
+
+    for entry in &entries {
+        let size = entry.size();
+        let path = entry.pathname.as_str();
+
+        if path == "[heap]" {
+            summary.heap += size;
+        } else if path.starts_with("[stack") {
+            summary.stack += size;
+        } else if path == "[vdso]" || path == "[vsyscall]" {
+            summary.code += size;
+        } else if entry.is_file_backed() {
+            if entry.is_executable() {
+                summary.code += size;
+            } else {
+                summary.mapped_files += size;
+            }
+            if is_shared_object(path) {
+                libraries.insert(path);
+                summary.shared_library_bytes += size;
+            }
+        } else if path != "[vvar]" {
+            // Anonymous, [anon:name], memfd and SysV shared memory
+            summary.anonymous += size;
+        }
+    }
+
+    summary.shared_libraries = libraries.len();
+    Ok(summary)
+}
+
+/// Memory accounting of a whole process from /proc/<pid>/smaps_rollup (all values in bytes)
+#[derive(Debug, Clone, Default)]
+pub struct SmapsRollup {
+    pub rss: u64,
+    /// Proportional set size: shared pages divided among the processes mapping them
+    pub pss: u64,
+    pub pss_anon: u64,
+    pub pss_file: u64,
+    pub pss_shmem: u64,
+    pub shared_clean: u64,
+    pub shared_dirty: u64,
+    pub private_clean: u64,
+    pub private_dirty: u64,
+    pub swap: u64,
+    /// Proportional share of swapped out pages
+    pub swap_pss: u64,
+}
+
+impl SmapsRollup {
+    /// Unique set size: pages no other process maps, i.e. what ending the process frees
+    pub fn uss(&self) -> u64 {
+        self.private_clean + self.private_dirty
+    }
+}
+
+/// Parse a "Key:   1234 kB" smaps line into (key, bytes)
+fn parse_smaps_field(line: &str) -> Option<(&str, u64)> {
+    let (key, value) = line.split_once(':')?;
+    let kb: u64 = value.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some((key, kb * 1024))
+}
+
+/// Read /proc/<pid>/smaps_rollup
+pub fn read_smaps_rollup(pid: u32) -> io::Result<SmapsRollup> {
+    let content = fs::read_to_string(format!("/proc/{}/smaps_rollup", pid))?;
+    let mut rollup = SmapsRollup::default();
+    let mut found = false;
+
+    for (key, bytes) in content.lines().filter_map(parse_smaps_field) {
+        let field = match key {
+            "Rss" => &mut rollup.rss,
+            "Pss" => &mut rollup.pss,
+            "Pss_Anon" => &mut rollup.pss_anon,
+            "Pss_File" => &mut rollup.pss_file,
+            "Pss_Shmem" => &mut rollup.pss_shmem,
+            "Shared_Clean" => &mut rollup.shared_clean,
+            "Shared_Dirty" => &mut rollup.shared_dirty,
+            "Private_Clean" => &mut rollup.private_clean,
+            "Private_Dirty" => &mut rollup.private_dirty,
+            "Swap" => &mut rollup.swap,
+            "SwapPss" => &mut rollup.swap_pss,
+            _ => continue,
+        };
+        *field = bytes;
+        found = true;
+    }
+
+    if !found {
+        // Kernel threads have an empty smaps_rollup
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "No data in smaps_rollup"));
+    }
+    Ok(rollup)
+}
+
+/// Read the proportional set size (PSS) of a process from /proc/<pid>/smaps_rollup
+/// Shared pages are divided among all processes mapping them, so PSS values can be summed
+pub fn read_pss(pid: u32) -> io::Result<u64> {
+    read_smaps_rollup(pid).map(|rollup| rollup.pss)
+}
+
+/// PSS and USS of processes, re-read from smaps_rollup every few refreshes
+#[derive(Default)]
+pub struct RollupCache {
+    /// Last (PSS, USS) reading, None if unreadable, and refreshes until the next read
+    entries: HashMap<ProcessKey, (Option<(u64, u64)>, u32)>,
+    /// Processes asked for during the current refresh
+    seen: HashSet<ProcessKey>,
+}
+
+impl RollupCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// (PSS, USS) of a process in bytes; None for kernel threads and processes we may not inspect
+    pub fn get(&mut self, key: ProcessKey) -> Option<(u64, u64)> {
+        let read = |pid| read_smaps_rollup(pid).ok().map(|rollup| (rollup.pss, rollup.uss()));
+        self.seen.insert(key);
+        let (usage, countdown) = self.entries.entry(key).or_insert_with(|| {
+            // Spread the re-reads of processes first seen together over several refreshes
+            (read(key.pid), 1 + key.pid % ROLLUP_REFRESH_INTERVAL)
+        });
+        if *countdown == 0 {
+            *usage = read(key.pid);
+            *countdown = ROLLUP_REFRESH_INTERVAL;
+        }
+        *countdown -= 1;
+        *usage
+    }
+
+    /// End a refresh; processes not asked for in it are forgotten
+    pub fn finish_refresh(&mut self) {
+        let seen = std::mem::take(&mut self.seen);
+        self.entries.retain(|key, _| seen.contains(key));
+    }
+}
+
+/// Resident memory of a single mapping from /proc/<pid>/smaps (bytes)
+#[derive(Debug, Clone)]
+pub struct MappingUsage {
+    pub entry: MapEntry,
+    pub rss: u64,
+    pub pss: u64,
+    pub swap: u64,
+}
+
+/// Read /proc/<pid>/smaps, largest resident mappings first
+pub fn read_smaps(pid: u32) -> io::Result<Vec<MappingUsage>> {
+    let content = fs::read_to_string(format!("/proc/{}/smaps", pid))?;
+    let mut mappings: Vec<MappingUsage> = Vec::new();
+
+    for line in content.lines() {
+        // Mapping headers look like maps lines; everything else is a "Key: value" field
+        if let Some((key, bytes)) = parse_smaps_field(line) {
+            if let Some(current) = mappings.last_mut() {
+                match key {
+                    "Rss" => current.rss = bytes,
+                    "Pss" => current.pss = bytes,
+                    "Swap" => current.swap = bytes,
+                    _ => {}
+                }
+            }
+        } else if let Some(entry) = parse_maps_line(line) {
+            mappings.push(MappingUsage {
+                entry,
+                rss: 0,
+                pss: 0,
+                swap: 0,
+            });
+        }
+    }
+
+    mappings.sort_by_key(|m| std::cmp::Reverse(m.rss));
+    Ok(mappings)
+}
+
+/// PSS of one process in a process tree
+#[derive(Debug, Clone)]
+pub struct TreeMemoryEntry {
+    pub pid: u32,
+    pub name: String,
+    /// None if smaps_rollup is not readable (other users' processes)
+    pub pss_bytes: Option<u64>,
+}
+
+/// PSS of a process and all of its descendants, largest consumers first
+pub fn read_process_tree_pss(pid: u32) -> Vec<TreeMemoryEntry> {
+    let mut entries: Vec<TreeMemoryEntry> = std::iter::once((pid, get_process_name(pid)))
+        .chain(get_descendant_processes(pid))
+        .map(|(pid, name)| TreeMemoryEntry {
+            pid,
+            name,
+            pss_bytes: read_pss(pid).ok(),
+        })
+        .collect();
+
+    entries.sort_by_key(|e| std::cmp::Reverse(e.pss_bytes));
+    entries
+}
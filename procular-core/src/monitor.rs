@@ -0,0 +1,1676 @@
+use futures_channel::oneshot;
+use sysinfo::{System, ProcessesToUpdate, ProcessRefreshKind, ThreadKind};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::cgroup_limits::{cgroup_path, is_cgroup_frozen};
+use crate::connections::{ListeningPort, ListeningPortTracker};
+use crate::cpu_times::{CpuSplit, CpuTimeTracker, CpuTimes};
+use crate::disks::{DiskActivity, DiskHistory, DiskTracker};
+use crate::memory_map::{read_pss, RollupCache};
+use crate::name_pattern::matches_pattern;
+use crate::net_usage::NetUsageTracker;
+use crate::offenders::OffenderLog;
+use crate::page_faults::{FaultRates, FaultTracker};
+use crate::process_actions::{
+    get_command_line, get_priority, read_allowed_cpus, read_cpu_time_secs, read_scheduling, Scheduling,
+};
+use crate::process_state::ProcessState;
+use crate::sandbox::{detect_sandbox, SandboxApp};
+use crate::sensors::{read_sensors, Sensor};
+use crate::snapshot::{ProcessSnapshot, SnapshotEntry};
+use crate::swap::{page_size, read_swap_pages};
+use crate::systemd_units::{process_origin, unit_from_cgroup, ProcessOrigin, SystemdUnit};
+use crate::user_usage::{usage_by_user, UserUsage};
+use crate::wakeups::{SwitchRates, WakeupTracker};
+
+/// CPU usage (normalized %) below which a process counts as idle
+const IDLE_CPU_THRESHOLD: f32 = 0.1;
+
+/// Number of busiest processes returned by each refresh unless set otherwise
+pub const DEFAULT_PROCESS_LIMIT: usize = 150;
+
+/// Difference between wall clock and monotonic time across one refresh that counts as a gap
+/// (suspend, or the clock being set)
+const GAP_CLOCK_JUMP: Duration = Duration::from_secs(5);
+
+/// Optional parts of a refresh; turning them off saves work on every refresh
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collectors {
+    /// GPU utilization, per-process GPU memory and NVIDIA temperatures (NVML)
+    pub gpu: bool,
+    /// Per-process disk read and write rates
+    pub disk_io: bool,
+    /// Per-process network traffic and listening ports (sock_diag)
+    pub network: bool,
+    /// Fold threads into their process; when off, threads are left out of the list and their
+    /// CPU is not added to the process
+    pub thread_grouping: bool,
+    /// Per-process /proc reads for the shown processes: scheduling, nice, unit, origin, sandbox,
+    /// freezer state, context switches, CPU time split, page faults, affinity and PSS/USS
+    pub process_details: bool,
+}
+
+impl Default for Collectors {
+    fn default() -> Self {
+        Self {
+            gpu: true,
+            disk_io: true,
+            network: true,
+            thread_grouping: true,
+            process_details: true,
+        }
+    }
+}
+
+/// Processes pinned to the top of the list; always returned by refresh regardless of rank
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pins {
+    /// Pinned process instances; PIDs are recycled, so the start time is part of the key
+    pub keys: HashSet<ProcessKey>,
+    /// Process name patterns with `*` and `?` wildcards
+    pub names: Vec<String>,
+}
+
+impl Pins {
+    pub fn is_pinned(&self, key: &ProcessKey, name: &str) -> bool {
+        self.keys.contains(key) || self.names.iter().any(|pattern| matches_pattern(pattern, name))
+    }
+}
+
+/// Read TracerPid from /proc/<pid>/status; None when not traced or the file cannot be read
+fn read_tracer(pid: u32) -> Option<u32> {
+    let content = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("TracerPid:"))
+        .and_then(|tracer| tracer.trim().parse().ok())
+        .filter(|&pid: &u32| pid != 0)
+}
+
+/// Read total network bytes (rx, tx) from /proc/net/dev
+/// Sums all non-loopback interfaces
+fn read_network_totals() -> (u64, u64) {
+    let mut rx_total = 0u64;
+    let mut tx_total = 0u64;
+
+    if let Ok(content) = fs::read_to_string("/proc/net/dev") {
+        for line in content.lines().skip(2) {
+            // Format: "iface: rx_bytes rx_packets ... tx_bytes tx_packets ..."
+            let line = line.trim();
+            if line.starts_with("lo:") {
+                continue; // Skip loopback
+            }
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() >= 10 {
+                // First field is "iface:" or "iface" depending on spacing
+                // rx_bytes is at index 1, tx_bytes is at index 9
+                let iface_and_rx: Vec<&str> = parts[0].split(':').collect();
+                let rx_idx = if iface_and_rx.len() > 1 && !iface_and_rx[1].is_empty() {
+                    // "iface:12345" format - rx is part of first field
+                    if let Ok(rx) = iface_and_rx[1].parse::<u64>() {
+                        rx_total += rx;
+                    }
+                    0 // tx_bytes is at parts[8]
+                } else {
+                    // "iface: 12345" format - rx is at parts[1]
+                    if let Ok(rx) = parts[1].parse::<u64>() {
+                        rx_total += rx;
+                    }
+                    1 // tx_bytes is at parts[9]
+                };
+                let tx_idx = rx_idx + 8;
+                if let Some(tx_str) = parts.get(tx_idx) {
+                    if let Ok(tx) = tx_str.parse::<u64>() {
+                        tx_total += tx;
+                    }
+                }
+            }
+        }
+    }
+
+    (rx_total, tx_total)
+}
+
+/// Identifies one process instance: PIDs are recycled, so the start time
+/// tells a new process apart from an earlier one with the same PID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProcessKey {
+    pub pid: u32,
+    /// Start time in seconds since the Unix epoch
+    pub start_time: u64,
+}
+
+/// Represents a single process with its resource usage
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// Start time in seconds since the Unix epoch
+    pub start_time: u64,
+    pub name: String,
+    /// Parent process; for a zombie, the process that has not reaped it
+    pub parent_pid: Option<u32>,
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+    /// Disk reads in bytes per second since the last refresh
+    pub disk_read_rate: u64,
+    /// Disk writes in bytes per second since the last refresh
+    pub disk_write_rate: u64,
+    pub gpu_percent: Option<f32>,
+    pub net_rx_bytes: u64,
+    pub net_tx_bytes: u64,
+    /// PID of the debugger/tracer attached via ptrace, if any
+    pub tracer_pid: Option<u32>,
+    /// Sockets the process listens on
+    pub listening_ports: Vec<ListeningPort>,
+    /// Scheduling policy; only read for the returned (top and tracked) processes and their threads
+    pub scheduling: Option<Scheduling>,
+    /// Nice value of the main thread; only read for the returned processes
+    pub nice: Option<i32>,
+    /// Scheduler state (R/S/D/T/Z)
+    pub state: ProcessState,
+    /// Kernel thread (PF_KTHREAD), such as kworker or ksoftirqd
+    pub kernel_thread: bool,
+    /// Pinned by key or name (see `SystemMonitor::pin` and `set_pinned_names`)
+    pub pinned: bool,
+    /// Real user ID of the owner
+    pub uid: Option<u32>,
+    /// The process's cgroup is frozen (see `cgroup_limits::freeze_process`)
+    pub frozen: bool,
+    /// systemd unit owning the process; only resolved for the returned processes
+    pub unit: Option<SystemdUnit>,
+    /// Session, system service or other user; only resolved for the returned processes
+    pub origin: Option<ProcessOrigin>,
+    /// Flatpak or Snap app the process belongs to; only resolved for the returned processes
+    pub sandbox: Option<SandboxApp>,
+    /// Wakeups per second of all threads; only sampled for the returned processes, None on the first sample
+    pub wakeups_per_sec: Option<f64>,
+    /// Voluntary and involuntary context switches per second of all threads; sampled with the wakeups
+    pub context_switches: Option<SwitchRates>,
+    /// CPUs the main thread may run on (index N is CPU N); only read for the returned processes
+    pub allowed_cpus: Option<Vec<bool>>,
+    /// Cumulative user and system CPU time of all threads; only read for the returned processes
+    pub cpu_times: Option<CpuTimes>,
+    /// User and system CPU percent since the previous refresh; sampled with `cpu_times`, None on the first sample
+    pub cpu_split: Option<CpuSplit>,
+    /// Page faults per second of all threads; only sampled for the returned processes, None on the first sample
+    pub page_faults: Option<FaultRates>,
+    /// Proportional and unique set size from smaps_rollup; only read for the returned processes,
+    /// every few refreshes, and None where smaps_rollup is unreadable (kernel threads, other users)
+    pub pss_bytes: Option<u64>,
+    pub uss_bytes: Option<u64>,
+    /// Child processes/threads
+    pub children: Vec<ProcessInfo>,
+    /// Whether this is a group (has children aggregated)
+    pub is_group: bool,
+}
+
+impl ProcessInfo {
+    pub fn key(&self) -> ProcessKey {
+        ProcessKey {
+            pid: self.pid,
+            start_time: self.start_time,
+        }
+    }
+
+    /// Scheduling shown for the process: its highest priority realtime thread if any, else its own
+    pub fn effective_scheduling(&self) -> Option<Scheduling> {
+        self.children
+            .iter()
+            .filter_map(|c| c.scheduling)
+            .filter(|s| s.policy.is_realtime())
+            .chain(self.scheduling)
+            .max_by_key(|s| s.rank())
+    }
+
+    /// Get total CPU including children
+    pub fn total_cpu(&self) -> f32 {
+        self.cpu_percent + self.children.iter().map(|c| c.cpu_percent).sum::<f32>()
+    }
+
+    /// Get total memory - for groups (threads), just use parent's memory since threads share memory space
+    pub fn total_memory(&self) -> u64 {
+        // Threads share the same memory space, so don't sum children's memory
+        self.memory_bytes
+    }
+
+    /// Get total disk read rate including children (bytes per second)
+    pub fn total_disk_read_rate(&self) -> u64 {
+        self.disk_read_rate + self.children.iter().map(|c| c.disk_read_rate).sum::<u64>()
+    }
+
+    /// Get total disk write rate including children (bytes per second)
+    pub fn total_disk_write_rate(&self) -> u64 {
+        self.disk_write_rate + self.children.iter().map(|c| c.disk_write_rate).sum::<u64>()
+    }
+
+    /// Get total disk I/O rate including children (bytes per second)
+    pub fn total_disk_rate(&self) -> u64 {
+        self.total_disk_read_rate() + self.total_disk_write_rate()
+    }
+
+    /// Get total GPU percent (max of self and children)
+    pub fn total_gpu(&self) -> f32 {
+        let self_gpu = self.gpu_percent.unwrap_or(0.0);
+        let children_max = self.children.iter()
+            .filter_map(|c| c.gpu_percent)
+            .fold(0.0_f32, f32::max);
+        self_gpu.max(children_max)
+    }
+
+    /// Get total network RX including children
+    pub fn total_net_rx(&self) -> u64 {
+        self.net_rx_bytes + self.children.iter().map(|c| c.net_rx_bytes).sum::<u64>()
+    }
+
+    /// Get total network TX including children
+    pub fn total_net_tx(&self) -> u64 {
+        self.net_tx_bytes + self.children.iter().map(|c| c.net_tx_bytes).sum::<u64>()
+    }
+
+    /// Get child count
+    pub fn child_count(&self) -> usize {
+        self.children.len()
+    }
+}
+
+/// History entry for graphing
+#[derive(Debug, Clone, Default)]
+pub struct ProcessHistory {
+    pub cpu_history: VecDeque<f32>,
+    pub memory_history: VecDeque<u64>,
+    pub disk_read_history: VecDeque<u64>,  // Bytes per second
+    pub disk_write_history: VecDeque<u64>,
+    pub gpu_mem_history: VecDeque<f32>,    // Per-process GPU memory %
+    pub gpu_util_history: VecDeque<f32>,   // System-wide GPU utilization %
+    pub net_rx_history: VecDeque<u64>,     // Per-process TCP bytes
+    pub net_tx_history: VecDeque<u64>,
+    pub minor_fault_history: VecDeque<f64>, // Faults per second
+    pub major_fault_history: VecDeque<f64>,
+    pub voluntary_switch_history: VecDeque<f64>, // Context switches per second
+    pub involuntary_switch_history: VecDeque<f64>,
+    pub user_cpu_history: VecDeque<f64>, // Percent of the whole machine, like cpu_history
+    pub system_cpu_history: VecDeque<f64>,
+    /// Wall clock time of each sample
+    pub sample_times: VecDeque<SystemTime>,
+}
+
+impl ProcessHistory {
+    pub fn add_sample(
+        &mut self,
+        cpu: f32,
+        memory: u64,
+        disk_read: u64,
+        disk_write: u64,
+        gpu_mem: f32,
+        gpu_util: f32,
+        net_rx: u64,
+        net_tx: u64,
+        max_samples: usize,
+    ) {
+        self.cpu_history.push_back(cpu);
+        self.memory_history.push_back(memory);
+        self.disk_read_history.push_back(disk_read);
+        self.disk_write_history.push_back(disk_write);
+        self.gpu_mem_history.push_back(gpu_mem);
+        self.gpu_util_history.push_back(gpu_util);
+        self.net_rx_history.push_back(net_rx);
+        self.net_tx_history.push_back(net_tx);
+
+        // Keep only the last max_samples - O(1) pop_front instead of O(n) remove(0)
+        while self.cpu_history.len() > max_samples {
+            self.cpu_history.pop_front();
+        }
+        while self.memory_history.len() > max_samples {
+            self.memory_history.pop_front();
+        }
+        while self.disk_read_history.len() > max_samples {
+            self.disk_read_history.pop_front();
+        }
+        while self.disk_write_history.len() > max_samples {
+            self.disk_write_history.pop_front();
+        }
+        while self.gpu_mem_history.len() > max_samples {
+            self.gpu_mem_history.pop_front();
+        }
+        while self.gpu_util_history.len() > max_samples {
+            self.gpu_util_history.pop_front();
+        }
+        while self.net_rx_history.len() > max_samples {
+            self.net_rx_history.pop_front();
+        }
+        while self.net_tx_history.len() > max_samples {
+            self.net_tx_history.pop_front();
+        }
+    }
+
+    /// Add page fault rates, aligned with the samples of `add_sample`
+    pub fn add_fault_sample(&mut self, faults: FaultRates, max_samples: usize) {
+        self.minor_fault_history.push_back(faults.minor_per_sec);
+        self.major_fault_history.push_back(faults.major_per_sec);
+        while self.minor_fault_history.len() > max_samples {
+            self.minor_fault_history.pop_front();
+        }
+        while self.major_fault_history.len() > max_samples {
+            self.major_fault_history.pop_front();
+        }
+    }
+
+    /// Add context switch rates, aligned with the samples of `add_sample`
+    pub fn add_switch_sample(&mut self, switches: SwitchRates, max_samples: usize) {
+        self.voluntary_switch_history.push_back(switches.voluntary_per_sec);
+        self.involuntary_switch_history.push_back(switches.involuntary_per_sec);
+        while self.voluntary_switch_history.len() > max_samples {
+            self.voluntary_switch_history.pop_front();
+        }
+        while self.involuntary_switch_history.len() > max_samples {
+            self.involuntary_switch_history.pop_front();
+        }
+    }
+
+    /// Add the user/system CPU split, aligned with the samples of `add_sample`
+    pub fn add_cpu_split_sample(&mut self, split: CpuSplit, max_samples: usize) {
+        self.user_cpu_history.push_back(split.user_percent);
+        self.system_cpu_history.push_back(split.system_percent);
+        while self.user_cpu_history.len() > max_samples {
+            self.user_cpu_history.pop_front();
+        }
+        while self.system_cpu_history.len() > max_samples {
+            self.system_cpu_history.pop_front();
+        }
+    }
+
+    /// Add the time of a sample, aligned with the samples of `add_sample`
+    pub fn add_sample_time(&mut self, time: SystemTime, max_samples: usize) {
+        self.sample_times.push_back(time);
+        while self.sample_times.len() > max_samples {
+            self.sample_times.pop_front();
+        }
+    }
+
+    /// Trim history to new max samples
+    pub fn trim_to(&mut self, max_samples: usize) {
+        while self.cpu_history.len() > max_samples {
+            self.cpu_history.pop_front();
+        }
+        while self.memory_history.len() > max_samples {
+            self.memory_history.pop_front();
+        }
+        while self.disk_read_history.len() > max_samples {
+            self.disk_read_history.pop_front();
+        }
+        while self.disk_write_history.len() > max_samples {
+            self.disk_write_history.pop_front();
+        }
+        while self.gpu_mem_history.len() > max_samples {
+            self.gpu_mem_history.pop_front();
+        }
+        while self.gpu_util_history.len() > max_samples {
+            self.gpu_util_history.pop_front();
+        }
+        while self.net_rx_history.len() > max_samples {
+            self.net_rx_history.pop_front();
+        }
+        while self.net_tx_history.len() > max_samples {
+            self.net_tx_history.pop_front();
+        }
+        while self.minor_fault_history.len() > max_samples {
+            self.minor_fault_history.pop_front();
+        }
+        while self.major_fault_history.len() > max_samples {
+            self.major_fault_history.pop_front();
+        }
+        while self.voluntary_switch_history.len() > max_samples {
+            self.voluntary_switch_history.pop_front();
+        }
+        while self.involuntary_switch_history.len() > max_samples {
+            self.involuntary_switch_history.pop_front();
+        }
+        while self.user_cpu_history.len() > max_samples {
+            self.user_cpu_history.pop_front();
+        }
+        while self.system_cpu_history.len() > max_samples {
+            self.system_cpu_history.pop_front();
+        }
+        while self.sample_times.len() > max_samples {
+            self.sample_times.pop_front();
+        }
+    }
+}
+
+/// Last observed activity of a process, tracked for every process (not only the top 150)
+#[derive(Debug, Clone)]
+struct ProcessActivity {
+    name: String,
+    memory_bytes: u64,
+    /// Last refresh with CPU, disk or network activity (or when first seen)
+    last_active: Instant,
+    first_seen: Instant,
+    /// TCP bytes received and sent since first seen
+    net_rx_total: u64,
+    net_tx_total: u64,
+    /// Bytes read from and written to disk since first seen
+    disk_read_total: u64,
+    disk_write_total: u64,
+}
+
+/// Network traffic of a process accumulated since Procular first saw it
+#[derive(Debug, Clone, Copy)]
+pub struct NetTotals {
+    pub received: u64,
+    pub sent: u64,
+    /// How long the process has been tracked
+    pub tracked_for: Duration,
+}
+
+/// Disk and network traffic of one process accumulated since Procular first saw it
+#[derive(Debug, Clone)]
+pub struct ProcessTotals {
+    pub key: ProcessKey,
+    pub name: String,
+    pub disk_read: u64,
+    pub disk_written: u64,
+    pub net_received: u64,
+    pub net_sent: u64,
+}
+
+/// A process that has shown no CPU, disk or network activity for a while
+#[derive(Debug, Clone)]
+pub struct IdleProcess {
+    pub pid: u32,
+    pub name: String,
+    /// Time since the last observed activity (bounded by how long we have been monitoring)
+    pub idle_for: Duration,
+    /// Memory that terminating the process would free (PSS, or RSS if unavailable)
+    pub memory_bytes: u64,
+}
+
+/// Video memory of one NVIDIA GPU
+#[derive(Debug, Clone)]
+pub struct GpuMemory {
+    pub index: u32,
+    pub name: String,
+    pub used: u64,
+    pub total: u64,
+}
+
+impl GpuMemory {
+    pub fn used_percent(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.used as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// A process holding video memory on one GPU
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuProcess {
+    pub pid: u32,
+    pub name: String,
+    pub gpu_index: u32,
+    pub vram_bytes: u64,
+}
+
+/// Percentage points usage must drop below the threshold before a GPU can alert again
+const VRAM_ALERT_HYSTERESIS_PERCENT: f64 = 5.0;
+
+/// Reports GPUs once when their video memory crosses a "nearly full" threshold
+#[derive(Default)]
+pub struct VramFullDetector {
+    /// Indices of GPUs currently above the threshold
+    alerted: Vec<u32>,
+}
+
+impl VramFullDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// GPUs that crossed `threshold_percent` since the last check
+    pub fn check<'a>(&mut self, gpus: &'a [GpuMemory], threshold_percent: f64) -> Vec<&'a GpuMemory> {
+        self.alerted.retain(|index| {
+            gpus.iter().any(|gpu| {
+                gpu.index == *index && gpu.used_percent() >= threshold_percent - VRAM_ALERT_HYSTERESIS_PERCENT
+            })
+        });
+
+        let mut crossed = Vec::new();
+        for gpu in gpus {
+            if gpu.used_percent() >= threshold_percent && !self.alerted.contains(&gpu.index) {
+                self.alerted.push(gpu.index);
+                crossed.push(gpu);
+            }
+        }
+        crossed
+    }
+}
+
+/// What one reading of the system needs to know from the monitor
+struct SampleRequest {
+    collectors: Collectors,
+    process_limit: Option<usize>,
+    pins: Pins,
+    tracked: HashSet<ProcessKey>,
+}
+
+/// One reading of the system, applied to the monitor by `SystemMonitor::finish_refresh`
+pub struct Sample {
+    /// Seconds since the previous reading
+    elapsed: f64,
+    /// Whether a gap (suspend or clock jump) preceded this reading
+    gap: bool,
+    time: SystemTime,
+    net_rx_rate: u64,
+    net_tx_rate: u64,
+    core_usage: Vec<f32>,
+    swap_in: f64,
+    swap_out: f64,
+    sensors: Vec<Sensor>,
+    disks: Vec<DiskActivity>,
+    /// None when the GPU collector is off
+    gpu_utilization: Option<f32>,
+    /// Every process: the listed ones first, then tracked ones outside the list, then the rest
+    processes: Vec<ProcessInfo>,
+    listed: usize,
+    recorded: usize,
+    /// Start time of every task by PID, threads included
+    start_times: HashMap<u32, u64>,
+}
+
+/// Reads the system for a refresh. This is the slow part of a refresh, so it holds no state
+/// the UI needs and can run on a worker thread while `SystemMonitor` stays on the main thread
+struct Sampler {
+    system: System,
+    nvml: Option<Arc<nvml_wrapper::Nvml>>,
+    cpu_count: usize,
+    // Network totals at the last reading (system-wide)
+    last_net_rx: u64,
+    last_net_tx: u64,
+    // Pages swapped in/out since boot at the last reading
+    last_swap_pages: Option<(u64, u64)>,
+    // Block device counters at the last reading
+    disks: DiskTracker,
+    // Per-process TCP accounting
+    net_usage: NetUsageTracker,
+    // Listening sockets per process
+    listening_ports: ListeningPortTracker,
+    // Context switch counters per process, for wakeups and switch rates
+    wakeups: WakeupTracker,
+    // Page fault counters per process
+    faults: FaultTracker,
+    // User and system CPU time per process
+    cpu_times: CpuTimeTracker,
+    // PSS/USS per process, re-read every few refreshes
+    rollups: RollupCache,
+    // Monotonic and wall clock time of the last reading, for gap detection
+    last_refresh: (Instant, SystemTime),
+}
+
+impl Sampler {
+    fn new(nvml: Option<Arc<nvml_wrapper::Nvml>>) -> Self {
+        let mut system = System::new();
+
+        // Get CPU count for normalization
+        system.refresh_cpu_all();
+        let cpu_count = system.cpus().len().max(1);
+
+        // Initial refresh to populate CPU usage (needs two samples)
+        let refresh_kind = ProcessRefreshKind::new()
+            .with_cpu()
+            .with_memory()
+            .with_disk_usage();
+        system.refresh_processes_specifics(ProcessesToUpdate::All, refresh_kind);
+
+        // Initialize network tracking
+        let (net_rx, net_tx) = read_network_totals();
+
+        Self {
+            system,
+            nvml,
+            cpu_count,
+            last_net_rx: net_rx,
+            last_net_tx: net_tx,
+            last_swap_pages: read_swap_pages(),
+            disks: DiskTracker::new(),
+            net_usage: NetUsageTracker::new(),
+            listening_ports: ListeningPortTracker::new(),
+            wakeups: WakeupTracker::new(),
+            faults: FaultTracker::new(),
+            cpu_times: CpuTimeTracker::new(cpu_count),
+            rollups: RollupCache::new(),
+            last_refresh: (Instant::now(), SystemTime::now()),
+        }
+    }
+
+    /// Read every process, sorted by total CPU usage and grouped by TGID. The busiest ones up to the
+    /// limit are listed, plus pinned ones, zombies and any in uninterruptible sleep
+    fn sample(&mut self, request: &SampleRequest) -> Sample {
+        let collectors = request.collectors;
+        let mut refresh_kind = ProcessRefreshKind::new().with_cpu().with_memory();
+        if collectors.disk_io {
+            refresh_kind = refresh_kind.with_disk_usage();
+        }
+        self.system.refresh_processes_specifics(ProcessesToUpdate::All, refresh_kind);
+
+        // Monotonic time stops during suspend while wall clock time keeps running
+        let elapsed = self.last_refresh.0.elapsed().as_secs_f64().max(0.001);
+        let gap = self.detect_gap();
+
+        // Update network rates (system-wide)
+        let (net_rx, net_tx) = read_network_totals();
+        let net_rx_rate = (net_rx.saturating_sub(self.last_net_rx) as f64 / elapsed) as u64;
+        let net_tx_rate = (net_tx.saturating_sub(self.last_net_tx) as f64 / elapsed) as u64;
+        self.last_net_rx = net_rx;
+        self.last_net_tx = net_tx;
+
+        // Per-core utilization
+        self.system.refresh_cpu_usage();
+        let core_usage = self.system.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+
+        // Swap activity (system-wide)
+        let swap_pages = read_swap_pages();
+        let (swap_in, swap_out) = match (swap_pages, self.last_swap_pages) {
+            (Some((now_in, now_out)), Some((last_in, last_out))) => {
+                let page_size = page_size() as f64;
+                (
+                    now_in.saturating_sub(last_in) as f64 * page_size / elapsed,
+                    now_out.saturating_sub(last_out) as f64 * page_size / elapsed,
+                )
+            }
+            _ => (0.0, 0.0),
+        };
+        self.last_swap_pages = swap_pages;
+
+        // Block device activity (system-wide)
+        let disks = self.disks.sample(elapsed);
+
+        // Temperatures
+        let mut sensors = read_sensors();
+        if collectors.gpu {
+            sensors.extend(self.nvidia_temperatures());
+        }
+
+        // Get GPU utilization (system-wide) and GPU memory usage per process if available
+        let (gpu_utilization, gpu_usage) = if collectors.gpu {
+            (Some(self.get_gpu_utilization()), self.get_gpu_process_usage())
+        } else {
+            (None, HashMap::new())
+        };
+
+        // Per-process network bytes since the last refresh (attributed to the TGID)
+        let (net_usage, mut listening_ports) = if collectors.network {
+            (self.net_usage.sample(), self.listening_ports.sample())
+        } else {
+            (HashMap::new(), HashMap::new())
+        };
+
+        // Normalize CPU by dividing by CPU count
+        let cpu_divisor = self.cpu_count as f32;
+
+        // TGID (Thread Group ID) identifies which thread group a process belongs to
+        // - If PID == TGID: this is the thread group leader (main process)
+        // - If PID != TGID: this is a thread belonging to the group with that TGID
+        // sysinfo already lists each process's tasks from /proc/<pid>/task, so no per-task reads are needed
+        let tgids: HashMap<u32, u32> = if collectors.thread_grouping {
+            self.system
+                .processes()
+                .iter()
+                .filter_map(|(pid, proc)| Some((pid.as_u32(), proc.tasks()?)))
+                .flat_map(|(tgid, tasks)| tasks.iter().map(move |tid| (tid.as_u32(), tgid)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        // First pass: collect all processes with their TGID
+        let mut all_processes: HashMap<u32, (ProcessInfo, Option<u32>)> = HashMap::new();
+        let mut start_times: HashMap<u32, u64> = HashMap::new();
+
+        for (pid, proc) in self.system.processes() {
+            let pid_u32 = pid.as_u32();
+            start_times.insert(pid_u32, proc.start_time());
+            // Threads are skipped without grouping, or when their process is unknown (a rare race)
+            let tgid = tgids.get(&pid_u32).copied();
+            if tgid.is_none() && proc.thread_kind() == Some(ThreadKind::Userland) {
+                continue;
+            }
+            let normalized_cpu = proc.cpu_usage() / cpu_divisor;
+            let (net_rx_bytes, net_tx_bytes) = net_usage.get(&pid_u32).copied().unwrap_or((0, 0));
+
+            let info = ProcessInfo {
+                pid: pid_u32,
+                start_time: proc.start_time(),
+                name: proc.name().to_string_lossy().to_string(),
+                parent_pid: proc.parent().map(|parent| parent.as_u32()),
+                cpu_percent: normalized_cpu,
+                memory_bytes: proc.memory(),
+                disk_read_rate: (proc.disk_usage().read_bytes as f64 / elapsed) as u64,
+                disk_write_rate: (proc.disk_usage().written_bytes as f64 / elapsed) as u64,
+                gpu_percent: gpu_usage.get(&pid_u32).copied(),
+                net_rx_bytes,
+                net_tx_bytes,
+                tracer_pid: None,
+                listening_ports: listening_ports.remove(&pid_u32).unwrap_or_default(),
+                scheduling: None,
+                nice: None,
+                state: ProcessState::from_status(proc.status()),
+                kernel_thread: proc.thread_kind() == Some(ThreadKind::Kernel),
+                pinned: false,
+                uid: proc.user_id().map(|uid| **uid),
+                frozen: false,
+                unit: None,
+                origin: None,
+                sandbox: None,
+                wakeups_per_sec: None,
+                context_switches: None,
+                allowed_cpus: None,
+                cpu_times: None,
+                cpu_split: None,
+                page_faults: None,
+                pss_bytes: None,
+                uss_bytes: None,
+                children: Vec::new(),
+                is_group: false,
+            };
+
+            all_processes.insert(pid_u32, (info, tgid));
+        }
+
+        // Second pass: identify threads (PID != TGID) and group leaders (PID == TGID)
+        let mut thread_group_leaders: HashMap<u32, ProcessInfo> = HashMap::new();
+        let mut threads_by_tgid: HashMap<u32, Vec<ProcessInfo>> = HashMap::new();
+
+        for (pid, (proc_info, tgid)) in all_processes {
+            match tgid {
+                Some(tgid) if tgid != pid => {
+                    // This is a thread (PID != TGID), group it under its TGID
+                    threads_by_tgid
+                        .entry(tgid)
+                        .or_default()
+                        .push(proc_info);
+                }
+                _ => {
+                    // This is a thread group leader (PID == TGID) or TGID unknown
+                    thread_group_leaders.insert(pid, proc_info);
+                }
+            }
+        }
+
+        // Third pass: attach threads to their group leaders
+        for (tgid, threads) in threads_by_tgid {
+            if let Some(leader) = thread_group_leaders.get_mut(&tgid) {
+                leader.is_group = true;
+                leader.children = threads;
+            }
+            // If the leader doesn't exist (rare race condition), threads are dropped
+        }
+
+        // Convert to vec and sort by total CPU usage
+        let mut processes: Vec<ProcessInfo> = thread_group_leaders.into_values().collect();
+        processes.sort_by(|a, b| {
+            b.total_cpu().partial_cmp(&a.total_cpu()).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // List the top processes, but keep recording tracked ones that fell out of the list.
+        // Processes in uninterruptible sleep use no CPU but are what one looks for when the system
+        // hangs, and zombies point at a parent that fails to reap them, so both are always listed,
+        // as are pinned processes
+        for proc in &mut processes {
+            proc.pinned = request.pins.is_pinned(&proc.key(), &proc.name);
+        }
+        let (stuck, below_cutoff): (Vec<ProcessInfo>, Vec<ProcessInfo>) = processes
+            .split_off(processes.len().min(request.process_limit.unwrap_or(usize::MAX)))
+            .into_iter()
+            .partition(|p| p.pinned || matches!(p.state, ProcessState::DiskSleep | ProcessState::Zombie));
+        processes.extend(stuck);
+        let listed = processes.len();
+        let (tracked, rest): (Vec<ProcessInfo>, Vec<ProcessInfo>) =
+            below_cutoff.into_iter().partition(|p| request.tracked.contains(&p.key()));
+        processes.extend(tracked);
+        let recorded = processes.len();
+
+        // The tracer, scheduling, unit, origin, sandbox, freezer state, context switches, CPU times, page faults,
+        // affinity and PSS/USS need /proc reads,
+        // so only for processes that are recorded, and except for the tracer only with the process details collector
+        let own_uid = unsafe { libc::getuid() };
+        let mut frozen_cgroups: HashMap<String, bool> = HashMap::new();
+        for proc in &mut processes {
+            proc.tracer_pid = read_tracer(proc.pid);
+            if !collectors.process_details {
+                continue;
+            }
+            let cgroup = cgroup_path(proc.pid);
+            proc.unit = cgroup.as_deref().and_then(unit_from_cgroup);
+            let uid = fs::metadata(format!("/proc/{}", proc.pid)).ok().map(|m| m.uid());
+            proc.origin = Some(process_origin(cgroup.as_deref(), uid, own_uid));
+            proc.sandbox = detect_sandbox(proc.pid, cgroup.as_deref());
+            proc.frozen = cgroup.is_some_and(|path| {
+                *frozen_cgroups.entry(path).or_insert_with_key(|path| is_cgroup_frozen(path))
+            });
+            proc.scheduling = read_scheduling(proc.pid);
+            proc.nice = get_priority(proc.pid).ok();
+            for thread in &mut proc.children {
+                thread.scheduling = read_scheduling(thread.pid);
+            }
+            let tids = std::iter::once(proc.pid).chain(proc.children.iter().map(|t| t.pid));
+            proc.context_switches = self.wakeups.sample(proc.key(), tids);
+            proc.wakeups_per_sec = proc.context_switches.map(|switches| switches.voluntary_per_sec);
+            (proc.cpu_times, proc.cpu_split) = self.cpu_times.sample(proc.key());
+            proc.page_faults = self.faults.sample(proc.key());
+            proc.allowed_cpus = read_allowed_cpus(proc.pid, self.cpu_count);
+            let rollup = self.rollups.get(proc.key());
+            proc.pss_bytes = rollup.map(|(pss, _)| pss);
+            proc.uss_bytes = rollup.map(|(_, uss)| uss);
+        }
+        self.wakeups.finish_refresh();
+        self.faults.finish_refresh();
+        self.cpu_times.finish_refresh();
+        self.rollups.finish_refresh();
+        processes.extend(rest);
+
+        Sample {
+            elapsed,
+            gap,
+            time: self.last_refresh.1,
+            net_rx_rate,
+            net_tx_rate,
+            core_usage,
+            swap_in,
+            swap_out,
+            sensors,
+            disks,
+            gpu_utilization,
+            processes,
+            listed,
+            recorded,
+            start_times,
+        }
+    }
+
+    /// Record the time of this reading and report whether a gap preceded it
+    fn detect_gap(&mut self) -> bool {
+        let (last_mono, last_wall) = self.last_refresh;
+        let now = (Instant::now(), SystemTime::now());
+        self.last_refresh = now;
+
+        let mono_elapsed = now.0.duration_since(last_mono);
+        match now.1.duration_since(last_wall) {
+            Ok(wall_elapsed) => wall_elapsed.max(mono_elapsed) - wall_elapsed.min(mono_elapsed) > GAP_CLOCK_JUMP,
+            Err(_) => true, // Clock went backwards
+        }
+    }
+
+    /// Get GPU usage per process (NVIDIA only)
+    fn get_gpu_process_usage(&self) -> HashMap<u32, f32> {
+        use nvml_wrapper::enums::device::UsedGpuMemory;
+
+        let mut usage = HashMap::new();
+
+        if let Some(ref nvml) = self.nvml {
+            // Try to get device count
+            if let Ok(device_count) = nvml.device_count() {
+                for i in 0..device_count {
+                    if let Ok(device) = nvml.device_by_index(i) {
+                        // Get running compute processes
+                        if let Ok(processes) = device.running_compute_processes() {
+                            for proc in processes {
+                                if let Ok(mem_info) = device.memory_info() {
+                                    if mem_info.total > 0 {
+                                        let mem_used = match proc.used_gpu_memory {
+                                            UsedGpuMemory::Used(bytes) => bytes,
+                                            UsedGpuMemory::Unavailable => 0,
+                                        };
+                                        let percent = (mem_used as f32 / mem_info.total as f32) * 100.0;
+                                        usage.insert(proc.pid, percent);
+                                    }
+                                }
+                            }
+                        }
+                        // Also check graphics processes - take max of compute and graphics usage
+                        if let Ok(processes) = device.running_graphics_processes() {
+                            for proc in processes {
+                                if let Ok(mem_info) = device.memory_info() {
+                                    if mem_info.total > 0 {
+                                        let mem_used = match proc.used_gpu_memory {
+                                            UsedGpuMemory::Used(bytes) => bytes,
+                                            UsedGpuMemory::Unavailable => 0,
+                                        };
+                                        let percent = (mem_used as f32 / mem_info.total as f32) * 100.0;
+                                        usage
+                                            .entry(proc.pid)
+                                            .and_modify(|existing| *existing = existing.max(percent))
+                                            .or_insert(percent);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        usage
+    }
+
+    /// Get overall GPU utilization (NVIDIA only)
+    /// Temperatures of NVIDIA GPUs, which the proprietary driver does not expose through hwmon
+    fn nvidia_temperatures(&self) -> Vec<Sensor> {
+        use nvml_wrapper::enum_wrappers::device::{TemperatureSensor, TemperatureThreshold};
+
+        let Some(nvml) = &self.nvml else {
+            return Vec::new();
+        };
+        let device_count = nvml.device_count().unwrap_or(0);
+        (0..device_count)
+            .filter_map(|index| {
+                let device = nvml.device_by_index(index).ok()?;
+                let celsius = device.temperature(TemperatureSensor::Gpu).ok()?;
+                let slowdown = device.temperature_threshold(TemperatureThreshold::Slowdown).ok();
+                let name = device.name().unwrap_or_else(|_| format!("GPU {}", index));
+                Some(Sensor::nvidia_gpu(index, &name, celsius as f64, slowdown.map(|t| t as f64)))
+            })
+            .collect()
+    }
+
+    fn get_gpu_utilization(&self) -> f32 {
+        if let Some(ref nvml) = self.nvml {
+            if let Ok(device_count) = nvml.device_count() {
+                let mut total_util = 0.0f32;
+                let mut count = 0;
+                for i in 0..device_count {
+                    if let Ok(device) = nvml.device_by_index(i) {
+                        if let Ok(utilization) = device.utilization_rates() {
+                            total_util += utilization.gpu as f32;
+                            count += 1;
+                        }
+                    }
+                }
+                if count > 0 {
+                    return total_util / count as f32;
+                }
+            }
+        }
+        0.0
+    }
+}
+
+/// A panic while reading leaves nothing half-updated that matters, so a poisoned lock is reused
+fn lock_sampler(sampler: &Mutex<Sampler>) -> std::sync::MutexGuard<'_, Sampler> {
+    sampler.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// System monitor that collects process information
+pub struct SystemMonitor {
+    // Reads the system; shared with the worker thread of a background refresh
+    sampler: Arc<Mutex<Sampler>>,
+    process_history: HashMap<ProcessKey, ProcessHistory>,
+    nvml: Option<Arc<nvml_wrapper::Nvml>>,
+    cpu_count: usize,
+    max_samples: usize,
+    // Network rates (system-wide)
+    net_rx_rate: u64,
+    net_tx_rate: u64,
+    // GPU utilization (system-wide)
+    gpu_utilization: f32,
+    // Activity tracking for idle detection
+    activity: HashMap<ProcessKey, ProcessActivity>,
+    // Per-program usage of the last day for the top offenders report
+    offenders: OffenderLog,
+    // Per-core utilization history (0-100% per core)
+    core_history: Vec<VecDeque<f32>>,
+    // System-wide swap-in and swap-out rates (bytes per second)
+    swap_in_history: VecDeque<f64>,
+    swap_out_history: VecDeque<f64>,
+    // Temperature sensors at the last refresh and their history (degrees Celsius) by sensor ID
+    sensors: Vec<Sensor>,
+    sensor_history: HashMap<String, VecDeque<f64>>,
+    // Block device activity at the last refresh and its history by device name
+    disks: Vec<DiskActivity>,
+    disk_history: HashMap<String, DiskHistory>,
+    // Totals per user of every process at the last refresh
+    users: Vec<UserUsage>,
+    // Result of the last refresh
+    latest: Vec<ProcessInfo>,
+    // Number of processes the last refresh left out of the list
+    hidden: usize,
+    // Number of busiest processes listed; None lists every process
+    process_limit: Option<usize>,
+    // Start time of every task at the last refresh by PID, threads included
+    start_times: HashMap<u32, u64>,
+    // Processes whose history is kept regardless of rank, with the number of track() calls
+    tracked: HashMap<ProcessKey, usize>,
+    // Per refresh: whether a gap (suspend or clock jump) preceded it, aligned with the newest samples
+    sample_gaps: VecDeque<bool>,
+    // Wall clock time of each refresh, aligned like `sample_gaps`
+    sample_times: VecDeque<SystemTime>,
+    // Wall clock time of the last refresh
+    last_refresh: SystemTime,
+    // Optional work done on refresh
+    collectors: Collectors,
+    // Processes always returned and listed first
+    pins: Pins,
+}
+
+impl Default for SystemMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemMonitor {
+    pub fn new() -> Self {
+        // Try to initialize NVML for GPU monitoring
+        let nvml = nvml_wrapper::Nvml::init().ok().map(Arc::new);
+        if nvml.is_some() {
+            eprintln!("NVIDIA GPU monitoring enabled");
+        }
+
+        let sampler = Sampler::new(nvml.clone());
+        let cpu_count = sampler.cpu_count;
+
+        Self {
+            sampler: Arc::new(Mutex::new(sampler)),
+            process_history: HashMap::new(),
+            nvml,
+            cpu_count,
+            max_samples: 60, // Default: 2 minutes at 2-second intervals
+            net_rx_rate: 0,
+            net_tx_rate: 0,
+            gpu_utilization: 0.0,
+            activity: HashMap::new(),
+            offenders: OffenderLog::new(cpu_count),
+            core_history: vec![VecDeque::new(); cpu_count],
+            swap_in_history: VecDeque::new(),
+            swap_out_history: VecDeque::new(),
+            sensors: Vec::new(),
+            sensor_history: HashMap::new(),
+            disks: Vec::new(),
+            disk_history: HashMap::new(),
+            users: Vec::new(),
+            latest: Vec::new(),
+            hidden: 0,
+            process_limit: Some(DEFAULT_PROCESS_LIMIT),
+            start_times: HashMap::new(),
+            tracked: HashMap::new(),
+            sample_gaps: VecDeque::new(),
+            sample_times: VecDeque::new(),
+            last_refresh: SystemTime::now(),
+            collectors: Collectors::default(),
+            pins: Pins::default(),
+        }
+    }
+
+    /// Choose the optional work done on refresh
+    pub fn set_collectors(&mut self, collectors: Collectors) {
+        self.collectors = collectors;
+        if !collectors.gpu {
+            self.gpu_utilization = 0.0;
+        }
+    }
+
+    pub fn collectors(&self) -> Collectors {
+        self.collectors
+    }
+
+    /// List at most `limit` of the busiest processes (pinned and stuck ones come on top); None lists all
+    pub fn set_process_limit(&mut self, limit: Option<usize>) {
+        self.process_limit = limit;
+    }
+
+    pub fn process_limit(&self) -> Option<usize> {
+        self.process_limit
+    }
+
+    /// Number of processes the last refresh left out of the list because of the limit
+    pub fn hidden_processes(&self) -> usize {
+        self.hidden
+    }
+
+    /// Pin every process whose name matches one of the patterns
+    pub fn set_pinned_names(&mut self, names: Vec<String>) {
+        self.pins.names = names;
+    }
+
+    /// Pin one process until it exits
+    pub fn pin(&mut self, key: ProcessKey) {
+        self.pins.keys.insert(key);
+    }
+
+    pub fn unpin(&mut self, key: ProcessKey) {
+        self.pins.keys.remove(&key);
+    }
+
+    pub fn pins(&self) -> &Pins {
+        &self.pins
+    }
+
+    /// Set the maximum number of history samples to keep
+    pub fn set_max_samples(&mut self, max_samples: usize) {
+        self.max_samples = max_samples;
+        // Trim existing histories
+        for history in self.process_history.values_mut() {
+            history.trim_to(max_samples);
+        }
+        for history in &mut self.core_history {
+            while history.len() > max_samples {
+                history.pop_front();
+            }
+        }
+        for history in [&mut self.swap_in_history, &mut self.swap_out_history]
+            .into_iter()
+            .chain(self.sensor_history.values_mut())
+        {
+            while history.len() > max_samples {
+                history.pop_front();
+            }
+        }
+        for history in self.disk_history.values_mut() {
+            history.trim_to(max_samples);
+        }
+        while self.sample_gaps.len() > max_samples {
+            self.sample_gaps.pop_front();
+        }
+        while self.sample_times.len() > max_samples {
+            self.sample_times.pop_front();
+        }
+    }
+
+    /// Get current max samples setting
+    #[allow(dead_code)]
+    pub fn max_samples(&self) -> usize {
+        self.max_samples
+    }
+
+    /// Get CPU count
+    #[allow(dead_code)]
+    pub fn cpu_count(&self) -> usize {
+        self.cpu_count
+    }
+
+    /// Get current network RX rate (bytes per second)
+    #[allow(dead_code)]
+    pub fn net_rx_rate(&self) -> u64 {
+        self.net_rx_rate
+    }
+
+    /// Get current network TX rate (bytes per second)
+    #[allow(dead_code)]
+    pub fn net_tx_rate(&self) -> u64 {
+        self.net_tx_rate
+    }
+
+    /// Get current GPU utilization (system-wide, percentage)
+    #[allow(dead_code)]
+    pub fn gpu_utilization(&self) -> f32 {
+        self.gpu_utilization
+    }
+
+    /// Refresh process data and return the busiest processes up to the limit, plus pinned ones,
+    /// zombies and any in uninterruptible sleep, grouped by TGID. Blocks while the system is read;
+    /// `start_refresh` reads it on a worker thread instead
+    pub fn refresh(&mut self) -> Vec<ProcessInfo> {
+        let request = self.sample_request();
+        let sample = lock_sampler(&self.sampler).sample(&request);
+        self.finish_refresh(sample)
+    }
+
+    /// Read the system on a worker thread; pass the sample to `finish_refresh` once it arrives.
+    /// The sample is cancelled only if reading panicked
+    pub fn start_refresh(&self) -> oneshot::Receiver<Sample> {
+        let (sender, receiver) = oneshot::channel();
+        let sampler = self.sampler.clone();
+        let request = self.sample_request();
+        thread::spawn(move || {
+            let sample = lock_sampler(&sampler).sample(&request);
+            // The receiver is gone when the window closed meanwhile
+            let _ = sender.send(sample);
+        });
+        receiver
+    }
+
+    fn sample_request(&self) -> SampleRequest {
+        SampleRequest {
+            collectors: self.collectors,
+            process_limit: self.process_limit,
+            pins: self.pins.clone(),
+            tracked: self.tracked.keys().copied().collect(),
+        }
+    }
+
+    /// Record a sample in the histories and return the listed processes, like `refresh`
+    pub fn finish_refresh(&mut self, sample: Sample) -> Vec<ProcessInfo> {
+        let Sample {
+            elapsed,
+            gap,
+            time,
+            net_rx_rate,
+            net_tx_rate,
+            core_usage,
+            swap_in,
+            swap_out,
+            sensors,
+            disks,
+            gpu_utilization,
+            mut processes,
+            listed,
+            recorded,
+            start_times,
+        } = sample;
+        let max_samples = self.max_samples;
+        self.last_refresh = time;
+        self.start_times = start_times;
+        self.sample_gaps.push_back(gap);
+        self.sample_times.push_back(time);
+        while self.sample_gaps.len() > max_samples {
+            self.sample_gaps.pop_front();
+        }
+        while self.sample_times.len() > max_samples {
+            self.sample_times.pop_front();
+        }
+
+        self.net_rx_rate = net_rx_rate;
+        self.net_tx_rate = net_tx_rate;
+        for (history, usage) in self.core_history.iter_mut().zip(core_usage) {
+            history.push_back(usage);
+            while history.len() > max_samples {
+                history.pop_front();
+            }
+        }
+        for (history, rate) in [(&mut self.swap_in_history, swap_in), (&mut self.swap_out_history, swap_out)] {
+            history.push_back(rate);
+            while history.len() > max_samples {
+                history.pop_front();
+            }
+        }
+
+        // Sensors that disappeared (e.g. an unplugged drive) lose their history
+        self.sensors = sensors;
+        let sensor_ids: Vec<&str> = self.sensors.iter().map(|s| s.id.as_str()).collect();
+        self.sensor_history.retain(|id, _| sensor_ids.contains(&id.as_str()));
+        for sensor in &self.sensors {
+            let history = self.sensor_history.entry(sensor.id.clone()).or_default();
+            history.push_back(sensor.celsius);
+            while history.len() > max_samples {
+                history.pop_front();
+            }
+        }
+        // Likewise for disks (a removed USB drive)
+        self.disks = disks;
+        let disk_names: Vec<&str> = self.disks.iter().map(|d| d.name.as_str()).collect();
+        self.disk_history.retain(|name, _| disk_names.contains(&name.as_str()));
+        for disk in &self.disks {
+            self.disk_history.entry(disk.name.clone()).or_default().add_sample(disk, max_samples);
+        }
+        if let Some(utilization) = gpu_utilization {
+            self.gpu_utilization = utilization;
+        }
+
+        // Track activity of every process, not only the listed ones
+        self.update_activity(&processes, elapsed);
+        self.users = usage_by_user(&processes, elapsed);
+        self.offenders.record(&processes, elapsed, time);
+
+        // Update history (use total values for groups)
+        self.hidden = processes.len() - listed;
+        processes.truncate(recorded);
+        let gpu_util = self.gpu_utilization;
+        for proc in &processes {
+            let history = self.process_history.entry(proc.key()).or_default();
+            history.add_sample(
+                proc.total_cpu(),
+                proc.total_memory(),
+                proc.total_disk_read_rate(),
+                proc.total_disk_write_rate(),
+                proc.total_gpu(),    // Per-process GPU memory
+                gpu_util,            // System-wide GPU utilization
+                proc.total_net_rx(),
+                proc.total_net_tx(),
+                max_samples,
+            );
+            history.add_fault_sample(proc.page_faults.unwrap_or_default(), max_samples);
+            history.add_switch_sample(proc.context_switches.unwrap_or_default(), max_samples);
+            history.add_cpu_split_sample(proc.cpu_split.unwrap_or_default(), max_samples);
+            history.add_sample_time(time, max_samples);
+        }
+
+        // Clean up history for processes that are no longer recorded (including recycled PIDs)
+        let current_keys: HashSet<ProcessKey> = processes.iter().map(|p| p.key()).collect();
+        self.process_history.retain(|key, _| current_keys.contains(key));
+        let activity = &self.activity;
+        self.tracked.retain(|key, _| activity.contains_key(key));
+        self.pins.keys.retain(|key| activity.contains_key(key));
+
+        processes.truncate(listed);
+        self.latest.clone_from(&processes);
+        processes
+    }
+
+    /// Keep recording history for `key` even while it is outside the top list
+    /// (selected or watched processes). Calls nest; pair each with `untrack`.
+    pub fn track(&mut self, key: ProcessKey) {
+        *self.tracked.entry(key).or_default() += 1;
+    }
+
+    /// Undo one `track` call
+    pub fn untrack(&mut self, key: ProcessKey) {
+        if let Some(count) = self.tracked.get_mut(&key) {
+            *count -= 1;
+            if *count == 0 {
+                self.tracked.remove(&key);
+            }
+        }
+    }
+
+    /// Processes returned by the last refresh
+    pub fn processes(&self) -> &[ProcessInfo] {
+        &self.latest
+    }
+
+    /// Whether the system was suspended (or the clock jumped) before each refresh, oldest first.
+    /// Aligned with the newest entries of every history, so graphs should not connect across a gap
+    pub fn sample_gaps(&self) -> &VecDeque<bool> {
+        &self.sample_gaps
+    }
+
+    /// Wall clock time of each refresh, oldest first, aligned like `sample_gaps`
+    pub fn sample_times(&self) -> &VecDeque<SystemTime> {
+        &self.sample_times
+    }
+
+    /// Utilization history of each CPU core, indexed by core number
+    pub fn core_history(&self) -> &[VecDeque<f32>] {
+        &self.core_history
+    }
+
+    /// Mean utilization of all cores at the last refresh, in percent
+    pub fn cpu_percent(&self) -> f64 {
+        let cores = &self.core_history;
+        cores.iter().filter_map(|history| history.back()).map(|&v| v as f64).sum::<f64>()
+            / cores.len().max(1) as f64
+    }
+
+    /// System-wide swap-in rate history (bytes per second)
+    pub fn swap_in_history(&self) -> &VecDeque<f64> {
+        &self.swap_in_history
+    }
+
+    /// System-wide swap-out rate history (bytes per second)
+    pub fn swap_out_history(&self) -> &VecDeque<f64> {
+        &self.swap_out_history
+    }
+
+    /// Temperature sensors read at the last refresh
+    pub fn sensors(&self) -> &[Sensor] {
+        &self.sensors
+    }
+
+    /// Temperature history of a sensor (degrees Celsius)
+    pub fn sensor_history(&self, id: &str) -> Option<&VecDeque<f64>> {
+        self.sensor_history.get(id)
+    }
+
+    /// Resource use per user at the last refresh, busiest first
+    pub fn users(&self) -> &[UserUsage] {
+        &self.users
+    }
+
+    /// Block device activity at the last refresh
+    pub fn disks(&self) -> &[DiskActivity] {
+        &self.disks
+    }
+
+    /// Recorded activity of a disk by kernel name
+    pub fn disk_history(&self, name: &str) -> Option<&DiskHistory> {
+        self.disk_history.get(name)
+    }
+
+    /// Get history for a specific process
+    pub fn get_history(&self, key: ProcessKey) -> Option<&ProcessHistory> {
+        self.process_history.get(&key)
+    }
+
+    /// Every process with recorded history (top and tracked processes), with its name
+    pub fn histories(&self) -> Vec<(ProcessKey, String, &ProcessHistory)> {
+        self.process_history
+            .iter()
+            .map(|(key, history)| {
+                let name = self.activity.get(key).map(|a| a.name.clone()).unwrap_or_default();
+                (*key, name, history)
+            })
+            .collect()
+    }
+
+    /// Per-program usage of the last day, including processes outside the top list
+    pub fn offenders(&self) -> &OffenderLog {
+        &self.offenders
+    }
+
+    /// Wall clock time of the last refresh, i.e. of the newest history samples
+    pub fn last_refresh_time(&self) -> SystemTime {
+        self.last_refresh
+    }
+
+    /// Key of the process that had `pid` at the last refresh
+    pub fn process_key(&self, pid: u32) -> Option<ProcessKey> {
+        self.start_times.get(&pid).map(|&start_time| ProcessKey { pid, start_time })
+    }
+
+    /// Record which processes did any work since the last refresh;
+    /// `elapsed` is the time since the previous refresh in seconds, to turn disk rates back into bytes
+    fn update_activity(&mut self, processes: &[ProcessInfo], elapsed: f64) {
+        let now = Instant::now();
+        for proc in processes {
+            let active = proc.total_cpu() >= IDLE_CPU_THRESHOLD
+                || proc.total_disk_rate() > 0
+                || proc.total_net_rx() + proc.total_net_tx() > 0;
+
+            let entry = self.activity.entry(proc.key()).or_insert_with(|| ProcessActivity {
+                name: String::new(),
+                memory_bytes: 0,
+                last_active: now,
+                first_seen: now,
+                net_rx_total: 0,
+                net_tx_total: 0,
+                disk_read_total: 0,
+                disk_write_total: 0,
+            });
+            entry.name.clone_from(&proc.name);
+            entry.memory_bytes = proc.memory_bytes;
+            entry.net_rx_total += proc.total_net_rx();
+            entry.net_tx_total += proc.total_net_tx();
+            entry.disk_read_total += (proc.total_disk_read_rate() as f64 * elapsed) as u64;
+            entry.disk_write_total += (proc.total_disk_write_rate() as f64 * elapsed) as u64;
+            if active {
+                entry.last_active = now;
+            }
+        }
+        let current: HashSet<ProcessKey> = processes.iter().map(|p| p.key()).collect();
+        self.activity.retain(|key, _| current.contains(key));
+    }
+
+    /// Network traffic of a process since it was first seen (tracked for every process)
+    pub fn net_totals(&self, key: ProcessKey) -> Option<NetTotals> {
+        self.activity.get(&key).map(|activity| NetTotals {
+            received: activity.net_rx_total,
+            sent: activity.net_tx_total,
+            tracked_for: activity.first_seen.elapsed(),
+        })
+    }
+
+    /// Disk and network totals of every process seen since it was first seen
+    pub fn process_totals(&self) -> Vec<ProcessTotals> {
+        self.activity
+            .iter()
+            .map(|(key, activity)| ProcessTotals {
+                key: *key,
+                name: activity.name.clone(),
+                disk_read: activity.disk_read_total,
+                disk_written: activity.disk_write_total,
+                net_received: activity.net_rx_total,
+                net_sent: activity.net_tx_total,
+            })
+            .collect()
+    }
+
+    /// Every process seen at the last refresh, with memory, CPU time and accumulated traffic
+    pub fn snapshot(&self) -> ProcessSnapshot {
+        let entries = self
+            .activity
+            .iter()
+            .map(|(key, activity)| SnapshotEntry {
+                key: *key,
+                name: activity.name.clone(),
+                command: get_command_line(key.pid),
+                memory_bytes: activity.memory_bytes,
+                cpu_time_secs: read_cpu_time_secs(key.pid).unwrap_or(0.0),
+                disk_read: activity.disk_read_total,
+                disk_written: activity.disk_write_total,
+                net_received: activity.net_rx_total,
+                net_sent: activity.net_tx_total,
+            })
+            .collect();
+        ProcessSnapshot::new(entries)
+    }
+
+    /// Processes of the current user that have been idle for at least `min_idle`,
+    /// largest memory consumers first
+    pub fn idle_processes(&self, min_idle: Duration) -> Vec<IdleProcess> {
+        let uid = unsafe { libc::getuid() };
+        let own_pid = std::process::id();
+
+        let mut idle: Vec<IdleProcess> = self
+            .activity
+            .iter()
+            .filter(|(key, activity)| {
+                key.pid != own_pid
+                    && activity.memory_bytes > 0 // Kernel threads have no memory
+                    && activity.last_active.elapsed() >= min_idle
+                    && fs::metadata(format!("/proc/{}", key.pid)).is_ok_and(|m| m.uid() == uid)
+            })
+            .map(|(&ProcessKey { pid, .. }, activity)| IdleProcess {
+                pid,
+                name: activity.name.clone(),
+                idle_for: activity.last_active.elapsed(),
+                memory_bytes: read_pss(pid).unwrap_or(activity.memory_bytes),
+            })
+            .collect();
+
+        idle.sort_by_key(|p| std::cmp::Reverse(p.memory_bytes));
+        idle
+    }
+
+    /// Whether NVIDIA GPU monitoring is available
+    pub fn has_gpu(&self) -> bool {
+        self.nvml.is_some()
+    }
+
+    /// Video memory use of each NVIDIA GPU
+    pub fn gpu_memory(&self) -> Vec<GpuMemory> {
+        let Some(nvml) = &self.nvml else {
+            return Vec::new();
+        };
+        let device_count = nvml.device_count().unwrap_or(0);
+        (0..device_count)
+            .filter_map(|index| {
+                let device = nvml.device_by_index(index).ok()?;
+                let memory = device.memory_info().ok()?;
+                Some(GpuMemory {
+                    index,
+                    name: device.name().unwrap_or_else(|_| format!("GPU {}", index)),
+                    used: memory.used,
+                    total: memory.total,
+                })
+            })
+            .collect()
+    }
+
+    /// Processes holding video memory, largest first (NVIDIA only)
+    pub fn gpu_processes(&self) -> Vec<GpuProcess> {
+        use nvml_wrapper::enums::device::UsedGpuMemory;
+
+        let Some(nvml) = &self.nvml else {
+            return Vec::new();
+        };
+        let mut processes: Vec<GpuProcess> = Vec::new();
+        let device_count = nvml.device_count().unwrap_or(0);
+        for gpu_index in 0..device_count {
+            let Ok(device) = nvml.device_by_index(gpu_index) else {
+                continue;
+            };
+            // A process can be listed as both compute and graphics user; count it once
+            let compute = device.running_compute_processes().unwrap_or_default();
+            let graphics = device.running_graphics_processes().unwrap_or_default();
+            for proc in compute.into_iter().chain(graphics) {
+                let vram_bytes = match proc.used_gpu_memory {
+                    UsedGpuMemory::Used(bytes) => bytes,
+                    UsedGpuMemory::Unavailable => 0,
+                };
+                if let Some(existing) = processes
+                    .iter_mut()
+                    .find(|p| p.pid == proc.pid && p.gpu_index == gpu_index)
+                {
+                    existing.vram_bytes = existing.vram_bytes.max(vram_bytes);
+                    continue;
+                }
+                let name = self
+                    .activity
+                    .iter()
+                    .find(|(key, _)| key.pid == proc.pid)
+                    .map(|(_, activity)| activity.name.clone())
+                    .unwrap_or_else(|| format!("PID {}", proc.pid));
+                processes.push(GpuProcess {
+                    pid: proc.pid,
+                    name,
+                    gpu_index,
+                    vram_bytes,
+                });
+            }
+        }
+        processes.sort_by_key(|p| std::cmp::Reverse(p.vram_bytes));
+        processes
+    }
+}
+
+/// Format bytes to human-readable string
+/// Format a duration as a short human readable string ("3h 12m", "45m", "20s")
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes) = (secs / 86400, (secs % 86400) / 3600, (secs % 3600) / 60);
+
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    if bytes >= GB {
+        format!("{:.1} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Format a rate in bytes per second ("1.5 MB/s")
+pub fn format_rate(bytes_per_sec: u64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec))
+}
@@ -0,0 +1,350 @@
+//! Per-process network accounting via netlink sock_diag
+//!
+//! The kernel does not expose per-process byte counters directly, so we dump all
+//! TCP sockets with `SOCK_DIAG_BY_FAMILY` (which includes `tcp_info` byte counters
+//! and the socket inode), then map socket inodes to processes by scanning
+//! `/proc/<pid>/fd`. Only TCP traffic is attributed; UDP sockets carry no byte
+//! counters in sock_diag.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::mem;
+
+// Netlink / sock_diag constants (from linux/netlink.h, linux/sock_diag.h, linux/inet_diag.h)
+const NETLINK_SOCK_DIAG: i32 = 4;
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const NLM_F_REQUEST: u16 = 0x1;
+const NLM_F_DUMP: u16 = 0x300;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const INET_DIAG_INFO: u16 = 2;
+
+// Offsets of the byte counters inside struct tcp_info
+const TCPI_BYTES_ACKED_OFFSET: usize = 120;
+const TCPI_BYTES_RECEIVED_OFFSET: usize = 128;
+
+#[repr(C)]
+struct NlMsgHdr {
+    nlmsg_len: u32,
+    nlmsg_type: u16,
+    nlmsg_flags: u16,
+    nlmsg_seq: u32,
+    nlmsg_pid: u32,
+}
+
+#[repr(C)]
+struct InetDiagSockId {
+    idiag_sport: u16,
+    idiag_dport: u16,
+    idiag_src: [u32; 4],
+    idiag_dst: [u32; 4],
+    idiag_if: u32,
+    idiag_cookie: [u32; 2],
+}
+
+#[repr(C)]
+struct InetDiagReqV2 {
+    sdiag_family: u8,
+    sdiag_protocol: u8,
+    idiag_ext: u8,
+    pad: u8,
+    idiag_states: u32,
+    id: InetDiagSockId,
+}
+
+#[repr(C)]
+struct DiagRequest {
+    header: NlMsgHdr,
+    request: InetDiagReqV2,
+}
+
+/// Size of struct inet_diag_msg (family/state/timer/retrans, sockid, expires, rqueue, wqueue, uid, inode)
+const INET_DIAG_MSG_LEN: usize = 4 + mem::size_of::<InetDiagSockId>() + 5 * 4;
+const INET_DIAG_MSG_INODE_OFFSET: usize = INET_DIAG_MSG_LEN - 4;
+
+/// Cumulative byte counters for a single TCP socket
+#[derive(Debug, Clone, Copy, Default)]
+struct SocketBytes {
+    received: u64,
+    acked: u64,
+}
+
+/// Tracks per-socket byte counters between refreshes and attributes deltas to processes
+pub struct NetUsageTracker {
+    /// Counters seen on the previous sample, keyed by socket inode
+    previous: HashMap<u32, SocketBytes>,
+    /// Cached socket inode -> owning PID (TGID)
+    inode_owner: HashMap<u32, u32>,
+    /// Active sockets a scan could not attribute, usually other users' without privileges;
+    /// remembered so they do not trigger a rescan on every sample
+    unresolved: HashSet<u32>,
+    /// Whether a baseline sample has been taken
+    primed: bool,
+}
+
+impl Default for NetUsageTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NetUsageTracker {
+    pub fn new() -> Self {
+        let mut tracker = Self {
+            previous: HashMap::new(),
+            inode_owner: HashMap::new(),
+            unresolved: HashSet::new(),
+            primed: false,
+        };
+        // Take a baseline so the first refresh does not report lifetime totals
+        tracker.sample();
+        tracker
+    }
+
+    /// Sample all TCP sockets and return bytes (rx, tx) transferred per PID since the last call
+    pub fn sample(&mut self) -> HashMap<u32, (u64, u64)> {
+        let mut usage: HashMap<u32, (u64, u64)> = HashMap::new();
+
+        let current = match dump_tcp_sockets() {
+            Ok(sockets) => sockets,
+            Err(_) => return usage, // sock_diag unavailable (e.g. restricted container)
+        };
+
+        // Compute per-socket deltas
+        let mut deltas: Vec<(u32, u64, u64)> = Vec::new();
+        for (&inode, bytes) in &current {
+            let (rx, tx) = match self.previous.get(&inode) {
+                Some(prev) => (
+                    bytes.received.saturating_sub(prev.received),
+                    bytes.acked.saturating_sub(prev.acked),
+                ),
+                // New socket since the last sample: everything it transferred is new
+                None if self.primed => (bytes.received, bytes.acked),
+                None => (0, 0),
+            };
+            if rx > 0 || tx > 0 {
+                deltas.push((inode, rx, tx));
+            }
+        }
+
+        // Only rescan /proc/<pid>/fd when an active socket is new, not just unresolved
+        let is_new = |inode: &u32| !self.inode_owner.contains_key(inode) && !self.unresolved.contains(inode);
+        if deltas.iter().any(|(inode, _, _)| is_new(inode)) {
+            self.inode_owner = map_socket_inodes_to_pids();
+            self.unresolved.extend(deltas.iter().map(|(inode, _, _)| *inode));
+            self.unresolved.retain(|inode| !self.inode_owner.contains_key(inode));
+        }
+
+        for (inode, rx, tx) in deltas {
+            if let Some(&pid) = self.inode_owner.get(&inode) {
+                let entry = usage.entry(pid).or_default();
+                entry.0 += rx;
+                entry.1 += tx;
+            }
+        }
+
+        // Forget owners of sockets that have been closed
+        let live: HashSet<u32> = current.keys().copied().collect();
+        self.inode_owner.retain(|inode, _| live.contains(inode));
+        self.unresolved.retain(|inode| live.contains(inode));
+
+        self.previous = current;
+        self.primed = true;
+        usage
+    }
+}
+
+/// Dump byte counters for all IPv4 and IPv6 TCP sockets, keyed by socket inode
+fn dump_tcp_sockets() -> io::Result<HashMap<u32, SocketBytes>> {
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            NETLINK_SOCK_DIAG,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut sockets = HashMap::new();
+    let result = dump_family(fd, libc::AF_INET as u8, &mut sockets)
+        .and_then(|_| dump_family(fd, libc::AF_INET6 as u8, &mut sockets));
+
+    unsafe {
+        libc::close(fd);
+    }
+
+    result.map(|_| sockets)
+}
+
+/// Send a dump request for one address family and collect the replies
+fn dump_family(fd: i32, family: u8, sockets: &mut HashMap<u32, SocketBytes>) -> io::Result<()> {
+    let request = DiagRequest {
+        header: NlMsgHdr {
+            nlmsg_len: mem::size_of::<DiagRequest>() as u32,
+            nlmsg_type: SOCK_DIAG_BY_FAMILY,
+            nlmsg_flags: NLM_F_REQUEST | NLM_F_DUMP,
+            nlmsg_seq: 1,
+            nlmsg_pid: 0,
+        },
+        request: InetDiagReqV2 {
+            sdiag_family: family,
+            sdiag_protocol: libc::IPPROTO_TCP as u8,
+            idiag_ext: 1 << (INET_DIAG_INFO - 1),
+            pad: 0,
+            idiag_states: u32::MAX, // All TCP states
+            id: InetDiagSockId {
+                idiag_sport: 0,
+                idiag_dport: 0,
+                idiag_src: [0; 4],
+                idiag_dst: [0; 4],
+                idiag_if: 0,
+                idiag_cookie: [0; 2],
+            },
+        },
+    };
+
+    let sent = unsafe {
+        libc::send(
+            fd,
+            &request as *const DiagRequest as *const libc::c_void,
+            mem::size_of::<DiagRequest>(),
+            0,
+        )
+    };
+    if sent < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut buf = vec![0u8; 32 * 1024];
+    loop {
+        let len = unsafe { libc::recv(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) };
+        if len < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if len == 0 {
+            return Ok(());
+        }
+
+        let data = &buf[..len as usize];
+        let mut offset = 0;
+        while offset + mem::size_of::<NlMsgHdr>() <= data.len() {
+            let msg_len = read_u32(data, offset) as usize;
+            let msg_type = read_u16(data, offset + 4);
+            if msg_len < mem::size_of::<NlMsgHdr>() || offset + msg_len > data.len() {
+                return Ok(()); // Truncated message
+            }
+
+            match msg_type {
+                NLMSG_DONE => return Ok(()),
+                NLMSG_ERROR => {
+                    return Err(io::Error::other("sock_diag request failed"));
+                }
+                SOCK_DIAG_BY_FAMILY => {
+                    let payload = &data[offset + mem::size_of::<NlMsgHdr>()..offset + msg_len];
+                    if let Some((inode, bytes)) = parse_diag_msg(payload) {
+                        sockets.insert(inode, bytes);
+                    }
+                }
+                _ => {}
+            }
+
+            offset += align4(msg_len);
+        }
+    }
+}
+
+/// Parse an inet_diag_msg payload, returning the socket inode and its tcp_info byte counters
+fn parse_diag_msg(payload: &[u8]) -> Option<(u32, SocketBytes)> {
+    if payload.len() < INET_DIAG_MSG_LEN {
+        return None;
+    }
+    let inode = read_u32(payload, INET_DIAG_MSG_INODE_OFFSET);
+    if inode == 0 {
+        return None; // Sockets in TIME_WAIT have no inode
+    }
+
+    // Walk the rtattr list following the fixed header
+    let mut offset = align4(INET_DIAG_MSG_LEN);
+    while offset + 4 <= payload.len() {
+        let rta_len = read_u16(payload, offset) as usize;
+        let rta_type = read_u16(payload, offset + 2);
+        if rta_len < 4 || offset + rta_len > payload.len() {
+            break;
+        }
+        if rta_type == INET_DIAG_INFO {
+            let info = &payload[offset + 4..offset + rta_len];
+            // Older kernels (< 4.2) don't report byte counters
+            if info.len() >= TCPI_BYTES_RECEIVED_OFFSET + 8 {
+                return Some((
+                    inode,
+                    SocketBytes {
+                        acked: read_u64(info, TCPI_BYTES_ACKED_OFFSET),
+                        received: read_u64(info, TCPI_BYTES_RECEIVED_OFFSET),
+                    },
+                ));
+            }
+        }
+        offset += align4(rta_len);
+    }
+
+    None
+}
+
+/// Build a map of socket inode -> PID by scanning /proc/<pid>/fd
+/// Only processes we are allowed to inspect will be found
+pub(crate) fn map_socket_inodes_to_pids() -> HashMap<u32, u32> {
+    let mut owners = HashMap::new();
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return owners;
+    };
+
+    for entry in entries.flatten() {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue; // Permission denied or process exited
+        };
+        for fd in fds.flatten() {
+            if let Ok(target) = fs::read_link(fd.path()) {
+                if let Some(inode) = parse_socket_inode(&target.to_string_lossy()) {
+                    owners.insert(inode, pid);
+                }
+            }
+        }
+    }
+
+    owners
+}
+
+/// Parse a "socket:[12345]" fd link target into the socket inode
+pub fn parse_socket_inode(target: &str) -> Option<u32> {
+    target
+        .strip_prefix("socket:[")
+        .and_then(|s| s.strip_suffix(']'))
+        .and_then(|s| s.parse().ok())
+}
+
+fn align4(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_ne_bytes(bytes)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_ne_bytes(bytes)
+}
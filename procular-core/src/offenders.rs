@@ -0,0 +1,257 @@
+//! Long-term record of the heaviest processes: "what was hammering the machine while I was at lunch?"
+//!
+//! The graph histories only cover minutes, so usage is also summed per program name into
+//! five-minute buckets kept for a day. When a bucket closes, only the programs among the
+//! top consumers of some metric in it are kept, which bounds memory on busy machines.
+//! Only time Procular was running is recorded.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, SystemTime};
+
+use crate::export::csv_field;
+use crate::monitor::ProcessInfo;
+
+/// Length of one bucket
+const BUCKET_SECS: u64 = 5 * 60;
+/// Buckets kept, one day
+const MAX_BUCKETS: usize = 24 * 60 * 60 / BUCKET_SECS as usize;
+/// Programs kept per metric when a bucket closes
+const KEEP_PER_METRIC: usize = 20;
+
+/// Resource a report ranks programs by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffenderMetric {
+    Cpu,
+    Memory,
+    Disk,
+    Network,
+}
+
+impl OffenderMetric {
+    pub fn all() -> &'static [OffenderMetric] {
+        &[
+            OffenderMetric::Cpu,
+            OffenderMetric::Memory,
+            OffenderMetric::Disk,
+            OffenderMetric::Network,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OffenderMetric::Cpu => "CPU",
+            OffenderMetric::Memory => "Memory",
+            OffenderMetric::Disk => "Disk I/O",
+            OffenderMetric::Network => "Network",
+        }
+    }
+}
+
+/// Usage of one program summed over the refreshes of a bucket
+#[derive(Debug, Clone, Copy, Default)]
+struct Usage {
+    /// Time the program was seen running
+    seconds: f64,
+    /// CPU percent (of the whole machine) times seconds
+    cpu_percent_seconds: f64,
+    cpu_peak: f64,
+    memory_byte_seconds: f64,
+    memory_peak: f64,
+    disk_bytes: f64,
+    /// Highest disk rate in bytes per second
+    disk_peak: f64,
+    net_bytes: f64,
+    /// Highest network rate in bytes per second
+    net_peak: f64,
+}
+
+impl Usage {
+    /// What programs are ranked by: CPU and memory integrated over time, or bytes transferred
+    fn score(&self, metric: OffenderMetric) -> f64 {
+        match metric {
+            OffenderMetric::Cpu => self.cpu_percent_seconds,
+            OffenderMetric::Memory => self.memory_byte_seconds,
+            OffenderMetric::Disk => self.disk_bytes,
+            OffenderMetric::Network => self.net_bytes,
+        }
+    }
+
+    fn merge(&mut self, other: &Usage) {
+        self.seconds += other.seconds;
+        self.cpu_percent_seconds += other.cpu_percent_seconds;
+        self.cpu_peak = self.cpu_peak.max(other.cpu_peak);
+        self.memory_byte_seconds += other.memory_byte_seconds;
+        self.memory_peak = self.memory_peak.max(other.memory_peak);
+        self.disk_bytes += other.disk_bytes;
+        self.disk_peak = self.disk_peak.max(other.disk_peak);
+        self.net_bytes += other.net_bytes;
+        self.net_peak = self.net_peak.max(other.net_peak);
+    }
+}
+
+struct Bucket {
+    start: SystemTime,
+    usage: HashMap<String, Usage>,
+}
+
+impl Bucket {
+    /// Drop programs that are not among the top consumers of any metric
+    fn prune(&mut self) {
+        let mut keep: HashSet<String> = HashSet::new();
+        for &metric in OffenderMetric::all() {
+            let mut ranked: Vec<(&String, f64)> = self
+                .usage
+                .iter()
+                .map(|(name, usage)| (name, usage.score(metric)))
+                .filter(|(_, score)| *score > 0.0)
+                .collect();
+            ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+            keep.extend(ranked.into_iter().take(KEEP_PER_METRIC).map(|(name, _)| name.clone()));
+        }
+        self.usage.retain(|name, _| keep.contains(name));
+    }
+}
+
+/// One program in a report. Averages are over the time it was running
+#[derive(Debug, Clone)]
+pub struct Offender {
+    pub name: String,
+    /// CPU percent, memory bytes, or bytes per second
+    pub average: f64,
+    pub peak: f64,
+    /// CPU time in core-seconds or bytes transferred; None for memory
+    pub total: Option<f64>,
+    /// How long the program was seen running in the period
+    pub running: Duration,
+}
+
+/// Per-program usage of the last day
+pub struct OffenderLog {
+    buckets: VecDeque<Bucket>,
+    cpu_count: usize,
+}
+
+impl OffenderLog {
+    pub fn new(cpu_count: usize) -> Self {
+        Self {
+            buckets: VecDeque::new(),
+            cpu_count: cpu_count.max(1),
+        }
+    }
+
+    /// Add one refresh of every process; `elapsed` is the time since the previous one in seconds
+    pub fn record(&mut self, processes: &[ProcessInfo], elapsed: f64, now: SystemTime) {
+        let current = self.buckets.back().is_some_and(|bucket| {
+            now.duration_since(bucket.start).is_ok_and(|age| age.as_secs() < BUCKET_SECS)
+        });
+        if !current {
+            if let Some(bucket) = self.buckets.back_mut() {
+                bucket.prune();
+            }
+            self.buckets.push_back(Bucket {
+                start: now,
+                usage: HashMap::new(),
+            });
+            while self.buckets.len() > MAX_BUCKETS {
+                self.buckets.pop_front();
+            }
+        }
+        let Some(bucket) = self.buckets.back_mut() else {
+            return;
+        };
+
+        // Sum the processes of a program first, so a browser's many processes count as one
+        let mut programs: HashMap<&str, (f64, f64, f64, f64)> = HashMap::new();
+        for proc in processes {
+            let program = programs.entry(proc.name.as_str()).or_default();
+            program.0 += proc.total_cpu() as f64;
+            program.1 += proc.total_memory() as f64;
+            program.2 += proc.total_disk_rate() as f64;
+            // Network counters are bytes since the previous refresh
+            program.3 += (proc.total_net_rx() + proc.total_net_tx()) as f64;
+        }
+        for (name, (cpu, memory, disk, net_bytes)) in programs {
+            bucket.usage.entry(name.to_string()).or_default().merge(&Usage {
+                seconds: elapsed,
+                cpu_percent_seconds: cpu * elapsed,
+                cpu_peak: cpu,
+                memory_byte_seconds: memory * elapsed,
+                memory_peak: memory,
+                disk_bytes: disk * elapsed,
+                disk_peak: disk,
+                net_bytes,
+                net_peak: net_bytes / elapsed,
+            });
+        }
+    }
+
+    /// How far back the record reaches, at most `period`
+    pub fn covered(&self, period: Duration, now: SystemTime) -> Duration {
+        self.buckets
+            .front()
+            .and_then(|bucket| now.duration_since(bucket.start).ok())
+            .unwrap_or_default()
+            .min(period)
+    }
+
+    /// The `limit` programs that used the most of `metric` in the last `period`
+    pub fn report(&self, metric: OffenderMetric, period: Duration, now: SystemTime, limit: usize) -> Vec<Offender> {
+        let cutoff = now.checked_sub(period).unwrap_or(SystemTime::UNIX_EPOCH);
+        let bucket_length = Duration::from_secs(BUCKET_SECS);
+        let mut totals: HashMap<&str, Usage> = HashMap::new();
+        for bucket in self.buckets.iter().filter(|bucket| bucket.start + bucket_length > cutoff) {
+            for (name, usage) in &bucket.usage {
+                totals.entry(name.as_str()).or_default().merge(usage);
+            }
+        }
+
+        let mut ranked: Vec<(&str, Usage)> = totals
+            .into_iter()
+            .filter(|(_, usage)| usage.score(metric) > 0.0 && usage.seconds > 0.0)
+            .collect();
+        ranked.sort_by(|a, b| b.1.score(metric).total_cmp(&a.1.score(metric)));
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(name, usage)| {
+                let (average, peak, total) = match metric {
+                    OffenderMetric::Cpu => (
+                        usage.cpu_percent_seconds / usage.seconds,
+                        usage.cpu_peak,
+                        Some(usage.cpu_percent_seconds / 100.0 * self.cpu_count as f64),
+                    ),
+                    OffenderMetric::Memory => (usage.memory_byte_seconds / usage.seconds, usage.memory_peak, None),
+                    OffenderMetric::Disk => (usage.disk_bytes / usage.seconds, usage.disk_peak, Some(usage.disk_bytes)),
+                    OffenderMetric::Network => (usage.net_bytes / usage.seconds, usage.net_peak, Some(usage.net_bytes)),
+                };
+                Offender {
+                    name: name.to_string(),
+                    average,
+                    peak,
+                    total,
+                    running: Duration::from_secs_f64(usage.seconds),
+                }
+            })
+            .collect()
+    }
+}
+
+/// CSV of reports for several metrics, one row per program and metric. Averages and peaks
+/// are percent (CPU), bytes (memory) or bytes per second; totals are core-seconds or bytes
+pub fn reports_to_csv(reports: &[(OffenderMetric, Vec<Offender>)]) -> String {
+    let mut csv = String::from("metric,name,average,peak,total,running_secs\n");
+    for (metric, offenders) in reports {
+        for offender in offenders {
+            csv.push_str(&format!(
+                "{},{},{:.2},{:.2},{},{}\n",
+                csv_field(metric.as_str()),
+                csv_field(&offender.name),
+                offender.average,
+                offender.peak,
+                offender.total.map(|total| format!("{:.2}", total)).unwrap_or_default(),
+                offender.running.as_secs()
+            ));
+        }
+    }
+    csv
+}
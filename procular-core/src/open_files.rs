@@ -0,0 +1,116 @@
+//! Open file descriptors of a process from /proc/<pid>/fd and /proc/<pid>/fdinfo
+
+use std::fs;
+use std::io;
+
+/// Kind of object a file descriptor refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FdKind {
+    File,
+    Directory,
+    Device,
+    Socket,
+    Pipe,
+    /// anon_inode objects (eventfd, epoll, timerfd, ...)
+    AnonInode,
+    Other,
+}
+
+impl FdKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FdKind::File => "File",
+            FdKind::Directory => "Directory",
+            FdKind::Device => "Device",
+            FdKind::Socket => "Socket",
+            FdKind::Pipe => "Pipe",
+            FdKind::AnonInode => "Anon Inode",
+            FdKind::Other => "Other",
+        }
+    }
+}
+
+/// A single open file descriptor
+#[derive(Debug, Clone)]
+pub struct OpenFile {
+    pub fd: u32,
+    pub kind: FdKind,
+    pub target: String,
+    /// Open flags from fdinfo (e.g. "RDWR|NONBLOCK|CLOEXEC"), empty if unreadable
+    pub flags: String,
+}
+
+/// Classify an fd link target
+fn classify_target(target: &str) -> FdKind {
+    if target.starts_with("socket:[") {
+        FdKind::Socket
+    } else if target.starts_with("pipe:[") {
+        FdKind::Pipe
+    } else if target.starts_with("anon_inode:") {
+        FdKind::AnonInode
+    } else if target.starts_with("/dev/") {
+        FdKind::Device
+    } else if target.starts_with('/') {
+        if fs::metadata(target).is_ok_and(|m| m.is_dir()) {
+            FdKind::Directory
+        } else {
+            FdKind::File
+        }
+    } else {
+        FdKind::Other
+    }
+}
+
+/// Format the octal "flags:" value of an fdinfo file
+pub fn format_open_flags(flags: i32) -> String {
+    let mut names = vec![match flags & libc::O_ACCMODE {
+        libc::O_RDONLY => "RDONLY",
+        libc::O_WRONLY => "WRONLY",
+        _ => "RDWR",
+    }];
+
+    for (flag, name) in [
+        (libc::O_APPEND, "APPEND"),
+        (libc::O_NONBLOCK, "NONBLOCK"),
+        (libc::O_SYNC, "SYNC"),
+        (libc::O_DIRECT, "DIRECT"),
+        (libc::O_PATH, "PATH"),
+        (libc::O_CLOEXEC, "CLOEXEC"),
+    ] {
+        if flags & flag == flag {
+            names.push(name);
+        }
+    }
+
+    names.join("|")
+}
+
+/// Read the open flags of a descriptor from /proc/<pid>/fdinfo/<fd>
+fn read_fd_flags(pid: u32, fd: u32) -> Option<i32> {
+    let content = fs::read_to_string(format!("/proc/{}/fdinfo/{}", pid, fd)).ok()?;
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("flags:"))
+        .and_then(|value| i32::from_str_radix(value.trim(), 8).ok())
+}
+
+/// List all open file descriptors of a process, sorted by fd number
+pub fn read_open_files(pid: u32) -> io::Result<Vec<OpenFile>> {
+    let mut files: Vec<OpenFile> = fs::read_dir(format!("/proc/{}/fd", pid))?
+        .flatten()
+        .filter_map(|entry| {
+            let fd: u32 = entry.file_name().to_string_lossy().parse().ok()?;
+            // The fd may be closed between listing and readlink
+            let target = fs::read_link(entry.path()).ok()?.to_string_lossy().to_string();
+            Some(OpenFile {
+                fd,
+                kind: classify_target(&target),
+                flags: read_fd_flags(pid, fd).map(format_open_flags).unwrap_or_default(),
+                target,
+            })
+        })
+        .collect();
+
+    files.sort_by_key(|f| f.fd);
+    Ok(files)
+}
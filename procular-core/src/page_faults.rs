@@ -0,0 +1,63 @@
+//! Per-process page fault rates from /proc/<pid>/stat
+//!
+//! Minor faults map a page that is already in memory (first touch of an allocation,
+//! shared library pages). Major faults have to wait for the disk: the page was
+//! swapped out or evicted from the page cache. A process with a steady stream of
+//! major faults is short of memory well before swap usage looks alarming.
+
+use std::collections::HashMap;
+use std::fs;
+use std::time::Instant;
+
+use crate::monitor::ProcessKey;
+
+/// Faults per second of a process and all its threads
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FaultRates {
+    pub minor_per_sec: f64,
+    pub major_per_sec: f64,
+}
+
+/// Minor and major fault totals of a process (all threads)
+pub fn read_page_faults(pid: u32) -> Option<(u64, u64)> {
+    let content = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let comm_end = content.rfind(')')?;
+    // Fields after the command name start at "state"; minflt and majflt are the 8th and 10th
+    let fields: Vec<&str> = content[comm_end + 1..].split_whitespace().take(10).collect();
+    let minor = fields.get(7)?.parse().ok()?;
+    let major = fields.get(9)?.parse().ok()?;
+    Some((minor, major))
+}
+
+/// Turns fault counters into rates between refreshes
+#[derive(Default)]
+pub struct FaultTracker {
+    /// Fault totals from the previous refresh
+    previous: HashMap<ProcessKey, ((u64, u64), Instant)>,
+    /// Fault totals sampled during the current refresh
+    current: HashMap<ProcessKey, ((u64, u64), Instant)>,
+}
+
+impl FaultTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fault rates of a process; None on its first sample or if /proc/<pid>/stat is gone
+    pub fn sample(&mut self, key: ProcessKey) -> Option<FaultRates> {
+        let (minor, major) = read_page_faults(key.pid)?;
+        let now = Instant::now();
+        self.current.insert(key, ((minor, major), now));
+        let ((last_minor, last_major), last_time) = self.previous.get(&key)?;
+        let elapsed = now.duration_since(*last_time).as_secs_f64().max(0.001);
+        Some(FaultRates {
+            minor_per_sec: minor.saturating_sub(*last_minor) as f64 / elapsed,
+            major_per_sec: major.saturating_sub(*last_major) as f64 / elapsed,
+        })
+    }
+
+    /// End a refresh; processes not sampled in it are forgotten
+    pub fn finish_refresh(&mut self) {
+        self.previous = std::mem::take(&mut self.current);
+    }
+}
@@ -0,0 +1,287 @@
+//! perf_event_open based counters for a single process (optional `perf` feature)
+//!
+//! Provides the syscall rate (tracepoint) and hardware counters (instructions,
+//! cycles, cache misses) shown in the process window.
+//!
+//! Counters are opened per thread (like `perf stat -p`), since a counter attached
+//! to the TGID only sees the main thread. Threads are rescanned on every sample so
+//! workers spawned later are picked up. Most events need `perf_event_paranoid`
+//! to be lowered or CAP_PERFMON.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::os::fd::{FromRawFd, OwnedFd};
+use std::time::Instant;
+
+const PERF_TYPE_HARDWARE: u32 = 0;
+const PERF_TYPE_TRACEPOINT: u32 = 2;
+const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+
+// Generic hardware event ids (perf_hw_id)
+const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+
+// perf_event_attr flag bits
+const ATTR_FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+const ATTR_FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+/// Mirror of struct perf_event_attr (PERF_ATTR_SIZE_VER5)
+#[repr(C)]
+#[derive(Default)]
+struct PerfEventAttr {
+    type_: u32,
+    size: u32,
+    config: u64,
+    sample_period: u64,
+    sample_type: u64,
+    read_format: u64,
+    flags: u64,
+    wakeup_events: u32,
+    bp_type: u32,
+    config1: u64,
+    config2: u64,
+    branch_sample_type: u64,
+    sample_regs_user: u64,
+    sample_stack_user: u32,
+    clockid: i32,
+    sample_regs_intr: u64,
+    aux_watermark: u32,
+    sample_max_stack: u16,
+    reserved: u16,
+}
+
+const USER_ONLY: u64 = ATTR_FLAG_EXCLUDE_KERNEL | ATTR_FLAG_EXCLUDE_HV;
+
+/// Events that can be counted for a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerfEvent {
+    /// Entries into any syscall (raw_syscalls:sys_enter tracepoint)
+    Syscalls,
+    /// Retired instructions (user space only)
+    Instructions,
+    /// CPU cycles (user space only)
+    Cycles,
+    /// Last-level cache misses (user space only)
+    CacheMisses,
+}
+
+impl PerfEvent {
+    /// Resolve the (type, config, flags) triple for perf_event_attr
+    fn attr_params(&self) -> io::Result<(u32, u64, u64)> {
+        match self {
+            PerfEvent::Syscalls => Ok((PERF_TYPE_TRACEPOINT, tracepoint_id("raw_syscalls/sys_enter")?, 0)),
+            // Excluding kernel/hypervisor lets these work at the default perf_event_paranoid=2
+            PerfEvent::Instructions => Ok((PERF_TYPE_HARDWARE, PERF_COUNT_HW_INSTRUCTIONS, USER_ONLY)),
+            PerfEvent::Cycles => Ok((PERF_TYPE_HARDWARE, PERF_COUNT_HW_CPU_CYCLES, USER_ONLY)),
+            PerfEvent::CacheMisses => Ok((PERF_TYPE_HARDWARE, PERF_COUNT_HW_CACHE_MISSES, USER_ONLY)),
+        }
+    }
+}
+
+/// Look up a tracepoint id in tracefs (mounted either standalone or under debugfs)
+fn tracepoint_id(name: &str) -> io::Result<u64> {
+    for base in ["/sys/kernel/tracing/events", "/sys/kernel/debug/tracing/events"] {
+        if let Ok(content) = fs::read_to_string(format!("{}/{}/id", base, name)) {
+            return content
+                .trim()
+                .parse()
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid tracepoint id"));
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("Tracepoint {} not available (is tracefs readable?)", name),
+    ))
+}
+
+/// Open a counting (non-sampling) perf event on a single thread
+fn open_thread_counter(tid: u32, type_: u32, config: u64, flags: u64) -> io::Result<File> {
+    let attr = PerfEventAttr {
+        type_,
+        size: std::mem::size_of::<PerfEventAttr>() as u32,
+        config,
+        flags,
+        ..Default::default()
+    };
+
+    let fd = unsafe {
+        libc::syscall(
+            libc::SYS_perf_event_open,
+            &attr as *const PerfEventAttr,
+            tid as libc::pid_t,
+            -1 as libc::c_int, // any CPU
+            -1 as libc::c_int, // no group
+            PERF_FLAG_FD_CLOEXEC,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(File::from(unsafe { OwnedFd::from_raw_fd(fd as i32) }))
+}
+
+/// List the thread IDs of a process
+fn list_threads(pid: u32) -> Vec<u32> {
+    fs::read_dir(format!("/proc/{}/task", pid))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.file_name().to_string_lossy().parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A perf counter opened on every thread of a process
+pub struct ProcessCounter {
+    pid: u32,
+    type_: u32,
+    config: u64,
+    flags: u64,
+    /// Per-thread counter file and last value read
+    threads: HashMap<u32, (File, u64)>,
+}
+
+impl ProcessCounter {
+    /// Open the event on all current threads of `pid`
+    /// Fails if the counter could not be opened on any thread
+    pub fn open(pid: u32, event: PerfEvent) -> io::Result<Self> {
+        let (type_, config, flags) = event.attr_params()?;
+        let mut counter = Self {
+            pid,
+            type_,
+            config,
+            flags,
+            threads: HashMap::new(),
+        };
+
+        let mut last_error = None;
+        for tid in list_threads(pid) {
+            match open_thread_counter(tid, type_, config, flags) {
+                Ok(file) => {
+                    counter.threads.insert(tid, (file, 0));
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        if counter.threads.is_empty() {
+            return Err(last_error.unwrap_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, "Process has no threads")
+            }));
+        }
+
+        // Prime the per-thread values so the first delta starts from now
+        counter.delta();
+        Ok(counter)
+    }
+
+    /// Return the number of events counted across all threads since the last call
+    pub fn delta(&mut self) -> u64 {
+        // Pick up newly spawned threads and drop the ones that exited
+        let current = list_threads(self.pid);
+        for &tid in &current {
+            if !self.threads.contains_key(&tid) {
+                if let Ok(file) = open_thread_counter(tid, self.type_, self.config, self.flags) {
+                    self.threads.insert(tid, (file, 0));
+                }
+            }
+        }
+
+        let mut total = 0u64;
+        for (file, last) in self.threads.values_mut() {
+            let mut buf = [0u8; 8];
+            if file.read_exact(&mut buf).is_ok() {
+                let value = u64::from_ne_bytes(buf);
+                total += value.saturating_sub(*last);
+                *last = value;
+            }
+        }
+
+        self.threads.retain(|tid, _| current.contains(tid));
+        total
+    }
+}
+
+/// Samples the syscall rate of a process
+pub struct SyscallSampler {
+    counter: ProcessCounter,
+    last_sample: Instant,
+}
+
+impl SyscallSampler {
+    pub fn new(pid: u32) -> io::Result<Self> {
+        Ok(Self {
+            counter: ProcessCounter::open(pid, PerfEvent::Syscalls)?,
+            last_sample: Instant::now(),
+        })
+    }
+
+    /// Syscalls per second since the previous sample
+    pub fn sample(&mut self) -> f64 {
+        let count = self.counter.delta();
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_sample).as_secs_f64();
+        self.last_sample = now;
+
+        if elapsed > 0.0 {
+            count as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+}
+
+/// One sample of hardware counter rates
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HardwareSample {
+    pub instructions_per_sec: f64,
+    pub cycles_per_sec: f64,
+    /// Instructions per cycle (0 when no cycles were counted)
+    pub ipc: f64,
+    pub cache_misses_per_sec: f64,
+}
+
+/// Samples hardware performance counters (instructions, cycles, cache misses) of a process
+pub struct HardwareCounterSampler {
+    instructions: ProcessCounter,
+    cycles: ProcessCounter,
+    /// Cache miss counting is not supported by every PMU (e.g. some VMs)
+    cache_misses: Option<ProcessCounter>,
+    last_sample: Instant,
+}
+
+impl HardwareCounterSampler {
+    pub fn new(pid: u32) -> io::Result<Self> {
+        Ok(Self {
+            instructions: ProcessCounter::open(pid, PerfEvent::Instructions)?,
+            cycles: ProcessCounter::open(pid, PerfEvent::Cycles)?,
+            cache_misses: ProcessCounter::open(pid, PerfEvent::CacheMisses).ok(),
+            last_sample: Instant::now(),
+        })
+    }
+
+    /// Counter rates since the previous sample
+    pub fn sample(&mut self) -> HardwareSample {
+        let instructions = self.instructions.delta();
+        let cycles = self.cycles.delta();
+        let cache_misses = self.cache_misses.as_mut().map(|c| c.delta()).unwrap_or(0);
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_sample).as_secs_f64();
+        self.last_sample = now;
+        if elapsed <= 0.0 {
+            return HardwareSample::default();
+        }
+
+        HardwareSample {
+            instructions_per_sec: instructions as f64 / elapsed,
+            cycles_per_sec: cycles as f64 / elapsed,
+            ipc: if cycles > 0 { instructions as f64 / cycles as f64 } else { 0.0 },
+            cache_misses_per_sec: cache_misses as f64 / elapsed,
+        }
+    }
+}
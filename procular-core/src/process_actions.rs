@@ -0,0 +1,902 @@
+//! Process management actions (kill, affinity, priority, etc.)
+//!
+//! Actions shell out to standard tools; when they fail with a permission error
+//! they are retried through pkexec so polkit can ask for authorization. Waiting for the
+//! polkit prompt blocks, so the UI runs them through `run_in_background`.
+
+use futures_channel::oneshot;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::thread;
+
+use crate::cpu_times::read_cpu_times;
+use crate::process_state::ProcessState;
+
+/// Exit codes used by pkexec when authorization fails
+const PKEXEC_NOT_AUTHORIZED: i32 = 126;
+const PKEXEC_AUTH_FAILED: i32 = 127;
+
+/// Find an executable in PATH
+pub(crate) fn find_in_path(program: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Run an action on a worker thread and wait for its result without blocking the caller's
+/// main loop, for actions that may sit on a polkit prompt
+pub async fn run_in_background<T: Send + 'static>(
+    action: impl FnOnce() -> io::Result<T> + Send + 'static,
+) -> io::Result<T> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let _ = sender.send(action());
+    });
+    receiver
+        .await
+        .unwrap_or_else(|_| Err(io::Error::other("The action ended without a result")))
+}
+
+/// Check whether pkexec (polkit) is available for privilege escalation
+pub fn is_pkexec_available() -> bool {
+    find_in_path("pkexec").is_some()
+}
+
+/// Check whether a failed command was refused for lack of privileges. The check matches
+/// the untranslated error text, so the command must have run with `LC_ALL=C`.
+fn is_permission_error(output: &Output) -> bool {
+    let stderr = String::from_utf8_lossy(&output.stderr).to_lowercase();
+    stderr.contains("operation not permitted") || stderr.contains("permission denied")
+}
+
+/// Run a process-control command, retrying through pkexec if it fails with a
+/// permission error. pkexec prompts for authorization via the session's polkit
+/// agent; if polkit is not installed the original permission error is returned.
+/// This blocks until the prompt is answered, so call it off the main thread.
+pub(crate) fn run_action_command(program: &str, args: &[String], error_prefix: &str) -> io::Result<()> {
+    let output = Command::new(program).args(args).env("LC_ALL", "C").output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    if is_permission_error(&output) {
+        // pkexec requires an absolute path, which also has to match the polkit policy
+        if let (Some(pkexec), Some(program_path)) = (find_in_path("pkexec"), find_in_path(program)) {
+            let escalated = Command::new(pkexec).arg(program_path).args(args).output()?;
+            if escalated.status.success() {
+                return Ok(());
+            }
+            return Err(match escalated.status.code() {
+                Some(PKEXEC_NOT_AUTHORIZED) | Some(PKEXEC_AUTH_FAILED) => io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("{}: authorization was denied or cancelled", error_prefix),
+                ),
+                _ => io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("{}: {}", error_prefix, String::from_utf8_lossy(&escalated.stderr).trim()),
+                ),
+            });
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "{}: {}\n\nInstall polkit (pkexec) to perform actions on other users' processes.",
+                error_prefix,
+                stderr.trim()
+            ),
+        ));
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        format!("{}: {}", error_prefix, stderr.trim()),
+    ))
+}
+
+/// Available signals for process management
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    Term,  // SIGTERM (15) - Graceful termination
+    Kill,  // SIGKILL (9) - Force kill
+    Stop,  // SIGSTOP (19) - Pause process
+    Cont,  // SIGCONT (18) - Resume process
+    Chld,  // SIGCHLD (17) - Ask a parent to reap its exited children
+}
+
+impl Signal {
+    fn number(&self) -> i32 {
+        match self {
+            Signal::Term => 15,
+            Signal::Kill => 9,
+            Signal::Stop => 19,
+            Signal::Cont => 18,
+            Signal::Chld => 17,
+        }
+    }
+
+    /// Signal name, e.g. "SIGTERM"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Signal::Term => "SIGTERM",
+            Signal::Kill => "SIGKILL",
+            Signal::Stop => "SIGSTOP",
+            Signal::Cont => "SIGCONT",
+            Signal::Chld => "SIGCHLD",
+        }
+    }
+}
+
+/// Send a signal to a process (escalating via polkit if needed)
+pub fn send_signal(pid: u32, signal: Signal) -> io::Result<()> {
+    send_signal_many(&[pid], signal)
+}
+
+/// Send a signal to several processes with a single command, so escalation
+/// asks for authorization only once
+pub fn send_signal_many(pids: &[u32], signal: Signal) -> io::Result<()> {
+    let mut args = vec![format!("-{}", signal.number())];
+    args.extend(pids.iter().map(|pid| pid.to_string()));
+    run_action_command("kill", &args, "Failed to send signal")
+}
+
+/// Kill a process (SIGTERM first, then SIGKILL if force is true)
+pub fn kill_process(pid: u32, force: bool) -> io::Result<()> {
+    kill_processes(&[pid], force)
+}
+
+/// Kill several processes (SIGTERM, or SIGKILL if force is true)
+pub fn kill_processes(pids: &[u32], force: bool) -> io::Result<()> {
+    if force {
+        send_signal_many(pids, Signal::Kill)
+    } else {
+        send_signal_many(pids, Signal::Term)
+    }
+}
+
+/// Get the number of CPU cores
+pub fn get_cpu_count() -> usize {
+    // Read from /proc/cpuinfo or use a simpler method
+    if let Ok(content) = fs::read_to_string("/proc/cpuinfo") {
+        content.matches("processor").count().max(1)
+    } else {
+        // Fallback: try sysconf
+        1
+    }
+}
+
+/// Get current CPU affinity for a process
+/// Returns a bitmask where bit N means CPU N is allowed
+pub fn get_cpu_affinity(pid: u32) -> io::Result<Vec<bool>> {
+    let output = Command::new("taskset")
+        .arg("-p")
+        .arg(pid.to_string())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "Failed to get CPU affinity",
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Output format: "pid XXXX's current affinity mask: ff"
+    if let Some(mask_str) = stdout.split(':').last() {
+        let mask_str = mask_str.trim();
+        if let Ok(mask) = u64::from_str_radix(mask_str, 16) {
+            let cpu_count = get_cpu_count();
+            let mut affinity = Vec::with_capacity(cpu_count);
+            for i in 0..cpu_count {
+                affinity.push((mask & (1 << i)) != 0);
+            }
+            return Ok(affinity);
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "Failed to parse CPU affinity",
+    ))
+}
+
+/// CPUs a process may run on, from `Cpus_allowed_list` in /proc/<pid>/status (index N is CPU N).
+/// Cheaper than `get_cpu_affinity`, so it can be read on every refresh
+pub fn read_allowed_cpus(pid: u32, cpu_count: usize) -> Option<Vec<bool>> {
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let list = status.lines().find_map(|line| line.strip_prefix("Cpus_allowed_list:"))?;
+    let mut allowed = vec![false; cpu_count];
+    for cpu in parse_cpu_list(list) {
+        if let Some(slot) = allowed.get_mut(cpu) {
+            *slot = true;
+        }
+    }
+    Some(allowed)
+}
+
+/// Set CPU affinity for a process
+/// cpus is a list of CPU indices (0-based)
+pub fn set_cpu_affinity(pid: u32, cpus: &[usize]) -> io::Result<()> {
+    if cpus.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Must select at least one CPU",
+        ));
+    }
+
+    // Build CPU list string (e.g., "0,2,3")
+    let cpu_list: String = cpus
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    run_action_command(
+        "taskset",
+        &["-pc".to_string(), cpu_list, pid.to_string()],
+        "Failed to set CPU affinity",
+    )
+}
+
+/// Highest priority a nice value can give
+pub const MIN_NICE: i32 = -20;
+/// Lowest priority a nice value can give
+pub const MAX_NICE: i32 = 19;
+
+/// Priority levels (nice values)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    VeryHigh,  // -20 (requires root)
+    High,      // -10 (requires root)
+    Normal,    // 0
+    Low,       // 10
+    VeryLow,   // 19
+}
+
+impl Priority {
+    pub fn nice_value(&self) -> i32 {
+        match self {
+            Priority::VeryHigh => -20,
+            Priority::High => -10,
+            Priority::Normal => 0,
+            Priority::Low => 10,
+            Priority::VeryLow => 19,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Priority::VeryHigh => "Very High (-20)",
+            Priority::High => "High (-10)",
+            Priority::Normal => "Normal (0)",
+            Priority::Low => "Low (10)",
+            Priority::VeryLow => "Very Low (19)",
+        }
+    }
+
+    /// Map a nice value back to its preset, if it is one
+    pub fn from_nice_value(nice: i32) -> Option<Priority> {
+        Priority::all().iter().copied().find(|p| p.nice_value() == nice)
+    }
+
+    pub fn all() -> &'static [Priority] {
+        &[
+            Priority::VeryHigh,
+            Priority::High,
+            Priority::Normal,
+            Priority::Low,
+            Priority::VeryLow,
+        ]
+    }
+}
+
+/// Whether a process is stopped by a signal (state "T" in /proc/<pid>/stat)
+pub fn is_stopped(pid: u32) -> bool {
+    fs::read_to_string(format!("/proc/{}/stat", pid))
+        .ok()
+        .and_then(|content| {
+            let state = content[content.rfind(')')? + 1..].split_whitespace().next()?;
+            Some(state == "T")
+        })
+        .unwrap_or(false)
+}
+
+/// Scheduling policy of a thread, see sched(7)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedPolicy {
+    Other,
+    Fifo,
+    RoundRobin,
+    Batch,
+    Idle,
+    Deadline,
+}
+
+impl SchedPolicy {
+    fn from_number(policy: u32) -> Option<Self> {
+        match policy {
+            0 => Some(SchedPolicy::Other),
+            1 => Some(SchedPolicy::Fifo),
+            2 => Some(SchedPolicy::RoundRobin),
+            3 => Some(SchedPolicy::Batch),
+            5 => Some(SchedPolicy::Idle),
+            6 => Some(SchedPolicy::Deadline),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchedPolicy::Other => "SCHED_OTHER",
+            SchedPolicy::Fifo => "SCHED_FIFO",
+            SchedPolicy::RoundRobin => "SCHED_RR",
+            SchedPolicy::Batch => "SCHED_BATCH",
+            SchedPolicy::Idle => "SCHED_IDLE",
+            SchedPolicy::Deadline => "SCHED_DEADLINE",
+        }
+    }
+
+    /// FIFO and round-robin threads run before every normal thread and can starve the system
+    pub fn is_realtime(&self) -> bool {
+        matches!(self, SchedPolicy::Fifo | SchedPolicy::RoundRobin)
+    }
+}
+
+/// Scheduling policy and realtime priority (1-99, 0 for normal policies)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scheduling {
+    pub policy: SchedPolicy,
+    pub rt_priority: u32,
+}
+
+impl Scheduling {
+    /// Short form such as "FIFO 50" or "OTHER"
+    pub fn short(&self) -> String {
+        let name = self.policy.as_str().trim_start_matches("SCHED_");
+        if self.policy.is_realtime() {
+            format!("{} {}", name, self.rt_priority)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Ordering key: realtime first by priority, then the normal policies
+    pub fn rank(&self) -> (bool, u32) {
+        (self.policy.is_realtime(), self.rt_priority)
+    }
+}
+
+/// Read policy and rt_priority (fields 41 and 40) from /proc/<pid>/stat; works for thread IDs too
+pub fn read_scheduling(pid: u32) -> Option<Scheduling> {
+    let content = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // Fields after the comm field start at field 3 (state)
+    let fields: Vec<&str> = content[content.rfind(')')? + 1..].split_whitespace().collect();
+    Some(Scheduling {
+        rt_priority: fields.get(37)?.parse().ok()?,
+        policy: SchedPolicy::from_number(fields.get(38)?.parse().ok()?)?,
+    })
+}
+
+/// Threads of a process with a realtime policy, highest priority first
+pub fn realtime_threads(pid: u32) -> Vec<(u32, Scheduling)> {
+    let Ok(entries) = fs::read_dir(format!("/proc/{}/task", pid)) else {
+        return Vec::new();
+    };
+    let mut threads: Vec<(u32, Scheduling)> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_string_lossy().parse::<u32>().ok())
+        .filter_map(|tid| Some((tid, read_scheduling(tid)?)))
+        .filter(|(_, scheduling)| scheduling.policy.is_realtime())
+        .collect();
+    threads.sort_by_key(|(_, scheduling)| std::cmp::Reverse(scheduling.rt_priority));
+    threads
+}
+
+/// Get current priority (nice value) for a process
+pub fn get_priority(pid: u32) -> io::Result<i32> {
+    let stat_path = format!("/proc/{}/stat", pid);
+    let content = fs::read_to_string(&stat_path)?;
+
+    // Find the closing paren of comm field (which may contain spaces)
+    let comm_end = content.find(')').ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "Invalid stat format")
+    })?;
+
+    let after_comm = &content[comm_end + 1..];
+    let fields: Vec<&str> = after_comm.split_whitespace().collect();
+
+    // nice is at index 16 (after comm and state)
+    // state=0, ppid=1, pgrp=2, session=3, tty_nr=4, tpgid=5, flags=6,
+    // minflt=7, cminflt=8, majflt=9, cmajflt=10, utime=11, stime=12,
+    // cutime=13, cstime=14, priority=15, nice=16
+    if fields.len() > 16 {
+        fields[16]
+            .parse()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid nice value"))
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Stat file too short",
+        ))
+    }
+}
+
+/// Set priority (nice value) for a process (escalating via polkit if needed)
+pub fn set_priority(pid: u32, priority: Priority) -> io::Result<()> {
+    set_priority_many(&[pid], priority)
+}
+
+/// Set the priority of several processes with a single command
+pub fn set_priority_many(pids: &[u32], priority: Priority) -> io::Result<()> {
+    set_nice_many(pids, priority.nice_value())
+}
+
+/// Set any nice value (clamped to -20..=19) for several processes with a single command
+pub fn set_nice_many(pids: &[u32], nice: i32) -> io::Result<()> {
+    let nice = nice.clamp(MIN_NICE, MAX_NICE);
+    let mut args = vec!["-n".to_string(), nice.to_string(), "-p".to_string()];
+    args.extend(pids.iter().map(|pid| pid.to_string()));
+    run_action_command("renice", &args, "Failed to set priority")
+}
+
+/// Run a process-control command without privilege escalation
+pub(crate) fn run_unprivileged_command(program: &str, args: &[String], error_prefix: &str) -> io::Result<()> {
+    let output = Command::new(program).args(args).env("LC_ALL", "C").output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let kind = if is_permission_error(&output) {
+        io::ErrorKind::PermissionDenied
+    } else {
+        io::ErrorKind::Other
+    };
+    Err(io::Error::new(
+        kind,
+        format!("{}: {}", error_prefix, String::from_utf8_lossy(&output.stderr).trim()),
+    ))
+}
+
+/// Set the nice value of a process without asking for authorization
+/// Used by automation rules, which must not pop up a polkit prompt on every change
+pub fn try_set_priority(pid: u32, priority: Priority) -> io::Result<()> {
+    run_unprivileged_command(
+        "renice",
+        &["-n".to_string(), priority.nice_value().to_string(), "-p".to_string(), pid.to_string()],
+        "Failed to set priority",
+    )
+}
+
+/// Set the CPU affinity of a process without asking for authorization (for automation rules)
+pub fn try_set_cpu_affinity(pid: u32, cpus: &[usize]) -> io::Result<()> {
+    if cpus.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Must select at least one CPU"));
+    }
+    let cpu_list = cpus.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(",");
+    run_unprivileged_command(
+        "taskset",
+        &["-pc".to_string(), cpu_list, pid.to_string()],
+        "Failed to set CPU affinity",
+    )
+}
+
+/// Send a signal without asking for authorization (for automation rules)
+pub fn try_send_signal(pid: u32, signal: Signal) -> io::Result<()> {
+    run_unprivileged_command(
+        "kill",
+        &[format!("-{}", signal.number()), pid.to_string()],
+        "Failed to send signal",
+    )
+}
+
+/// Get the command line for a process
+pub fn get_command_line(pid: u32) -> Option<String> {
+    let cmdline_path = format!("/proc/{}/cmdline", pid);
+    fs::read_to_string(&cmdline_path)
+        .ok()
+        .map(|s| s.replace('\0', " ").trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Path of a process's executable from /proc/<pid>/exe; the kernel's " (deleted)" suffix for a
+/// replaced or removed binary is dropped. Reading the link of another user's process needs root
+pub fn get_executable_path(pid: u32) -> io::Result<PathBuf> {
+    let exe = fs::read_link(format!("/proc/{}/exe", pid))?;
+    match exe.to_str().and_then(|path| path.strip_suffix(" (deleted)")) {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => Ok(exe),
+    }
+}
+
+/// Get the short name (comm) of a process
+pub fn get_process_name(pid: u32) -> String {
+    fs::read_to_string(format!("/proc/{}/comm", pid))
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Parent of a process as (pid, name); None when it has exited or has no parent
+pub fn get_parent(pid: u32) -> Option<(u32, String)> {
+    let content = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let (_, ppid) = parse_stat_for_ppid(&content)?;
+    (ppid != 0).then(|| (ppid, get_process_name(ppid)))
+}
+
+/// Get all descendant processes (children, grandchildren, ...) of a process as (pid, name)
+pub fn get_descendant_processes(pid: u32) -> Vec<(u32, String)> {
+    descendants_in(&read_children_map(), pid)
+}
+
+/// Parent PID -> children as (pid, name), for all processes in /proc
+pub fn read_children_map() -> HashMap<u32, Vec<(u32, String)>> {
+    let mut children: HashMap<u32, Vec<(u32, String)>> = HashMap::new();
+    if let Ok(entries) = fs::read_dir("/proc") {
+        for entry in entries.flatten() {
+            let Ok(child_pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+                continue;
+            };
+            if let Ok(content) = fs::read_to_string(format!("/proc/{}/stat", child_pid)) {
+                if let Some((name, ppid)) = parse_stat_for_ppid(&content) {
+                    children.entry(ppid).or_default().push((child_pid, name));
+                }
+            }
+        }
+    }
+    children
+}
+
+/// Descendants of `pid` in a map from `read_children_map`
+pub fn descendants_in(children: &HashMap<u32, Vec<(u32, String)>>, pid: u32) -> Vec<(u32, String)> {
+    let mut descendants = Vec::new();
+    // Guards against cycles from PIDs reused while /proc was read
+    let mut visited = HashSet::from([pid]);
+    let mut queue = vec![pid];
+    while let Some(parent) = queue.pop() {
+        for (child_pid, name) in children.get(&parent).into_iter().flatten() {
+            if visited.insert(*child_pid) {
+                queue.push(*child_pid);
+                descendants.push((*child_pid, name.clone()));
+            }
+        }
+    }
+    descendants
+}
+
+/// Parse /proc/[pid]/stat for the process name and parent PID
+fn parse_stat_for_ppid(content: &str) -> Option<(String, u32)> {
+    let comm_start = content.find('(')?;
+    let comm_end = content.rfind(')')?;
+    let name = content.get(comm_start + 1..comm_end)?.to_string();
+    // Fields after comm: state=0, ppid=1
+    let ppid = content[comm_end + 1..].split_whitespace().nth(1)?.parse().ok()?;
+    Some((name, ppid))
+}
+
+/// Get the start time of a process in seconds since boot (field 22 of /proc/<pid>/stat)
+pub fn get_start_time_secs(pid: u32) -> Option<f64> {
+    let content = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let comm_end = content.rfind(')')?;
+    // Fields after comm start at state (field 3), so starttime is at index 19
+    let ticks: u64 = content[comm_end + 1..].split_whitespace().nth(19)?.parse().ok()?;
+    let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as f64;
+    Some(ticks as f64 / ticks_per_sec)
+}
+
+/// Whether the process started at `start_time_secs` (see `get_start_time_secs`) still runs;
+/// false once it exited, became a zombie or its PID was reused
+pub fn is_process_alive(pid: u32, start_time_secs: f64) -> bool {
+    let Ok(content) = fs::read_to_string(format!("/proc/{}/stat", pid)) else {
+        return false;
+    };
+    let Some(comm_end) = content.rfind(')') else {
+        return false;
+    };
+    let mut fields = content[comm_end + 1..].split_whitespace();
+    let zombie = fields.next() == Some("Z");
+    !zombie && get_start_time_secs(pid) == Some(start_time_secs)
+}
+
+/// User plus system CPU time of a process in seconds (fields 14 and 15 of /proc/<pid>/stat)
+pub fn read_cpu_time_secs(pid: u32) -> Option<f64> {
+    read_cpu_times(pid).map(|times| times.total_secs())
+}
+
+/// Check if a process is still running
+pub fn is_process_running(pid: u32) -> bool {
+    std::path::Path::new(&format!("/proc/{}", pid)).exists()
+}
+
+/// Information about a thread's CPU assignment
+#[derive(Debug, Clone)]
+pub struct ThreadCpuInfo {
+    pub tid: u32,
+    pub name: String,
+    pub state: ProcessState,
+    pub current_cpu: Option<usize>,
+    /// User plus system CPU time used so far, in seconds
+    pub cpu_time_secs: f64,
+}
+
+/// Get CPU information for all threads of a process
+/// Returns a list of threads with their current CPU assignments
+pub fn get_thread_cpu_info(pid: u32) -> Vec<ThreadCpuInfo> {
+    let task_dir = format!("/proc/{}/task", pid);
+    let mut threads = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(&task_dir) {
+        for entry in entries.flatten() {
+            if let Ok(tid) = entry.file_name().to_string_lossy().parse::<u32>() {
+                let stat_path = format!("/proc/{}/task/{}/stat", pid, tid);
+                if let Ok(content) = fs::read_to_string(&stat_path) {
+                    threads.push(parse_thread_stat(tid, &content));
+                }
+            }
+        }
+    }
+
+    // Sort by TID (main thread first)
+    threads.sort_by_key(|t| t.tid);
+    threads
+}
+
+/// Parse /proc/[pid]/task/[tid]/stat for the name, state, CPU time and current processor
+fn parse_thread_stat(tid: u32, content: &str) -> ThreadCpuInfo {
+    // Format: pid (comm) state ppid pgrp session tty_nr tpgid flags ...
+    // The comm field can contain spaces and parentheses, so find it by parens
+    let comm_start = content.find('(').unwrap_or(0);
+    let comm_end = content.rfind(')').unwrap_or(content.len());
+
+    let name = if comm_start < comm_end {
+        content[comm_start + 1..comm_end].to_string()
+    } else {
+        "unknown".to_string()
+    };
+
+    // After the closing paren, we have: state ppid pgrp session tty_nr tpgid flags
+    // minflt cminflt majflt cmajflt utime stime cutime cstime priority nice
+    // num_threads itrealvalue starttime vsize rss rsslim startcode endcode
+    // startstack kstkesp kstkeip signal blocked sigignore sigcatch wchan
+    // nswap cnswap exit_signal processor ...
+    let after_comm = content.get(comm_end + 1..).unwrap_or("");
+    let fields: Vec<&str> = after_comm.split_whitespace().collect();
+
+    // state=0, ppid=1, pgrp=2, session=3, tty_nr=4, tpgid=5, flags=6,
+    // minflt=7, cminflt=8, majflt=9, cmajflt=10, utime=11, stime=12,
+    // cutime=13, cstime=14, priority=15, nice=16, num_threads=17,
+    // itrealvalue=18, starttime=19, vsize=20, rss=21, rsslim=22,
+    // startcode=23, endcode=24, startstack=25, kstkesp=26, kstkeip=27,
+    // signal=28, blocked=29, sigignore=30, sigcatch=31, wchan=32,
+    // nswap=33, cnswap=34, exit_signal=35, processor=36
+    let state = fields
+        .first()
+        .and_then(|s| s.chars().next())
+        .map(ProcessState::from_letter)
+        .unwrap_or_default();
+    let ticks = |index: usize| fields.get(index).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as f64;
+
+    ThreadCpuInfo {
+        tid,
+        name,
+        state,
+        current_cpu: fields.get(36).and_then(|s| s.parse().ok()),
+        cpu_time_secs: (ticks(11) + ticks(12)) as f64 / ticks_per_sec,
+    }
+}
+
+/// CPU core type information
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoreType {
+    /// Intel Performance core
+    PCore,
+    /// Intel Efficiency core
+    ECore,
+    /// AMD X3D V-Cache core (large L3)
+    X3D,
+    /// Standard core (no special type detected)
+    Standard,
+}
+
+impl CoreType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CoreType::PCore => "P-Core",
+            CoreType::ECore => "E-Core",
+            CoreType::X3D => "X3D",
+            CoreType::Standard => "",
+        }
+    }
+
+    pub fn css_class(&self) -> Option<&'static str> {
+        match self {
+            CoreType::PCore => Some("accent"),
+            CoreType::ECore => Some("dim-label"),
+            CoreType::X3D => Some("success"),
+            CoreType::Standard => None,
+        }
+    }
+}
+
+/// Information about a CPU core
+#[derive(Debug, Clone)]
+pub struct CpuCoreInfo {
+    pub cpu_id: usize,
+    pub core_type: CoreType,
+    pub die_id: Option<usize>,
+    #[allow(dead_code)] // Stored for potential future use (tooltips, detailed view)
+    pub l3_cache_kb: Option<usize>,
+}
+
+/// Get detailed information about all CPU cores
+pub fn get_cpu_core_info() -> Vec<CpuCoreInfo> {
+    let cpu_count = get_cpu_count();
+    let mut cores = Vec::with_capacity(cpu_count);
+
+    // Try to detect Intel hybrid (P-core/E-core)
+    let intel_core_types = detect_intel_hybrid_cores();
+
+    // Try to detect AMD X3D cores
+    let amd_x3d_cores = detect_amd_x3d_cores(cpu_count);
+
+    for i in 0..cpu_count {
+        let core_type = if let Some(ref types) = intel_core_types {
+            types.get(i).cloned().unwrap_or(CoreType::Standard)
+        } else if let Some(ref x3d) = amd_x3d_cores {
+            if x3d.contains(&i) {
+                CoreType::X3D
+            } else {
+                CoreType::Standard
+            }
+        } else {
+            CoreType::Standard
+        };
+
+        let die_id = fs::read_to_string(format!("/sys/devices/system/cpu/cpu{}/topology/die_id", i))
+            .ok()
+            .and_then(|s| s.trim().parse().ok());
+
+        let l3_cache_kb = fs::read_to_string(format!("/sys/devices/system/cpu/cpu{}/cache/index3/size", i))
+            .ok()
+            .and_then(|s| {
+                let s = s.trim();
+                if s.ends_with('K') {
+                    s[..s.len()-1].parse().ok()
+                } else if s.ends_with('M') {
+                    s[..s.len()-1].parse::<usize>().ok().map(|m| m * 1024)
+                } else {
+                    s.parse().ok()
+                }
+            });
+
+        cores.push(CpuCoreInfo {
+            cpu_id: i,
+            core_type,
+            die_id,
+            l3_cache_kb,
+        });
+    }
+
+    cores
+}
+
+/// Detect Intel hybrid cores (P-core vs E-core)
+/// Returns None if not an Intel hybrid CPU
+fn detect_intel_hybrid_cores() -> Option<Vec<CoreType>> {
+    let types_dir = std::path::Path::new("/sys/devices/system/cpu/types");
+    if !types_dir.exists() {
+        return None;
+    }
+
+    let cpu_count = get_cpu_count();
+    let mut core_types = vec![CoreType::Standard; cpu_count];
+
+    // Look for intel_pcore and intel_ecore directories
+    if let Ok(entries) = fs::read_dir(types_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name_str = name.to_string_lossy();
+
+            let core_type = if name_str.contains("pcore") || name_str.contains("atom") == false && name_str.contains("intel") {
+                // Check if it's specifically a performance core
+                if name_str.to_lowercase().contains("pcore") || name_str.to_lowercase().contains("core") && !name_str.to_lowercase().contains("ecore") {
+                    Some(CoreType::PCore)
+                } else {
+                    None
+                }
+            } else if name_str.to_lowercase().contains("ecore") || name_str.to_lowercase().contains("atom") {
+                Some(CoreType::ECore)
+            } else {
+                None
+            };
+
+            if let Some(ct) = core_type {
+                // Read the cpulist file
+                let cpulist_path = entry.path().join("cpulist");
+                if let Ok(cpulist) = fs::read_to_string(&cpulist_path) {
+                    for cpu in parse_cpu_list(&cpulist) {
+                        if cpu < cpu_count {
+                            core_types[cpu] = ct.clone();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Check if we found any hybrid cores
+    if core_types.iter().any(|t| *t != CoreType::Standard) {
+        Some(core_types)
+    } else {
+        None
+    }
+}
+
+/// Detect AMD X3D cores based on L3 cache size
+/// X3D CCDs have significantly larger L3 cache (96MB vs 32MB per CCD)
+fn detect_amd_x3d_cores(cpu_count: usize) -> Option<Vec<usize>> {
+    // Check if this is an AMD CPU
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    if !cpuinfo.contains("AMD") {
+        return None;
+    }
+
+    // Group cores by their L3 cache size
+    let mut cache_sizes: std::collections::HashMap<usize, Vec<usize>> = std::collections::HashMap::new();
+
+    for i in 0..cpu_count {
+        let cache_path = format!("/sys/devices/system/cpu/cpu{}/cache/index3/size", i);
+        if let Ok(size_str) = fs::read_to_string(&cache_path) {
+            let size_str = size_str.trim();
+            let size_kb = if size_str.ends_with('K') {
+                size_str[..size_str.len()-1].parse::<usize>().unwrap_or(0)
+            } else if size_str.ends_with('M') {
+                size_str[..size_str.len()-1].parse::<usize>().unwrap_or(0) * 1024
+            } else {
+                size_str.parse::<usize>().unwrap_or(0)
+            };
+
+            cache_sizes.entry(size_kb).or_default().push(i);
+        }
+    }
+
+    // If there are different L3 cache sizes, the larger ones are likely X3D
+    if cache_sizes.len() > 1 {
+        let max_size = *cache_sizes.keys().max()?;
+        // X3D cores typically have 96MB L3 cache per CCD (98304 KB)
+        // Regular CCDs have ~32MB (32768 KB)
+        // Consider cores with largest cache as X3D if it's significantly larger
+        let min_size = *cache_sizes.keys().min()?;
+        if max_size > min_size * 2 {
+            return Some(cache_sizes.get(&max_size)?.clone());
+        }
+    }
+
+    None
+}
+
+/// Parse a CPU list string like "0-3,8-11" into individual CPU numbers
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',') {
+        let part = part.trim();
+        if part.contains('-') {
+            let mut range = part.split('-');
+            if let (Some(start), Some(end)) = (range.next(), range.next()) {
+                if let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) {
+                    for i in start..=end {
+                        cpus.push(i);
+                    }
+                }
+            }
+        } else if let Ok(cpu) = part.parse::<usize>() {
+            cpus.push(cpu);
+        }
+    }
+    cpus
+}
@@ -0,0 +1,234 @@
+//! Everything /proc tells about one process for the Properties dialog: paths, identity,
+//! cgroup, namespaces, resource limits and capabilities
+//!
+//! Paths and namespaces of another user's process need ptrace access; they are left empty
+//! when they cannot be read.
+
+use std::fs;
+use std::io;
+
+use crate::cgroup_limits::cgroup_path;
+use crate::process_actions::{get_command_line, get_executable_path};
+
+/// Names of the capability bits, from linux/capability.h
+const CAPABILITY_NAMES: [&str; 41] = [
+    "cap_chown",
+    "cap_dac_override",
+    "cap_dac_read_search",
+    "cap_fowner",
+    "cap_fsetid",
+    "cap_kill",
+    "cap_setgid",
+    "cap_setuid",
+    "cap_setpcap",
+    "cap_linux_immutable",
+    "cap_net_bind_service",
+    "cap_net_broadcast",
+    "cap_net_admin",
+    "cap_net_raw",
+    "cap_ipc_lock",
+    "cap_ipc_owner",
+    "cap_sys_module",
+    "cap_sys_rawio",
+    "cap_sys_chroot",
+    "cap_sys_ptrace",
+    "cap_sys_pacct",
+    "cap_sys_admin",
+    "cap_sys_boot",
+    "cap_sys_nice",
+    "cap_sys_resource",
+    "cap_sys_time",
+    "cap_sys_tty_config",
+    "cap_mknod",
+    "cap_lease",
+    "cap_audit_write",
+    "cap_audit_control",
+    "cap_setfcap",
+    "cap_mac_override",
+    "cap_mac_admin",
+    "cap_syslog",
+    "cap_wake_alarm",
+    "cap_block_suspend",
+    "cap_audit_read",
+    "cap_perfmon",
+    "cap_bpf",
+    "cap_checkpoint_restore",
+];
+
+/// Capability sets in the order of /proc/<pid>/status, with their status key
+const CAPABILITY_SETS: [(&str, &str); 5] = [
+    ("Inheritable", "CapInh:"),
+    ("Permitted", "CapPrm:"),
+    ("Effective", "CapEff:"),
+    ("Bounding", "CapBnd:"),
+    ("Ambient", "CapAmb:"),
+];
+
+/// One capability set of a process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilitySet {
+    /// "Effective", "Permitted", ...
+    pub name: &'static str,
+    pub mask: u64,
+}
+
+impl CapabilitySet {
+    /// Whether every capability this kernel knows of is in the set
+    pub fn is_full(&self) -> bool {
+        let known = (1u64 << CAPABILITY_NAMES.len()) - 1;
+        self.mask & known == known
+    }
+
+    /// Names of the capabilities in the set; unknown bits show as their number
+    pub fn names(&self) -> Vec<String> {
+        (0..64)
+            .filter(|bit| self.mask & (1u64 << bit) != 0)
+            .map(|bit| match CAPABILITY_NAMES.get(bit) {
+                Some(name) => name.to_string(),
+                None => format!("cap_{}", bit),
+            })
+            .collect()
+    }
+}
+
+/// One namespace of a process, such as "net" with id "net:[4026531840]"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Namespace {
+    pub kind: String,
+    pub id: String,
+    /// Whether Procular itself is in the same namespace; false for containerized processes
+    pub shared_with_self: bool,
+}
+
+/// One line of /proc/<pid>/limits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceLimit {
+    pub name: String,
+    pub soft: String,
+    pub hard: String,
+    /// Empty for limits without a unit, such as the nice priority
+    pub units: String,
+}
+
+/// What /proc has on one process
+#[derive(Debug, Clone, Default)]
+pub struct ProcessProperties {
+    pub pid: u32,
+    pub name: String,
+    pub command: Option<String>,
+    pub exe: Option<String>,
+    /// Working directory
+    pub cwd: Option<String>,
+    /// Root directory; other than "/" for chrooted and containerized processes
+    pub root: Option<String>,
+    pub parent_pid: Option<u32>,
+    /// Real, effective, saved and filesystem UIDs
+    pub uids: Vec<u32>,
+    /// Real, effective, saved and filesystem GIDs
+    pub gids: Vec<u32>,
+    /// Supplementary groups
+    pub groups: Vec<u32>,
+    pub cgroup: Option<String>,
+    pub namespaces: Vec<Namespace>,
+    pub limits: Vec<ResourceLimit>,
+    pub capabilities: Vec<CapabilitySet>,
+    pub no_new_privs: Option<bool>,
+    /// 0 disabled, 1 strict, 2 filter
+    pub seccomp: Option<u32>,
+}
+
+fn read_link(pid: u32, name: &str) -> Option<String> {
+    fs::read_link(format!("/proc/{}/{}", pid, name))
+        .ok()
+        .map(|path| path.display().to_string())
+}
+
+fn numbers(value: &str) -> Vec<u32> {
+    value.split_whitespace().filter_map(|n| n.parse().ok()).collect()
+}
+
+/// Namespaces of `pid`, compared with our own
+fn read_namespaces(pid: u32) -> Vec<Namespace> {
+    let Ok(entries) = fs::read_dir(format!("/proc/{}/ns", pid)) else {
+        return Vec::new();
+    };
+    let mut namespaces: Vec<Namespace> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let kind = entry.file_name().to_string_lossy().into_owned();
+            let id = fs::read_link(entry.path()).ok()?.display().to_string();
+            let own = fs::read_link(format!("/proc/self/ns/{}", kind)).ok();
+            let shared_with_self = own.is_some_and(|own| own.display().to_string() == id);
+            Some(Namespace { kind, id, shared_with_self })
+        })
+        .collect();
+    namespaces.sort_by(|a, b| a.kind.cmp(&b.kind));
+    namespaces
+}
+
+/// Parse /proc/<pid>/limits; the name has several words and the units may be missing, so
+/// the soft limit is found as the first value
+fn parse_limits(content: &str) -> Vec<ResourceLimit> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let first_value = words
+                .iter()
+                .position(|word| *word == "unlimited" || word.parse::<u64>().is_ok())?;
+            Some(ResourceLimit {
+                name: words[..first_value].join(" "),
+                soft: words.get(first_value)?.to_string(),
+                hard: words.get(first_value + 1)?.to_string(),
+                units: words.get(first_value + 2..).unwrap_or_default().join(" "),
+            })
+        })
+        .collect()
+}
+
+/// Gather the properties of `pid`; fails only when the process is gone
+pub fn read_process_properties(pid: u32) -> io::Result<ProcessProperties> {
+    let status = fs::read_to_string(format!("/proc/{}/status", pid))?;
+
+    let mut properties = ProcessProperties {
+        pid,
+        command: get_command_line(pid),
+        exe: get_executable_path(pid).ok().map(|path| path.display().to_string()),
+        cwd: read_link(pid, "cwd"),
+        root: read_link(pid, "root"),
+        cgroup: cgroup_path(pid),
+        namespaces: read_namespaces(pid),
+        limits: fs::read_to_string(format!("/proc/{}/limits", pid))
+            .map(|content| parse_limits(&content))
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+
+    for line in status.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key {
+            "Name" => properties.name = value.to_string(),
+            "PPid" => properties.parent_pid = value.parse().ok(),
+            "Uid" => properties.uids = numbers(value),
+            "Gid" => properties.gids = numbers(value),
+            "Groups" => properties.groups = numbers(value),
+            "NoNewPrivs" => properties.no_new_privs = Some(value == "1"),
+            "Seccomp" => properties.seccomp = value.parse().ok(),
+            _ => {}
+        }
+    }
+    properties.capabilities = CAPABILITY_SETS
+        .iter()
+        .filter_map(|(name, key)| {
+            let value = status.lines().find_map(|line| line.strip_prefix(key))?;
+            let mask = u64::from_str_radix(value.trim(), 16).ok()?;
+            Some(CapabilitySet { name, mask })
+        })
+        .collect();
+
+    Ok(properties)
+}
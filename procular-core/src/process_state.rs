@@ -0,0 +1,94 @@
+//! Scheduler state of a process, the R/S/D/T/Z letter shown by ps and top
+
+use sysinfo::ProcessStatus;
+
+/// State of a process's main thread; the order is used for sorting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum ProcessState {
+    Running,
+    #[default]
+    Sleeping,
+    /// Uninterruptible sleep, usually waiting for I/O; cannot be killed until the wait ends
+    DiskSleep,
+    /// Stopped by a signal or a debugger
+    Stopped,
+    /// Exited but not yet reaped by its parent
+    Zombie,
+    /// Idle kernel thread
+    Idle,
+}
+
+impl ProcessState {
+    pub fn all() -> &'static [ProcessState] {
+        &[
+            ProcessState::Running,
+            ProcessState::Sleeping,
+            ProcessState::DiskSleep,
+            ProcessState::Stopped,
+            ProcessState::Zombie,
+            ProcessState::Idle,
+        ]
+    }
+
+    pub fn from_status(status: ProcessStatus) -> Self {
+        match status {
+            ProcessStatus::Run | ProcessStatus::Waking => ProcessState::Running,
+            ProcessStatus::UninterruptibleDiskSleep | ProcessStatus::LockBlocked => ProcessState::DiskSleep,
+            ProcessStatus::Stop | ProcessStatus::Tracing => ProcessState::Stopped,
+            ProcessStatus::Zombie | ProcessStatus::Dead => ProcessState::Zombie,
+            ProcessStatus::Idle => ProcessState::Idle,
+            _ => ProcessState::Sleeping,
+        }
+    }
+
+    /// State from the letter in /proc/<pid>/stat; unknown letters count as sleeping
+    pub fn from_letter(letter: char) -> Self {
+        match letter {
+            'R' => ProcessState::Running,
+            'D' => ProcessState::DiskSleep,
+            'T' | 't' => ProcessState::Stopped,
+            'Z' | 'X' => ProcessState::Zombie,
+            'I' => ProcessState::Idle,
+            _ => ProcessState::Sleeping,
+        }
+    }
+
+    /// Letter used by ps
+    pub fn letter(&self) -> char {
+        match self {
+            ProcessState::Running => 'R',
+            ProcessState::Sleeping => 'S',
+            ProcessState::DiskSleep => 'D',
+            ProcessState::Stopped => 'T',
+            ProcessState::Zombie => 'Z',
+            ProcessState::Idle => 'I',
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProcessState::Running => "Running",
+            ProcessState::Sleeping => "Sleeping",
+            ProcessState::DiskSleep => "Uninterruptible",
+            ProcessState::Stopped => "Stopped",
+            ProcessState::Zombie => "Zombie",
+            ProcessState::Idle => "Idle",
+        }
+    }
+
+    /// Identifier for actions
+    pub fn key(&self) -> &'static str {
+        match self {
+            ProcessState::Running => "running",
+            ProcessState::Sleeping => "sleeping",
+            ProcessState::DiskSleep => "disk-sleep",
+            ProcessState::Stopped => "stopped",
+            ProcessState::Zombie => "zombie",
+            ProcessState::Idle => "idle",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|state| state.key() == key)
+    }
+}
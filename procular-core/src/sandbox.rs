@@ -0,0 +1,94 @@
+//! Flatpak and Snap application identification
+//!
+//! Sandboxed apps run behind generic helpers such as `bwrap` and
+//! `xdg-dbus-proxy`. Both Flatpak and snapd start every app in its own systemd
+//! scope named after the app ("app-flatpak-<app id>-<n>.scope",
+//! "snap.<name>.<app>-<uuid>.scope"), so the cgroup path identifies the app for
+//! all of its processes. Processes outside such a scope fall back to
+//! /.flatpak-info in their root and to executables below /snap.
+
+use std::fs;
+
+/// Processes that are part of the sandbox machinery rather than the app itself
+const SANDBOX_HELPERS: &[&str] = &["bwrap", "xdg-dbus-proxy", "snap-confine", "flatpak-spawn"];
+
+/// Packaging system of a sandboxed app
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SandboxKind {
+    Flatpak,
+    Snap,
+}
+
+impl SandboxKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SandboxKind::Flatpak => "Flatpak",
+            SandboxKind::Snap => "Snap",
+        }
+    }
+}
+
+/// The sandboxed app a process belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SandboxApp {
+    pub kind: SandboxKind,
+    /// Flatpak application ID (e.g. "org.mozilla.firefox") or snap name
+    pub app_id: String,
+}
+
+/// Whether a process name is a generic sandbox helper
+pub fn is_sandbox_helper(name: &str) -> bool {
+    SANDBOX_HELPERS.contains(&name)
+}
+
+/// App a process belongs to; `cgroup` is its cgroup path (from `cgroup_path`) if already known
+pub fn detect_sandbox(pid: u32, cgroup: Option<&str>) -> Option<SandboxApp> {
+    cgroup
+        .and_then(|path| path.rsplit('/').next())
+        .and_then(app_from_unit)
+        .or_else(|| read_flatpak_info(pid))
+        .or_else(|| snap_from_exe(pid))
+}
+
+/// App from a scope or service name created by Flatpak or snapd
+fn app_from_unit(unit: &str) -> Option<SandboxApp> {
+    if let Some(rest) = unit.strip_prefix("app-flatpak-") {
+        // "<app id>-<number>.scope"; systemd escapes '-' inside the app ID
+        let (app_id, _) = rest.strip_suffix(".scope")?.rsplit_once('-')?;
+        return Some(SandboxApp {
+            kind: SandboxKind::Flatpak,
+            app_id: app_id.replace("\\x2d", "-"),
+        });
+    }
+    // "snap.<name>.<app>-<uuid>.scope" for apps, "snap.<name>.<app>.service" for daemons
+    let name = unit.strip_prefix("snap.")?.split('.').next()?;
+    (!name.is_empty()).then(|| SandboxApp {
+        kind: SandboxKind::Snap,
+        app_id: name.to_string(),
+    })
+}
+
+/// Application ID from the [Application] group of /.flatpak-info inside the sandbox
+fn read_flatpak_info(pid: u32) -> Option<SandboxApp> {
+    let info = fs::read_to_string(format!("/proc/{}/root/.flatpak-info", pid)).ok()?;
+    let app_id = info
+        .lines()
+        .skip_while(|line| line.trim() != "[Application]")
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .find_map(|line| line.strip_prefix("name="))?;
+    Some(SandboxApp {
+        kind: SandboxKind::Flatpak,
+        app_id: app_id.trim().to_string(),
+    })
+}
+
+/// Snap name of an executable below /snap/<name>/
+fn snap_from_exe(pid: u32) -> Option<SandboxApp> {
+    let exe = fs::read_link(format!("/proc/{}/exe", pid)).ok()?;
+    let name = exe.to_str()?.strip_prefix("/snap/")?.split('/').next()?;
+    (!name.is_empty() && name != "bin").then(|| SandboxApp {
+        kind: SandboxKind::Snap,
+        app_id: name.to_string(),
+    })
+}
@@ -0,0 +1,98 @@
+//! Procular's own resource use, and backing off when monitoring gets expensive
+//!
+//! Refreshing every process every two seconds costs noticeable CPU on machines
+//! with thousands of processes. `RefreshThrottle` stretches the refresh interval
+//! while Procular's own CPU use stays above a limit and shortens it again once
+//! it has dropped well below.
+
+use std::fs;
+use std::time::Instant;
+
+use crate::process_actions::read_cpu_time_secs;
+use crate::swap::page_size;
+
+/// Largest factor the refresh interval is stretched by
+pub const MAX_SLOWDOWN: u32 = 8;
+
+/// Procular's own CPU and memory use
+#[derive(Debug, Clone, Copy)]
+pub struct SelfSample {
+    /// Percent of one core since the previous sample; None on the first sample
+    pub cpu_percent: Option<f64>,
+    /// Resident memory
+    pub memory_bytes: u64,
+}
+
+/// Samples the resource use of this process
+#[derive(Default)]
+pub struct SelfUsage {
+    last: Option<(f64, Instant)>,
+}
+
+impl SelfUsage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sample(&mut self) -> SelfSample {
+        let now = Instant::now();
+        let cpu_time = read_cpu_time_secs(std::process::id());
+        let cpu_percent = match (cpu_time, self.last) {
+            (Some(cpu_time), Some((last_cpu_time, last_time))) => {
+                let elapsed = now.duration_since(last_time).as_secs_f64().max(0.001);
+                Some((cpu_time - last_cpu_time).max(0.0) * 100.0 / elapsed)
+            }
+            _ => None,
+        };
+        self.last = cpu_time.map(|cpu_time| (cpu_time, now));
+        SelfSample {
+            cpu_percent,
+            memory_bytes: read_resident_bytes().unwrap_or(0),
+        }
+    }
+}
+
+/// Resident set size of this process from /proc/self/statm
+fn read_resident_bytes() -> Option<u64> {
+    let statm = fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * page_size())
+}
+
+/// Factor by which the refresh interval is stretched to keep Procular's CPU use under a limit
+pub struct RefreshThrottle {
+    slowdown: u32,
+}
+
+impl Default for RefreshThrottle {
+    fn default() -> Self {
+        Self { slowdown: 1 }
+    }
+}
+
+impl RefreshThrottle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current factor, 1 when refreshing at the normal rate
+    pub fn slowdown(&self) -> u32 {
+        self.slowdown
+    }
+
+    /// Double the interval while `cpu_percent` is above `limit_percent`, halve it
+    /// once usage is below half the limit; returns the new factor
+    pub fn update(&mut self, cpu_percent: f64, limit_percent: f64) -> u32 {
+        if cpu_percent > limit_percent {
+            self.slowdown = (self.slowdown * 2).min(MAX_SLOWDOWN);
+        } else if cpu_percent < limit_percent / 2.0 {
+            self.slowdown = (self.slowdown / 2).max(1);
+        }
+        self.slowdown
+    }
+
+    /// Back to the normal rate (when the limit is turned off)
+    pub fn reset(&mut self) {
+        self.slowdown = 1;
+    }
+}
@@ -0,0 +1,194 @@
+//! Temperature sensors from /sys/class/hwmon
+//!
+//! Every hwmon chip has a `name` (coretemp, k10temp, amdgpu, nvme, acpitz, nct6775, ...)
+//! and temperature inputs `temp<N>_input` in millidegrees Celsius, optionally with
+//! `temp<N>_label`, `temp<N>_max` and `temp<N>_crit`. hwmon numbers change between
+//! boots, so sensors are identified by chip name, device and input number instead.
+//! The proprietary NVIDIA driver has no hwmon chip; `SystemMonitor` adds its GPUs
+//! through NVML.
+
+use std::fs;
+use std::path::Path;
+
+const HWMON_DIR: &str = "/sys/class/hwmon";
+
+/// Degrees a sensor must cool below its warning threshold before it can alert again
+const OVERHEAT_HYSTERESIS_CELSIUS: f64 = 5.0;
+
+/// What a sensor measures, from its chip driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SensorKind {
+    Cpu,
+    Gpu,
+    Storage,
+    Board,
+    Other,
+}
+
+impl SensorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SensorKind::Cpu => "CPU",
+            SensorKind::Gpu => "GPU",
+            SensorKind::Storage => "Storage",
+            SensorKind::Board => "Motherboard",
+            SensorKind::Other => "Other",
+        }
+    }
+
+    fn from_chip(chip: &str) -> Self {
+        match chip {
+            "coretemp" | "k10temp" | "zenpower" | "cpu_thermal" => SensorKind::Cpu,
+            "amdgpu" | "radeon" | "nouveau" | "i915" | "xe" => SensorKind::Gpu,
+            "nvme" | "drivetemp" => SensorKind::Storage,
+            _ if chip.starts_with("nct") || chip.starts_with("it87") || chip.starts_with("asus") => {
+                SensorKind::Board
+            }
+            "acpitz" => SensorKind::Board,
+            _ => SensorKind::Other,
+        }
+    }
+
+    /// Warning threshold when the driver reports no limit
+    fn default_warning(&self) -> f64 {
+        match self {
+            SensorKind::Cpu | SensorKind::Gpu => 90.0,
+            SensorKind::Storage => 70.0,
+            SensorKind::Board => 80.0,
+            SensorKind::Other => 85.0,
+        }
+    }
+}
+
+/// One temperature reading
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sensor {
+    /// Stable identifier, e.g. "nvme@nvme0/temp1"
+    pub id: String,
+    pub kind: SensorKind,
+    /// hwmon chip name, e.g. "k10temp"
+    pub chip: String,
+    /// Driver label ("Package id 0", "Composite") or the input name
+    pub label: String,
+    pub celsius: f64,
+    /// High temperature limit reported by the driver
+    pub max: Option<f64>,
+    /// Critical temperature reported by the driver
+    pub crit: Option<f64>,
+}
+
+impl Sensor {
+    /// Sensor for an NVIDIA GPU read through NVML; `slowdown` is the driver's throttling temperature
+    pub fn nvidia_gpu(index: u32, name: &str, celsius: f64, slowdown: Option<f64>) -> Self {
+        Self {
+            id: format!("nvidia/gpu{}", index),
+            kind: SensorKind::Gpu,
+            chip: "nvidia".to_string(),
+            label: name.to_string(),
+            celsius,
+            max: slowdown,
+            crit: None,
+        }
+    }
+
+    /// Warning threshold when the user has not set one: the driver's high limit,
+    /// a few degrees below its critical limit, or a default for the kind of sensor (whole degrees)
+    pub fn default_warning(&self) -> f64 {
+        self.max
+            .or(self.crit.map(|crit| crit - OVERHEAT_HYSTERESIS_CELSIUS))
+            .unwrap_or_else(|| self.kind.default_warning())
+            .round()
+    }
+}
+
+/// All hwmon temperature sensors, grouped by kind
+pub fn read_sensors() -> Vec<Sensor> {
+    let Ok(entries) = fs::read_dir(HWMON_DIR) else {
+        return Vec::new();
+    };
+    let mut sensors: Vec<Sensor> = entries
+        .flatten()
+        .flat_map(|entry| read_chip(&entry.path()))
+        .collect();
+    sensors.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.id.cmp(&b.id)));
+    sensors
+}
+
+/// Temperature inputs of one hwmon chip
+fn read_chip(dir: &Path) -> Vec<Sensor> {
+    let read = |file: &str| fs::read_to_string(dir.join(file)).ok().map(|s| s.trim().to_string());
+    let millidegrees = |file: &str| read(file).and_then(|s| s.parse::<f64>().ok()).map(|v| v / 1000.0);
+    let Some(chip) = read("name") else {
+        return Vec::new();
+    };
+    // Several chips can share a driver name (one per NVMe drive), so include the device
+    let device = fs::read_link(dir.join("device"))
+        .ok()
+        .and_then(|link| link.file_name().map(|name| name.to_string_lossy().into_owned()));
+    let chip_id = match &device {
+        Some(device) => format!("{}@{}", chip, device),
+        None => chip.clone(),
+    };
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut inputs: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file = entry.file_name().to_string_lossy().into_owned();
+            let input = file.strip_suffix("_input")?;
+            input.starts_with("temp").then(|| input.to_string())
+        })
+        .collect();
+    inputs.sort_by_key(|input| input[4..].parse::<u32>().unwrap_or(0));
+
+    let kind = SensorKind::from_chip(&chip);
+    inputs
+        .into_iter()
+        .filter_map(|input| {
+            let celsius = millidegrees(&format!("{}_input", input))?;
+            Some(Sensor {
+                id: format!("{}/{}", chip_id, input),
+                kind,
+                chip: chip.clone(),
+                label: read(&format!("{}_label", input)).unwrap_or_else(|| input.clone()),
+                celsius,
+                // Some drivers report 0 or absurd values for missing limits
+                max: millidegrees(&format!("{}_max", input)).filter(|max| (20.0..150.0).contains(max)),
+                crit: millidegrees(&format!("{}_crit", input)).filter(|crit| (20.0..150.0).contains(crit)),
+            })
+        })
+        .collect()
+}
+
+/// Reports sensors once when they reach their warning threshold
+#[derive(Default)]
+pub struct OverheatDetector {
+    /// IDs of sensors currently above their threshold
+    alerted: Vec<String>,
+}
+
+impl OverheatDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sensors that reached `warning(sensor)` degrees since the last check
+    pub fn check<'a>(&mut self, sensors: &'a [Sensor], warning: impl Fn(&Sensor) -> f64) -> Vec<&'a Sensor> {
+        self.alerted.retain(|id| {
+            sensors
+                .iter()
+                .any(|s| s.id == *id && s.celsius >= warning(s) - OVERHEAT_HYSTERESIS_CELSIUS)
+        });
+
+        let mut crossed = Vec::new();
+        for sensor in sensors {
+            if sensor.celsius >= warning(sensor) && !self.alerted.contains(&sensor.id) {
+                self.alerted.push(sensor.id.clone());
+                crossed.push(sensor);
+            }
+        }
+        crossed
+    }
+}
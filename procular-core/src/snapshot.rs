@@ -0,0 +1,244 @@
+//! Process snapshots and the differences between two of them
+//!
+//! A snapshot records every process with its memory, CPU time and the disk and
+//! network totals the monitor has accumulated. Comparing two snapshots lists the
+//! processes that started, stopped or kept running in between with their metric
+//! deltas, e.g. for "what changed during the maintenance window" in a change ticket.
+//! Disk and network totals only count traffic seen while Procular was running.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::export::{csv_field, json_string};
+use crate::monitor::{format_bytes, ProcessKey};
+
+/// One process in a snapshot
+#[derive(Debug, Clone)]
+pub struct SnapshotEntry {
+    pub key: ProcessKey,
+    pub name: String,
+    pub command: Option<String>,
+    pub memory_bytes: u64,
+    /// User plus system CPU time since the process started
+    pub cpu_time_secs: f64,
+    pub disk_read: u64,
+    pub disk_written: u64,
+    pub net_received: u64,
+    pub net_sent: u64,
+}
+
+/// All processes at one point in time
+#[derive(Debug, Clone)]
+pub struct ProcessSnapshot {
+    /// Seconds since the Unix epoch
+    pub taken: u64,
+    pub entries: Vec<SnapshotEntry>,
+}
+
+impl ProcessSnapshot {
+    pub fn new(entries: Vec<SnapshotEntry>) -> Self {
+        Self {
+            taken: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            entries,
+        }
+    }
+}
+
+/// How a process changed between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Started,
+    Stopped,
+    Running,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Started => "started",
+            ChangeKind::Stopped => "stopped",
+            ChangeKind::Running => "running",
+        }
+    }
+}
+
+/// A process that started, stopped or kept running, with its metric deltas.
+/// Started processes count from zero; stopped ones give up their memory and record no other change
+#[derive(Debug, Clone)]
+pub struct ProcessChange {
+    pub kind: ChangeKind,
+    pub key: ProcessKey,
+    pub name: String,
+    pub command: Option<String>,
+    pub memory_delta: i64,
+    pub cpu_time_delta: f64,
+    pub disk_read_delta: u64,
+    pub disk_written_delta: u64,
+    pub net_received_delta: u64,
+    pub net_sent_delta: u64,
+}
+
+impl ProcessChange {
+    /// Whether anything but the process's existence changed
+    fn is_idle(&self) -> bool {
+        self.kind == ChangeKind::Running
+            && self.memory_delta == 0
+            && self.cpu_time_delta < 0.01
+            && self.disk_read_delta + self.disk_written_delta + self.net_received_delta + self.net_sent_delta == 0
+    }
+}
+
+/// Differences between two snapshots
+#[derive(Debug, Clone)]
+pub struct SnapshotDiff {
+    pub from: u64,
+    pub to: u64,
+    /// Started first, then stopped, then running processes by CPU time used
+    pub changes: Vec<ProcessChange>,
+}
+
+const CSV_HEADER: &str = "change,pid,start_time,name,command,memory_delta_bytes,cpu_time_delta_secs,\
+disk_read_bytes,disk_written_bytes,net_received_bytes,net_sent_bytes";
+
+impl SnapshotDiff {
+    /// Compare `before` with `after`; running processes that did nothing are left out
+    pub fn between(before: &ProcessSnapshot, after: &ProcessSnapshot) -> Self {
+        let old: HashMap<ProcessKey, &SnapshotEntry> = before.entries.iter().map(|e| (e.key, e)).collect();
+        let mut changes: Vec<ProcessChange> = after
+            .entries
+            .iter()
+            .map(|new| match old.get(&new.key) {
+                Some(old) => ProcessChange {
+                    kind: ChangeKind::Running,
+                    key: new.key,
+                    name: new.name.clone(),
+                    command: new.command.clone(),
+                    memory_delta: new.memory_bytes as i64 - old.memory_bytes as i64,
+                    cpu_time_delta: (new.cpu_time_secs - old.cpu_time_secs).max(0.0),
+                    disk_read_delta: new.disk_read.saturating_sub(old.disk_read),
+                    disk_written_delta: new.disk_written.saturating_sub(old.disk_written),
+                    net_received_delta: new.net_received.saturating_sub(old.net_received),
+                    net_sent_delta: new.net_sent.saturating_sub(old.net_sent),
+                },
+                None => ProcessChange {
+                    kind: ChangeKind::Started,
+                    key: new.key,
+                    name: new.name.clone(),
+                    command: new.command.clone(),
+                    memory_delta: new.memory_bytes as i64,
+                    cpu_time_delta: new.cpu_time_secs,
+                    disk_read_delta: new.disk_read,
+                    disk_written_delta: new.disk_written,
+                    net_received_delta: new.net_received,
+                    net_sent_delta: new.net_sent,
+                },
+            })
+            .filter(|change| !change.is_idle())
+            .collect();
+
+        let current: HashSet<ProcessKey> = after.entries.iter().map(|e| e.key).collect();
+        changes.extend(
+            before
+                .entries
+                .iter()
+                .filter(|old| !current.contains(&old.key))
+                .map(|old| ProcessChange {
+                    kind: ChangeKind::Stopped,
+                    key: old.key,
+                    name: old.name.clone(),
+                    command: old.command.clone(),
+                    memory_delta: -(old.memory_bytes as i64),
+                    cpu_time_delta: 0.0,
+                    disk_read_delta: 0,
+                    disk_written_delta: 0,
+                    net_received_delta: 0,
+                    net_sent_delta: 0,
+                }),
+        );
+        changes.sort_by(|a, b| {
+            a.kind
+                .cmp(&b.kind)
+                .then(b.cpu_time_delta.partial_cmp(&a.cpu_time_delta).unwrap_or(std::cmp::Ordering::Equal))
+        });
+
+        Self {
+            from: before.taken,
+            to: after.taken,
+            changes,
+        }
+    }
+
+    /// Number of started and stopped processes
+    pub fn counts(&self) -> (usize, usize) {
+        let count = |kind| self.changes.iter().filter(|c| c.kind == kind).count();
+        (count(ChangeKind::Started), count(ChangeKind::Stopped))
+    }
+
+    /// JSON document with both timestamps and one object per change
+    pub fn to_json(&self) -> String {
+        let changes: Vec<String> = self
+            .changes
+            .iter()
+            .map(|c| {
+                format!(
+                    "{{\"change\":\"{}\",\"pid\":{},\"start_time\":{},\"name\":{},\"command\":{},\
+                     \"memory_delta_bytes\":{},\"cpu_time_delta_secs\":{:.2},\"disk_read_bytes\":{},\
+                     \"disk_written_bytes\":{},\"net_received_bytes\":{},\"net_sent_bytes\":{}}}",
+                    c.kind.as_str(),
+                    c.key.pid,
+                    c.key.start_time,
+                    json_string(&c.name),
+                    c.command.as_deref().map_or("null".to_string(), json_string),
+                    c.memory_delta,
+                    c.cpu_time_delta,
+                    c.disk_read_delta,
+                    c.disk_written_delta,
+                    c.net_received_delta,
+                    c.net_sent_delta,
+                )
+            })
+            .collect();
+        format!(
+            "{{\"from\":{},\"to\":{},\"changes\":[\n{}\n]}}\n",
+            self.from,
+            self.to,
+            changes.join(",\n")
+        )
+    }
+
+    /// CSV with a header row and one line per change
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(CSV_HEADER);
+        csv.push('\n');
+        for c in &self.changes {
+            let fields = [
+                c.kind.as_str().to_string(),
+                c.key.pid.to_string(),
+                c.key.start_time.to_string(),
+                csv_field(&c.name),
+                csv_field(c.command.as_deref().unwrap_or("")),
+                c.memory_delta.to_string(),
+                format!("{:.2}", c.cpu_time_delta),
+                c.disk_read_delta.to_string(),
+                c.disk_written_delta.to_string(),
+                c.net_received_delta.to_string(),
+                c.net_sent_delta.to_string(),
+            ];
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Memory delta as text with its sign, e.g. "-12.0 MB"
+pub fn format_signed_bytes(delta: i64) -> String {
+    if delta < 0 {
+        format!("-{}", format_bytes(delta.unsigned_abs()))
+    } else {
+        format_bytes(delta as u64)
+    }
+}
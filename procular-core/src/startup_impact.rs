@@ -0,0 +1,187 @@
+//! Startup impact of the login session's applications
+//!
+//! Applications started by the systemd user manager (including XDG autostart
+//! entries) each run in their own cgroup under user@<uid>.service. The cgroup's
+//! cumulative CPU and I/O counters, together with when its processes started
+//! relative to login, show which autostarted applications weigh on login.
+//! Requires cgroup v2. Counters are lifetime totals, so the view is most accurate
+//! shortly after logging in.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::process_actions::{get_process_name, get_start_time_secs};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Resource usage of one application/service cgroup of the session
+#[derive(Debug, Clone)]
+pub struct StartupUnit {
+    /// systemd unit name (e.g. "app-gnome-evolution\\x2dalarm\\x2dnotify-2412.scope")
+    pub unit: String,
+    /// Name of the oldest process in the unit
+    pub process_name: String,
+    /// Time between login and the unit's first process starting
+    pub started_after_login: Duration,
+    pub cpu_time: Duration,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    pub process_count: usize,
+}
+
+/// Startup impact of the current session
+#[derive(Debug, Clone)]
+pub struct StartupReport {
+    /// Time since login
+    pub session_age: Duration,
+    /// Units started within the requested window, heaviest CPU users first
+    pub units: Vec<StartupUnit>,
+}
+
+/// Path of the current user's systemd user manager cgroup
+fn user_manager_cgroup() -> PathBuf {
+    let uid = unsafe { libc::getuid() };
+    Path::new(CGROUP_ROOT)
+        .join("user.slice")
+        .join(format!("user-{}.slice", uid))
+        .join(format!("user@{}.service", uid))
+}
+
+/// Collect the PIDs of a cgroup and all of its descendants
+fn cgroup_pids(dir: &Path, pids: &mut Vec<u32>) {
+    if let Ok(content) = fs::read_to_string(dir.join("cgroup.procs")) {
+        pids.extend(content.lines().filter_map(|l| l.trim().parse::<u32>().ok()));
+    }
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                cgroup_pids(&entry.path(), pids);
+            }
+        }
+    }
+}
+
+/// Read a "key value" field from a flat-keyed cgroup file (cpu.stat)
+fn read_keyed_value(path: &Path, key: &str) -> Option<u64> {
+    let content = fs::read_to_string(path).ok()?;
+    content.lines().find_map(|line| {
+        let (k, v) = line.split_once(' ')?;
+        if k == key { v.trim().parse().ok() } else { None }
+    })
+}
+
+/// Sum rbytes/wbytes over all devices in io.stat
+fn read_io_stat(dir: &Path) -> Option<(u64, u64)> {
+    let content = fs::read_to_string(dir.join("io.stat")).ok()?;
+    let mut totals = (0, 0);
+    for field in content.split_whitespace() {
+        if let Some(v) = field.strip_prefix("rbytes=") {
+            totals.0 += v.parse::<u64>().unwrap_or(0);
+        } else if let Some(v) = field.strip_prefix("wbytes=") {
+            totals.1 += v.parse::<u64>().unwrap_or(0);
+        }
+    }
+    Some(totals)
+}
+
+/// Sum read_bytes/write_bytes of /proc/<pid>/io (used when the io controller is not delegated)
+fn read_process_io(pids: &[u32]) -> (u64, u64) {
+    let mut totals = (0, 0);
+    for pid in pids {
+        let Ok(content) = fs::read_to_string(format!("/proc/{}/io", pid)) else {
+            continue;
+        };
+        for line in content.lines() {
+            if let Some(v) = line.strip_prefix("read_bytes:") {
+                totals.0 += v.trim().parse::<u64>().unwrap_or(0);
+            } else if let Some(v) = line.strip_prefix("write_bytes:") {
+                totals.1 += v.trim().parse::<u64>().unwrap_or(0);
+            }
+        }
+    }
+    totals
+}
+
+fn read_uptime_secs() -> io::Result<f64> {
+    let content = fs::read_to_string("/proc/uptime")?;
+    content
+        .split_whitespace()
+        .next()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid /proc/uptime"))
+}
+
+/// Measure the units of the session that started within `window` after login
+pub fn read_startup_impact(window: Duration) -> io::Result<StartupReport> {
+    let manager = user_manager_cgroup();
+    if !manager.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "No systemd user session cgroup found (requires systemd and cgroup v2)",
+        ));
+    }
+
+    // Login time is when the user manager (systemd --user) started
+    let mut manager_pids = Vec::new();
+    cgroup_pids(&manager.join("init.scope"), &mut manager_pids);
+    let login_secs = manager_pids
+        .iter()
+        .filter_map(|&pid| get_start_time_secs(pid))
+        .fold(f64::INFINITY, f64::min);
+    if !login_secs.is_finite() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "Cannot determine the session start time",
+        ));
+    }
+    let uptime = read_uptime_secs()?;
+
+    let mut units = Vec::new();
+    for slice in ["app.slice", "session.slice", "background.slice"] {
+        let Ok(entries) = fs::read_dir(manager.join(slice)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+                continue;
+            }
+            let dir = entry.path();
+            let mut pids = Vec::new();
+            cgroup_pids(&dir, &mut pids);
+
+            // The oldest live process tells when the unit started
+            let Some((first_pid, start)) = pids
+                .iter()
+                .filter_map(|&pid| get_start_time_secs(pid).map(|s| (pid, s)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+            else {
+                continue;
+            };
+            let started_after = (start - login_secs).max(0.0);
+            if started_after > window.as_secs_f64() {
+                continue;
+            }
+
+            let cpu_usec = read_keyed_value(&dir.join("cpu.stat"), "usage_usec").unwrap_or(0);
+            let (read_bytes, write_bytes) = read_io_stat(&dir).unwrap_or_else(|| read_process_io(&pids));
+
+            units.push(StartupUnit {
+                unit: entry.file_name().to_string_lossy().to_string(),
+                process_name: get_process_name(first_pid),
+                started_after_login: Duration::from_secs_f64(started_after),
+                cpu_time: Duration::from_micros(cpu_usec),
+                read_bytes,
+                write_bytes,
+                process_count: pids.len(),
+            });
+        }
+    }
+
+    units.sort_by_key(|u| std::cmp::Reverse(u.cpu_time));
+    Ok(StartupReport {
+        session_age: Duration::from_secs_f64((uptime - login_secs).max(0.0)),
+        units,
+    })
+}
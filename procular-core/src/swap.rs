@@ -0,0 +1,135 @@
+//! System-wide swap activity from /proc/vmstat and the processes holding swap
+//!
+//! The kernel counts pages swapped in and out since boot (`pswpin`/`pswpout`);
+//! the monitor turns the deltas into rates. Per-process usage is `VmSwap` from
+//! /proc/<pid>/status.
+
+use std::fs;
+
+/// RAM and swap space from /proc/meminfo (bytes)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryUsage {
+    pub mem_total: u64,
+    /// Total minus available, so reclaimable caches count as free
+    pub mem_used: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+}
+
+impl MemoryUsage {
+    /// Share of RAM in use, in percent
+    pub fn used_percent(&self) -> f64 {
+        if self.mem_total > 0 {
+            self.mem_used as f64 * 100.0 / self.mem_total as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A process with pages in swap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapUser {
+    pub pid: u32,
+    pub name: String,
+    pub swap_bytes: u64,
+}
+
+/// Pages swapped in and out since boot
+pub fn read_swap_pages() -> Option<(u64, u64)> {
+    let vmstat = fs::read_to_string("/proc/vmstat").ok()?;
+    Some((read_field(&vmstat, "pswpin")?, read_field(&vmstat, "pswpout")?))
+}
+
+/// Size of a memory page in bytes
+pub fn page_size() -> u64 {
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size > 0 {
+        size as u64
+    } else {
+        4096
+    }
+}
+
+/// Current RAM and swap usage
+pub fn read_memory_usage() -> MemoryUsage {
+    let meminfo = fs::read_to_string("/proc/meminfo").unwrap_or_default();
+    let kib = |key: &str| read_field(&meminfo, key).unwrap_or(0) * 1024;
+    let mem_total = kib("MemTotal:");
+    let swap_total = kib("SwapTotal:");
+    MemoryUsage {
+        mem_total,
+        mem_used: mem_total.saturating_sub(kib("MemAvailable:")),
+        swap_total,
+        swap_used: swap_total.saturating_sub(kib("SwapFree:")),
+    }
+}
+
+/// Processes with the most memory in swap, largest first
+pub fn top_swap_users(limit: usize) -> Vec<SwapUser> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let mut users: Vec<SwapUser> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let status = fs::read_to_string(entry.path().join("status")).ok()?;
+            // Kernel threads have no VmSwap line
+            let swap_bytes = read_field(&status, "VmSwap:")? * 1024;
+            let name = status.lines().find_map(|line| line.strip_prefix("Name:"))?.trim().to_string();
+            (swap_bytes > 0).then_some(SwapUser { pid, name, swap_bytes })
+        })
+        .collect();
+    users.sort_by_key(|user| std::cmp::Reverse(user.swap_bytes));
+    users.truncate(limit);
+    users
+}
+
+/// Reports swapping that stays above a rate for several refreshes in a row
+pub struct SwappingDetector {
+    /// Consecutive refreshes needed to alert, and to re-arm after the rate drops
+    required: usize,
+    busy: usize,
+    quiet: usize,
+    alerted: bool,
+}
+
+impl SwappingDetector {
+    pub fn new(required: usize) -> Self {
+        Self {
+            required: required.max(1),
+            busy: 0,
+            quiet: 0,
+            alerted: false,
+        }
+    }
+
+    /// Feed the latest swap rate; true once when it has stayed at or above `threshold` long enough
+    pub fn check(&mut self, rate: f64, threshold: f64) -> bool {
+        if rate >= threshold {
+            self.busy += 1;
+            self.quiet = 0;
+        } else {
+            self.busy = 0;
+            self.quiet += 1;
+            if self.quiet >= self.required {
+                self.alerted = false;
+            }
+        }
+        if self.busy >= self.required && !self.alerted {
+            self.alerted = true;
+            return true;
+        }
+        false
+    }
+}
+
+/// Value of the first line starting with `key` in a "key value" file
+fn read_field(content: &str, key: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix(key))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|value| value.parse().ok())
+}
@@ -0,0 +1,207 @@
+//! The systemd unit and slice owning a process, resolved from its cgroup path
+//!
+//! systemd names every cgroup after the unit it belongs to, e.g.
+//! "/system.slice/nginx.service" or
+//! "/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox.scope".
+//! Units below user@<uid>.service belong to that user's systemd instance and are
+//! controlled with `systemctl --user`; everything else goes to the system manager.
+
+use std::io;
+use std::process::Command;
+
+use crate::cgroup_limits::cgroup_path;
+use crate::process_actions::{run_action_command, run_unprivileged_command};
+
+/// Unit types that own processes
+const PROCESS_UNIT_SUFFIXES: &[&str] = &[".service", ".scope", ".socket", ".mount", ".swap"];
+
+/// The unit a process belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SystemdUnit {
+    /// Unit name, e.g. "nginx.service"
+    pub name: String,
+    /// Innermost slice containing the unit, e.g. "system.slice"
+    pub slice: Option<String>,
+    /// Whether the user's systemd instance manages it
+    pub user_manager: bool,
+}
+
+impl SystemdUnit {
+    /// Which manager owns the unit, for tooltips and dialogs
+    pub fn manager_label(&self) -> &'static str {
+        if self.user_manager {
+            "user session"
+        } else {
+            "system"
+        }
+    }
+}
+
+/// First UID of regular accounts (UID_MIN in /etc/login.defs on most distributions)
+const FIRST_USER_UID: u32 = 1000;
+/// The "nobody" account, used by sandboxed system services
+const NOBODY_UID: u32 = 65534;
+
+/// Who a process runs for, to separate the desktop session from system services
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProcessOrigin {
+    /// Apps and services of the user running Procular
+    Session,
+    /// System services, kernel threads and system accounts
+    System,
+    /// Processes of other logged-in users
+    OtherUser,
+}
+
+impl ProcessOrigin {
+    pub fn all() -> &'static [ProcessOrigin] {
+        &[ProcessOrigin::Session, ProcessOrigin::System, ProcessOrigin::OtherUser]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProcessOrigin::Session => "My Session",
+            ProcessOrigin::System => "System Services",
+            ProcessOrigin::OtherUser => "Other Users",
+        }
+    }
+
+    /// Identifier for settings and actions
+    pub fn key(&self) -> &'static str {
+        match self {
+            ProcessOrigin::Session => "session",
+            ProcessOrigin::System => "system",
+            ProcessOrigin::OtherUser => "other-users",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|origin| origin.key() == key)
+    }
+}
+
+/// Origin of a process from its cgroup path and owner. A user-<uid>.slice decides
+/// where systemd manages sessions; elsewhere (no systemd, containers) the UID does
+pub fn process_origin(cgroup: Option<&str>, uid: Option<u32>, own_uid: u32) -> ProcessOrigin {
+    let cgroup = cgroup.unwrap_or_default();
+    let slice_uid = cgroup
+        .split('/')
+        .find_map(|part| part.strip_prefix("user-")?.strip_suffix(".slice")?.parse::<u32>().ok());
+    if let Some(slice_uid) = slice_uid {
+        return if slice_uid == own_uid {
+            ProcessOrigin::Session
+        } else {
+            ProcessOrigin::OtherUser
+        };
+    }
+    if cgroup.starts_with("/system.slice") || cgroup.starts_with("/init.scope") {
+        return ProcessOrigin::System;
+    }
+    match uid {
+        Some(uid) if uid == own_uid => ProcessOrigin::Session,
+        Some(uid) if uid >= FIRST_USER_UID && uid != NOBODY_UID => ProcessOrigin::OtherUser,
+        _ => ProcessOrigin::System,
+    }
+}
+
+/// Action on a unit from the process list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitAction {
+    Restart,
+    Stop,
+}
+
+impl UnitAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnitAction::Restart => "Restart",
+            UnitAction::Stop => "Stop",
+        }
+    }
+
+    fn command(&self) -> &'static str {
+        match self {
+            UnitAction::Restart => "restart",
+            UnitAction::Stop => "stop",
+        }
+    }
+}
+
+/// Whether the cgroup at `path` is managed by a user's systemd instance.
+/// The user manager owns everything below user@<uid>.service, but not that unit itself
+pub fn is_user_managed(path: &str) -> bool {
+    let parts: Vec<&str> = path.split('/').collect();
+    parts
+        .iter()
+        .position(|part| part.starts_with("user@") && part.ends_with(".service"))
+        .is_some_and(|index| index + 1 < parts.len())
+}
+
+/// Unit owning the cgroup at `path` (from `cgroup_path`); None for the root cgroup and kernel threads
+pub fn unit_from_cgroup(path: &str) -> Option<SystemdUnit> {
+    let parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+    // Services may create sub-cgroups of their own below the unit, so take the innermost unit
+    let index = parts
+        .iter()
+        .rposition(|part| PROCESS_UNIT_SUFFIXES.iter().any(|suffix| part.ends_with(suffix)))?;
+    let slice = parts[..index]
+        .iter()
+        .rev()
+        .find(|part| part.ends_with(".slice"))
+        .map(|slice| slice.to_string());
+    Some(SystemdUnit {
+        name: parts[index].to_string(),
+        slice,
+        user_manager: is_user_managed(&format!("/{}", parts[..=index].join("/"))),
+    })
+}
+
+/// Unit owning a process, if it runs under systemd with cgroup v2 (or the hybrid hierarchy)
+pub fn process_unit(pid: u32) -> Option<SystemdUnit> {
+    unit_from_cgroup(&cgroup_path(pid)?)
+}
+
+/// Run systemctl or busctl against the user or system manager; the system manager goes through pkexec
+pub(crate) fn run_manager_command(
+    user_manager: bool,
+    program: &str,
+    args: Vec<String>,
+    error_prefix: &str,
+) -> io::Result<()> {
+    if user_manager {
+        let mut user_args = vec!["--user".to_string()];
+        user_args.extend(args);
+        run_unprivileged_command(program, &user_args, error_prefix)
+    } else {
+        run_action_command(program, &args, error_prefix)
+    }
+}
+
+/// Restart or stop a unit
+pub fn run_unit_action(unit: &SystemdUnit, action: UnitAction) -> io::Result<()> {
+    run_manager_command(
+        unit.user_manager,
+        "systemctl",
+        vec![action.command().to_string(), unit.name.clone()],
+        &format!("Failed to {} {}", action.command(), unit.name),
+    )
+}
+
+/// `systemctl status` output of a unit, including its latest log lines
+pub fn unit_status(unit: &SystemdUnit) -> io::Result<String> {
+    let mut command = Command::new("systemctl");
+    if unit.user_manager {
+        command.arg("--user");
+    }
+    // Exits non-zero for inactive or failed units, which still print their status
+    let output = command
+        .args(["status", "--no-pager", "--full", "--lines=20", &unit.name])
+        .output()?;
+    if output.stdout.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,54 @@
+//! Resource use per account, summed over every process of a refresh (not only the listed ones),
+//! to see who is using a shared machine
+
+use std::collections::HashMap;
+
+use crate::monitor::ProcessInfo;
+
+/// Totals of the processes of one user
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserUsage {
+    pub uid: u32,
+    pub processes: usize,
+    /// Percent of the whole machine
+    pub cpu_percent: f32,
+    /// Sum of resident memory; memory shared between the processes is counted once per process
+    pub memory_bytes: u64,
+    /// Bytes per second
+    pub disk_read_rate: u64,
+    pub disk_write_rate: u64,
+    pub net_rx_rate: u64,
+    pub net_tx_rate: u64,
+}
+
+/// Sum `processes` per owner, busiest first; network bytes since the previous refresh are turned
+/// into rates over `elapsed` seconds. Processes whose owner is unknown are left out
+pub fn usage_by_user(processes: &[ProcessInfo], elapsed: f64) -> Vec<UserUsage> {
+    let mut users: HashMap<u32, UserUsage> = HashMap::new();
+    for process in processes {
+        let Some(uid) = process.uid else {
+            continue;
+        };
+        let usage = users.entry(uid).or_insert_with(|| UserUsage { uid, ..Default::default() });
+        usage.processes += 1;
+        usage.cpu_percent += process.total_cpu();
+        usage.memory_bytes += process.total_memory();
+        usage.disk_read_rate += process.total_disk_read_rate();
+        usage.disk_write_rate += process.total_disk_write_rate();
+        // Bytes until divided below
+        usage.net_rx_rate += process.total_net_rx();
+        usage.net_tx_rate += process.total_net_tx();
+    }
+
+    let elapsed = elapsed.max(0.001);
+    let mut users: Vec<UserUsage> = users
+        .into_values()
+        .map(|mut usage| {
+            usage.net_rx_rate = (usage.net_rx_rate as f64 / elapsed) as u64;
+            usage.net_tx_rate = (usage.net_tx_rate as f64 / elapsed) as u64;
+            usage
+        })
+        .collect();
+    users.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent).then(a.uid.cmp(&b.uid)));
+    users
+}
@@ -0,0 +1,52 @@
+//! User names of accounts, looked up through NSS so that LDAP and systemd-homed users
+//! resolve too. Names are cached, since the same few users are asked for on every refresh
+
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// Largest buffer offered to getpw*_r before giving up
+const MAX_BUFFER: usize = 1 << 20;
+
+/// Run getpwuid_r or getpwnam_r, growing the buffer while it is too small; (name, UID) of the account
+fn lookup(
+    call: impl Fn(&mut libc::passwd, &mut [libc::c_char], &mut *mut libc::passwd) -> libc::c_int,
+) -> Option<(String, u32)> {
+    let mut buffer: Vec<libc::c_char> = vec![0; 1024];
+    loop {
+        let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+        let mut result = std::ptr::null_mut();
+        match call(&mut passwd, &mut buffer, &mut result) {
+            libc::ERANGE if buffer.len() < MAX_BUFFER => buffer.resize(buffer.len() * 2, 0),
+            0 if !result.is_null() => {
+                let name = unsafe { CStr::from_ptr(passwd.pw_name) };
+                return Some((name.to_string_lossy().into_owned(), passwd.pw_uid));
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Name of the account with `uid`
+pub fn user_name(uid: u32) -> Option<String> {
+    static NAMES: OnceLock<Mutex<HashMap<u32, Option<String>>>> = OnceLock::new();
+    let mut names = NAMES.get_or_init(Default::default).lock().unwrap_or_else(PoisonError::into_inner);
+    names
+        .entry(uid)
+        .or_insert_with(|| {
+            lookup(|passwd, buffer, result| unsafe {
+                libc::getpwuid_r(uid, passwd, buffer.as_mut_ptr(), buffer.len(), result)
+            })
+            .map(|(name, _)| name)
+        })
+        .clone()
+}
+
+/// UID of the account called `name`
+pub fn uid_for_name(name: &str) -> Option<u32> {
+    let name = CString::new(name).ok()?;
+    lookup(|passwd, buffer, result| unsafe {
+        libc::getpwnam_r(name.as_ptr(), passwd, buffer.as_mut_ptr(), buffer.len(), result)
+    })
+    .map(|(_, uid)| uid)
+}
@@ -0,0 +1,127 @@
+//! Per-process video memory alarms: steady growth (a likely leak) and use above a limit
+//!
+//! GPU drivers allocate video memory in large chunks and programs rarely give it back
+//! bit by bit, so a process whose VRAM only ever grows for minutes is a good leak
+//! candidate. Small drops are treated as noise so a leak with occasional frees still
+//! counts as one growing run.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::monitor::{GpuMemory, GpuProcess};
+
+/// How long VRAM must keep growing before it counts as a leak
+pub const LEAK_MIN_DURATION: Duration = Duration::from_secs(5 * 60);
+/// Growth over the run needed for a leak alert
+pub const LEAK_MIN_GROWTH_BYTES: u64 = 256 * 1024 * 1024;
+/// Drops smaller than this do not end a growing run
+const NOISE_BYTES: u64 = 4 * 1024 * 1024;
+/// Percentage points usage must drop below the limit before a process can alert again
+const LIMIT_HYSTERESIS_PERCENT: f64 = 5.0;
+
+/// Why a process was flagged
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VramAlertKind {
+    /// VRAM grew by `grown_bytes` over `duration` without dropping
+    Growing { grown_bytes: u64, duration: Duration },
+    /// VRAM use is at `percent` of the GPU's memory, above the limit
+    AboveLimit { percent: f64 },
+}
+
+/// A process flagged by `VramLeakDetector`
+#[derive(Debug, Clone)]
+pub struct VramAlert {
+    pub process: GpuProcess,
+    /// Name of the GPU the memory is on
+    pub gpu_name: String,
+    pub kind: VramAlertKind,
+}
+
+/// Start of a process's current growing run
+#[derive(Debug, Clone, Copy)]
+struct GrowthRun {
+    since: Instant,
+    start_bytes: u64,
+    /// Highest usage in the run, drops are measured from it
+    peak_bytes: u64,
+}
+
+/// Reports processes once when their VRAM keeps growing or passes a limit
+#[derive(Default)]
+pub struct VramLeakDetector {
+    /// Keyed by PID and GPU index, since a process can hold memory on several GPUs
+    runs: HashMap<(u32, u32), GrowthRun>,
+    /// Processes alerted for their current growing run
+    growth_alerted: HashSet<(u32, u32)>,
+    /// Processes currently above the limit
+    limit_alerted: HashSet<(u32, u32)>,
+}
+
+impl VramLeakDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Processes that started leaking or passed `limit_percent` of their GPU's memory since
+    /// the last check; a limit of None only looks for leaks
+    pub fn check(
+        &mut self,
+        processes: &[GpuProcess],
+        gpus: &[GpuMemory],
+        limit_percent: Option<f64>,
+        now: Instant,
+    ) -> Vec<VramAlert> {
+        let present: HashSet<(u32, u32)> = processes.iter().map(|p| (p.pid, p.gpu_index)).collect();
+        self.runs.retain(|key, _| present.contains(key));
+        self.growth_alerted.retain(|key| present.contains(key));
+        self.limit_alerted.retain(|key| present.contains(key));
+
+        let mut alerts = Vec::new();
+        for process in processes {
+            let Some(gpu) = gpus.iter().find(|gpu| gpu.index == process.gpu_index) else {
+                continue;
+            };
+            let key = (process.pid, process.gpu_index);
+            let alert = |kind| VramAlert {
+                process: process.clone(),
+                gpu_name: gpu.name.clone(),
+                kind,
+            };
+
+            let run = self.runs.entry(key).or_insert(GrowthRun {
+                since: now,
+                start_bytes: process.vram_bytes,
+                peak_bytes: process.vram_bytes,
+            });
+            if process.vram_bytes + NOISE_BYTES < run.peak_bytes {
+                // Memory was given back; start a new run from here
+                *run = GrowthRun {
+                    since: now,
+                    start_bytes: process.vram_bytes,
+                    peak_bytes: process.vram_bytes,
+                };
+                self.growth_alerted.remove(&key);
+            }
+            run.peak_bytes = run.peak_bytes.max(process.vram_bytes);
+            let grown_bytes = process.vram_bytes.saturating_sub(run.start_bytes);
+            let duration = now.duration_since(run.since);
+            if duration >= LEAK_MIN_DURATION
+                && grown_bytes >= LEAK_MIN_GROWTH_BYTES
+                && self.growth_alerted.insert(key)
+            {
+                alerts.push(alert(VramAlertKind::Growing { grown_bytes, duration }));
+            }
+
+            let Some(limit) = limit_percent.filter(|_| gpu.total > 0) else {
+                continue;
+            };
+            let percent = process.vram_bytes as f64 / gpu.total as f64 * 100.0;
+            if percent < limit - LIMIT_HYSTERESIS_PERCENT {
+                self.limit_alerted.remove(&key);
+            } else if percent >= limit && self.limit_alerted.insert(key) {
+                alerts.push(alert(VramAlertKind::AboveLimit { percent }));
+            }
+        }
+        alerts
+    }
+}
@@ -0,0 +1,78 @@
+//! Per-process wakeup and context switch rates
+//!
+//! powertop counts wakeups with scheduler tracepoints, and /proc/timer_list only
+//! lists pending timers (and needs root). A thread that wakes up from sleep has
+//! always just ended a voluntary context switch, so the rate of voluntary switches
+//! summed over all threads (from /proc/<tid>/status) approximates wakeups/s without
+//! privileges. Frequent wakeups keep CPUs out of deep idle states and drain the
+//! battery even when CPU usage looks low.
+//!
+//! The same status files also count involuntary switches, where the scheduler took the
+//! CPU away. Many voluntary switches at low CPU use point to lock contention (threads
+//! blocking on each other); many involuntary ones mean more runnable threads than CPUs.
+
+use std::collections::HashMap;
+use std::fs;
+use std::time::Instant;
+
+use crate::monitor::ProcessKey;
+
+/// Wakeups per second above which a process noticeably prevents deep CPU sleep states
+pub const HIGH_WAKEUP_RATE: f64 = 100.0;
+
+/// Context switches per second of a process and all its threads
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SwitchRates {
+    /// Switches where a thread gave up the CPU to wait; approximates wakeups
+    pub voluntary_per_sec: f64,
+    /// Switches where the scheduler preempted a runnable thread
+    pub involuntary_per_sec: f64,
+}
+
+/// Voluntary and involuntary context switches of one thread (or the main thread of a process)
+pub fn read_context_switches(tid: u32) -> Option<(u64, u64)> {
+    let status = fs::read_to_string(format!("/proc/{}/status", tid)).ok()?;
+    let field = |prefix: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(prefix))
+            .and_then(|value| value.trim().parse().ok())
+    };
+    Some((field("voluntary_ctxt_switches:")?, field("nonvoluntary_ctxt_switches:")?))
+}
+
+/// Turns switch counters into rates between refreshes
+#[derive(Default)]
+pub struct WakeupTracker {
+    /// Voluntary and involuntary switch totals from the previous refresh
+    previous: HashMap<ProcessKey, ((u64, u64), Instant)>,
+    /// Switch totals sampled during the current refresh
+    current: HashMap<ProcessKey, ((u64, u64), Instant)>,
+}
+
+impl WakeupTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Switch rates of a process given all of its thread IDs; None on its first sample.
+    /// Threads that exited take their switches with them, which reads as a quiet interval
+    pub fn sample(&mut self, key: ProcessKey, tids: impl Iterator<Item = u32>) -> Option<SwitchRates> {
+        let (voluntary, involuntary) = tids
+            .filter_map(read_context_switches)
+            .fold((0, 0), |(v, i), (tv, ti)| (v + tv, i + ti));
+        let now = Instant::now();
+        self.current.insert(key, ((voluntary, involuntary), now));
+        let ((last_voluntary, last_involuntary), last_time) = self.previous.get(&key)?;
+        let elapsed = now.duration_since(*last_time).as_secs_f64().max(0.001);
+        Some(SwitchRates {
+            voluntary_per_sec: voluntary.saturating_sub(*last_voluntary) as f64 / elapsed,
+            involuntary_per_sec: involuntary.saturating_sub(*last_involuntary) as f64 / elapsed,
+        })
+    }
+
+    /// End a refresh; processes not sampled in it are forgotten
+    pub fn finish_refresh(&mut self) {
+        self.previous = std::mem::take(&mut self.current);
+    }
+}
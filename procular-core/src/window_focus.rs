@@ -0,0 +1,30 @@
+//! Association between processes and their top-level windows
+//!
+//! Uses the EWMH properties exposed through xprop, so it works on X11 and for
+//! XWayland clients (most games). Native Wayland clients cannot be resolved since
+//! the protocol does not expose other clients' focus state.
+
+use std::process::Command;
+
+/// Run xprop and return its trimmed stdout
+fn run_xprop(args: &[&str]) -> Option<String> {
+    let output = Command::new("xprop").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Get the PID owning the currently focused window, if it can be determined
+pub fn focused_window_pid() -> Option<u32> {
+    // "_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007"
+    let active = run_xprop(&["-root", "_NET_ACTIVE_WINDOW"])?;
+    let window_id = active.rsplit(' ').next()?;
+    if !window_id.starts_with("0x") || window_id == "0x0" {
+        return None; // No window focused (e.g. desktop)
+    }
+
+    // "_NET_WM_PID(CARDINAL) = 1234"
+    let pid_line = run_xprop(&["-id", window_id, "_NET_WM_PID"])?;
+    pid_line.rsplit('=').next()?.trim().parse().ok()
+}
@@ -0,0 +1,36 @@
+//! Narrow window layout for small laptop screens and phones
+//!
+//! Below `NARROW_WIDTH_SP` the page switcher moves from the header bar to a bar at
+//! the bottom of the window, like in GNOME's own apps. Windows using this must set
+//! a minimum size so the breakpoint can shrink them.
+
+use gtk4::prelude::*;
+use libadwaita as adw;
+
+/// Window width at which the narrow layout starts
+const NARROW_WIDTH_SP: f64 = 600.0;
+
+/// Smallest window size, as on GNOME phones
+pub const MIN_WIDTH: i32 = 360;
+pub const MIN_HEIGHT: i32 = 294;
+
+/// Bottom switcher for `stack`, hidden until the narrow breakpoint applies
+pub fn view_switcher_bar(stack: &adw::ViewStack) -> adw::ViewSwitcherBar {
+    let bar = adw::ViewSwitcherBar::new();
+    bar.set_stack(Some(stack));
+    bar
+}
+
+/// Breakpoint that hides the header bar's title switcher and reveals `switcher_bar`;
+/// callers add setters for their own widgets before adding it to the window
+pub fn narrow_breakpoint(header: &adw::HeaderBar, switcher_bar: &adw::ViewSwitcherBar) -> adw::Breakpoint {
+    let condition = adw::BreakpointCondition::new_length(
+        adw::BreakpointConditionLengthType::MaxWidth,
+        NARROW_WIDTH_SP,
+        adw::LengthUnit::Sp,
+    );
+    let breakpoint = adw::Breakpoint::new(condition);
+    breakpoint.add_setter(header, "show-title", Some(&false.to_value()));
+    breakpoint.add_setter(switcher_bar, "reveal", Some(&true.to_value()));
+    breakpoint
+}
@@ -0,0 +1,38 @@
+//! State shared between the main window and process windows
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use procular_core::monitor::SystemMonitor;
+
+use crate::audit_log::AuditLog;
+use crate::automation::AutomationEngine;
+use crate::budgets::BudgetMonitor;
+use crate::inhibit::SuspendInhibitor;
+use crate::lockdown::Lockdown;
+use crate::settings::Settings;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub monitor: Rc<RefCell<SystemMonitor>>,
+    pub automation: Rc<RefCell<AutomationEngine>>,
+    pub budgets: Rc<RefCell<BudgetMonitor>>,
+    pub settings: Rc<RefCell<Settings>>,
+    pub inhibitor: Rc<SuspendInhibitor>,
+    pub lockdown: Rc<Lockdown>,
+    /// Signals sent from Procular
+    pub audit_log: Rc<RefCell<AuditLog>>,
+    /// Seconds between monitor refreshes; longer than usual while the self-limit slows refreshing down
+    pub sample_interval_secs: Rc<Cell<u64>>,
+}
+
+impl AppState {
+    /// Factor from the monitor's whole-machine CPU percentages to the displayed ones
+    pub fn cpu_scale(&self) -> f32 {
+        if self.settings.borrow().cpu_per_core {
+            self.monitor.borrow().cpu_count() as f32
+        } else {
+            1.0
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Audit log of the signals sent from Procular
+//!
+//! Every signal Procular sends is appended to ~/.config/procular/audit.log: from the process
+//! list, process windows, the idle panel, the GPU page, the D-Bus Kill method and memory limit
+//! automation rules. Each process gets one tab-separated line with the Unix time, PID, name,
+//! signal and outcome. Quick-kill mode skips the confirmation before ending processes, so this
+//! log is what records them.
+
+use std::cell::RefCell;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use procular_core::export::csv_field;
+use procular_core::process_actions::{run_in_background, send_signal_many, Signal};
+
+use crate::config;
+
+const AUDIT_FILE: &str = "audit.log";
+/// Entries loaded and kept in memory; the file itself is never truncated
+const MAX_ENTRIES: usize = 1000;
+
+/// One signal sent to one process
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    /// Seconds since the Unix epoch
+    pub time: u64,
+    pub pid: u32,
+    pub name: String,
+    /// Signal name, e.g. "SIGKILL"
+    pub signal: String,
+    /// "sent" or the error
+    pub outcome: String,
+}
+
+impl AuditEntry {
+    fn to_line(&self) -> String {
+        // Names and errors must not break the line format
+        let clean = |text: &str| text.replace(['\t', '\n'], " ");
+        format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            self.time,
+            self.pid,
+            clean(&self.name),
+            self.signal,
+            clean(&self.outcome)
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, '\t');
+        Some(Self {
+            time: fields.next()?.parse().ok()?,
+            pid: fields.next()?.parse().ok()?,
+            name: fields.next()?.to_string(),
+            signal: fields.next()?.to_string(),
+            outcome: fields.next()?.to_string(),
+        })
+    }
+}
+
+/// The most recent entries of the audit log
+#[derive(Default)]
+pub struct AuditLog {
+    entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    pub fn load() -> Self {
+        let content = fs::read_to_string(config::config_dir().join(AUDIT_FILE)).unwrap_or_default();
+        let mut entries: Vec<AuditEntry> = content.lines().filter_map(AuditEntry::from_line).collect();
+        entries.drain(..entries.len().saturating_sub(MAX_ENTRIES));
+        Self { entries }
+    }
+
+    /// Entries, oldest first
+    pub fn entries(&self) -> &[AuditEntry] {
+        &self.entries
+    }
+
+    /// Record the outcome of sending `signal` to each of `targets` (PID, name)
+    pub fn record(&mut self, targets: &[(u32, String)], signal: Signal, result: &io::Result<()>) -> io::Result<()> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let outcome = match result {
+            Ok(()) => "sent".to_string(),
+            Err(e) => e.to_string(),
+        };
+        let new_entries: Vec<AuditEntry> = targets
+            .iter()
+            .map(|(pid, name)| AuditEntry {
+                time,
+                pid: *pid,
+                name: name.clone(),
+                signal: signal.name().to_string(),
+                outcome: outcome.clone(),
+            })
+            .collect();
+
+        let dir = config::config_dir();
+        fs::create_dir_all(&dir)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(dir.join(AUDIT_FILE))?;
+        let text: String = new_entries.iter().map(AuditEntry::to_line).collect();
+        let written = file.write_all(text.as_bytes());
+
+        self.entries.extend(new_entries);
+        self.entries.drain(..self.entries.len().saturating_sub(MAX_ENTRIES));
+        written
+    }
+
+    /// Entries as CSV with Unix timestamps
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("time,pid,name,signal,outcome\n");
+        for entry in &self.entries {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                entry.time,
+                entry.pid,
+                csv_field(&entry.name),
+                entry.signal,
+                csv_field(&entry.outcome)
+            ));
+        }
+        csv
+    }
+}
+
+/// Send `signal` to `targets` with one command and record the outcome in `log`. The command
+/// runs on a worker thread, since a polkit prompt may wait for the user
+pub async fn send_signal_audited(
+    log: Rc<RefCell<AuditLog>>,
+    targets: Vec<(u32, String)>,
+    signal: Signal,
+) -> io::Result<()> {
+    let pids: Vec<u32> = targets.iter().map(|(pid, _)| *pid).collect();
+    let result = run_in_background(move || send_signal_many(&pids, signal)).await;
+    if let Err(e) = log.borrow_mut().record(&targets, signal, &result) {
+        eprintln!("Failed to write the audit log: {}", e);
+    }
+    result
+}
@@ -0,0 +1,117 @@
+//! Audit log window: every signal sent from Procular, newest first, with CSV export
+
+use gtk4::prelude::*;
+use gtk4::{gio, Box as GtkBox, Button, Label, Orientation};
+use libadwaita as adw;
+use adw::prelude::*;
+
+use crate::app_state::AppState;
+use crate::table_view::{ColumnKind, TableView};
+
+/// Show the recorded signals
+pub fn show_audit_log_dialog(parent: &impl IsA<gtk4::Window>, state: &AppState) {
+    let dialog = adw::Window::builder()
+        .title("Audit Log")
+        .transient_for(parent)
+        .default_width(750)
+        .default_height(500)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+    let csv_btn = Button::with_label("Export CSV…");
+    header.pack_end(&csv_btn);
+    main_box.append(&header);
+
+    let toast_overlay = adw::ToastOverlay::new();
+    let content = GtkBox::new(Orientation::Vertical, 8);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+    content.set_margin_start(12);
+    content.set_margin_end(12);
+
+    let audit_log = state.audit_log.borrow();
+    let entries = audit_log.entries();
+    let failed = entries.iter().filter(|entry| entry.outcome != "sent").count();
+    let summary_label = Label::new(Some(&format!(
+        "{} signals recorded, {} of them failed.",
+        entries.len(),
+        failed
+    )));
+    summary_label.set_halign(gtk4::Align::Start);
+    summary_label.set_wrap(true);
+    content.append(&summary_label);
+
+    let table = TableView::new(&[
+        ("Time", ColumnKind::Short),
+        ("PID", ColumnKind::Number),
+        ("Process", ColumnKind::Text),
+        ("Signal", ColumnKind::Short),
+        ("Outcome", ColumnKind::Text),
+    ]);
+    table.set_rows(
+        entries
+            .iter()
+            .rev()
+            .map(|entry| {
+                let time = glib::DateTime::from_unix_local(entry.time as i64)
+                    .and_then(|time| time.format("%Y-%m-%d %H:%M:%S"))
+                    .map(|time| time.to_string())
+                    .unwrap_or_default();
+                vec![
+                    time,
+                    entry.pid.to_string(),
+                    entry.name.clone(),
+                    entry.signal.clone(),
+                    entry.outcome.clone(),
+                ]
+            })
+            .collect(),
+    );
+    content.append(&table.widget);
+    drop(audit_log);
+
+    let note = Label::new(Some(
+        "Every signal sent from Procular, including by automation rules and the D-Bus interface, \
+         is appended to ~/.config/procular/audit.log; only the latest entries are shown.",
+    ));
+    note.add_css_class("dim-label");
+    note.set_halign(gtk4::Align::Start);
+    note.set_wrap(true);
+    content.append(&note);
+
+    toast_overlay.set_child(Some(&content));
+    main_box.append(&toast_overlay);
+    dialog.set_content(Some(&main_box));
+
+    let dialog_weak = dialog.downgrade();
+    let audit_log = state.audit_log.clone();
+    csv_btn.connect_clicked(move |_| {
+        let Some(dialog) = dialog_weak.upgrade() else {
+            return;
+        };
+        let text = audit_log.borrow().to_csv();
+        let toast_overlay = toast_overlay.clone();
+        let file_dialog = gtk4::FileDialog::builder()
+            .title("Export Audit Log")
+            .initial_name("procular-audit.csv")
+            .build();
+        file_dialog.save(Some(&dialog), gio::Cancellable::NONE, move |result| {
+            // Cancelled dialogs also end up here
+            let Ok(file) = result else {
+                return;
+            };
+            let Some(path) = file.path() else {
+                return;
+            };
+            let message = match std::fs::write(&path, &text) {
+                Ok(()) => format!("Exported to {}", path.display()),
+                Err(e) => format!("Export failed: {}", e),
+            };
+            toast_overlay.add_toast(adw::Toast::new(&message));
+        });
+    });
+
+    dialog.present();
+}
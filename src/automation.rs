@@ -0,0 +1,412 @@
+//! User-defined automation rules, evaluated on every refresh
+//!
+//! Rules are stored in ~/.config/procular/rules.ini with one group per rule.
+//! Actions are applied without privilege escalation; raising a priority above
+//! Normal needs CAP_SYS_NICE or a suitable RLIMIT_NICE, and only the user's own
+//! processes can be re-pinned or ended.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+
+use procular_core::monitor::{ProcessInfo, ProcessKey};
+use procular_core::name_pattern::matches_pattern;
+use procular_core::process_actions::{
+    descendants_in, read_children_map, try_send_signal, try_set_cpu_affinity, try_set_priority, Priority, Signal,
+};
+use procular_core::window_focus::focused_window_pid;
+
+use crate::audit_log::AuditLog;
+use crate::config;
+
+const RULES_FILE: &str = "rules.ini";
+const FOCUS_PRIORITY_TYPE: &str = "focus-priority";
+const PRIORITY_TYPE: &str = "priority";
+const AFFINITY_TYPE: &str = "affinity";
+const MEMORY_LIMIT_TYPE: &str = "memory-limit";
+
+/// A single automation rule, matched against process names
+///
+/// Focus rules name one process exactly; the others take a pattern where `*`
+/// matches any run of characters and `?` a single character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// Apply `focused` while the process owns the focused window, `unfocused` otherwise
+    FocusPriority {
+        process_name: String,
+        focused: Priority,
+        unfocused: Priority,
+    },
+    /// Renice matching processes, and with `include_children` everything they spawn
+    Priority {
+        pattern: String,
+        priority: Priority,
+        include_children: bool,
+    },
+    /// Restrict matching processes (and optionally their descendants) to the given CPUs
+    Affinity {
+        pattern: String,
+        cpus: Vec<usize>,
+        include_children: bool,
+    },
+    /// Ask matching processes to terminate once their memory exceeds the limit
+    MemoryLimit { pattern: String, limit_bytes: u64 },
+}
+
+impl Rule {
+    /// The process name or pattern the rule applies to
+    pub fn process_name(&self) -> &str {
+        match self {
+            Rule::FocusPriority { process_name, .. } => process_name,
+            Rule::Priority { pattern, .. } | Rule::Affinity { pattern, .. } | Rule::MemoryLimit { pattern, .. } => {
+                pattern
+            }
+        }
+    }
+
+    /// Whether the rule applies to a process with this name
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Rule::FocusPriority { process_name, .. } => process_name == name,
+            _ => matches_pattern(self.process_name(), name),
+        }
+    }
+
+    /// Whether the rule also applies to descendants of matching processes
+    pub fn includes_children(&self) -> bool {
+        matches!(
+            self,
+            Rule::Priority { include_children: true, .. } | Rule::Affinity { include_children: true, .. }
+        )
+    }
+
+    /// Read a rule from a key file group
+    fn load(key_file: &glib::KeyFile, group: &str) -> Option<Rule> {
+        match key_file.string(group, "type").ok()?.as_str() {
+            FOCUS_PRIORITY_TYPE => Some(Rule::FocusPriority {
+                process_name: key_file.string(group, "process").ok()?.to_string(),
+                focused: Priority::from_nice_value(key_file.integer(group, "focused").ok()?)?,
+                unfocused: Priority::from_nice_value(key_file.integer(group, "unfocused").ok()?)?,
+            }),
+            PRIORITY_TYPE => Some(Rule::Priority {
+                pattern: key_file.string(group, "pattern").ok()?.to_string(),
+                priority: Priority::from_nice_value(key_file.integer(group, "nice").ok()?)?,
+                include_children: key_file.boolean(group, "include-children").unwrap_or(false),
+            }),
+            AFFINITY_TYPE => Some(Rule::Affinity {
+                pattern: key_file.string(group, "pattern").ok()?.to_string(),
+                cpus: parse_cpu_list(&key_file.string(group, "cpus").ok()?)?,
+                include_children: key_file.boolean(group, "include-children").unwrap_or(false),
+            }),
+            MEMORY_LIMIT_TYPE => Some(Rule::MemoryLimit {
+                pattern: key_file.string(group, "pattern").ok()?.to_string(),
+                limit_bytes: key_file.uint64(group, "limit-bytes").ok()?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Write a rule into a key file group
+    fn save(&self, key_file: &glib::KeyFile, group: &str) {
+        match self {
+            Rule::FocusPriority { process_name, focused, unfocused } => {
+                key_file.set_string(group, "type", FOCUS_PRIORITY_TYPE);
+                key_file.set_string(group, "process", process_name);
+                key_file.set_integer(group, "focused", focused.nice_value());
+                key_file.set_integer(group, "unfocused", unfocused.nice_value());
+            }
+            Rule::Priority { pattern, priority, include_children } => {
+                key_file.set_string(group, "type", PRIORITY_TYPE);
+                key_file.set_string(group, "pattern", pattern);
+                key_file.set_integer(group, "nice", priority.nice_value());
+                key_file.set_boolean(group, "include-children", *include_children);
+            }
+            Rule::Affinity { pattern, cpus, include_children } => {
+                key_file.set_string(group, "type", AFFINITY_TYPE);
+                key_file.set_string(group, "pattern", pattern);
+                key_file.set_string(group, "cpus", &format_cpu_list(cpus));
+                key_file.set_boolean(group, "include-children", *include_children);
+            }
+            Rule::MemoryLimit { pattern, limit_bytes } => {
+                key_file.set_string(group, "type", MEMORY_LIMIT_TYPE);
+                key_file.set_string(group, "pattern", pattern);
+                key_file.set_uint64(group, "limit-bytes", *limit_bytes);
+            }
+        }
+    }
+}
+
+/// Holds the configured rules and the state needed to apply them incrementally
+pub struct AutomationEngine {
+    rules: Vec<Rule>,
+    /// Priority last applied per process, so actions only run when the target changes
+    applied: HashMap<ProcessKey, Priority>,
+    /// Processes already re-pinned by an affinity rule
+    pinned: HashSet<ProcessKey>,
+    /// Processes already sent SIGTERM by a memory limit rule
+    terminated: HashSet<ProcessKey>,
+    /// Priority applied to descendants of matching processes, by PID (they may
+    /// not be in the process list, so their start time is unknown)
+    child_priorities: HashMap<u32, Priority>,
+    /// Descendants already re-pinned, by PID
+    pinned_children: HashSet<u32>,
+}
+
+impl AutomationEngine {
+    /// Load rules from the config directory
+    pub fn load() -> Self {
+        let key_file = config::load_key_file(RULES_FILE);
+        let rules = key_file
+            .groups()
+            .iter()
+            .filter_map(|group| Rule::load(&key_file, group.as_str()))
+            .collect();
+
+        Self {
+            rules,
+            applied: HashMap::new(),
+            pinned: HashSet::new(),
+            terminated: HashSet::new(),
+            child_priorities: HashMap::new(),
+            pinned_children: HashSet::new(),
+        }
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Replace all rules (from the rule editor)
+    pub fn set_rules(&mut self, rules: Vec<Rule>) {
+        self.rules = rules;
+        self.reset_applied();
+    }
+
+    /// Forget what was applied so every rule runs again on the next evaluation
+    fn reset_applied(&mut self) {
+        self.applied.clear();
+        self.pinned.clear();
+        self.terminated.clear();
+        self.child_priorities.clear();
+        self.pinned_children.clear();
+    }
+
+    /// Persist the rules to the config directory
+    pub fn save(&self) -> io::Result<()> {
+        let key_file = glib::KeyFile::new();
+        for (i, rule) in self.rules.iter().enumerate() {
+            rule.save(&key_file, &format!("Rule {}", i + 1));
+        }
+        config::save_key_file(RULES_FILE, &key_file)
+    }
+
+    /// Whether a focus priority rule exists for the given process name
+    pub fn has_focus_rule(&self, process_name: &str) -> bool {
+        self.rules.iter().any(|rule| {
+            matches!(rule, Rule::FocusPriority { .. }) && rule.process_name() == process_name
+        })
+    }
+
+    /// Add or replace the focus priority rule for a process name (None removes it)
+    pub fn set_focus_rule(&mut self, process_name: &str, priorities: Option<(Priority, Priority)>) {
+        self.rules.retain(|rule| {
+            !(matches!(rule, Rule::FocusPriority { .. }) && rule.process_name() == process_name)
+        });
+        if let Some((focused, unfocused)) = priorities {
+            self.rules.push(Rule::FocusPriority {
+                process_name: process_name.to_string(),
+                focused,
+                unfocused,
+            });
+        }
+        // Re-apply everything on the next evaluation
+        self.reset_applied();
+    }
+
+    /// Add a rule applying `priority` to a process name and every child it starts,
+    /// replacing an earlier one (used by the priority dialogs)
+    pub fn set_inherited_priority(&mut self, process_name: &str, priority: Priority) {
+        self.rules.retain(|rule| {
+            !(matches!(rule, Rule::Priority { include_children: true, .. }) && rule.process_name() == process_name)
+        });
+        self.rules.push(Rule::Priority {
+            pattern: process_name.to_string(),
+            priority,
+            include_children: true,
+        });
+        self.reset_applied();
+    }
+
+    /// Add a rule pinning a process name and every child it starts to `cpus`,
+    /// replacing an earlier one (used by the affinity dialogs)
+    pub fn set_inherited_affinity(&mut self, process_name: &str, cpus: Vec<usize>) {
+        self.rules.retain(|rule| {
+            !(matches!(rule, Rule::Affinity { include_children: true, .. }) && rule.process_name() == process_name)
+        });
+        self.rules.push(Rule::Affinity {
+            pattern: process_name.to_string(),
+            cpus,
+            include_children: true,
+        });
+        self.reset_applied();
+    }
+
+    /// Apply all rules to the current process list
+    ///
+    /// When several priority rules match a process, the first one in the list wins.
+    /// Processes ended by a memory limit are recorded in `audit_log`.
+    pub fn evaluate(&mut self, processes: &[ProcessInfo], audit_log: &mut AuditLog) {
+        let alive: HashSet<ProcessKey> = processes.iter().map(|p| p.key()).collect();
+        self.applied.retain(|key, _| alive.contains(key));
+        self.pinned.retain(|key| alive.contains(key));
+        self.terminated.retain(|key| alive.contains(key));
+        if self.rules.is_empty() {
+            return;
+        }
+
+        // Only query the window system when a focus rule needs it
+        let focused_pid = if self.rules.iter().any(|r| matches!(r, Rule::FocusPriority { .. })) {
+            focused_window_pid()
+        } else {
+            None
+        };
+        let own_pid = std::process::id();
+        let mut prioritized: HashSet<ProcessKey> = HashSet::new();
+
+        for rule in &self.rules {
+            for proc in processes.iter().filter(|p| p.pid != own_pid && rule.matches(&p.name)) {
+                let key = proc.key();
+                let result = match rule {
+                    Rule::FocusPriority { focused, unfocused, .. } => {
+                        let target = if focused_pid == Some(proc.pid) { *focused } else { *unfocused };
+                        if !prioritized.insert(key) || self.applied.get(&key) == Some(&target) {
+                            continue;
+                        }
+                        // Recorded even on failure so errors are not repeated every refresh
+                        self.applied.insert(key, target);
+                        try_set_priority(proc.pid, target)
+                    }
+                    Rule::Priority { priority, .. } => {
+                        if !prioritized.insert(key) || self.applied.get(&key) == Some(priority) {
+                            continue;
+                        }
+                        self.applied.insert(key, *priority);
+                        try_set_priority(proc.pid, *priority)
+                    }
+                    Rule::Affinity { cpus, .. } => {
+                        if !self.pinned.insert(key) {
+                            continue;
+                        }
+                        try_set_cpu_affinity(proc.pid, cpus)
+                    }
+                    Rule::MemoryLimit { limit_bytes, .. } => {
+                        if proc.total_memory() <= *limit_bytes || !self.terminated.insert(key) {
+                            continue;
+                        }
+                        eprintln!(
+                            "Automation: ending PID {} ({}), using {} bytes of memory",
+                            proc.pid,
+                            proc.name,
+                            proc.total_memory()
+                        );
+                        let result = try_send_signal(proc.pid, Signal::Term);
+                        if let Err(e) = audit_log.record(&[(proc.pid, proc.name.clone())], Signal::Term, &result) {
+                            eprintln!("Failed to write the audit log: {}", e);
+                        }
+                        result
+                    }
+                };
+                if let Err(e) = result {
+                    eprintln!("Automation: PID {} ({}): {}", proc.pid, proc.name, e);
+                }
+            }
+        }
+
+        if self.rules.iter().any(Rule::includes_children) {
+            self.apply_to_children();
+        }
+    }
+
+    /// Apply rules with `include_children` to everything matching processes spawned.
+    /// Parents and children are looked up in /proc, since idle ones may be outside
+    /// the process list.
+    fn apply_to_children(&mut self) {
+        let children = read_children_map();
+        let existing: HashSet<u32> = children.values().flatten().map(|(pid, _)| *pid).collect();
+        self.child_priorities.retain(|pid, _| existing.contains(pid));
+        self.pinned_children.retain(|pid| existing.contains(pid));
+
+        let own_pid = std::process::id();
+        let mut prioritized: HashSet<u32> = HashSet::new();
+        for rule in self.rules.iter().filter(|rule| rule.includes_children()) {
+            let parents: Vec<u32> = children
+                .values()
+                .flatten()
+                .filter(|(_, name)| rule.matches(name))
+                .map(|(pid, _)| *pid)
+                .collect();
+            for (pid, name) in parents.into_iter().flat_map(|parent| descendants_in(&children, parent)) {
+                if pid == own_pid {
+                    continue;
+                }
+                let result = match rule {
+                    Rule::Priority { priority, .. } => {
+                        if !prioritized.insert(pid) || self.child_priorities.get(&pid) == Some(priority) {
+                            continue;
+                        }
+                        self.child_priorities.insert(pid, *priority);
+                        try_set_priority(pid, *priority)
+                    }
+                    Rule::Affinity { cpus, .. } => {
+                        if !self.pinned_children.insert(pid) {
+                            continue;
+                        }
+                        try_set_cpu_affinity(pid, cpus)
+                    }
+                    _ => continue,
+                };
+                if let Err(e) = result {
+                    eprintln!("Automation: PID {} ({}, child of a rule match): {}", pid, name, e);
+                }
+            }
+        }
+    }
+}
+
+/// Parse a CPU list such as "0-3,6"
+pub fn parse_cpu_list(text: &str) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+    for part in text.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (start.trim().parse::<usize>().ok()?, end.trim().parse::<usize>().ok()?);
+                if start > end {
+                    return None;
+                }
+                cpus.extend(start..=end);
+            }
+            None => cpus.push(part.parse().ok()?),
+        }
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    (!cpus.is_empty()).then_some(cpus)
+}
+
+/// Format CPUs as a compact list such as "0-3,6"
+pub fn format_cpu_list(cpus: &[usize]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < cpus.len() {
+        let start = cpus[i];
+        while i + 1 < cpus.len() && cpus[i + 1] == cpus[i] + 1 {
+            i += 1;
+        }
+        ranges.push(if cpus[i] == start {
+            start.to_string()
+        } else {
+            format!("{}-{}", start, cpus[i])
+        });
+        i += 1;
+    }
+    ranges.join(",")
+}
@@ -0,0 +1,194 @@
+//! Daily disk and network budgets per process
+//!
+//! Budgets are stored in ~/.config/procular/budgets.ini with one group per budget.
+//! Usage counts from local midnight, or from when Procular first saw the process,
+//! so traffic from before Procular started is not included.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+
+use procular_core::monitor::{ProcessKey, ProcessTotals};
+use procular_core::name_pattern::matches_pattern;
+
+use crate::config;
+
+const BUDGETS_FILE: &str = "budgets.ini";
+
+/// Counter a budget limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetMetric {
+    DiskWrite,
+    DiskRead,
+    Upload,
+    Download,
+}
+
+impl BudgetMetric {
+    pub fn all() -> &'static [BudgetMetric] {
+        &[
+            BudgetMetric::DiskWrite,
+            BudgetMetric::DiskRead,
+            BudgetMetric::Upload,
+            BudgetMetric::Download,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetMetric::DiskWrite => "Disk Writes",
+            BudgetMetric::DiskRead => "Disk Reads",
+            BudgetMetric::Upload => "Upload",
+            BudgetMetric::Download => "Download",
+        }
+    }
+
+    /// Past-tense verb for alerts, e.g. "wrote 50 GB"
+    pub fn verb(&self) -> &'static str {
+        match self {
+            BudgetMetric::DiskWrite => "wrote",
+            BudgetMetric::DiskRead => "read",
+            BudgetMetric::Upload => "uploaded",
+            BudgetMetric::Download => "downloaded",
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            BudgetMetric::DiskWrite => "disk-write",
+            BudgetMetric::DiskRead => "disk-read",
+            BudgetMetric::Upload => "upload",
+            BudgetMetric::Download => "download",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|metric| metric.key() == key)
+    }
+
+    fn value(&self, totals: &ProcessTotals) -> u64 {
+        match self {
+            BudgetMetric::DiskWrite => totals.disk_written,
+            BudgetMetric::DiskRead => totals.disk_read,
+            BudgetMetric::Upload => totals.net_sent,
+            BudgetMetric::Download => totals.net_received,
+        }
+    }
+}
+
+/// Alert when a process matching `pattern` exceeds `limit_bytes` of `metric` in a day
+///
+/// The pattern matches process names; `*` matches any run of characters and `?` a single character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Budget {
+    pub pattern: String,
+    pub metric: BudgetMetric,
+    pub limit_bytes: u64,
+}
+
+/// A process that went over a budget
+#[derive(Debug, Clone)]
+pub struct BudgetAlert {
+    pub pid: u32,
+    pub name: String,
+    pub budget: Budget,
+    /// Usage today
+    pub used: u64,
+}
+
+/// Holds the configured budgets and today's usage baselines
+pub struct BudgetMonitor {
+    budgets: Vec<Budget>,
+    /// Local (year, day of year) the baselines belong to
+    day: Option<(i32, i32)>,
+    /// Totals at midnight of processes that were already running then
+    baselines: HashMap<ProcessKey, ProcessTotals>,
+    /// (process, budget index) pairs already reported today
+    alerted: HashSet<(ProcessKey, usize)>,
+}
+
+impl BudgetMonitor {
+    /// Load budgets from the config directory
+    pub fn load() -> Self {
+        let key_file = config::load_key_file(BUDGETS_FILE);
+        let budgets = key_file
+            .groups()
+            .iter()
+            .filter_map(|group| {
+                let group = group.as_str();
+                Some(Budget {
+                    pattern: key_file.string(group, "pattern").ok()?.to_string(),
+                    metric: BudgetMetric::from_key(&key_file.string(group, "metric").ok()?)?,
+                    limit_bytes: key_file.uint64(group, "limit-bytes").ok()?,
+                })
+            })
+            .collect();
+
+        Self {
+            budgets,
+            day: None,
+            baselines: HashMap::new(),
+            alerted: HashSet::new(),
+        }
+    }
+
+    pub fn budgets(&self) -> &[Budget] {
+        &self.budgets
+    }
+
+    /// Replace all budgets (from the budget editor); processes already over a new budget alert again
+    pub fn set_budgets(&mut self, budgets: Vec<Budget>) {
+        self.budgets = budgets;
+        self.alerted.clear();
+    }
+
+    /// Persist the budgets to the config directory
+    pub fn save(&self) -> io::Result<()> {
+        let key_file = glib::KeyFile::new();
+        for (i, budget) in self.budgets.iter().enumerate() {
+            let group = format!("Budget {}", i + 1);
+            key_file.set_string(&group, "pattern", &budget.pattern);
+            key_file.set_string(&group, "metric", budget.metric.key());
+            key_file.set_uint64(&group, "limit-bytes", budget.limit_bytes);
+        }
+        config::save_key_file(BUDGETS_FILE, &key_file)
+    }
+
+    /// Processes that went over a budget since the last check; each is reported once per day
+    pub fn check(&mut self, totals: &[ProcessTotals]) -> Vec<BudgetAlert> {
+        let today = glib::DateTime::now_local()
+            .ok()
+            .map(|now| (now.year(), now.day_of_year()));
+        if today != self.day {
+            self.day = today;
+            self.baselines = totals.iter().map(|t| (t.key, t.clone())).collect();
+            self.alerted.clear();
+        } else {
+            let current: HashSet<ProcessKey> = totals.iter().map(|t| t.key).collect();
+            self.baselines.retain(|key, _| current.contains(key));
+            self.alerted.retain(|(key, _)| current.contains(key));
+        }
+
+        let mut alerts = Vec::new();
+        for process in totals {
+            let baseline = self.baselines.get(&process.key);
+            for (index, budget) in self.budgets.iter().enumerate() {
+                if !matches_pattern(&budget.pattern, &process.name) {
+                    continue;
+                }
+                let used = budget
+                    .metric
+                    .value(process)
+                    .saturating_sub(baseline.map_or(0, |b| budget.metric.value(b)));
+                if used >= budget.limit_bytes && self.alerted.insert((process.key, index)) {
+                    alerts.push(BudgetAlert {
+                        pid: process.key.pid,
+                        name: process.name.clone(),
+                        budget: budget.clone(),
+                        used,
+                    });
+                }
+            }
+        }
+        alerts
+    }
+}
@@ -0,0 +1,147 @@
+//! Editor for daily disk and network budgets
+
+use gtk4::prelude::*;
+use gtk4::{Button, StringList};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use procular_core::monitor::format_bytes;
+
+use crate::app_state::AppState;
+use crate::budgets::{Budget, BudgetMetric};
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Show the budget editor
+pub fn show_budgets_dialog(parent: &impl IsA<gtk4::Window>, state: &AppState) {
+    let dialog = adw::Window::builder()
+        .title("I/O Budgets")
+        .transient_for(parent)
+        .modal(true)
+        .default_width(560)
+        .default_height(560)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&adw::HeaderBar::new());
+    let page = adw::PreferencesPage::new();
+    let toast_overlay = adw::ToastOverlay::new();
+    toast_overlay.set_child(Some(&page));
+    toolbar.set_content(Some(&toast_overlay));
+    dialog.set_content(Some(&toolbar));
+
+    // New budget form
+    let add_group = adw::PreferencesGroup::builder()
+        .title("New Budget")
+        .description("Patterns match process names; * matches anything and ? a single character")
+        .build();
+    let add_btn = Button::with_label("Add");
+    add_btn.add_css_class("suggested-action");
+    add_btn.set_valign(gtk4::Align::Center);
+    add_group.set_header_suffix(Some(&add_btn));
+
+    let pattern_row = adw::EntryRow::builder().title("Process Name Pattern").build();
+    let metric_names: Vec<&str> = BudgetMetric::all().iter().map(|m| m.as_str()).collect();
+    let metric_row = adw::ComboRow::builder()
+        .title("Counter")
+        .model(&StringList::new(&metric_names))
+        .build();
+    let limit_row = adw::SpinRow::builder()
+        .title("Daily Limit")
+        .subtitle("GiB per process and day")
+        .digits(1)
+        .adjustment(&gtk4::Adjustment::new(10.0, 0.1, 100_000.0, 1.0, 10.0, 0.0))
+        .build();
+    add_group.add(&pattern_row);
+    add_group.add(&metric_row);
+    add_group.add(&limit_row);
+    page.add(&add_group);
+
+    // Configured budgets, rebuilt when one is added since groups can't be cleared
+    let budgets_group: Rc<RefCell<Option<adw::PreferencesGroup>>> = Rc::default();
+    let populate = {
+        let page = page.clone();
+        let state = state.clone();
+        let toast_overlay = toast_overlay.clone();
+        move || {
+            if let Some(group) = budgets_group.borrow_mut().take() {
+                page.remove(&group);
+            }
+            let budgets = state.budgets.borrow().budgets().to_vec();
+            let group = adw::PreferencesGroup::builder()
+                .title("Budgets")
+                .description(if budgets.is_empty() {
+                    "No budgets yet"
+                } else {
+                    "Each process alerts once per day; usage counts from midnight or from when Procular started"
+                })
+                .build();
+            for budget in budgets {
+                let row = adw::ActionRow::builder()
+                    .title(format!("{} above {} per day", budget.metric.as_str(), format_bytes(budget.limit_bytes)))
+                    .subtitle(&budget.pattern)
+                    .build();
+                let remove_btn = Button::from_icon_name("user-trash-symbolic");
+                remove_btn.set_tooltip_text(Some("Remove Budget"));
+                remove_btn.set_valign(gtk4::Align::Center);
+                remove_btn.add_css_class("flat");
+                let state = state.clone();
+                let toast_overlay = toast_overlay.clone();
+                let group_weak = group.downgrade();
+                let row_weak = row.downgrade();
+                remove_btn.connect_clicked(move |_| {
+                    let mut budgets = state.budgets.borrow().budgets().to_vec();
+                    if let Some(index) = budgets.iter().position(|b| *b == budget) {
+                        budgets.remove(index);
+                    }
+                    let now_empty = budgets.is_empty();
+                    save_budgets(&state, budgets, &toast_overlay);
+                    if let (Some(group), Some(row)) = (group_weak.upgrade(), row_weak.upgrade()) {
+                        group.remove(&row);
+                        if now_empty {
+                            group.set_description(Some("No budgets yet"));
+                        }
+                    }
+                });
+                row.add_suffix(&remove_btn);
+                group.add(&row);
+            }
+            page.add(&group);
+            *budgets_group.borrow_mut() = Some(group);
+        }
+    };
+    populate();
+
+    let state_clone = state.clone();
+    let toast_overlay_clone = toast_overlay.clone();
+    add_btn.connect_clicked(move |_| {
+        let pattern = pattern_row.text().trim().to_string();
+        if pattern.is_empty() {
+            toast_overlay_clone.add_toast(adw::Toast::new("Enter a process name pattern"));
+            return;
+        }
+        let budget = Budget {
+            pattern,
+            metric: BudgetMetric::all()[metric_row.selected() as usize],
+            limit_bytes: (limit_row.value() * GIB) as u64,
+        };
+        let mut budgets = state_clone.budgets.borrow().budgets().to_vec();
+        budgets.push(budget);
+        save_budgets(&state_clone, budgets, &toast_overlay_clone);
+        pattern_row.set_text("");
+        populate();
+    });
+
+    dialog.present();
+}
+
+/// Apply and persist a new budget list
+fn save_budgets(state: &AppState, budgets: Vec<Budget>, toast_overlay: &adw::ToastOverlay) {
+    let mut monitor = state.budgets.borrow_mut();
+    monitor.set_budgets(budgets);
+    if let Err(e) = monitor.save() {
+        toast_overlay.add_toast(adw::Toast::new(&format!("Failed to save budgets: {}", e)));
+    }
+}
@@ -0,0 +1,194 @@
+//! Window overlaying the CPU and memory histories of a few processes, to spot the odd one out
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Label, Orientation, Window};
+use libadwaita as adw;
+use adw::prelude::*;
+use glib::ControlFlow;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+use procular_core::monitor::{format_bytes, ProcessKey, SystemMonitor};
+
+use crate::adaptive;
+use crate::app_state::AppState;
+use crate::graph::{color_swatch, GraphSeries, GraphWidget};
+use crate::shortcuts;
+
+const UPDATE_INTERVAL_MS: u64 = 2000;
+
+/// Most processes in one comparison; more lines get hard to tell apart
+pub const MAX_COMPARED: usize = 4;
+
+/// Line colors, one per compared process
+const SERIES_COLORS: [(f64, f64, f64); MAX_COMPARED] = [
+    (0.204, 0.396, 0.643),
+    (0.902, 0.494, 0.133),
+    (0.180, 0.545, 0.341),
+    (0.839, 0.153, 0.157),
+];
+
+/// A compared process and its legend entry
+struct Compared {
+    pid: u32,
+    name: String,
+    key: Option<ProcessKey>,
+    color: (f64, f64, f64),
+    legend: Label,
+}
+
+/// Graphs of the compared processes
+struct CompareView {
+    processes: Vec<Compared>,
+    cpu_graph: GraphWidget,
+    memory_graph: GraphWidget,
+}
+
+impl CompareView {
+    fn update(&self, mon: &SystemMonitor, cpu_scale: f64, sample_interval: u64) {
+        let gaps: Vec<bool> = mon.sample_gaps().iter().copied().collect();
+        let times: Vec<SystemTime> = mon.sample_times().iter().copied().collect();
+        let mut cpu_series = Vec::new();
+        let mut memory_series = Vec::new();
+        for process in &self.processes {
+            let name = glib::markup_escape_text(&process.name);
+            let Some(history) = process.key.and_then(|key| mon.get_history(key)) else {
+                process.legend.set_markup(&format!(
+                    "{} {} ({}) <i>ended</i>",
+                    color_swatch(process.color),
+                    name,
+                    process.pid
+                ));
+                continue;
+            };
+            let cpu: Vec<f64> = history.cpu_history.iter().map(|&v| v as f64 * cpu_scale).collect();
+            let memory: Vec<f64> = history.memory_history.iter().map(|&v| v as f64).collect();
+            process.legend.set_markup(&format!(
+                "{} {} ({}) · CPU {:.1}% · {}",
+                color_swatch(process.color),
+                name,
+                process.pid,
+                cpu.last().copied().unwrap_or(0.0),
+                format_bytes(history.memory_history.back().copied().unwrap_or(0))
+            ));
+            let label = format!("{} ({})", process.name, process.pid);
+            cpu_series.push(GraphSeries { name: label.clone(), values: cpu, color: process.color });
+            memory_series.push(GraphSeries { name: label, values: memory, color: process.color });
+        }
+
+        let num_samples = mon.max_samples();
+        for graph in [&self.cpu_graph, &self.memory_graph] {
+            graph.set_gaps(&gaps);
+            graph.set_times(&times);
+        }
+        self.cpu_graph.update_series(cpu_series, num_samples, sample_interval);
+        self.memory_graph.update_series(memory_series, num_samples, sample_interval);
+    }
+}
+
+/// Heading and graph of one compared metric
+fn graph_section(title: &str, graph: &GraphWidget) -> GtkBox {
+    let section = GtkBox::new(Orientation::Vertical, 4);
+    section.set_vexpand(true);
+    let header = GtkBox::new(Orientation::Horizontal, 8);
+    let label = Label::new(Some(title));
+    label.add_css_class("heading");
+    label.set_halign(gtk4::Align::Start);
+    header.append(&label);
+    graph.set_title_label(&label);
+    if !graph.is_percentage() {
+        let log_toggle = graph.log_scale_toggle();
+        log_toggle.set_hexpand(true);
+        log_toggle.set_halign(gtk4::Align::End);
+        header.append(&log_toggle);
+    }
+    section.append(&header);
+    section.append(&graph.drawing_area);
+    section
+}
+
+/// Create and show a window comparing up to `MAX_COMPARED` processes
+pub fn open_compare_window(parent: &impl IsA<Window>, processes: Vec<(u32, String)>, state: AppState) {
+    let monitor = state.monitor.clone();
+
+    let window = adw::Window::builder()
+        .title(format!("Compare {} Processes - Procular", processes.len().min(MAX_COMPARED)))
+        .icon_name("procular")
+        .default_width(800)
+        .default_height(600)
+        .width_request(adaptive::MIN_WIDTH)
+        .height_request(adaptive::MIN_HEIGHT)
+        .transient_for(parent)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 0);
+    main_box.append(&adw::HeaderBar::new());
+
+    let content = GtkBox::new(Orientation::Vertical, 12);
+    content.set_margin_start(12);
+    content.set_margin_end(12);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+
+    // Legend with the current values of each process
+    let legend_box = GtkBox::new(Orientation::Vertical, 4);
+    let compared: Vec<Compared> = {
+        let mut mon = monitor.borrow_mut();
+        processes
+            .into_iter()
+            .take(MAX_COMPARED)
+            .zip(SERIES_COLORS)
+            .map(|((pid, name), color)| {
+                // Keep recording every compared process even while it is not among the busiest
+                let key = mon.process_key(pid);
+                if let Some(key) = key {
+                    mon.track(key);
+                }
+                let legend = Label::new(None);
+                legend.set_halign(gtk4::Align::Start);
+                legend.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+                legend_box.append(&legend);
+                Compared { pid, name, key, color, legend }
+            })
+            .collect()
+    };
+    content.append(&legend_box);
+
+    let view = Rc::new(CompareView {
+        processes: compared,
+        cpu_graph: GraphWidget::new(SERIES_COLORS[0], true, false),
+        memory_graph: GraphWidget::new(SERIES_COLORS[0], false, true),
+    });
+    content.append(&graph_section("CPU Usage", &view.cpu_graph));
+    content.append(&graph_section("Memory", &view.memory_graph));
+    main_box.append(&content);
+    window.set_content(Some(&main_box));
+    shortcuts::add_close_shortcut(&window);
+
+    view.update(&monitor.borrow(), state.cpu_scale() as f64, state.sample_interval_secs.get());
+
+    let view_clone = view.clone();
+    let monitor_clone = monitor.clone();
+    let state_clone = state.clone();
+    let source_id = glib::timeout_add_local(Duration::from_millis(UPDATE_INTERVAL_MS), move || {
+        let cpu_scale = state_clone.cpu_scale() as f64;
+        view_clone.update(&monitor_clone.borrow(), cpu_scale, state_clone.sample_interval_secs.get());
+        ControlFlow::Continue
+    });
+
+    // Stop updating and recording the processes on close
+    let source_id = RefCell::new(Some(source_id));
+    window.connect_close_request(move |_| {
+        if let Some(id) = source_id.borrow_mut().take() {
+            id.remove();
+            let mut mon = monitor.borrow_mut();
+            for key in view.processes.iter().filter_map(|process| process.key) {
+                mon.untrack(key);
+            }
+        }
+        glib::Propagation::Proceed
+    });
+
+    window.present();
+}
@@ -0,0 +1,31 @@
+//! Locations and helpers for files under ~/.config/procular
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Directory holding Procular's configuration files
+pub fn config_dir() -> PathBuf {
+    glib::user_config_dir().join("procular")
+}
+
+/// Load a key file from the config directory (empty if missing or unreadable)
+pub fn load_key_file(name: &str) -> glib::KeyFile {
+    let key_file = glib::KeyFile::new();
+    let path = config_dir().join(name);
+    if path.exists() {
+        if let Err(e) = key_file.load_from_file(&path, glib::KeyFileFlags::NONE) {
+            eprintln!("Failed to load {}: {}", path.display(), e);
+        }
+    }
+    key_file
+}
+
+/// Save a key file to the config directory, creating it if needed
+pub fn save_key_file(name: &str, key_file: &glib::KeyFile) -> io::Result<()> {
+    let dir = config_dir();
+    fs::create_dir_all(&dir)?;
+    key_file
+        .save_to_file(dir.join(name))
+        .map_err(|e| io::Error::other(e.to_string()))
+}
@@ -0,0 +1,78 @@
+//! "Connections" tab of the process window
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Label, Orientation};
+
+use procular_core::connections::{format_endpoint, read_connections};
+
+use crate::table_view::{ColumnKind, TableView};
+
+pub struct ConnectionsView {
+    pub widget: GtkBox,
+    summary_label: Label,
+    table: TableView,
+}
+
+impl ConnectionsView {
+    pub fn new() -> Self {
+        let widget = GtkBox::new(Orientation::Vertical, 8);
+        widget.set_margin_top(12);
+        widget.set_margin_bottom(12);
+        widget.set_margin_start(12);
+        widget.set_margin_end(12);
+
+        let summary_label = Label::new(None);
+        summary_label.set_halign(gtk4::Align::Start);
+        summary_label.set_wrap(true);
+        widget.append(&summary_label);
+
+        let table = TableView::new(&[
+            ("Protocol", ColumnKind::Short),
+            ("Local Address", ColumnKind::Text),
+            ("Remote Address", ColumnKind::Text),
+            ("State", ColumnKind::Short),
+        ]);
+        widget.append(&table.widget);
+
+        Self {
+            widget,
+            summary_label,
+            table,
+        }
+    }
+
+    /// Re-read the process's sockets and update the table
+    pub fn refresh(&self, pid: u32) {
+        let connections = match read_connections(pid) {
+            Ok(connections) => connections,
+            Err(e) => {
+                self.summary_label
+                    .set_text(&format!("Cannot read connections: {}", e));
+                self.table.set_rows(Vec::new());
+                return;
+            }
+        };
+
+        let listening = connections.iter().filter(|c| c.is_listening()).count();
+        self.summary_label.set_text(&format!(
+            "{} sockets: {} listening, {} other",
+            connections.len(),
+            listening,
+            connections.len() - listening
+        ));
+
+        self.table.set_rows(
+            connections
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.protocol.label().to_string(),
+                        format_endpoint(&c.local),
+                        format_endpoint(&c.remote),
+                        c.state.to_string(),
+                    ]
+                })
+                .collect(),
+        );
+    }
+}
@@ -4,46 +4,104 @@ use gtk4::prelude::*;
 use gtk4::gdk::Display;
 use gtk4::{
     gio, CheckButton, Label, Orientation,
-    ScrolledWindow, Box as GtkBox, Button,
+    ScrolledWindow, Box as GtkBox, Button, SpinButton,
 };
 use libadwaita as adw;
 use adw::prelude::*;
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::rc::Rc;
 
-use crate::monitor::SystemMonitor;
-use crate::process_actions::{
-    self, get_cpu_affinity, get_cpu_core_info, kill_process, set_cpu_affinity,
-    set_priority, Priority, CoreType,
+use procular_core::cgroup_limits::{freeze_process, thaw_process};
+use procular_core::developer_tools::{debug_in_terminal, trace_in_terminal};
+use procular_core::export::process_json;
+use procular_core::name_pattern::matches_pattern;
+use procular_core::process_actions::{
+    self, get_cpu_affinity, get_cpu_core_info, get_parent, run_in_background, set_cpu_affinity, set_nice_many,
+    Priority, CoreType, Signal, MAX_NICE, MIN_NICE,
 };
+use procular_core::systemd_units::{process_unit, SystemdUnit, UnitAction};
+
+use crate::app_state::AppState;
+use crate::audit_log::send_signal_audited;
+use crate::automation::AutomationEngine;
+use crate::compare_window::{self, MAX_COMPARED};
+use crate::graceful_end;
+use crate::limits_dialog;
 use crate::process_window;
+use crate::properties_dialog;
+use crate::settings::Settings;
+use crate::unit_dialog;
 
-/// Create the context menu for a process
-pub fn create_process_menu() -> gio::Menu {
+/// Limit for "Open in Window" with many selected processes
+const MAX_OPEN_WINDOWS: usize = 8;
+
+/// Create the context menu for a process; read-only mode leaves out actions that change it
+pub fn create_process_menu(read_only: bool) -> gio::Menu {
     let menu = gio::Menu::new();
 
     // Open in Window
     menu.append(Some("Open in Window"), Some("process.open-window"));
-
-    // Separator
-    menu.append(None, None);
-
-    // End Process submenu
-    let end_menu = gio::Menu::new();
-    end_menu.append(Some("End Process (SIGTERM)"), Some("process.end"));
-    end_menu.append(Some("Force Kill (SIGKILL)"), Some("process.kill"));
-    end_menu.append(Some("Pause (SIGSTOP)"), Some("process.stop"));
-    end_menu.append(Some("Resume (SIGCONT)"), Some("process.cont"));
-    menu.append_submenu(Some("Send Signal"), &end_menu);
-
-    // Separator
-    menu.append(None, None);
-
-    // CPU Affinity
-    menu.append(Some("Set CPU Affinity..."), Some("process.affinity"));
-
-    // Priority
-    menu.append(Some("Set Priority..."), Some("process.priority"));
+    menu.append(Some("Open Containing Folder"), Some("process.open-folder"));
+    menu.append(Some("Properties"), Some("process.properties"));
+    if !read_only {
+        // Tracers attach through ptrace, possibly with sudo; the debugger also stops the
+        // process and can change it
+        menu.append(Some("Trace in Terminal"), Some("process.trace"));
+        menu.append(Some("Attach Debugger"), Some("process.debug"));
+    }
+    menu.append(Some("Compare Selected"), Some("process.compare"));
+
+    // Pinned processes stay at the top and are never cut from the list
+    let pin_menu = gio::Menu::new();
+    pin_menu.append(Some("Pin to Top"), Some("process.pin"));
+    pin_menu.append(Some("Always Pin by Name"), Some("process.pin-name"));
+    pin_menu.append(Some("Unpin"), Some("process.unpin"));
+    menu.append_section(None, &pin_menu);
+
+    // Owning systemd unit
+    let unit_menu = gio::Menu::new();
+    unit_menu.append(Some("Show Status"), Some("process.unit-status"));
+    if !read_only {
+        unit_menu.append(Some("Restart Unit..."), Some("process.unit-restart"));
+        unit_menu.append(Some("Stop Unit..."), Some("process.unit-stop"));
+    }
+    menu.append_submenu(Some("systemd Unit"), &unit_menu);
+
+    if !read_only {
+        // Separator
+        menu.append(None, None);
+
+        // End Process submenu
+        let end_menu = gio::Menu::new();
+        end_menu.append(Some("End Process (SIGTERM)"), Some("process.end"));
+        end_menu.append(Some("End Gently (SIGTERM, then SIGKILL)"), Some("process.end-gently"));
+        end_menu.append(Some("Force Kill (SIGKILL)"), Some("process.kill"));
+        end_menu.append(Some("Pause (SIGSTOP)"), Some("process.stop"));
+        end_menu.append(Some("Resume (SIGCONT)"), Some("process.cont"));
+        end_menu.append(Some("Signal Parent (SIGCHLD)"), Some("process.signal-parent"));
+        menu.append_submenu(Some("Send Signal"), &end_menu);
+
+        // cgroup freezer: pauses without SIGSTOP, so job control is not confused
+        menu.append(Some("Freeze"), Some("process.freeze"));
+        menu.append(Some("Thaw"), Some("process.thaw"));
+
+        // Separator
+        menu.append(None, None);
+
+        // CPU Affinity
+        menu.append(Some("Set CPU Affinity..."), Some("process.affinity"));
+
+        // Priority
+        let priority_menu = gio::Menu::new();
+        priority_menu.append(Some("Set Priority..."), Some("process.priority"));
+        priority_menu.append(Some("Raise Priority (Nice −1)"), Some("process.raise-priority"));
+        priority_menu.append(Some("Lower Priority (Nice +1)"), Some("process.lower-priority"));
+        menu.append_submenu(Some("Priority"), &priority_menu);
+
+        // cgroup limits
+        menu.append(Some("Limit Resources..."), Some("process.limit-resources"));
+    }
 
     // Separator
     menu.append(None, None);
@@ -51,152 +109,622 @@ pub fn create_process_menu() -> gio::Menu {
     // Copy options
     menu.append(Some("Copy PID"), Some("process.copy-pid"));
     menu.append(Some("Copy Command"), Some("process.copy-command"));
+    menu.append(Some("Copy Details"), Some("process.copy-details"));
 
     menu
 }
 
-/// Set up actions for the process context menu
+/// Set up actions for the process context menu; they apply to every selected process
 pub fn setup_process_actions(
     widget: &impl IsA<gtk4::Widget>,
-    get_selected: impl Fn() -> Option<(u32, String)> + 'static,
+    get_selected: impl Fn() -> Vec<(u32, String)> + 'static,
     get_window: impl Fn() -> Option<gtk4::Window> + 'static,
-    monitor: Rc<RefCell<SystemMonitor>>,
+    state: AppState,
 ) {
     let action_group = gio::SimpleActionGroup::new();
 
     // Open in Window action
     let get_selected_clone = Rc::new(get_selected);
     let get_window_clone = Rc::new(get_window);
-    let monitor_clone = monitor.clone();
 
     let get_sel = get_selected_clone.clone();
     let get_win = get_window_clone.clone();
-    let mon = monitor_clone.clone();
     let open_action = gio::SimpleAction::new("open-window", None);
+    let state_clone = state.clone();
     open_action.connect_activate(move |_, _| {
-        if let (Some((pid, name)), Some(window)) = (get_sel(), get_win()) {
-            process_window::open_process_window(&window, pid, &name, mon.clone());
+        let Some(window) = get_win() else {
+            return;
+        };
+        for (pid, name) in get_sel().into_iter().take(MAX_OPEN_WINDOWS) {
+            process_window::open_process_window(
+                &window,
+                pid,
+                &name,
+                state_clone.clone(),
+            );
         }
     });
     action_group.add_action(&open_action);
 
-    // End Process action (SIGTERM)
+    // Show each selected executable in the file manager
     let get_sel = get_selected_clone.clone();
     let get_win = get_window_clone.clone();
-    let end_action = gio::SimpleAction::new("end", None);
-    end_action.connect_activate(move |_, _| {
-        if let Some((pid, _)) = get_sel() {
-            if let Err(e) = kill_process(pid, false) {
-                if let Some(win) = get_win() {
-                    show_error(&win, "Failed to end process", &e.to_string());
+    let open_folder_action = gio::SimpleAction::new("open-folder", None);
+    open_folder_action.connect_activate(move |_, _| {
+        let window = get_win();
+        for (pid, name) in get_sel().into_iter().take(MAX_OPEN_WINDOWS) {
+            let path = match process_actions::get_executable_path(pid) {
+                Ok(path) => path,
+                Err(e) => {
+                    if let Some(window) = &window {
+                        show_error(
+                            window,
+                            "Cannot find executable",
+                            &format!("The executable of {} (PID {}) cannot be resolved: {}", name, pid, e),
+                        );
+                    }
+                    continue;
                 }
-            }
+            };
+            // Asks the file manager (FileManager1 D-Bus interface or the portal) to show the file
+            let launcher = gtk4::FileLauncher::new(Some(&gio::File::for_path(&path)));
+            let window_clone = window.clone();
+            launcher.open_containing_folder(window.as_ref(), gio::Cancellable::NONE, move |result| {
+                if let (Err(e), Some(window)) = (result, &window_clone) {
+                    if !e.matches(gtk4::DialogError::Dismissed) {
+                        show_error(window, "Cannot open folder", &e.to_string());
+                    }
+                }
+            });
         }
     });
-    action_group.add_action(&end_action);
+    action_group.add_action(&open_folder_action);
 
-    // Kill action (SIGKILL)
+    // Everything /proc has on each selected process
     let get_sel = get_selected_clone.clone();
     let get_win = get_window_clone.clone();
-    let kill_action = gio::SimpleAction::new("kill", None);
-    kill_action.connect_activate(move |_, _| {
-        if let Some((pid, _)) = get_sel() {
-            if let Err(e) = kill_process(pid, true) {
-                if let Some(win) = get_win() {
-                    show_error(&win, "Failed to kill process", &e.to_string());
+    let properties_action = gio::SimpleAction::new("properties", None);
+    properties_action.connect_activate(move |_, _| {
+        let Some(window) = get_win() else {
+            return;
+        };
+        for (pid, name) in get_sel().into_iter().take(MAX_OPEN_WINDOWS) {
+            properties_dialog::show_properties_dialog(&window, pid, &name);
+        }
+    });
+    action_group.add_action(&properties_action);
+
+    // Attach the tracer from Preferences to each selected process in a terminal
+    let get_sel = get_selected_clone.clone();
+    let get_win = get_window_clone.clone();
+    let trace_action = gio::SimpleAction::new("trace", None);
+    let settings = state.settings.clone();
+    trace_action.connect_activate(move |_, _| {
+        let (terminal, tracer) = {
+            let settings = settings.borrow();
+            (settings.terminal.clone(), settings.tracer)
+        };
+        for (pid, name) in get_sel().into_iter().take(MAX_OPEN_WINDOWS) {
+            if let Err(e) = trace_in_terminal(&terminal, tracer, pid) {
+                if let Some(window) = get_win() {
+                    show_error(&window, &format!("Cannot trace {}", name), &e.to_string());
                 }
+                return;
             }
         }
     });
-    action_group.add_action(&kill_action);
+    state.lockdown.bind_action(&trace_action);
+    action_group.add_action(&trace_action);
 
-    // Stop action (SIGSTOP)
+    // Attach the debugger command from Preferences to the first selected process in a terminal
     let get_sel = get_selected_clone.clone();
     let get_win = get_window_clone.clone();
-    let stop_action = gio::SimpleAction::new("stop", None);
-    stop_action.connect_activate(move |_, _| {
-        if let Some((pid, _)) = get_sel() {
-            if let Err(e) = process_actions::send_signal(pid, process_actions::Signal::Stop) {
-                if let Some(win) = get_win() {
-                    show_error(&win, "Failed to pause process", &e.to_string());
-                }
+    let debug_action = gio::SimpleAction::new("debug", None);
+    let settings = state.settings.clone();
+    debug_action.connect_activate(move |_, _| {
+        let Some((pid, name)) = get_sel().into_iter().next() else {
+            return;
+        };
+        let (terminal, debugger_command) = {
+            let settings = settings.borrow();
+            (settings.terminal.clone(), settings.debugger_command.clone())
+        };
+        if let Err(e) = debug_in_terminal(&terminal, &debugger_command, pid) {
+            if let Some(window) = get_win() {
+                show_error(&window, &format!("Cannot debug {}", name), &e.to_string());
+            }
+        }
+    });
+    state.lockdown.bind_action(&debug_action);
+    action_group.add_action(&debug_action);
+
+    // Overlay the histories of a few selected processes in one window
+    let get_sel = get_selected_clone.clone();
+    let get_win = get_window_clone.clone();
+    let compare_action = gio::SimpleAction::new("compare", None);
+    let state_clone = state.clone();
+    compare_action.connect_activate(move |_, _| {
+        let Some(window) = get_win() else {
+            return;
+        };
+        let selected = get_sel();
+        if !(2..=MAX_COMPARED).contains(&selected.len()) {
+            show_error(
+                &window,
+                "Cannot compare processes",
+                &format!("Select between 2 and {} processes to compare.", MAX_COMPARED),
+            );
+            return;
+        }
+        compare_window::open_compare_window(&window, selected, state_clone.clone());
+    });
+    action_group.add_action(&compare_action);
+
+    // Pin the selected processes until they exit
+    let get_sel = get_selected_clone.clone();
+    let pin_action = gio::SimpleAction::new("pin", None);
+    let monitor = state.monitor.clone();
+    pin_action.connect_activate(move |_, _| {
+        let mut monitor = monitor.borrow_mut();
+        for (pid, _) in get_sel() {
+            if let Some(key) = monitor.process_key(pid) {
+                monitor.pin(key);
             }
         }
     });
-    action_group.add_action(&stop_action);
+    action_group.add_action(&pin_action);
 
-    // Continue action (SIGCONT)
+    // Pin by name: also covers later instances, and is remembered (patterns are edited in Preferences)
     let get_sel = get_selected_clone.clone();
     let get_win = get_window_clone.clone();
-    let cont_action = gio::SimpleAction::new("cont", None);
-    cont_action.connect_activate(move |_, _| {
-        if let Some((pid, _)) = get_sel() {
-            if let Err(e) = process_actions::send_signal(pid, process_actions::Signal::Cont) {
-                if let Some(win) = get_win() {
-                    show_error(&win, "Failed to resume process", &e.to_string());
+    let pin_name_action = gio::SimpleAction::new("pin-name", None);
+    let state_clone = state.clone();
+    pin_name_action.connect_activate(move |_, _| {
+        let mut settings = state_clone.settings.borrow_mut();
+        for name in selected_names(&get_sel()) {
+            if !settings.pinned_names.iter().any(|pattern| matches_pattern(pattern, &name)) {
+                settings.pinned_names.push(name);
+            }
+        }
+        save_pinned_names(&settings, &state_clone, get_win());
+    });
+    action_group.add_action(&pin_name_action);
+
+    // Unpin drops both the process's own pin and the name patterns matching it
+    let get_sel = get_selected_clone.clone();
+    let get_win = get_window_clone.clone();
+    let unpin_action = gio::SimpleAction::new("unpin", None);
+    let state_clone = state.clone();
+    unpin_action.connect_activate(move |_, _| {
+        let selected = get_sel();
+        {
+            let mut monitor = state_clone.monitor.borrow_mut();
+            for (pid, _) in &selected {
+                if let Some(key) = monitor.process_key(*pid) {
+                    monitor.unpin(key);
+                }
+            }
+        }
+        let mut settings = state_clone.settings.borrow_mut();
+        let count = settings.pinned_names.len();
+        settings
+            .pinned_names
+            .retain(|pattern| !selected.iter().any(|(_, name)| matches_pattern(pattern, name)));
+        if settings.pinned_names.len() != count {
+            save_pinned_names(&settings, &state_clone, get_win());
+        }
+    });
+    action_group.add_action(&unpin_action);
+
+    // Signal actions; one command for all selected processes, so polkit asks only once
+    let signals = [
+        ("end", Signal::Term, "Failed to end process"),
+        ("kill", Signal::Kill, "Failed to kill process"),
+        ("stop", Signal::Stop, "Failed to pause process"),
+        ("cont", Signal::Cont, "Failed to resume process"),
+    ];
+    for (name, signal, error_title) in signals {
+        let get_sel = get_selected_clone.clone();
+        let get_win = get_window_clone.clone();
+        let action = gio::SimpleAction::new(name, None);
+        let state_clone = state.clone();
+        action.connect_activate(move |_, _| {
+            let selected = get_sel();
+            let Some(win) = get_win() else {
+                return;
+            };
+            if selected.is_empty() {
+                return;
+            }
+            let win_clone = win.clone();
+            send_signal_confirmed(&win, selected, signal, &state_clone, move |result| {
+                if let Err(e) = result {
+                    show_error(&win_clone, error_title, &e.to_string());
                 }
+            });
+        });
+        state.lockdown.bind_action(&action);
+        action_group.add_action(&action);
+    }
+
+    // Signal Parent: SIGCHLD reminds a parent to reap zombie children; a zombie itself
+    // cannot be killed, it goes away once the parent collects its exit status
+    let get_sel = get_selected_clone.clone();
+    let get_win = get_window_clone.clone();
+    let signal_parent_action = gio::SimpleAction::new("signal-parent", None);
+    let state_clone = state.clone();
+    signal_parent_action.connect_activate(move |_, _| {
+        let Some(win) = get_win() else {
+            return;
+        };
+        let mut parents: Vec<(u32, String)> = Vec::new();
+        for parent in get_sel().iter().filter_map(|(pid, _)| get_parent(*pid)) {
+            if !parents.contains(&parent) {
+                parents.push(parent);
+            }
+        }
+        if parents.is_empty() {
+            show_error(&win, "Failed to signal parent", "The selected processes have no parent.");
+            return;
+        }
+        let win_clone = win.clone();
+        send_signal_confirmed(&win, parents, Signal::Chld, &state_clone, move |result| {
+            if let Err(e) = result {
+                show_error(&win_clone, "Failed to signal parent", &e.to_string());
             }
+        });
+    });
+    state.lockdown.bind_action(&signal_parent_action);
+    action_group.add_action(&signal_parent_action);
+
+    // End Gently: SIGTERM, then SIGKILL after the grace period for processes still running
+    let get_sel = get_selected_clone.clone();
+    let get_win = get_window_clone.clone();
+    let end_gently_action = gio::SimpleAction::new("end-gently", None);
+    let state_clone = state.clone();
+    end_gently_action.connect_activate(move |_, _| {
+        if let Some(win) = get_win() {
+            graceful_end::end_gently(&win, get_sel(), &state_clone);
         }
     });
-    action_group.add_action(&cont_action);
+    state.lockdown.bind_action(&end_gently_action);
+    action_group.add_action(&end_gently_action);
+
+    // Freeze/thaw actions; each process gets its own scope, so they run one by one
+    let freezer = [
+        ("freeze", freeze_process as fn(u32) -> std::io::Result<()>, "Failed to freeze process"),
+        ("thaw", thaw_process, "Failed to thaw process"),
+    ];
+    for (name, apply, error_title) in freezer {
+        let get_sel = get_selected_clone.clone();
+        let get_win = get_window_clone.clone();
+        let action = gio::SimpleAction::new(name, None);
+        action.connect_activate(move |_, _| {
+            let selected = get_sel();
+            let get_win = get_win.clone();
+            glib::spawn_future_local(async move {
+                let errors = run_in_background(move || {
+                    Ok(selected
+                        .iter()
+                        .filter_map(|(pid, name)| apply(*pid).err().map(|e| format!("{} ({}): {}", name, pid, e)))
+                        .collect::<Vec<String>>())
+                })
+                .await
+                .unwrap_or_else(|e| vec![e.to_string()]);
+                if let (false, Some(win)) = (errors.is_empty(), get_win()) {
+                    show_error(&win, error_title, &errors.join("\n"));
+                }
+            });
+        });
+        state.lockdown.bind_action(&action);
+        action_group.add_action(&action);
+    }
 
     // CPU Affinity action
     let get_sel = get_selected_clone.clone();
     let get_win = get_window_clone.clone();
     let affinity_action = gio::SimpleAction::new("affinity", None);
+    let automation = state.automation.clone();
     affinity_action.connect_activate(move |_, _| {
-        if let (Some((pid, _)), Some(win)) = (get_sel(), get_win()) {
-            show_affinity_dialog(&win, pid);
+        let selected = get_sel();
+        if let (false, Some(win)) = (selected.is_empty(), get_win()) {
+            show_affinity_dialog(&win, selected, automation.clone());
         }
     });
+    state.lockdown.bind_action(&affinity_action);
     action_group.add_action(&affinity_action);
 
     // Priority action
     let get_sel = get_selected_clone.clone();
     let get_win = get_window_clone.clone();
     let priority_action = gio::SimpleAction::new("priority", None);
+    let automation = state.automation.clone();
     priority_action.connect_activate(move |_, _| {
-        if let (Some((pid, _)), Some(win)) = (get_sel(), get_win()) {
-            show_priority_dialog(&win, pid);
+        let selected = get_sel();
+        if let (false, Some(win)) = (selected.is_empty(), get_win()) {
+            show_priority_dialog(&win, selected, automation.clone());
         }
     });
+    state.lockdown.bind_action(&priority_action);
     action_group.add_action(&priority_action);
 
-    // Copy PID action
+    // Step the nice value of each selected process by one
+    let nice_steps = [
+        ("raise-priority", -1, "Failed to raise priority"),
+        ("lower-priority", 1, "Failed to lower priority"),
+    ];
+    for (name, step, error_title) in nice_steps {
+        let get_sel = get_selected_clone.clone();
+        let get_win = get_window_clone.clone();
+        let action = gio::SimpleAction::new(name, None);
+        action.connect_activate(move |_, _| {
+            let pids = selected_pids(&get_sel());
+            let get_win = get_win.clone();
+            glib::spawn_future_local(async move {
+                if let (Err(e), Some(win)) = (run_in_background(move || step_nice(&pids, step)).await, get_win()) {
+                    show_error(&win, error_title, &e.to_string());
+                }
+            });
+        });
+        state.lockdown.bind_action(&action);
+        action_group.add_action(&action);
+    }
+
+    // Limit Resources action
+    let get_sel = get_selected_clone.clone();
+    let get_win = get_window_clone.clone();
+    let limits_action = gio::SimpleAction::new("limit-resources", None);
+    limits_action.connect_activate(move |_, _| {
+        let selected = get_sel();
+        if let (false, Some(win)) = (selected.is_empty(), get_win()) {
+            limits_dialog::show_limits_dialog(&win, selected);
+        }
+    });
+    state.lockdown.bind_action(&limits_action);
+    action_group.add_action(&limits_action);
+
+    // Unit status windows, one per distinct unit
+    let get_sel = get_selected_clone.clone();
+    let get_win = get_window_clone.clone();
+    let unit_status_action = gio::SimpleAction::new("unit-status", None);
+    let lockdown = state.lockdown.clone();
+    unit_status_action.connect_activate(move |_, _| {
+        let Some(win) = get_win() else {
+            return;
+        };
+        let units = selected_units(&get_sel());
+        if units.is_empty() {
+            show_error(&win, "No systemd unit", "The selected processes do not belong to a systemd unit.");
+        }
+        for unit in units.into_iter().take(MAX_OPEN_WINDOWS) {
+            unit_dialog::show_unit_dialog(&win, unit, lockdown.clone());
+        }
+    });
+    action_group.add_action(&unit_status_action);
+
+    // Restart/stop the owning units after confirmation
+    for (name, unit_action) in [("unit-restart", UnitAction::Restart), ("unit-stop", UnitAction::Stop)] {
+        let get_sel = get_selected_clone.clone();
+        let get_win = get_window_clone.clone();
+        let action = gio::SimpleAction::new(name, None);
+        action.connect_activate(move |_, _| {
+            let Some(win) = get_win() else {
+                return;
+            };
+            let units = selected_units(&get_sel());
+            if units.is_empty() {
+                show_error(&win, "No systemd unit", "The selected processes do not belong to a systemd unit.");
+                return;
+            }
+            unit_dialog::confirm_unit_action(&win, units, unit_action, || {});
+        });
+        state.lockdown.bind_action(&action);
+        action_group.add_action(&action);
+    }
+
+    // Copy PID action (one per line)
     let get_sel = get_selected_clone.clone();
     let copy_pid_action = gio::SimpleAction::new("copy-pid", None);
     copy_pid_action.connect_activate(move |_, _| {
-        if let Some((pid, _)) = get_sel() {
-            if let Some(display) = Display::default() {
-                let clipboard = display.clipboard();
-                clipboard.set_text(&pid.to_string());
-            }
+        let pids = selected_pids(&get_sel());
+        if pids.is_empty() {
+            return;
+        }
+        if let Some(display) = Display::default() {
+            let text = pids.iter().map(|pid| pid.to_string()).collect::<Vec<_>>().join("\n");
+            display.clipboard().set_text(&text);
         }
     });
     action_group.add_action(&copy_pid_action);
 
-    // Copy Command action
+    // Copy Command action (one per line)
     let get_sel = get_selected_clone.clone();
     let copy_cmd_action = gio::SimpleAction::new("copy-command", None);
     copy_cmd_action.connect_activate(move |_, _| {
-        if let Some((pid, _)) = get_sel() {
-            if let Some(cmd) = process_actions::get_command_line(pid) {
-                if let Some(display) = Display::default() {
-                    let clipboard = display.clipboard();
-                    clipboard.set_text(&cmd);
-                }
-            }
+        let commands: Vec<String> = get_sel()
+            .iter()
+            .filter_map(|(pid, _)| process_actions::get_command_line(*pid))
+            .collect();
+        if commands.is_empty() {
+            return;
+        }
+        if let Some(display) = Display::default() {
+            display.clipboard().set_text(&commands.join("\n"));
         }
     });
     action_group.add_action(&copy_cmd_action);
 
+    // Copy Details action: all metrics of the last refresh as JSON, an array for several processes
+    let get_sel = get_selected_clone.clone();
+    let copy_details_action = gio::SimpleAction::new("copy-details", None);
+    let monitor = state.monitor.clone();
+    copy_details_action.connect_activate(move |_, _| {
+        let monitor = monitor.borrow();
+        let processes = monitor.processes();
+        let details: Vec<String> = get_sel()
+            .iter()
+            .filter_map(|(pid, _)| processes.iter().find(|p| p.pid == *pid))
+            .map(|process| process_json(process, processes))
+            .collect();
+        let text = match details.as_slice() {
+            [] => return,
+            [single] => single.clone(),
+            _ => format!("[{}]", details.join(",\n")),
+        };
+        if let Some(display) = Display::default() {
+            display.clipboard().set_text(&text);
+        }
+    });
+    action_group.add_action(&copy_details_action);
+
     widget.insert_action_group("process", Some(&action_group));
+
+    // Delete ends and Shift+Delete kills the selection, only while the widget has focus
+    // so that deleting text in the search box never sends a signal
+    let shortcuts = gtk4::ShortcutController::new();
+    for (trigger, action) in [("Delete", "process.end"), ("<Shift>Delete", "process.kill")] {
+        shortcuts.add_shortcut(gtk4::Shortcut::new(
+            gtk4::ShortcutTrigger::parse_string(trigger),
+            Some(gtk4::NamedAction::new(action)),
+        ));
+    }
+    widget.add_controller(shortcuts);
+}
+
+/// Send `signal` to `targets`, asking first before ending or killing them unless quick-kill
+/// mode is on. Every attempt is written to the audit log; `on_done` gets the outcome once the
+/// signal was sent, which may be after a polkit prompt
+pub fn send_signal_confirmed(
+    parent: &gtk4::Window,
+    targets: Vec<(u32, String)>,
+    signal: Signal,
+    state: &AppState,
+    on_done: impl Fn(std::io::Result<()>) + 'static,
+) {
+    if targets.is_empty() {
+        return;
+    }
+    let audit_log = state.audit_log.clone();
+    let destructive = matches!(signal, Signal::Term | Signal::Kill);
+    if !destructive || state.settings.borrow().quick_kill {
+        glib::spawn_future_local(async move {
+            on_done(send_signal_audited(audit_log, targets, signal).await);
+        });
+        return;
+    }
+
+    let verb = if signal == Signal::Kill { "Force Kill" } else { "End" };
+    let heading = if targets.len() == 1 {
+        format!("{} {}?", verb, targets[0].1)
+    } else {
+        format!("{} {} Processes?", verb, targets.len())
+    };
+    let mut names = selected_names(&targets);
+    names.truncate(10);
+    let consequence = if signal == Signal::Kill {
+        "Unsaved work will be lost; the processes cannot clean up."
+    } else {
+        "Unsaved work may be lost."
+    };
+    let body = format!(
+        "{}\n\n{}\n\nQuick-Kill Mode in Preferences skips this question.",
+        names.join(", "),
+        consequence
+    );
+    let confirm = adw::MessageDialog::builder()
+        .transient_for(parent)
+        .heading(heading)
+        .body(body)
+        .build();
+    confirm.add_response("cancel", "Cancel");
+    confirm.add_response("confirm", verb);
+    confirm.set_response_appearance("confirm", adw::ResponseAppearance::Destructive);
+    confirm.set_default_response(Some("cancel"));
+    confirm.set_close_response("cancel");
+
+    let on_done = Rc::new(on_done);
+    confirm.connect_response(None, move |_, response| {
+        if response == "confirm" {
+            let (audit_log, targets, on_done) = (audit_log.clone(), targets.clone(), on_done.clone());
+            glib::spawn_future_local(async move {
+                on_done(send_signal_audited(audit_log, targets, signal).await);
+            });
+        }
+    });
+    confirm.present();
+}
+
+/// Apply the pinned name patterns to the monitor and save them
+fn save_pinned_names(settings: &Settings, state: &AppState, window: Option<gtk4::Window>) {
+    state.monitor.borrow_mut().set_pinned_names(settings.pinned_names.clone());
+    if let (Err(e), Some(win)) = (settings.save(), window) {
+        show_error(&win, "Failed to save preferences", &e.to_string());
+    }
+}
+
+/// Distinct units owning the selected processes
+fn selected_units(selected: &[(u32, String)]) -> Vec<SystemdUnit> {
+    let mut units: Vec<SystemdUnit> = Vec::new();
+    for unit in selected.iter().filter_map(|(pid, _)| process_unit(*pid)) {
+        if !units.contains(&unit) {
+            units.push(unit);
+        }
+    }
+    units
+}
+
+fn selected_pids(selected: &[(u32, String)]) -> Vec<u32> {
+    selected.iter().map(|(pid, _)| *pid).collect()
 }
 
-/// Show CPU affinity dialog with core type information
-fn show_affinity_dialog(parent: &gtk4::Window, pid: u32) {
+/// Distinct process names of a selection, for rules keyed on the executable
+fn selected_names(selected: &[(u32, String)]) -> Vec<String> {
+    let mut names: Vec<String> = selected.iter().map(|(_, name)| name.clone()).collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Check button offering to keep applying a setting to children started later
+pub fn create_inherit_check() -> CheckButton {
+    let check = CheckButton::with_label("Also apply to child processes started later");
+    check.set_tooltip_text(Some(
+        "Saves an automation rule for this program, so workers it spawns get the same setting",
+    ));
+    check.set_margin_top(8);
+    check
+}
+
+/// Save inherited automation rules for the given process names
+pub fn save_inherited_rules(
+    parent: &gtk4::Window,
+    automation: &RefCell<AutomationEngine>,
+    names: &[String],
+    add_rule: impl Fn(&mut AutomationEngine, &str),
+) {
+    let mut engine = automation.borrow_mut();
+    for name in names {
+        add_rule(&mut engine, name);
+    }
+    if let Err(e) = engine.save() {
+        show_error(parent, "Failed to save automation rule", &e.to_string());
+    }
+}
+
+/// "process 1234" or "3 processes", for dialog text
+fn describe_pids(pids: &[u32]) -> String {
+    match pids {
+        [pid] => format!("process {}", pid),
+        _ => format!("{} processes", pids.len()),
+    }
+}
+
+/// Show CPU affinity dialog with core type information; the first process's
+/// affinity is shown initially
+fn show_affinity_dialog(parent: &gtk4::Window, selected: Vec<(u32, String)>, automation: Rc<RefCell<AutomationEngine>>) {
+    let pids = selected_pids(&selected);
     let core_info = get_cpu_core_info();
-    let current_affinity = get_cpu_affinity(pid).unwrap_or_else(|_| vec![true; core_info.len()]);
+    let current_affinity = get_cpu_affinity(pids[0]).unwrap_or_else(|_| vec![true; core_info.len()]);
 
     let dialog = adw::Window::builder()
         .title("Set CPU Affinity")
@@ -229,8 +757,8 @@ fn show_affinity_dialog(parent: &gtk4::Window, pid: u32) {
     content.set_margin_end(12);
 
     let label = Label::new(Some(&format!(
-        "Select which CPU cores process {} can run on:",
-        pid
+        "Select which CPU cores {} can run on:",
+        describe_pids(&pids)
     )));
     label.set_halign(gtk4::Align::Start);
     content.append(&label);
@@ -358,6 +886,9 @@ fn show_affinity_dialog(parent: &gtk4::Window, pid: u32) {
         content.append(&type_btn_box);
     }
 
+    let inherit_check = create_inherit_check();
+    content.append(&inherit_check);
+
     main_box.append(&content);
     dialog.set_content(Some(&main_box));
 
@@ -386,9 +917,27 @@ fn show_affinity_dialog(parent: &gtk4::Window, pid: u32) {
             if let Some(parent) = parent_weak.upgrade() {
                 show_error(&parent, "Invalid Selection", "You must select at least one CPU.");
             }
-        } else if let Err(e) = set_cpu_affinity(pid, &selected_cpus) {
-            if let Some(parent) = parent_weak.upgrade() {
-                show_error(&parent, "Failed to set CPU affinity", &e.to_string());
+        } else {
+            // taskset takes one PID at a time; report all failures together
+            let (pids, cpus, error_parent) = (pids.clone(), selected_cpus.clone(), parent_weak.clone());
+            glib::spawn_future_local(async move {
+                let errors = run_in_background(move || {
+                    Ok(pids
+                        .iter()
+                        .filter_map(|&pid| set_cpu_affinity(pid, &cpus).err())
+                        .map(|e| e.to_string())
+                        .collect::<Vec<String>>())
+                })
+                .await
+                .unwrap_or_else(|e| vec![e.to_string()]);
+                if let (false, Some(parent)) = (errors.is_empty(), error_parent.upgrade()) {
+                    show_error(&parent, "Failed to set CPU affinity", &errors.join("\n"));
+                }
+            });
+            if let (true, Some(parent)) = (inherit_check.is_active(), parent_weak.upgrade()) {
+                save_inherited_rules(&parent, &automation, &selected_names(&selected), |engine, name| {
+                    engine.set_inherited_affinity(name, selected_cpus.clone())
+                });
             }
         }
 
@@ -401,7 +950,7 @@ fn show_affinity_dialog(parent: &gtk4::Window, pid: u32) {
 }
 
 /// Create a legend showing core type colors
-fn create_core_type_legend(core_info: &[crate::process_actions::CpuCoreInfo]) -> GtkBox {
+fn create_core_type_legend(core_info: &[procular_core::process_actions::CpuCoreInfo]) -> GtkBox {
     let legend = GtkBox::new(Orientation::Horizontal, 16);
     legend.set_halign(gtk4::Align::Center);
     legend.set_margin_top(4);
@@ -432,16 +981,31 @@ fn create_core_type_legend(core_info: &[crate::process_actions::CpuCoreInfo]) ->
     legend
 }
 
-/// Show priority dialog using adw::Window
-fn show_priority_dialog(parent: &gtk4::Window, pid: u32) {
-    let current_priority = process_actions::get_priority(pid).unwrap_or(0);
+/// Move the nice value of each process by `step`, leaving those at the end of the range alone.
+/// Processes ending up at the same value are reniced together, so there is one prompt per value
+fn step_nice(pids: &[u32], step: i32) -> std::io::Result<()> {
+    let mut targets: BTreeMap<i32, Vec<u32>> = BTreeMap::new();
+    for &pid in pids {
+        let current = process_actions::get_priority(pid)?;
+        let nice = (current + step).clamp(MIN_NICE, MAX_NICE);
+        if nice != current {
+            targets.entry(nice).or_default().push(pid);
+        }
+    }
+    targets.iter().try_for_each(|(nice, pids)| set_nice_many(pids, *nice))
+}
+
+/// Show priority dialog using adw::Window; the first process's priority is shown as current
+fn show_priority_dialog(parent: &gtk4::Window, selected: Vec<(u32, String)>, automation: Rc<RefCell<AutomationEngine>>) {
+    let pids = selected_pids(&selected);
+    let current_priority = process_actions::get_priority(pids[0]).unwrap_or(0);
 
     let dialog = adw::Window::builder()
         .title("Set Process Priority")
         .transient_for(parent)
         .modal(true)
         .default_width(300)
-        .default_height(350)
+        .default_height(390)
         .build();
 
     let main_box = GtkBox::new(Orientation::Vertical, 0);
@@ -465,10 +1029,11 @@ fn show_priority_dialog(parent: &gtk4::Window, pid: u32) {
     content.set_margin_start(12);
     content.set_margin_end(12);
 
-    let label = Label::new(Some(&format!(
-        "Current priority (nice value): {}\n\nSelect new priority:",
-        current_priority
-    )));
+    let label = Label::new(Some(&if pids.len() == 1 {
+        format!("Current priority (nice value): {}\n\nSelect new priority:", current_priority)
+    } else {
+        format!("Select new priority for {}:", describe_pids(&pids))
+    }));
     label.set_halign(gtk4::Align::Start);
     content.append(&label);
 
@@ -493,16 +1058,46 @@ fn show_priority_dialog(parent: &gtk4::Window, pid: u32) {
         buttons.borrow_mut().push((radio, *priority));
     }
 
+    // Any other nice value
+    let custom_row = GtkBox::new(Orientation::Horizontal, 8);
+    let custom_radio = CheckButton::with_label("Custom:");
+    custom_radio.set_group(first_button.as_ref());
+    let custom_spin = SpinButton::with_range(MIN_NICE as f64, MAX_NICE as f64, 1.0);
+    custom_spin.set_value(current_priority as f64);
+    if Priority::from_nice_value(current_priority).is_none() {
+        custom_radio.set_active(true);
+    }
+    let custom_radio_weak = custom_radio.downgrade();
+    custom_spin.connect_value_changed(move |_| {
+        if let Some(radio) = custom_radio_weak.upgrade() {
+            radio.set_active(true);
+        }
+    });
+    custom_row.append(&custom_radio);
+    custom_row.append(&custom_spin);
+    priority_box.append(&custom_row);
+
     content.append(&priority_box);
 
-    let note = Label::new(Some(
-        "Note: Higher priority (lower nice value) may require root privileges.",
-    ));
+    let note = Label::new(Some(if process_actions::is_pkexec_available() {
+        "Note: Higher priority (lower nice value) will ask for administrator authorization."
+    } else {
+        "Note: Higher priority (lower nice value) may require root privileges."
+    }));
     note.add_css_class("dim-label");
     note.set_halign(gtk4::Align::Start);
     note.set_wrap(true);
     content.append(&note);
 
+    let inherit_check = create_inherit_check();
+    content.append(&inherit_check);
+    // Automation rules only know the presets
+    custom_radio
+        .bind_property("active", &inherit_check, "sensitive")
+        .invert_boolean()
+        .sync_create()
+        .build();
+
     main_box.append(&content);
     dialog.set_content(Some(&main_box));
 
@@ -519,15 +1114,29 @@ fn show_priority_dialog(parent: &gtk4::Window, pid: u32) {
     let parent_weak = parent.downgrade();
     let dialog_weak = dialog.downgrade();
     apply_btn.connect_clicked(move |_| {
-        for (radio, priority) in buttons_clone.borrow().iter() {
-            if radio.is_active() {
-                if let Err(e) = set_priority(pid, *priority) {
-                    if let Some(parent) = parent_weak.upgrade() {
-                        show_error(&parent, "Failed to set priority", &e.to_string());
-                    }
+        let preset = buttons_clone
+            .borrow()
+            .iter()
+            .find(|(radio, _)| radio.is_active())
+            .map(|(_, priority)| *priority);
+        let nice = match preset {
+            Some(priority) => Some(priority.nice_value()),
+            None => custom_radio.is_active().then(|| custom_spin.value_as_int()),
+        };
+        if let Some(nice) = nice {
+            let (pids, error_parent) = (pids.clone(), parent_weak.clone());
+            glib::spawn_future_local(async move {
+                if let (Err(e), Some(parent)) =
+                    (run_in_background(move || set_nice_many(&pids, nice)).await, error_parent.upgrade())
+                {
+                    show_error(&parent, "Failed to set priority", &e.to_string());
                 }
-                break;
-            }
+            });
+        }
+        if let (Some(priority), true, Some(parent)) = (preset, inherit_check.is_active(), parent_weak.upgrade()) {
+            save_inherited_rules(&parent, &automation, &selected_names(&selected), |engine, name| {
+                engine.set_inherited_priority(name, priority)
+            });
         }
 
         if let Some(d) = dialog_weak.upgrade() {
@@ -0,0 +1,96 @@
+//! Per-core CPU utilization graphs
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, FlowBox, Label, Orientation, ScrolledWindow};
+use std::time::SystemTime;
+
+use procular_core::monitor::SystemMonitor;
+use procular_core::process_actions::{get_cpu_core_info, CoreType};
+
+use crate::graph::GraphWidget;
+
+const CORE_COLOR: (f64, f64, f64) = (0.204, 0.396, 0.643); // Blue, same as process CPU graphs
+const CORE_GRAPH_HEIGHT: i32 = 80;
+
+/// Graph and current-load label for one core
+struct CoreGraph {
+    graph: GraphWidget,
+    usage_label: Label,
+}
+
+/// Grid of small graphs, one per CPU core
+pub struct CpuView {
+    pub widget: ScrolledWindow,
+    cores: Vec<CoreGraph>,
+}
+
+impl CpuView {
+    pub fn new() -> Self {
+        let flow_box = FlowBox::new();
+        flow_box.set_homogeneous(true);
+        flow_box.set_selection_mode(gtk4::SelectionMode::None);
+        flow_box.set_min_children_per_line(2);
+        flow_box.set_max_children_per_line(8);
+        flow_box.set_row_spacing(12);
+        flow_box.set_column_spacing(12);
+        flow_box.set_valign(gtk4::Align::Start);
+        flow_box.set_margin_top(12);
+        flow_box.set_margin_bottom(12);
+        flow_box.set_margin_start(12);
+        flow_box.set_margin_end(12);
+
+        let core_info = get_cpu_core_info();
+        let cores = core_info
+            .iter()
+            .map(|info| {
+                let cell = GtkBox::new(Orientation::Vertical, 4);
+
+                let header = GtkBox::new(Orientation::Horizontal, 8);
+                let title = match &info.core_type {
+                    CoreType::Standard => format!("CPU {}", info.cpu_id),
+                    core_type => format!("CPU {} · {}", info.cpu_id, core_type.label()),
+                };
+                let title_label = Label::new(Some(&title));
+                title_label.add_css_class("heading");
+                title_label.set_halign(gtk4::Align::Start);
+                title_label.set_hexpand(true);
+                header.append(&title_label);
+
+                let usage_label = Label::new(Some("0%"));
+                usage_label.add_css_class("numeric");
+                header.append(&usage_label);
+                cell.append(&header);
+
+                let graph = GraphWidget::new(CORE_COLOR, true, false);
+                graph.drawing_area.set_size_request(200, CORE_GRAPH_HEIGHT);
+                // Keep the scale at 100% so cores are comparable at a glance
+                graph.set_fixed_max(Some(100.0));
+                graph.set_title_label(&title_label);
+                cell.append(&graph.drawing_area);
+
+                flow_box.append(&cell);
+                CoreGraph { graph, usage_label }
+            })
+            .collect();
+
+        let widget = ScrolledWindow::new();
+        widget.set_vexpand(true);
+        widget.set_child(Some(&flow_box));
+
+        Self { widget, cores }
+    }
+
+    /// Update the graphs from the monitor's per-core history, suspend gaps and sample times
+    pub fn update(&self, monitor: &SystemMonitor, sample_interval_secs: u64) {
+        let gaps: Vec<bool> = monitor.sample_gaps().iter().copied().collect();
+        let times: Vec<SystemTime> = monitor.sample_times().iter().copied().collect();
+        for (core, history) in self.cores.iter().zip(monitor.core_history()) {
+            let values: Vec<f64> = history.iter().map(|&v| v as f64).collect();
+            core.graph.set_gaps(&gaps);
+            core.graph.set_times(&times);
+            core.graph.update(&values, monitor.max_samples(), sample_interval_secs);
+            core.usage_label
+                .set_text(&format!("{:.0}%", values.last().copied().unwrap_or(0.0)));
+        }
+    }
+}
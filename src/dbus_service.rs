@@ -0,0 +1,179 @@
+//! Session D-Bus interface so scripts and other tools can query the monitor
+//!
+//! The object lives at `/org/procular/ProcessMonitor` on the application's own bus name
+//! (`org.procular.ProcessMonitor`, owned by GApplication) next to the standard
+//! `org.gtk.Application` interface.
+
+use gtk4::gio;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use std::collections::HashMap;
+
+use procular_core::process_actions::{get_process_name, Signal};
+
+use crate::app_state::AppState;
+use crate::audit_log::send_signal_audited;
+
+const OBJECT_PATH: &str = "/org/procular/ProcessMonitor";
+const INTERFACE_NAME: &str = "org.procular.ProcessMonitor";
+const ERROR_NO_SUCH_PROCESS: &str = "org.procular.ProcessMonitor.Error.NoSuchProcess";
+const ERROR_FAILED: &str = "org.procular.ProcessMonitor.Error.Failed";
+const ERROR_READ_ONLY: &str = "org.procular.ProcessMonitor.Error.ReadOnly";
+
+/// One ListProcesses entry, matching the `(utsdttttt)` signature
+type ProcessEntry = (u32, u64, String, f64, u64, u64, u64, u64, u64);
+
+const INTROSPECTION_XML: &str = r#"
+<node>
+  <interface name="org.procular.ProcessMonitor">
+    <!-- Processes of the last refresh: pid, start time, name, CPU %, memory,
+         then disk read/write and network rx/tx bytes since the previous refresh -->
+    <method name="ListProcesses">
+      <arg name="processes" type="a(utsdttttt)" direction="out"/>
+    </method>
+    <!-- Recorded samples per metric: cpu, memory, disk-read, disk-write, net-rx, net-tx -->
+    <method name="GetHistory">
+      <arg name="pid" type="u" direction="in"/>
+      <arg name="history" type="a{sad}" direction="out"/>
+    </method>
+    <method name="Kill">
+      <arg name="pid" type="u" direction="in"/>
+      <arg name="force" type="b" direction="in"/>
+    </method>
+    <!-- kind is "disk-full", "vram-full", "io-budget", "swapping" or "overheating"; subject names what crossed the threshold -->
+    <signal name="ThresholdAlert">
+      <arg name="kind" type="s"/>
+      <arg name="subject" type="s"/>
+      <arg name="value" type="d"/>
+    </signal>
+  </interface>
+</node>
+"#;
+
+/// Registered D-Bus object; unregistered when dropped
+pub struct DbusService {
+    connection: gio::DBusConnection,
+    registration: Option<gio::RegistrationId>,
+}
+
+impl DbusService {
+    /// Export the interface on the application's bus connection.
+    /// Returns None when the application is not on the session bus.
+    pub fn register(app: &adw::Application, state: &AppState) -> Option<Self> {
+        let connection = app.dbus_connection()?;
+        let interface = gio::DBusNodeInfo::for_xml(INTROSPECTION_XML)
+            .ok()?
+            .lookup_interface(INTERFACE_NAME)?;
+
+        let state = state.clone();
+        let registration = connection
+            .register_object(OBJECT_PATH, &interface)
+            .method_call(move |_, _, _, _, method, params, invocation| {
+                handle_method_call(&state, method, &params, invocation);
+            })
+            .build();
+
+        match registration {
+            Ok(registration) => Some(Self {
+                connection,
+                registration: Some(registration),
+            }),
+            Err(e) => {
+                // Another window of this instance already exports the object
+                eprintln!("Failed to register D-Bus interface: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Broadcast a ThresholdAlert signal
+    pub fn emit_threshold_alert(&self, kind: &str, subject: &str, value: f64) {
+        let result = self.connection.emit_signal(
+            None,
+            OBJECT_PATH,
+            INTERFACE_NAME,
+            "ThresholdAlert",
+            Some(&(kind, subject, value).to_variant()),
+        );
+        if let Err(e) = result {
+            eprintln!("Failed to emit ThresholdAlert: {}", e);
+        }
+    }
+}
+
+impl Drop for DbusService {
+    fn drop(&mut self) {
+        if let Some(registration) = self.registration.take() {
+            let _ = self.connection.unregister_object(registration);
+        }
+    }
+}
+
+fn handle_method_call(state: &AppState, method: &str, params: &glib::Variant, invocation: gio::DBusMethodInvocation) {
+    match method {
+        "ListProcesses" => {
+            let monitor = state.monitor.borrow();
+            let processes: Vec<ProcessEntry> = monitor
+                .processes()
+                .iter()
+                .map(|p| {
+                    (
+                        p.pid,
+                        p.start_time,
+                        p.name.clone(),
+                        p.total_cpu() as f64,
+                        p.total_memory(),
+                        p.total_disk_read_rate(),
+                        p.total_disk_write_rate(),
+                        p.total_net_rx(),
+                        p.total_net_tx(),
+                    )
+                })
+                .collect();
+            invocation.return_value(Some(&(processes,).to_variant()));
+        }
+        "GetHistory" => {
+            let Some((pid,)) = params.get::<(u32,)>() else {
+                return invocation.return_dbus_error(ERROR_FAILED, "Invalid arguments");
+            };
+            let monitor = state.monitor.borrow();
+            let Some(history) = monitor.process_key(pid).and_then(|key| monitor.get_history(key)) else {
+                return invocation.return_dbus_error(
+                    ERROR_NO_SUCH_PROCESS,
+                    &format!("No history for PID {}", pid),
+                );
+            };
+
+            let mut series: HashMap<String, Vec<f64>> = HashMap::new();
+            series.insert("cpu".into(), history.cpu_history.iter().map(|&v| v as f64).collect());
+            series.insert("memory".into(), history.memory_history.iter().map(|&v| v as f64).collect());
+            series.insert("disk-read".into(), history.disk_read_history.iter().map(|&v| v as f64).collect());
+            series.insert("disk-write".into(), history.disk_write_history.iter().map(|&v| v as f64).collect());
+            series.insert("net-rx".into(), history.net_rx_history.iter().map(|&v| v as f64).collect());
+            series.insert("net-tx".into(), history.net_tx_history.iter().map(|&v| v as f64).collect());
+            invocation.return_value(Some(&(series,).to_variant()));
+        }
+        "Kill" => {
+            let Some((pid, force)) = params.get::<(u32, bool)>() else {
+                return invocation.return_dbus_error(ERROR_FAILED, "Invalid arguments");
+            };
+            if state.lockdown.is_read_only() {
+                return invocation.return_dbus_error(ERROR_READ_ONLY, "Procular is in read-only mode");
+            }
+            let signal = if force { Signal::Kill } else { Signal::Term };
+            let name = get_process_name(pid);
+            // Replied to once the signal was sent, which may wait for a polkit prompt
+            let audit_log = state.audit_log.clone();
+            glib::spawn_future_local(async move {
+                match send_signal_audited(audit_log, vec![(pid, name)], signal).await {
+                    Ok(()) => invocation.return_value(None),
+                    Err(e) => invocation.return_dbus_error(ERROR_FAILED, &e.to_string()),
+                }
+            });
+        }
+        _ => invocation.return_dbus_error(
+            "org.freedesktop.DBus.Error.UnknownMethod",
+            &format!("Unknown method {}", method),
+        ),
+    }
+}
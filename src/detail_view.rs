@@ -1,10 +1,23 @@
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, DrawingArea, DropDown, FlowBox, Grid, Label, Orientation, ScrolledWindow, Separator, StringList};
-use std::cell::RefCell;
+use gtk4::accessible::Relation;
+use gtk4::{Box as GtkBox, Button, DropDown, FlowBox, Grid, Label, Orientation, ScrolledWindow, Separator, StringList};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
-
-use crate::monitor::{ProcessHistory, format_bytes};
-use crate::process_actions::{get_cpu_core_info, get_thread_cpu_info, CoreType};
+use std::time::SystemTime;
+
+use procular_core::cgroup_limits::{cgroup_path, is_cgroup_frozen};
+use procular_core::kernel_wait::{read_kernel_stack, read_wchan};
+use procular_core::memory_map::{read_memory_map_summary, read_process_tree_pss};
+use procular_core::monitor::{NetTotals, ProcessHistory, format_bytes, format_duration, format_rate};
+#[cfg(feature = "perf")]
+use procular_core::perf_events::HardwareSample;
+use procular_core::process_actions::{
+    get_cpu_core_info, get_process_name, get_thread_cpu_info, read_scheduling, realtime_threads, CoreType, Scheduling,
+};
+use procular_core::users::user_name;
+
+use crate::graph::{color_swatch, GraphWidget};
+use crate::help;
 
 /// Colors for the graphs
 const CPU_COLOR: (f64, f64, f64) = (0.204, 0.396, 0.643); // Blue
@@ -15,263 +28,24 @@ const GPU_MEM_COLOR: (f64, f64, f64) = (0.118, 0.565, 0.659); // Teal
 const GPU_UTIL_COLOR: (f64, f64, f64) = (0.0, 0.6, 0.4); // Green-teal
 const NET_RX_COLOR: (f64, f64, f64) = (0.608, 0.349, 0.714); // Light purple
 const NET_TX_COLOR: (f64, f64, f64) = (0.839, 0.153, 0.157); // Red
-
-/// Graph configuration
-const GRAPH_LEFT_MARGIN: f64 = 55.0;  // Space for Y-axis labels
-const GRAPH_BOTTOM_MARGIN: f64 = 20.0; // Space for X-axis labels
-const GRAPH_RIGHT_MARGIN: f64 = 10.0;
-const GRAPH_TOP_MARGIN: f64 = 5.0;
-
-/// Format a value for Y-axis display
-fn format_y_value(value: f64, is_percentage: bool, is_bytes: bool) -> String {
-    if is_percentage {
-        format!("{:.0}%", value)
-    } else if is_bytes {
-        format_bytes(value as u64)
-    } else {
-        format!("{:.1}", value)
-    }
-}
-
-/// Calculate nice Y-axis tick values
-fn calculate_y_ticks(max_value: f64, is_percentage: bool) -> Vec<f64> {
-    if is_percentage {
-        // For percentages, use fixed ticks
-        let max_tick = if max_value <= 25.0 {
-            25.0
-        } else if max_value <= 50.0 {
-            50.0
-        } else if max_value <= 100.0 {
-            100.0
-        } else {
-            ((max_value / 50.0).ceil() * 50.0).max(100.0)
-        };
-        vec![0.0, max_tick * 0.25, max_tick * 0.5, max_tick * 0.75, max_tick]
-    } else {
-        // For other values, calculate nice intervals
-        if max_value <= 0.0 {
-            return vec![0.0];
-        }
-
-        let magnitude = 10_f64.powf(max_value.log10().floor());
-        let normalized = max_value / magnitude;
-
-        let nice_max = if normalized <= 1.0 {
-            magnitude
-        } else if normalized <= 2.0 {
-            2.0 * magnitude
-        } else if normalized <= 5.0 {
-            5.0 * magnitude
-        } else {
-            10.0 * magnitude
-        };
-
-        vec![0.0, nice_max * 0.25, nice_max * 0.5, nice_max * 0.75, nice_max]
-    }
-}
-
-/// Graph data with metadata
-#[derive(Clone)]
-struct GraphData {
-    values: Vec<f64>,
-    max_value: f64,
-    is_percentage: bool,
-    is_bytes: bool,
-    num_samples: usize,
-    sample_interval_secs: u64,
-}
-
-impl Default for GraphData {
-    fn default() -> Self {
-        Self {
-            values: Vec::new(),
-            max_value: 100.0,
-            is_percentage: false,
-            is_bytes: false,
-            num_samples: 60,
-            sample_interval_secs: 2,
-        }
-    }
-}
-
-/// A single graph widget with axis labels
-struct GraphWidget {
-    drawing_area: DrawingArea,
-    data: Rc<RefCell<GraphData>>,
-    color: (f64, f64, f64),
-}
-
-impl GraphWidget {
-    fn new(color: (f64, f64, f64), is_percentage: bool, is_bytes: bool) -> Self {
-        let drawing_area = DrawingArea::new();
-        drawing_area.set_size_request(-1, 120);
-        drawing_area.set_hexpand(true);
-        drawing_area.set_vexpand(true);
-
-        let data = Rc::new(RefCell::new(GraphData {
-            is_percentage,
-            is_bytes,
-            ..Default::default()
-        }));
-
-        let data_clone = data.clone();
-        let color_clone = color;
-
-        drawing_area.set_draw_func(move |_widget, cr, width, height| {
-            let data = data_clone.borrow();
-            let width_f = width as f64;
-            let height_f = height as f64;
-
-            // Calculate graph area
-            let graph_left = GRAPH_LEFT_MARGIN;
-            let graph_right = width_f - GRAPH_RIGHT_MARGIN;
-            let graph_top = GRAPH_TOP_MARGIN;
-            let graph_bottom = height_f - GRAPH_BOTTOM_MARGIN;
-            let graph_width = graph_right - graph_left;
-            let graph_height = graph_bottom - graph_top;
-
-            // Background
-            cr.set_source_rgb(0.12, 0.12, 0.12);
-            let _ = cr.paint();
-
-            // Calculate Y-axis ticks
-            let y_ticks = calculate_y_ticks(data.max_value, data.is_percentage);
-            let y_max = *y_ticks.last().unwrap_or(&100.0);
-
-            // Draw grid lines and Y-axis labels
-            cr.set_source_rgba(0.3, 0.3, 0.3, 0.8);
-            cr.set_line_width(1.0);
-
-            for &tick in &y_ticks {
-                let y = graph_bottom - (tick / y_max) * graph_height;
-
-                // Grid line
-                cr.move_to(graph_left, y);
-                cr.line_to(graph_right, y);
-                let _ = cr.stroke();
-
-                // Y-axis label
-                cr.set_source_rgba(0.7, 0.7, 0.7, 1.0);
-                let label = format_y_value(tick, data.is_percentage, data.is_bytes);
-                if let Ok(extents) = cr.text_extents(&label) {
-                    cr.move_to(graph_left - extents.width() - 5.0, y + extents.height() / 2.0);
-                    let _ = cr.show_text(&label);
-                }
-                cr.set_source_rgba(0.3, 0.3, 0.3, 0.8);
-            }
-
-            // Draw X-axis labels (time)
-            let total_time_secs = data.num_samples as u64 * data.sample_interval_secs;
-            cr.set_source_rgba(0.7, 0.7, 0.7, 1.0);
-
-            // Show labels at 0%, 50%, 100% of the time range
-            let time_labels = [
-                (0.0, format!("{}s", total_time_secs)),
-                (0.5, format!("{}s", total_time_secs / 2)),
-                (1.0, "now".to_string()),
-            ];
-
-            for (pos, label) in &time_labels {
-                let x = graph_left + pos * graph_width;
-                if let Ok(extents) = cr.text_extents(label) {
-                    let x_centered = if *pos == 0.0 {
-                        x
-                    } else if *pos == 1.0 {
-                        x - extents.width()
-                    } else {
-                        x - extents.width() / 2.0
-                    };
-                    cr.move_to(x_centered, height_f - 3.0);
-                    let _ = cr.show_text(label);
-                }
-            }
-
-            // Draw data if we have any
-            if data.values.len() >= 2 {
-                let num_points = data.values.len();
-                let step = graph_width / (num_points - 1) as f64;
-
-                // Fill area under curve
-                cr.move_to(graph_left, graph_bottom);
-                for (i, &value) in data.values.iter().enumerate() {
-                    let x = graph_left + i as f64 * step;
-                    let normalized = if y_max > 0.0 {
-                        (value / y_max).clamp(0.0, 1.0)
-                    } else {
-                        0.0
-                    };
-                    let y = graph_bottom - (normalized * graph_height);
-                    cr.line_to(x, y);
-                }
-                cr.line_to(graph_right, graph_bottom);
-                cr.close_path();
-                cr.set_source_rgba(color_clone.0, color_clone.1, color_clone.2, 0.3);
-                let _ = cr.fill();
-
-                // Draw line on top
-                cr.set_source_rgb(color_clone.0, color_clone.1, color_clone.2);
-                cr.set_line_width(2.0);
-                for (i, &value) in data.values.iter().enumerate() {
-                    let x = graph_left + i as f64 * step;
-                    let normalized = if y_max > 0.0 {
-                        (value / y_max).clamp(0.0, 1.0)
-                    } else {
-                        0.0
-                    };
-                    let y = graph_bottom - (normalized * graph_height);
-                    if i == 0 {
-                        cr.move_to(x, y);
-                    } else {
-                        cr.line_to(x, y);
-                    }
-                }
-                let _ = cr.stroke();
-            } else if data.values.len() == 1 {
-                // Single data point - draw a dot
-                let normalized = if y_max > 0.0 {
-                    (data.values[0] / y_max).clamp(0.0, 1.0)
-                } else {
-                    0.0
-                };
-                let y = graph_bottom - (normalized * graph_height);
-                cr.set_source_rgb(color_clone.0, color_clone.1, color_clone.2);
-                cr.arc(graph_right, y, 3.0, 0.0, 2.0 * std::f64::consts::PI);
-                let _ = cr.fill();
-            }
-
-            // Border around graph area
-            cr.set_source_rgba(0.4, 0.4, 0.4, 1.0);
-            cr.set_line_width(1.0);
-            cr.rectangle(graph_left, graph_top, graph_width, graph_height);
-            let _ = cr.stroke();
-        });
-
-        Self {
-            drawing_area,
-            data,
-            color,
-        }
-    }
-
-    fn update(&self, values: &[f64], num_samples: usize, sample_interval_secs: u64) {
-        let mut data = self.data.borrow_mut();
-        data.values = values.to_vec();
-        data.num_samples = num_samples;
-        data.sample_interval_secs = sample_interval_secs;
-
-        // Auto-scale: find max value with some headroom
-        let max_val = values.iter().cloned().fold(0.0_f64, f64::max);
-        // Ensure minimum of 1.0 to avoid division issues and provide meaningful scale
-        data.max_value = max_val.max(1.0);
-
-        self.drawing_area.queue_draw();
-    }
-
-    #[allow(dead_code)]
-    fn color(&self) -> (f64, f64, f64) {
-        self.color
-    }
-}
+const USER_CPU_COLOR: (f64, f64, f64) = (0.204, 0.396, 0.643); // Blue, same as CPU
+const KERNEL_CPU_COLOR: (f64, f64, f64) = (0.878, 0.106, 0.141); // Red
+const MINOR_FAULT_COLOR: (f64, f64, f64) = (0.467, 0.463, 0.482); // Gray
+const MAJOR_FAULT_COLOR: (f64, f64, f64) = (0.757, 0.490, 0.067); // Amber
+const VOLUNTARY_SWITCH_COLOR: (f64, f64, f64) = (0.204, 0.396, 0.643); // Blue
+const INVOLUNTARY_SWITCH_COLOR: (f64, f64, f64) = (0.839, 0.153, 0.157); // Red
+const FPS_COLOR: (f64, f64, f64) = (0.180, 0.545, 0.341); // Green
+const FRAME_TIME_COLOR: (f64, f64, f64) = (0.902, 0.494, 0.133); // Orange
+#[cfg(feature = "perf")]
+const SYSCALL_COLOR: (f64, f64, f64) = (0.757, 0.490, 0.067); // Amber
+#[cfg(feature = "perf")]
+const INSTRUCTIONS_COLOR: (f64, f64, f64) = (0.204, 0.396, 0.643); // Blue
+#[cfg(feature = "perf")]
+const CYCLES_COLOR: (f64, f64, f64) = (0.584, 0.345, 0.698); // Purple
+#[cfg(feature = "perf")]
+const IPC_COLOR: (f64, f64, f64) = (0.180, 0.545, 0.341); // Green
+#[cfg(feature = "perf")]
+const CACHE_MISS_COLOR: (f64, f64, f64) = (0.839, 0.153, 0.157); // Red
 
 /// Statistics for a metric
 struct MetricStats {
@@ -320,9 +94,19 @@ pub struct DetailView {
     // Process info section
     title_label: Label,
     info_labels: ProcessInfoLabels,
+    tracer_row: TracerRow,
+    kernel_wait_row: Rc<KernelWaitRow>,
     // CPU core display
     cpu_core_display: CpuCoreDisplay,
-    current_pid: RefCell<Option<u32>>,
+    current_pid: Rc<RefCell<Option<u32>>>,
+    /// Factor applied to CPU percentages (core count in per-core mode)
+    cpu_scale: Cell<f32>,
+    /// Seconds between history samples, for the time axis
+    sample_interval_secs: Cell<u64>,
+    // Memory composition (filled on demand)
+    memory_map_panel: Rc<MemoryMapPanel>,
+    // PSS breakdown of the process and its children (refreshed while expanded)
+    tree_memory_panel: Rc<TreeMemoryPanel>,
     // Graph grid and sections (for layout switching)
     #[allow(dead_code)]
     graph_grid: Grid,
@@ -348,6 +132,173 @@ pub struct DetailView {
     disk_write_stats: StatsLabels,
     net_rx_stats: StatsLabels,
     net_tx_stats: StatsLabels,
+    // User time stacked on kernel time, below the main grid
+    cpu_split_graph: GraphWidget,
+    user_cpu_label: Label,
+    kernel_cpu_label: Label,
+    // Page fault rates
+    minor_fault_graph: GraphWidget,
+    minor_fault_stats: StatsLabels,
+    major_fault_graph: GraphWidget,
+    major_fault_stats: StatsLabels,
+    // Context switch rates, below the page faults
+    voluntary_switch_graph: GraphWidget,
+    voluntary_switch_stats: StatsLabels,
+    involuntary_switch_graph: GraphWidget,
+    involuntary_switch_stats: StatsLabels,
+    // MangoHud frame rate (hidden unless the process has MangoHud loaded)
+    frame_rate_section: FrameRateSection,
+    // perf_event based graphs (hidden until a collector provides data)
+    #[cfg(feature = "perf")]
+    perf_section: PerfSection,
+}
+
+/// FPS and frame time read from MangoHud's log, for games
+struct FrameRateSection {
+    container: GtkBox,
+    status_label: Label,
+    logging_button: Button,
+    fps_graph: GraphWidget,
+    fps_stats: StatsLabels,
+    frame_time_graph: GraphWidget,
+    frame_time_stats: StatsLabels,
+}
+
+impl FrameRateSection {
+    fn new() -> Self {
+        let container = GtkBox::new(Orientation::Vertical, 8);
+        container.set_visible(false);
+
+        let header = GtkBox::new(Orientation::Horizontal, 8);
+        let title = Label::new(Some("Frame Rate"));
+        title.add_css_class("heading");
+        header.append(&title);
+        header.append(&help::help_button("frame-rate"));
+        let status_label = Label::new(None);
+        status_label.add_css_class("dim-label");
+        status_label.set_hexpand(true);
+        status_label.set_halign(gtk4::Align::Start);
+        status_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        header.append(&status_label);
+        let logging_button = Button::with_label("Start/Stop Logging");
+        logging_button.set_tooltip_text(Some("Toggle MangoHud's logging through its control socket"));
+        header.append(&logging_button);
+        container.append(&header);
+
+        let fps_graph = GraphWidget::new(FPS_COLOR, false, false);
+        let fps_stats = StatsLabels::new();
+        let frame_time_graph = GraphWidget::new(FRAME_TIME_COLOR, false, false);
+        let frame_time_stats = StatsLabels::new();
+        let grid = Grid::new();
+        grid.set_column_spacing(12);
+        grid.set_column_homogeneous(true);
+        grid.attach(&DetailView::create_graph_section("FPS", &fps_graph, &fps_stats, None), 0, 0, 1, 1);
+        grid.attach(
+            &DetailView::create_graph_section("Worst Frame Time (ms)", &frame_time_graph, &frame_time_stats, None),
+            1,
+            0,
+            1,
+            1,
+        );
+        container.append(&grid);
+
+        Self {
+            container,
+            status_label,
+            logging_button,
+            fps_graph,
+            fps_stats,
+            frame_time_graph,
+            frame_time_stats,
+        }
+    }
+}
+
+/// Graphs fed by the optional perf_event collectors
+#[cfg(feature = "perf")]
+struct PerfSection {
+    container: GtkBox,
+    syscall_section: GtkBox,
+    syscall_graph: GraphWidget,
+    syscall_stats: StatsLabels,
+    hardware_grid: Grid,
+    instructions_graph: GraphWidget,
+    instructions_stats: StatsLabels,
+    cycles_graph: GraphWidget,
+    cycles_stats: StatsLabels,
+    ipc_graph: GraphWidget,
+    ipc_stats: StatsLabels,
+    cache_miss_graph: GraphWidget,
+    cache_miss_stats: StatsLabels,
+}
+
+#[cfg(feature = "perf")]
+impl PerfSection {
+    fn new() -> Self {
+        let container = GtkBox::new(Orientation::Vertical, 8);
+        container.set_visible(false);
+
+        let header = GtkBox::new(Orientation::Horizontal, 8);
+        let title = Label::new(Some("Performance Counters"));
+        title.add_css_class("heading");
+        header.append(&title);
+        header.append(&help::help_button("perf"));
+        container.append(&header);
+
+        let syscall_graph = GraphWidget::new(SYSCALL_COLOR, false, false);
+        let syscall_stats = StatsLabels::new();
+        let syscall_section = DetailView::create_graph_section("Syscalls/s", &syscall_graph, &syscall_stats, None);
+        syscall_section.set_visible(false);
+        container.append(&syscall_section);
+
+        let instructions_graph = GraphWidget::new(INSTRUCTIONS_COLOR, false, false);
+        let instructions_stats = StatsLabels::new();
+        let cycles_graph = GraphWidget::new(CYCLES_COLOR, false, false);
+        let cycles_stats = StatsLabels::new();
+        let ipc_graph = GraphWidget::new(IPC_COLOR, false, false);
+        let ipc_stats = StatsLabels::new();
+        let cache_miss_graph = GraphWidget::new(CACHE_MISS_COLOR, false, false);
+        let cache_miss_stats = StatsLabels::new();
+
+        let hardware_grid = Grid::new();
+        hardware_grid.set_column_spacing(12);
+        hardware_grid.set_row_spacing(12);
+        hardware_grid.set_column_homogeneous(true);
+        hardware_grid.set_visible(false);
+        hardware_grid.attach(
+            &DetailView::create_graph_section("Instructions/s", &instructions_graph, &instructions_stats, None),
+            0, 0, 1, 1,
+        );
+        hardware_grid.attach(
+            &DetailView::create_graph_section("Cycles/s", &cycles_graph, &cycles_stats, None),
+            1, 0, 1, 1,
+        );
+        hardware_grid.attach(
+            &DetailView::create_graph_section("Instructions per Cycle", &ipc_graph, &ipc_stats, None),
+            0, 1, 1, 1,
+        );
+        hardware_grid.attach(
+            &DetailView::create_graph_section("Cache Misses/s", &cache_miss_graph, &cache_miss_stats, None),
+            1, 1, 1, 1,
+        );
+        container.append(&hardware_grid);
+
+        Self {
+            container,
+            syscall_section,
+            syscall_graph,
+            syscall_stats,
+            hardware_grid,
+            instructions_graph,
+            instructions_stats,
+            cycles_graph,
+            cycles_stats,
+            ipc_graph,
+            ipc_stats,
+            cache_miss_graph,
+            cache_miss_stats,
+        }
+    }
 }
 
 struct ProcessInfoLabels {
@@ -355,6 +306,172 @@ struct ProcessInfoLabels {
     threads: Label,
     state: Label,
     user: Label,
+    scheduling: Label,
+    network_total: Label,
+}
+
+/// "Debugged by" row, shown only while the process is traced
+struct TracerRow {
+    row: GtkBox,
+    label: Label,
+    open_button: Button,
+    tracer_pid: Rc<Cell<Option<u32>>>,
+}
+
+impl TracerRow {
+    fn new() -> Self {
+        let row = GtkBox::new(Orientation::Horizontal, 8);
+        row.set_margin_start(8);
+        row.set_margin_end(8);
+        row.set_margin_top(2);
+        row.set_margin_bottom(2);
+        row.set_visible(false);
+
+        let title = Label::new(Some("Debugged by"));
+        title.set_halign(gtk4::Align::Start);
+        title.set_width_chars(10);
+        title.add_css_class("dim-label");
+        row.append(&title);
+
+        let label = Label::new(Some("-"));
+        label.set_halign(gtk4::Align::Start);
+        label.add_css_class("warning");
+        row.append(&label);
+
+        let open_button = Button::from_icon_name("go-jump-symbolic");
+        open_button.add_css_class("flat");
+        open_button.set_tooltip_text(Some("Open the tracing process"));
+        row.append(&open_button);
+
+        Self {
+            row,
+            label,
+            open_button,
+            tracer_pid: Rc::new(Cell::new(None)),
+        }
+    }
+
+    fn update(&self, tracer_pid: Option<u32>) {
+        self.tracer_pid.set(tracer_pid);
+        match tracer_pid {
+            Some(tracer) => {
+                self.label
+                    .set_label(&format!("{} (PID {})", get_process_name(tracer), tracer));
+                self.row.set_visible(true);
+            }
+            None => self.row.set_visible(false),
+        }
+    }
+}
+
+/// Samples without CPU use (at 2 s each) before a sleeping process counts as long-sleeping
+const LONG_SLEEP_SAMPLES: usize = 15;
+
+/// "Waiting in" row with the kernel stack, shown for blocked or long-sleeping processes
+struct KernelWaitRow {
+    container: GtkBox,
+    wchan_label: Label,
+    expander: gtk4::Expander,
+    stack_label: Label,
+    pid: Rc<Cell<Option<u32>>>,
+}
+
+impl KernelWaitRow {
+    fn new() -> Rc<Self> {
+        let container = GtkBox::new(Orientation::Vertical, 2);
+        container.set_margin_start(8);
+        container.set_margin_end(8);
+        container.set_margin_top(2);
+        container.set_margin_bottom(2);
+        container.set_visible(false);
+
+        let row = GtkBox::new(Orientation::Horizontal, 8);
+        let title = Label::new(Some("Waiting in"));
+        title.set_halign(gtk4::Align::Start);
+        title.set_width_chars(10);
+        title.add_css_class("dim-label");
+        row.append(&title);
+
+        let wchan_label = Label::new(Some("-"));
+        wchan_label.set_halign(gtk4::Align::Start);
+        wchan_label.add_css_class("monospace");
+        wchan_label.set_tooltip_text(Some("Kernel function the process is blocked in (/proc/<pid>/wchan)"));
+        row.append(&wchan_label);
+        container.append(&row);
+
+        let expander = gtk4::Expander::new(Some("Kernel Stack"));
+        let stack_label = Label::new(None);
+        stack_label.set_halign(gtk4::Align::Start);
+        stack_label.set_selectable(true);
+        stack_label.set_wrap(true);
+        stack_label.add_css_class("monospace");
+        stack_label.set_margin_start(12);
+        expander.set_child(Some(&stack_label));
+        container.append(&expander);
+
+        let wait_row = Rc::new(Self {
+            container,
+            wchan_label,
+            expander,
+            stack_label,
+            pid: Rc::new(Cell::new(None)),
+        });
+
+        let wait_row_clone = wait_row.clone();
+        wait_row.expander.connect_expanded_notify(move |expander| {
+            if expander.is_expanded() {
+                wait_row_clone.refresh_stack();
+            }
+        });
+
+        wait_row
+    }
+
+    fn update(&self, pid: u32, details: Option<&ProcessDetails>, history: Option<&ProcessHistory>) {
+        let Some(details) = details else {
+            self.container.set_visible(false);
+            return;
+        };
+
+        let long_sleeping = details.state == "Sleeping"
+            && history.is_some_and(|h| {
+                h.cpu_history.len() >= LONG_SLEEP_SAMPLES
+                    && h.cpu_history.iter().rev().take(LONG_SLEEP_SAMPLES).all(|&cpu| cpu < 0.01)
+            });
+        if details.state != "Disk Sleep" && !long_sleeping {
+            self.container.set_visible(false);
+            return;
+        }
+
+        self.pid.set(Some(pid));
+        self.wchan_label
+            .set_label(read_wchan(pid).as_deref().unwrap_or("unknown"));
+        if details.state == "Disk Sleep" {
+            self.wchan_label.add_css_class("warning");
+        } else {
+            self.wchan_label.remove_css_class("warning");
+        }
+        self.container.set_visible(true);
+
+        if self.expander.is_expanded() {
+            self.refresh_stack();
+        }
+    }
+
+    fn refresh_stack(&self) {
+        let Some(pid) = self.pid.get() else {
+            return;
+        };
+        let text = match read_kernel_stack(pid) {
+            Ok(frames) if frames.is_empty() => "Not blocked in the kernel right now".to_string(),
+            Ok(frames) => frames.join("\n"),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                "Reading the kernel stack requires root (CAP_SYS_ADMIN)".to_string()
+            }
+            Err(e) => format!("Kernel stack unavailable: {}", e),
+        };
+        self.stack_label.set_label(&text);
+    }
 }
 
 /// Visual display of CPU cores showing thread distribution
@@ -454,6 +571,185 @@ impl CpuCoreDisplay {
     }
 }
 
+/// On-demand breakdown of /proc/<pid>/maps shown under the memory graph
+struct MemoryMapPanel {
+    container: GtkBox,
+    grid: Grid,
+    code: Label,
+    heap: Label,
+    stack: Label,
+    mapped_files: Label,
+    anonymous: Label,
+    libraries: Label,
+    total: Label,
+}
+
+impl MemoryMapPanel {
+    fn new(current_pid: Rc<RefCell<Option<u32>>>) -> Rc<Self> {
+        let container = GtkBox::new(Orientation::Vertical, 4);
+        container.set_margin_top(4);
+
+        let analyze_btn = gtk4::Button::with_label("Analyze Memory Map");
+        analyze_btn.add_css_class("flat");
+        analyze_btn.set_halign(gtk4::Align::Start);
+        analyze_btn.set_tooltip_text(Some("Summarize /proc/<pid>/maps (virtual sizes)"));
+        container.append(&analyze_btn);
+
+        let grid = Grid::new();
+        grid.set_column_spacing(16);
+        grid.set_row_spacing(2);
+        grid.set_visible(false);
+
+        let make_row = |row: i32, name: &str| {
+            let name_label = Label::new(Some(name));
+            name_label.add_css_class("dim-label");
+            name_label.set_halign(gtk4::Align::Start);
+            grid.attach(&name_label, 0, row, 1, 1);
+
+            let value = Label::new(Some("-"));
+            value.set_halign(gtk4::Align::End);
+            value.add_css_class("monospace");
+            grid.attach(&value, 1, row, 1, 1);
+            value
+        };
+
+        let panel = Rc::new(Self {
+            code: make_row(0, "Code"),
+            heap: make_row(1, "Heap"),
+            stack: make_row(2, "Stack"),
+            mapped_files: make_row(3, "Mapped Files"),
+            anonymous: make_row(4, "Anonymous"),
+            libraries: make_row(5, "Shared Libraries"),
+            total: make_row(6, "Total Virtual"),
+            container,
+            grid,
+        });
+        panel.container.append(&panel.grid);
+
+        let panel_clone = panel.clone();
+        analyze_btn.connect_clicked(move |_| {
+            if let Some(pid) = *current_pid.borrow() {
+                panel_clone.refresh(pid);
+            }
+        });
+
+        panel
+    }
+
+    fn refresh(&self, pid: u32) {
+        self.grid.set_visible(true);
+        match read_memory_map_summary(pid) {
+            Ok(summary) => {
+                self.code.set_label(&format_bytes(summary.code));
+                self.heap.set_label(&format_bytes(summary.heap));
+                self.stack.set_label(&format_bytes(summary.stack));
+                self.mapped_files.set_label(&format_bytes(summary.mapped_files));
+                self.anonymous.set_label(&format_bytes(summary.anonymous));
+                self.libraries.set_label(&format!(
+                    "{} ({})",
+                    summary.shared_libraries,
+                    format_bytes(summary.shared_library_bytes)
+                ));
+                self.total.set_label(&format_bytes(summary.total()));
+            }
+            Err(_) => {
+                // Permission denied for other users' processes
+                for label in [&self.code, &self.heap, &self.stack, &self.mapped_files, &self.anonymous, &self.total] {
+                    label.set_label("-");
+                }
+                self.libraries.set_label("Access denied");
+            }
+        }
+    }
+
+    fn clear(&self) {
+        self.grid.set_visible(false);
+    }
+}
+
+/// Expander listing PSS of the process and its child processes, largest first
+struct TreeMemoryPanel {
+    expander: gtk4::Expander,
+    total_label: Label,
+    rows: GtkBox,
+}
+
+impl TreeMemoryPanel {
+    fn new(current_pid: Rc<RefCell<Option<u32>>>) -> Rc<Self> {
+        let expander = gtk4::Expander::new(None);
+        let expander_title = GtkBox::new(Orientation::Horizontal, 8);
+        expander_title.append(&Label::new(Some("Process Tree Memory (PSS)")));
+        expander_title.append(&help::help_button("pss"));
+        expander.set_label_widget(Some(&expander_title));
+        expander.set_margin_top(4);
+        expander.set_tooltip_text(Some(
+            "Proportional set size of this process and its children; shared pages are split between processes so values add up",
+        ));
+
+        let content = GtkBox::new(Orientation::Vertical, 2);
+        content.set_margin_start(12);
+        content.set_margin_top(4);
+
+        let total_label = Label::new(Some("-"));
+        total_label.set_halign(gtk4::Align::Start);
+        total_label.add_css_class("heading");
+        content.append(&total_label);
+
+        let rows = GtkBox::new(Orientation::Vertical, 2);
+        content.append(&rows);
+        expander.set_child(Some(&content));
+
+        let panel = Rc::new(Self {
+            expander,
+            total_label,
+            rows,
+        });
+
+        let panel_clone = panel.clone();
+        panel.expander.connect_expanded_notify(move |expander| {
+            if expander.is_expanded() {
+                if let Some(pid) = *current_pid.borrow() {
+                    panel_clone.refresh(pid);
+                }
+            }
+        });
+
+        panel
+    }
+
+    fn refresh(&self, pid: u32) {
+        let entries = read_process_tree_pss(pid);
+        let total: u64 = entries.iter().filter_map(|e| e.pss_bytes).sum();
+        let unreadable = entries.iter().filter(|e| e.pss_bytes.is_none()).count();
+
+        let mut total_text = format!("Total: {} across {} processes", format_bytes(total), entries.len());
+        if unreadable > 0 {
+            total_text.push_str(&format!(" ({} not readable)", unreadable));
+        }
+        self.total_label.set_label(&total_text);
+
+        while let Some(child) = self.rows.first_child() {
+            self.rows.remove(&child);
+        }
+        for entry in &entries {
+            let row = GtkBox::new(Orientation::Horizontal, 8);
+
+            let name = Label::new(Some(&format!("{} ({})", entry.name, entry.pid)));
+            name.set_halign(gtk4::Align::Start);
+            name.set_hexpand(true);
+            name.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+            row.append(&name);
+
+            let value = Label::new(Some(&entry.pss_bytes.map(format_bytes).unwrap_or_else(|| "-".to_string())));
+            value.set_halign(gtk4::Align::End);
+            value.add_css_class("monospace");
+            row.append(&value);
+
+            self.rows.append(&row);
+        }
+    }
+}
+
 struct StatsLabels {
     current: Label,
     min: Label,
@@ -478,16 +774,24 @@ impl StatsLabels {
     }
 
     fn update(&self, stats: Option<MetricStats>, is_percentage: bool, is_bytes: bool) {
+        self.set_values(stats, |v| {
+            if is_percentage {
+                format!("{:.1}%", v)
+            } else if is_bytes {
+                format_bytes(v as u64)
+            } else {
+                format!("{:.1}", v)
+            }
+        });
+    }
+
+    /// Show statistics of a bytes-per-second series
+    fn update_rate(&self, stats: Option<MetricStats>) {
+        self.set_values(stats, |v| format_rate(v as u64));
+    }
+
+    fn set_values(&self, stats: Option<MetricStats>, format_val: impl Fn(f64) -> String) {
         if let Some(stats) = stats {
-            let format_val = |v: f64| {
-                if is_percentage {
-                    format!("{:.1}%", v)
-                } else if is_bytes {
-                    format_bytes(v as u64)
-                } else {
-                    format!("{:.1}", v)
-                }
-            };
             self.current.set_label(&format_val(stats.current));
             self.min.set_label(&format_val(stats.min));
             self.max.set_label(&format_val(stats.max));
@@ -523,11 +827,17 @@ impl DetailView {
         info_box.set_margin_bottom(8);
 
         let info_labels = ProcessInfoLabels {
-            command: Self::create_info_row(&info_box, "Command"),
-            threads: Self::create_info_row(&info_box, "Threads"),
-            state: Self::create_info_row(&info_box, "State"),
-            user: Self::create_info_row(&info_box, "User"),
+            command: Self::create_info_row(&info_box, "Command", None),
+            threads: Self::create_info_row(&info_box, "Threads", None),
+            state: Self::create_info_row(&info_box, "State", Some("state")),
+            user: Self::create_info_row(&info_box, "User", None),
+            scheduling: Self::create_info_row(&info_box, "Scheduling", Some("sched")),
+            network_total: Self::create_info_row(&info_box, "Network Total", None),
         };
+        let tracer_row = TracerRow::new();
+        info_box.append(&tracer_row.row);
+        let kernel_wait_row = KernelWaitRow::new();
+        info_box.append(&kernel_wait_row.container);
         container.append(&info_box);
 
         // CPU core display showing thread distribution
@@ -575,14 +885,19 @@ impl DetailView {
         let net_tx_stats = StatsLabels::new();
 
         // Create graph sections
-        let cpu_section = Self::create_graph_section("CPU Usage", &cpu_graph, &cpu_stats);
-        let memory_section = Self::create_graph_section("Memory", &memory_graph, &memory_stats);
-        let gpu_mem_section = Self::create_graph_section("GPU Memory", &gpu_mem_graph, &gpu_mem_stats);
-        let gpu_util_section = Self::create_graph_section("GPU Util", &gpu_util_graph, &gpu_util_stats);
-        let disk_read_section = Self::create_graph_section("Disk Read", &disk_read_graph, &disk_read_stats);
-        let disk_write_section = Self::create_graph_section("Disk Write", &disk_write_graph, &disk_write_stats);
-        let net_rx_section = Self::create_graph_section("Net RX", &net_rx_graph, &net_rx_stats);
-        let net_tx_section = Self::create_graph_section("Net TX", &net_tx_graph, &net_tx_stats);
+        let cpu_section = Self::create_graph_section("CPU Usage", &cpu_graph, &cpu_stats, Some("cpu"));
+        let memory_section = Self::create_graph_section("Memory", &memory_graph, &memory_stats, Some("memory"));
+        let current_pid = Rc::new(RefCell::new(None));
+        let memory_map_panel = MemoryMapPanel::new(current_pid.clone());
+        memory_section.append(&memory_map_panel.container);
+        let tree_memory_panel = TreeMemoryPanel::new(current_pid.clone());
+        memory_section.append(&tree_memory_panel.expander);
+        let gpu_mem_section = Self::create_graph_section("GPU Memory", &gpu_mem_graph, &gpu_mem_stats, Some("gpu-graphs"));
+        let gpu_util_section = Self::create_graph_section("GPU Util", &gpu_util_graph, &gpu_util_stats, Some("gpu-graphs"));
+        let disk_read_section = Self::create_graph_section("Disk Read", &disk_read_graph, &disk_read_stats, None);
+        let disk_write_section = Self::create_graph_section("Disk Write", &disk_write_graph, &disk_write_stats, None);
+        let net_rx_section = Self::create_graph_section("Net RX", &net_rx_graph, &net_rx_stats, None);
+        let net_tx_section = Self::create_graph_section("Net TX", &net_tx_graph, &net_tx_stats, None);
 
         let graph_sections = vec![
             cpu_section,
@@ -609,6 +924,95 @@ impl DetailView {
 
         container.append(&graph_grid);
 
+        // User vs kernel CPU: a large kernel share means many system calls or page faults
+        let split_header = GtkBox::new(Orientation::Horizontal, 8);
+        let split_title = Label::new(Some("User and Kernel CPU"));
+        split_title.add_css_class("heading");
+        split_header.append(&split_title);
+        split_header.append(&help::help_button("cpu-split"));
+        container.append(&split_header);
+        let cpu_split_graph = GraphWidget::new(USER_CPU_COLOR, true, false);
+        cpu_split_graph.set_title_label(&split_title);
+        cpu_split_graph.drawing_area.set_vexpand(false);
+        container.append(&cpu_split_graph.drawing_area);
+        let legend = GtkBox::new(Orientation::Horizontal, 16);
+        let user_cpu_label = Label::new(None);
+        let kernel_cpu_label = Label::new(None);
+        for label in [&user_cpu_label, &kernel_cpu_label] {
+            label.set_use_markup(true);
+            label.add_css_class("numeric");
+            legend.append(label);
+        }
+        container.append(&legend);
+
+        // Page faults: major faults wait for the disk and hint at memory pressure
+        let fault_header = GtkBox::new(Orientation::Horizontal, 8);
+        let fault_title = Label::new(Some("Page Faults"));
+        fault_title.add_css_class("heading");
+        fault_header.append(&fault_title);
+        fault_header.append(&help::help_button("page-faults"));
+        container.append(&fault_header);
+        let minor_fault_graph = GraphWidget::new(MINOR_FAULT_COLOR, false, false);
+        let minor_fault_stats = StatsLabels::new();
+        let major_fault_graph = GraphWidget::new(MAJOR_FAULT_COLOR, false, false);
+        let major_fault_stats = StatsLabels::new();
+        let fault_grid = Grid::new();
+        fault_grid.set_column_spacing(12);
+        fault_grid.set_column_homogeneous(true);
+        fault_grid.attach(
+            &Self::create_graph_section("Minor Faults/s", &minor_fault_graph, &minor_fault_stats, None),
+            0,
+            0,
+            1,
+            1,
+        );
+        fault_grid.attach(
+            &Self::create_graph_section("Major Faults/s", &major_fault_graph, &major_fault_stats, None),
+            1,
+            0,
+            1,
+            1,
+        );
+        container.append(&fault_grid);
+
+        // Context switches: voluntary ones hint at lock contention, involuntary ones at oversubscription
+        let switch_header = GtkBox::new(Orientation::Horizontal, 8);
+        let switch_title = Label::new(Some("Context Switches"));
+        switch_title.add_css_class("heading");
+        switch_header.append(&switch_title);
+        switch_header.append(&help::help_button("context-switches"));
+        container.append(&switch_header);
+        let voluntary_switch_graph = GraphWidget::new(VOLUNTARY_SWITCH_COLOR, false, false);
+        let voluntary_switch_stats = StatsLabels::new();
+        let involuntary_switch_graph = GraphWidget::new(INVOLUNTARY_SWITCH_COLOR, false, false);
+        let involuntary_switch_stats = StatsLabels::new();
+        let switch_grid = Grid::new();
+        switch_grid.set_column_spacing(12);
+        switch_grid.set_column_homogeneous(true);
+        switch_grid.attach(
+            &Self::create_graph_section("Voluntary/s", &voluntary_switch_graph, &voluntary_switch_stats, None),
+            0,
+            0,
+            1,
+            1,
+        );
+        switch_grid.attach(
+            &Self::create_graph_section("Involuntary/s", &involuntary_switch_graph, &involuntary_switch_stats, None),
+            1,
+            0,
+            1,
+            1,
+        );
+        container.append(&switch_grid);
+
+        let frame_rate_section = FrameRateSection::new();
+        container.append(&frame_rate_section.container);
+
+        #[cfg(feature = "perf")]
+        let perf_section = PerfSection::new();
+        #[cfg(feature = "perf")]
+        container.append(&perf_section.container);
+
         // Connect layout dropdown
         let graph_grid_clone = graph_grid.clone();
         let graph_sections_clone: Vec<GtkBox> = graph_sections.iter().map(|s| s.clone()).collect();
@@ -638,8 +1042,14 @@ impl DetailView {
             container,
             title_label,
             info_labels,
+            tracer_row,
+            kernel_wait_row,
             cpu_core_display,
-            current_pid: RefCell::new(None),
+            current_pid,
+            cpu_scale: Cell::new(1.0),
+            sample_interval_secs: Cell::new(2),
+            memory_map_panel,
+            tree_memory_panel,
             graph_grid,
             graph_sections,
             current_layout,
@@ -659,6 +1069,20 @@ impl DetailView {
             disk_write_stats,
             net_rx_stats,
             net_tx_stats,
+            cpu_split_graph,
+            user_cpu_label,
+            kernel_cpu_label,
+            minor_fault_graph,
+            minor_fault_stats,
+            major_fault_graph,
+            major_fault_stats,
+            voluntary_switch_graph,
+            voluntary_switch_stats,
+            involuntary_switch_graph,
+            involuntary_switch_stats,
+            frame_rate_section,
+            #[cfg(feature = "perf")]
+            perf_section,
         }
     }
 
@@ -677,7 +1101,8 @@ impl DetailView {
         }
     }
 
-    fn create_info_row(parent: &GtkBox, label_text: &str) -> Label {
+    /// Label/value row; `help_topic` adds a button explaining the value
+    fn create_info_row(parent: &GtkBox, label_text: &str, help_topic: Option<&str>) -> Label {
         let row = GtkBox::new(Orientation::Horizontal, 8);
         row.set_margin_start(8);
         row.set_margin_end(8);
@@ -697,11 +1122,15 @@ impl DetailView {
         value.set_selectable(true);
         row.append(&value);
 
+        if let Some(topic) = help_topic {
+            row.append(&help::help_button(topic));
+        }
+
         parent.append(&row);
         value
     }
 
-    fn create_graph_section(title: &str, graph: &GraphWidget, stats: &StatsLabels) -> GtkBox {
+    fn create_graph_section(title: &str, graph: &GraphWidget, stats: &StatsLabels, help_topic: Option<&str>) -> GtkBox {
         let section = GtkBox::new(Orientation::Vertical, 4);
         section.set_vexpand(true);
 
@@ -711,6 +1140,17 @@ impl DetailView {
         label.add_css_class("heading");
         label.set_halign(gtk4::Align::Start);
         header.append(&label);
+        graph.set_title_label(&label);
+        if let Some(topic) = help_topic {
+            header.append(&help::help_button(topic));
+        }
+        // Rates and sizes can spike by orders of magnitude; percentages cannot
+        if !graph.is_percentage() {
+            let log_toggle = graph.log_scale_toggle();
+            log_toggle.set_hexpand(true);
+            log_toggle.set_halign(gtk4::Align::End);
+            header.append(&log_toggle);
+        }
         section.append(&header);
 
         // Graph
@@ -733,6 +1173,7 @@ impl DetailView {
 
             label.set_halign(gtk4::Align::End);
             label.set_hexpand(true);
+            label.update_relation(&[Relation::LabelledBy(&[name_label.upcast_ref()])]);
             stat_box.append(label);
 
             parent.append(&stat_box);
@@ -748,10 +1189,113 @@ impl DetailView {
         section
     }
 
-    /// Update the detail view for a process
-    pub fn update(&self, name: &str, pid: u32, history: Option<&ProcessHistory>, process_info: Option<&ProcessDetails>) {
+    /// Show CPU relative to one core (`cpu_scale` = core count) or the whole machine (1.0)
+    pub fn set_cpu_scale(&self, cpu_scale: f32) {
+        self.cpu_scale.set(cpu_scale);
+    }
+
+    /// Seconds between the monitor's history samples
+    pub fn set_sample_interval(&self, secs: u64) {
+        self.sample_interval_secs.set(secs);
+    }
+
+    /// Show the frame rate section for a game running with MangoHud; `can_toggle` when its
+    /// control socket was found
+    pub fn show_frame_rate(&self, status: &str, can_toggle: bool) {
+        let section = &self.frame_rate_section;
+        section.container.set_visible(true);
+        section.status_label.set_label(status);
+        section.logging_button.set_sensitive(can_toggle);
+        if !can_toggle {
+            section
+                .logging_button
+                .set_tooltip_text(Some("Start MangoHud with the control option, or press its logging key (Shift+F2)"));
+        }
+    }
+
+    pub fn hide_frame_rate(&self) {
+        self.frame_rate_section.container.set_visible(false);
+    }
+
+    /// Update the frame rate graphs (average FPS and worst frame time, one value per refresh)
+    pub fn update_frame_rate(&self, fps: &[f64], worst_frame_times: &[f64], sample_interval_secs: u64) {
+        let section = &self.frame_rate_section;
+        let num_samples = fps.len().max(1);
+        section.fps_graph.update(fps, num_samples, sample_interval_secs);
+        section.fps_stats.update(MetricStats::from_data(fps), false, false);
+        section.frame_time_graph.update(worst_frame_times, num_samples, sample_interval_secs);
+        section.frame_time_stats.update(MetricStats::from_data(worst_frame_times), false, false);
+    }
+
+    pub fn connect_toggle_frame_logging<F: Fn() + 'static>(&self, f: F) {
+        self.frame_rate_section.logging_button.connect_clicked(move |_| f());
+    }
+
+    /// Update the syscall rate graph (syscalls per second, one value per sample)
+    #[cfg(feature = "perf")]
+    pub fn update_syscall_rate(&self, rates: &[f64], sample_interval_secs: u64) {
+        let section = &self.perf_section;
+        section.container.set_visible(true);
+        section.syscall_section.set_visible(true);
+        section.syscall_graph.update(rates, rates.len().max(1), sample_interval_secs);
+        section.syscall_stats.update(MetricStats::from_data(rates), false, false);
+    }
+
+    /// Update the hardware counter graphs (one sample per refresh)
+    #[cfg(feature = "perf")]
+    pub fn update_hardware_counters(&self, samples: &[HardwareSample], sample_interval_secs: u64) {
+        let section = &self.perf_section;
+        section.container.set_visible(true);
+        section.hardware_grid.set_visible(true);
+        let num_samples = samples.len().max(1);
+
+        let instructions: Vec<f64> = samples.iter().map(|s| s.instructions_per_sec).collect();
+        section.instructions_graph.update(&instructions, num_samples, sample_interval_secs);
+        section.instructions_stats.update(MetricStats::from_data(&instructions), false, false);
+
+        let cycles: Vec<f64> = samples.iter().map(|s| s.cycles_per_sec).collect();
+        section.cycles_graph.update(&cycles, num_samples, sample_interval_secs);
+        section.cycles_stats.update(MetricStats::from_data(&cycles), false, false);
+
+        let ipc: Vec<f64> = samples.iter().map(|s| s.ipc).collect();
+        section.ipc_graph.update(&ipc, num_samples, sample_interval_secs);
+        section.ipc_stats.update(MetricStats::from_data(&ipc), false, false);
+
+        let cache_misses: Vec<f64> = samples.iter().map(|s| s.cache_misses_per_sec).collect();
+        section.cache_miss_graph.update(&cache_misses, num_samples, sample_interval_secs);
+        section.cache_miss_stats.update(MetricStats::from_data(&cache_misses), false, false);
+    }
+
+    /// Call `f` with the tracer PID when the "Debugged by" jump button is clicked
+    pub fn connect_open_tracer<F: Fn(u32) + 'static>(&self, f: F) {
+        let tracer_pid = self.tracer_row.tracer_pid.clone();
+        self.tracer_row.open_button.connect_clicked(move |_| {
+            if let Some(pid) = tracer_pid.get() {
+                f(pid);
+            }
+        });
+    }
+
+    /// Update the detail view for a process; `gaps` marks samples that follow a suspend
+    pub fn update(
+        &self,
+        name: &str,
+        pid: u32,
+        history: Option<&ProcessHistory>,
+        gaps: &[bool],
+        net_totals: Option<NetTotals>,
+        process_info: Option<&ProcessDetails>,
+    ) {
         self.title_label.set_label(&format!("{} (PID: {})", name, pid));
-        *self.current_pid.borrow_mut() = Some(pid);
+        let previous_pid = self.current_pid.borrow_mut().replace(pid);
+        if previous_pid != Some(pid) {
+            // Stale breakdown from another process
+            self.memory_map_panel.clear();
+            self.tree_memory_panel.expander.set_expanded(false);
+        }
+        if self.tree_memory_panel.expander.is_expanded() {
+            self.tree_memory_panel.refresh(pid);
+        }
 
         // Update process info
         if let Some(info) = process_info {
@@ -760,12 +1304,35 @@ impl DetailView {
             self.info_labels.threads.set_label(&format!("{}", info.thread_count));
             self.info_labels.state.set_label(&info.state);
             self.info_labels.user.set_label(&info.user);
+            self.info_labels.scheduling.set_label(&info.describe_scheduling());
+            if info.is_realtime() {
+                self.info_labels.scheduling.add_css_class("warning");
+            } else {
+                self.info_labels.scheduling.remove_css_class("warning");
+            }
+            self.tracer_row.update(info.tracer_pid);
         } else {
             self.info_labels.command.set_label("-");
             self.info_labels.command.set_tooltip_text(None);
             self.info_labels.threads.set_label("-");
             self.info_labels.state.set_label("-");
             self.info_labels.user.set_label("-");
+            self.info_labels.scheduling.set_label("-");
+            self.info_labels.scheduling.remove_css_class("warning");
+            self.tracer_row.update(None);
+        }
+
+        self.kernel_wait_row.update(pid, process_info, history);
+
+        // TCP traffic accumulated since first seen, even while outside the top list
+        match net_totals {
+            Some(totals) => self.info_labels.network_total.set_label(&format!(
+                "↓ {} received · ↑ {} sent in {}",
+                format_bytes(totals.received),
+                format_bytes(totals.sent),
+                format_duration(totals.tracked_for)
+            )),
+            None => self.info_labels.network_total.set_label("-"),
         }
 
         // Update CPU core display showing thread distribution
@@ -773,10 +1340,31 @@ impl DetailView {
 
         if let Some(history) = history {
             let num_samples = history.cpu_history.len().max(1);
-            let sample_interval = 2; // 2 seconds
+            let sample_interval = self.sample_interval_secs.get();
+            let times: Vec<SystemTime> = history.sample_times.iter().copied().collect();
+
+            for graph in [
+                &self.cpu_graph,
+                &self.memory_graph,
+                &self.gpu_mem_graph,
+                &self.gpu_util_graph,
+                &self.disk_read_graph,
+                &self.disk_write_graph,
+                &self.net_rx_graph,
+                &self.net_tx_graph,
+                &self.cpu_split_graph,
+                &self.minor_fault_graph,
+                &self.major_fault_graph,
+                &self.voluntary_switch_graph,
+                &self.involuntary_switch_graph,
+            ] {
+                graph.set_gaps(gaps);
+                graph.set_times(&times);
+            }
 
             // CPU
-            let cpu_data: Vec<f64> = history.cpu_history.iter().map(|&v| v as f64).collect();
+            let cpu_scale = self.cpu_scale.get() as f64;
+            let cpu_data: Vec<f64> = history.cpu_history.iter().map(|&v| v as f64 * cpu_scale).collect();
             self.cpu_graph.update(&cpu_data, num_samples, sample_interval);
             self.cpu_stats.update(MetricStats::from_data(&cpu_data), true, false);
 
@@ -795,25 +1383,51 @@ impl DetailView {
             self.gpu_util_graph.update(&gpu_util_data, num_samples, sample_interval);
             self.gpu_util_stats.update(MetricStats::from_data(&gpu_util_data), true, false);
 
-            // Disk read
+            // Disk read (bytes per second)
             let disk_read_data: Vec<f64> = history.disk_read_history.iter().map(|&v| v as f64).collect();
             self.disk_read_graph.update(&disk_read_data, num_samples, sample_interval);
-            self.disk_read_stats.update(MetricStats::from_data(&disk_read_data), false, true);
+            self.disk_read_stats.update_rate(MetricStats::from_data(&disk_read_data));
 
             // Disk write
             let disk_write_data: Vec<f64> = history.disk_write_history.iter().map(|&v| v as f64).collect();
             self.disk_write_graph.update(&disk_write_data, num_samples, sample_interval);
-            self.disk_write_stats.update(MetricStats::from_data(&disk_write_data), false, true);
+            self.disk_write_stats.update_rate(MetricStats::from_data(&disk_write_data));
 
-            // Network RX (system-wide)
+            // Network RX (per-process TCP)
             let net_rx_data: Vec<f64> = history.net_rx_history.iter().map(|&v| v as f64).collect();
             self.net_rx_graph.update(&net_rx_data, num_samples, sample_interval);
             self.net_rx_stats.update(MetricStats::from_data(&net_rx_data), false, true);
 
-            // Network TX (system-wide)
+            // Network TX (per-process TCP)
             let net_tx_data: Vec<f64> = history.net_tx_history.iter().map(|&v| v as f64).collect();
             self.net_tx_graph.update(&net_tx_data, num_samples, sample_interval);
             self.net_tx_stats.update(MetricStats::from_data(&net_tx_data), false, true);
+
+            // User time stacked on kernel time, scaled like the CPU graph
+            let user_cpu: Vec<f64> = history.user_cpu_history.iter().map(|&v| v * cpu_scale).collect();
+            let kernel_cpu: Vec<f64> = history.system_cpu_history.iter().map(|&v| v * cpu_scale).collect();
+            self.cpu_split_graph
+                .update_stacked(&kernel_cpu, &user_cpu, KERNEL_CPU_COLOR, num_samples, sample_interval);
+            self.user_cpu_label
+                .set_markup(&legend_markup(USER_CPU_COLOR, "User", user_cpu.last().copied()));
+            self.kernel_cpu_label
+                .set_markup(&legend_markup(KERNEL_CPU_COLOR, "Kernel", kernel_cpu.last().copied()));
+
+            // Page faults per second (process-wide)
+            let minor_faults: Vec<f64> = history.minor_fault_history.iter().copied().collect();
+            self.minor_fault_graph.update(&minor_faults, num_samples, sample_interval);
+            self.minor_fault_stats.update(MetricStats::from_data(&minor_faults), false, false);
+            let major_faults: Vec<f64> = history.major_fault_history.iter().copied().collect();
+            self.major_fault_graph.update(&major_faults, num_samples, sample_interval);
+            self.major_fault_stats.update(MetricStats::from_data(&major_faults), false, false);
+
+            // Context switches per second (all threads)
+            let voluntary: Vec<f64> = history.voluntary_switch_history.iter().copied().collect();
+            self.voluntary_switch_graph.update(&voluntary, num_samples, sample_interval);
+            self.voluntary_switch_stats.update(MetricStats::from_data(&voluntary), false, false);
+            let involuntary: Vec<f64> = history.involuntary_switch_history.iter().copied().collect();
+            self.involuntary_switch_graph.update(&involuntary, num_samples, sample_interval);
+            self.involuntary_switch_stats.update(MetricStats::from_data(&involuntary), false, false);
         } else {
             // No history yet - show empty graphs
             self.cpu_graph.update(&[], 60, 2);
@@ -824,18 +1438,35 @@ impl DetailView {
             self.disk_write_graph.update(&[], 60, 2);
             self.net_rx_graph.update(&[], 60, 2);
             self.net_tx_graph.update(&[], 60, 2);
+            self.cpu_split_graph.update_stacked(&[], &[], KERNEL_CPU_COLOR, 60, 2);
+            self.user_cpu_label.set_markup(&legend_markup(USER_CPU_COLOR, "User", None));
+            self.kernel_cpu_label.set_markup(&legend_markup(KERNEL_CPU_COLOR, "Kernel", None));
+            self.minor_fault_graph.update(&[], 60, 2);
+            self.major_fault_graph.update(&[], 60, 2);
+            self.voluntary_switch_graph.update(&[], 60, 2);
+            self.involuntary_switch_graph.update(&[], 60, 2);
             self.cpu_stats.update(None, true, false);
             self.memory_stats.update(None, false, true);
             self.gpu_mem_stats.update(None, true, false);
             self.gpu_util_stats.update(None, true, false);
-            self.disk_read_stats.update(None, false, true);
-            self.disk_write_stats.update(None, false, true);
+            self.disk_read_stats.update_rate(None);
+            self.disk_write_stats.update_rate(None);
             self.net_rx_stats.update(None, false, true);
             self.net_tx_stats.update(None, false, true);
+            self.minor_fault_stats.update(None, false, false);
+            self.major_fault_stats.update(None, false, false);
+            self.voluntary_switch_stats.update(None, false, false);
+            self.involuntary_switch_stats.update(None, false, false);
         }
     }
 }
 
+/// Legend entry with a swatch in the series color, e.g. "■ User 12.5%"
+fn legend_markup(color: (f64, f64, f64), name: &str, value: Option<f64>) -> String {
+    let value = value.map_or_else(|| "-".to_string(), |v| format!("{:.1}%", v));
+    format!("{} {} {}", color_swatch(color), name, value)
+}
+
 /// Additional process details read from /proc
 #[derive(Debug, Clone)]
 pub struct ProcessDetails {
@@ -843,6 +1474,11 @@ pub struct ProcessDetails {
     pub thread_count: u32,
     pub state: String,
     pub user: String,
+    /// PID of the attached debugger (TracerPid), if any
+    pub tracer_pid: Option<u32>,
+    pub scheduling: Option<Scheduling>,
+    /// Other threads with SCHED_FIFO or SCHED_RR, highest priority first
+    pub realtime_threads: Vec<(u32, Scheduling)>,
 }
 
 impl ProcessDetails {
@@ -863,6 +1499,7 @@ impl ProcessDetails {
         let mut thread_count = 1u32;
         let mut state = "Unknown".to_string();
         let mut uid = 0u32;
+        let mut tracer_pid = None;
 
         for line in status_content.lines() {
             if let Some(threads_str) = line.strip_prefix("Threads:") {
@@ -879,6 +1516,8 @@ impl ProcessDetails {
                     Some('I') => "Idle".to_string(),
                     _ => state_str.trim().to_string(),
                 };
+            } else if let Some(tracer_str) = line.strip_prefix("TracerPid:") {
+                tracer_pid = tracer_str.trim().parse().ok().filter(|&pid: &u32| pid != 0);
             } else if let Some(uid_str) = line.strip_prefix("Uid:") {
                 // Format: real, effective, saved, filesystem - we want real UID
                 if let Some(real_uid) = uid_str.split_whitespace().next() {
@@ -887,6 +1526,10 @@ impl ProcessDetails {
             }
         }
 
+        if cgroup_path(pid).is_some_and(|path| is_cgroup_frozen(&path)) {
+            state.push_str(" (frozen)");
+        }
+
         // Convert UID to username
         let user = uid_to_username(uid);
 
@@ -895,23 +1538,45 @@ impl ProcessDetails {
             thread_count,
             state,
             user,
+            tracer_pid,
+            scheduling: read_scheduling(pid),
+            realtime_threads: realtime_threads(pid).into_iter().filter(|(tid, _)| *tid != pid).collect(),
         })
     }
-}
 
-/// Convert UID to username by reading /etc/passwd
-fn uid_to_username(uid: u32) -> String {
-    if let Ok(content) = std::fs::read_to_string("/etc/passwd") {
-        for line in content.lines() {
-            let parts: Vec<&str> = line.split(':').collect();
-            if parts.len() >= 3 {
-                if let Ok(line_uid) = parts[2].parse::<u32>() {
-                    if line_uid == uid {
-                        return parts[0].to_string();
-                    }
-                }
-            }
+    /// Whether the process or one of its threads has a realtime policy
+    pub fn is_realtime(&self) -> bool {
+        self.scheduling.is_some_and(|s| s.policy.is_realtime()) || !self.realtime_threads.is_empty()
+    }
+
+    /// e.g. "SCHED_OTHER · 2 realtime threads, highest SCHED_FIFO 88 (TID 1234)"
+    pub fn describe_scheduling(&self) -> String {
+        let Some(scheduling) = self.scheduling else {
+            return "-".to_string();
+        };
+        let mut text = scheduling.policy.as_str().to_string();
+        if scheduling.policy.is_realtime() {
+            text.push_str(&format!(", realtime priority {}", scheduling.rt_priority));
+        }
+        if let Some((tid, highest)) = self.realtime_threads.first() {
+            let count = self.realtime_threads.len();
+            text.push_str(&format!(
+                " · {} realtime thread{}, highest {} {} (TID {})",
+                count,
+                if count == 1 { "" } else { "s" },
+                highest.policy.as_str(),
+                highest.rt_priority,
+                tid
+            ));
+        }
+        if self.is_realtime() {
+            text.push_str(" · can starve other processes");
         }
+        text
     }
-    format!("{}", uid)
+}
+
+/// Convert UID to username, falling back to the number
+fn uid_to_username(uid: u32) -> String {
+    user_name(uid).unwrap_or_else(|| uid.to_string())
 }
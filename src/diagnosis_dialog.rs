@@ -0,0 +1,193 @@
+//! "Why is my system slow?" window showing ranked findings from the diagnosis module
+
+use gtk4::prelude::*;
+use gtk4::Button;
+use libadwaita as adw;
+use adw::prelude::*;
+use std::rc::Rc;
+use std::time::Duration;
+
+use procular_core::diagnosis::{diagnose, take_snapshot, Finding, Pressure, Rates, Severity, SystemSnapshot};
+use procular_core::monitor::format_bytes;
+
+use crate::app_state::AppState;
+use crate::process_window;
+
+/// Time between the two snapshots; one refresh, so process rates are current too
+const MEASURE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Show the diagnosis window and start measuring
+pub fn show_diagnosis_dialog(parent: &impl IsA<gtk4::Window>, state: &AppState) {
+    let dialog = adw::Window::builder()
+        .title("Why Is My System Slow?")
+        .transient_for(parent)
+        .default_width(600)
+        .default_height(680)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    let header = adw::HeaderBar::new();
+    let rerun_btn = Button::from_icon_name("view-refresh-symbolic");
+    rerun_btn.set_tooltip_text(Some("Measure Again"));
+    header.pack_start(&rerun_btn);
+    toolbar.add_top_bar(&header);
+    dialog.set_content(Some(&toolbar));
+
+    let run: Rc<dyn Fn()> = Rc::new({
+        let toolbar = toolbar.clone();
+        let rerun_btn = rerun_btn.clone();
+        let dialog = dialog.downgrade();
+        let state = state.clone();
+        move || {
+            rerun_btn.set_sensitive(false);
+            let spinner = gtk4::Spinner::builder().spinning(true).width_request(32).height_request(32).build();
+            let status = adw::StatusPage::builder()
+                .title("Measuring…")
+                .description("Watching CPU, memory, disk and swap activity for a moment")
+                .child(&spinner)
+                .build();
+            toolbar.set_content(Some(&status));
+
+            let before = take_snapshot();
+            let toolbar = toolbar.clone();
+            let rerun_btn = rerun_btn.clone();
+            let dialog = dialog.clone();
+            let state = state.clone();
+            glib::timeout_add_local_once(MEASURE_INTERVAL, move || {
+                let Some(dialog) = dialog.upgrade() else {
+                    return;
+                };
+                let after = take_snapshot();
+                let findings = diagnose(&before, &after, state.monitor.borrow().processes());
+                let page = build_results_page(&dialog, &state, &findings, &before, &after);
+                toolbar.set_content(Some(&page));
+                rerun_btn.set_sensitive(true);
+            });
+        }
+    });
+
+    let run_clone = run.clone();
+    rerun_btn.connect_clicked(move |_| run_clone());
+    run();
+
+    dialog.present();
+}
+
+fn build_results_page(
+    dialog: &adw::Window,
+    state: &AppState,
+    findings: &[Finding],
+    before: &SystemSnapshot,
+    after: &SystemSnapshot,
+) -> adw::PreferencesPage {
+    let page = adw::PreferencesPage::new();
+
+    let diagnosis_group = adw::PreferencesGroup::builder()
+        .title("Diagnosis")
+        .description("Most likely cause first; expand a finding for the processes involved")
+        .build();
+    if findings.is_empty() {
+        let row = adw::ActionRow::builder()
+            .title("No bottleneck found")
+            .subtitle(
+                "CPU, memory and disks have headroom right now. If the slowdown comes and goes, \
+                 measure again while it happens.",
+            )
+            .build();
+        row.add_prefix(&severity_icon(None));
+        diagnosis_group.add(&row);
+    }
+    for (i, finding) in findings.iter().enumerate() {
+        let row = adw::ExpanderRow::builder()
+            .title(&finding.title)
+            .subtitle(&finding.explanation)
+            .expanded(i == 0 && !finding.suspects.is_empty())
+            .enable_expansion(!finding.suspects.is_empty())
+            .build();
+        row.add_prefix(&severity_icon(Some(finding.severity)));
+        for suspect in &finding.suspects {
+            let suspect_row = adw::ActionRow::builder()
+                .title(&suspect.name)
+                .subtitle(format!("PID {} · {}", suspect.pid, suspect.detail))
+                .build();
+            let open_btn = Button::from_icon_name("go-next-symbolic");
+            open_btn.set_tooltip_text(Some("Open in Window"));
+            open_btn.set_valign(gtk4::Align::Center);
+            open_btn.add_css_class("flat");
+            let dialog = dialog.downgrade();
+            let state = state.clone();
+            let (pid, name) = (suspect.pid, suspect.name.clone());
+            open_btn.connect_clicked(move |_| {
+                // Parented to the main window so it outlives this one
+                if let Some(dialog) = dialog.upgrade() {
+                    let parent = dialog.transient_for().unwrap_or_else(|| dialog.upcast());
+                    process_window::open_process_window(&parent, pid, &name, state.clone());
+                }
+            });
+            suspect_row.add_suffix(&open_btn);
+            suspect_row.set_activatable_widget(Some(&open_btn));
+            row.add_row(&suspect_row);
+        }
+        diagnosis_group.add(&row);
+    }
+    page.add(&diagnosis_group);
+
+    let rates = Rates::between(before, after);
+    let measurements = adw::PreferencesGroup::builder()
+        .title("Measurements")
+        .description("Pressure is the share of the last 10 seconds tasks spent stalled")
+        .build();
+    let add = |title: &str, value: String| {
+        let row = adw::ActionRow::builder().title(title).subtitle(value).build();
+        row.add_css_class("property");
+        measurements.add(&row);
+    };
+    add("CPU Busy", format!("{:.0}%", rates.cpu_busy_percent));
+    add("CPU Pressure", format_pressure(after.cpu_pressure));
+    add(
+        "Memory Available",
+        format!("{} of {}", format_bytes(after.mem_available), format_bytes(after.mem_total)),
+    );
+    add("Memory Pressure", format_pressure(after.memory_pressure));
+    if after.swap_total > 0 {
+        add(
+            "Swap",
+            format!(
+                "{} used · {:.0} pages/s in, {:.0} pages/s out",
+                format_bytes(after.swap_total.saturating_sub(after.swap_free)),
+                rates.swap_in_per_sec,
+                rates.swap_out_per_sec
+            ),
+        );
+    }
+    add("I/O Wait", format!("{:.0}% of CPU time", rates.iowait_percent));
+    add("I/O Pressure", format_pressure(after.io_pressure));
+    if let Some(ratio) = after.frequency_ratio {
+        add("Clock Speed", format!("{:.0}% of maximum on average", ratio * 100.0));
+    }
+    if let Some(events) = rates.throttle_events {
+        add("Thermal Throttling", format!("{} events while measuring", events));
+    }
+    page.add(&measurements);
+
+    page
+}
+
+fn format_pressure(pressure: Option<Pressure>) -> String {
+    match pressure {
+        Some(p) => format!("{:.1}% some, {:.1}% full", p.some, p.full),
+        None => "Not available (kernel without PSI)".to_string(),
+    }
+}
+
+fn severity_icon(severity: Option<Severity>) -> gtk4::Image {
+    let (icon, css_class) = match severity {
+        Some(Severity::Critical) => ("dialog-error-symbolic", "error"),
+        Some(Severity::Warning) => ("dialog-warning-symbolic", "warning"),
+        Some(Severity::Info) => ("dialog-information-symbolic", "accent"),
+        None => ("emblem-ok-symbolic", "success"),
+    };
+    let image = gtk4::Image::from_icon_name(icon);
+    image.add_css_class(css_class);
+    image
+}
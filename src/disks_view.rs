@@ -0,0 +1,192 @@
+//! Disks page of the main window: throughput, operations, utilization and latency of each
+//! block device, complementing the per-process disk columns
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Grid, Label, Orientation, ScrolledWindow};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use procular_core::disks::DiskActivity;
+use procular_core::monitor::{format_rate, SystemMonitor};
+
+use crate::graph::{GraphSeries, GraphWidget};
+
+const DISK_GRAPH_HEIGHT: i32 = 80;
+
+const READ_COLOR: (f64, f64, f64) = (0.180, 0.545, 0.341); // Green, same as disk read graphs
+const WRITE_COLOR: (f64, f64, f64) = (0.902, 0.494, 0.133); // Orange, same as disk write graphs
+const UTILIZATION_COLOR: (f64, f64, f64) = (0.204, 0.396, 0.643); // Blue
+const LATENCY_COLOR: (f64, f64, f64) = (0.459, 0.314, 0.482); // Purple
+
+/// Graphs and current values of one disk
+struct DiskRow {
+    name: String,
+    summary: Label,
+    throughput: GraphWidget,
+    operations: GraphWidget,
+    utilization: GraphWidget,
+    latency: GraphWidget,
+}
+
+/// Read and write series of one metric
+fn read_write_series(read: &VecDeque<f64>, write: &VecDeque<f64>) -> Vec<GraphSeries> {
+    vec![
+        GraphSeries { name: "Read".to_string(), values: read.iter().copied().collect(), color: READ_COLOR },
+        GraphSeries { name: "Write".to_string(), values: write.iter().copied().collect(), color: WRITE_COLOR },
+    ]
+}
+
+/// Per-device activity from /proc/diskstats
+pub struct DisksView {
+    pub widget: ScrolledWindow,
+    content: GtkBox,
+    rows: RefCell<Vec<DiskRow>>,
+}
+
+impl DisksView {
+    pub fn new() -> Self {
+        let content = GtkBox::new(Orientation::Vertical, 12);
+        content.set_margin_top(12);
+        content.set_margin_bottom(12);
+        content.set_margin_start(12);
+        content.set_margin_end(12);
+
+        let widget = ScrolledWindow::new();
+        widget.set_child(Some(&content));
+
+        Self {
+            widget,
+            content,
+            rows: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Show the latest activity; the page is rebuilt only when disks appear or disappear
+    pub fn update(&self, monitor: &SystemMonitor, sample_interval_secs: u64) {
+        let disks = monitor.disks();
+        let unchanged = {
+            let rows = self.rows.borrow();
+            rows.len() == disks.len() && rows.iter().zip(disks).all(|(row, disk)| row.name == disk.name)
+        };
+        if !unchanged {
+            self.rebuild(disks);
+        }
+
+        let gaps: Vec<bool> = monitor.sample_gaps().iter().copied().collect();
+        let times: Vec<SystemTime> = monitor.sample_times().iter().copied().collect();
+        let num_samples = monitor.max_samples();
+        for (row, disk) in self.rows.borrow().iter().zip(disks) {
+            row.summary.set_text(&format!(
+                "Read {} · Write {} · {:.0} IOPS · {:.0}% busy · {:.1} ms",
+                format_rate(disk.read_rate as u64),
+                format_rate(disk.write_rate as u64),
+                disk.read_iops + disk.write_iops,
+                disk.utilization,
+                disk.latency_ms
+            ));
+            let Some(history) = monitor.disk_history(&disk.name) else {
+                continue;
+            };
+            for graph in [&row.throughput, &row.operations, &row.utilization, &row.latency] {
+                graph.set_gaps(&gaps);
+                graph.set_times(&times);
+            }
+            row.throughput.update_series(
+                read_write_series(&history.read_rate, &history.write_rate),
+                num_samples,
+                sample_interval_secs,
+            );
+            row.operations.update_series(
+                read_write_series(&history.read_iops, &history.write_iops),
+                num_samples,
+                sample_interval_secs,
+            );
+            let utilization: Vec<f64> = history.utilization.iter().copied().collect();
+            row.utilization.update(&utilization, num_samples, sample_interval_secs);
+            let latency: Vec<f64> = history.latency_ms.iter().copied().collect();
+            row.latency.update(&latency, num_samples, sample_interval_secs);
+        }
+    }
+
+    fn rebuild(&self, disks: &[DiskActivity]) {
+        while let Some(child) = self.content.first_child() {
+            self.content.remove(&child);
+        }
+        let mut rows = self.rows.borrow_mut();
+        rows.clear();
+
+        if disks.is_empty() {
+            let placeholder = Label::new(Some("No disks with activity found in /proc/diskstats."));
+            placeholder.add_css_class("dim-label");
+            placeholder.set_wrap(true);
+            placeholder.set_margin_top(24);
+            self.content.append(&placeholder);
+            return;
+        }
+
+        for disk in disks {
+            rows.push(self.create_row(disk));
+        }
+    }
+
+    fn create_row(&self, disk: &DiskActivity) -> DiskRow {
+        let card = GtkBox::new(Orientation::Vertical, 8);
+        card.add_css_class("card");
+
+        let header = GtkBox::new(Orientation::Horizontal, 8);
+        header.set_margin_top(8);
+        header.set_margin_start(12);
+        header.set_margin_end(12);
+        let title = Label::new(Some(&disk.name));
+        title.add_css_class("heading");
+        header.append(&title);
+        let description = Label::new(disk.description.as_deref());
+        description.add_css_class("dim-label");
+        description.add_css_class("caption");
+        description.set_halign(gtk4::Align::Start);
+        description.set_hexpand(true);
+        description.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        header.append(&description);
+        let summary = Label::new(None);
+        summary.add_css_class("numeric");
+        header.append(&summary);
+        card.append(&header);
+
+        let grid = Grid::new();
+        grid.set_column_spacing(12);
+        grid.set_row_spacing(8);
+        grid.set_column_homogeneous(true);
+        grid.set_margin_start(12);
+        grid.set_margin_end(12);
+        grid.set_margin_bottom(12);
+        let graph = |column: i32, row: i32, heading: &str, graph: GraphWidget| {
+            let section = GtkBox::new(Orientation::Vertical, 4);
+            let label = Label::new(Some(heading));
+            label.add_css_class("caption-heading");
+            label.set_halign(gtk4::Align::Start);
+            section.append(&label);
+            graph.set_title_label(&label);
+            graph.drawing_area.set_size_request(-1, DISK_GRAPH_HEIGHT);
+            graph.drawing_area.set_vexpand(false);
+            section.append(&graph.drawing_area);
+            grid.attach(&section, column, row, 1, 1);
+            graph
+        };
+        let throughput = graph(0, 0, "Throughput", GraphWidget::new(READ_COLOR, false, true));
+        let operations = graph(1, 0, "Operations per Second", GraphWidget::new(READ_COLOR, false, false));
+        let utilization = graph(0, 1, "Utilization", GraphWidget::new(UTILIZATION_COLOR, true, false));
+        let latency = graph(1, 1, "Latency (ms)", GraphWidget::new(LATENCY_COLOR, false, false));
+        card.append(&grid);
+        self.content.append(&card);
+
+        DiskRow {
+            name: disk.name.clone(),
+            summary,
+            throughput,
+            operations,
+            utilization,
+            latency,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! "Environment" tab of the process window
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Label, Orientation, SearchEntry};
+use std::rc::Rc;
+
+use procular_core::environment::read_environment;
+
+use crate::table_view::{ColumnKind, TableView};
+
+pub struct EnvironmentView {
+    pub widget: GtkBox,
+    status_label: Label,
+    table: Rc<TableView>,
+}
+
+impl EnvironmentView {
+    pub fn new() -> Self {
+        let widget = GtkBox::new(Orientation::Vertical, 8);
+        widget.set_margin_top(12);
+        widget.set_margin_bottom(12);
+        widget.set_margin_start(12);
+        widget.set_margin_end(12);
+
+        // Search and copy controls
+        let toolbar = GtkBox::new(Orientation::Horizontal, 8);
+        let search_entry = SearchEntry::new();
+        search_entry.set_placeholder_text(Some("Search variables..."));
+        search_entry.set_hexpand(true);
+        toolbar.append(&search_entry);
+
+        let copy_btn = Button::with_label("Copy");
+        copy_btn.set_tooltip_text(Some("Copy the selected variable as NAME=value"));
+        toolbar.append(&copy_btn);
+
+        let copy_all_btn = Button::with_label("Copy All");
+        copy_all_btn.set_tooltip_text(Some("Copy all shown variables, one NAME=value per line"));
+        toolbar.append(&copy_all_btn);
+        widget.append(&toolbar);
+
+        let status_label = Label::new(None);
+        status_label.set_halign(gtk4::Align::Start);
+        status_label.set_wrap(true);
+        widget.append(&status_label);
+
+        let table = Rc::new(TableView::new(&[
+            ("Name", ColumnKind::Short),
+            ("Value", ColumnKind::Text),
+        ]));
+        widget.append(&table.widget);
+
+        let table_clone = table.clone();
+        search_entry.connect_search_changed(move |entry| {
+            table_clone.set_filter(&entry.text());
+        });
+
+        let table_clone = table.clone();
+        copy_btn.connect_clicked(move |btn| {
+            if let Some(row) = table_clone.selected_row() {
+                btn.clipboard().set_text(&row.join("="));
+            }
+        });
+
+        let table_clone = table.clone();
+        copy_all_btn.connect_clicked(move |btn| {
+            let text: Vec<String> = table_clone
+                .visible_rows()
+                .iter()
+                .map(|row| row.join("="))
+                .collect();
+            btn.clipboard().set_text(&text.join("\n"));
+        });
+
+        Self {
+            widget,
+            status_label,
+            table,
+        }
+    }
+
+    /// Re-read the process environment
+    pub fn refresh(&self, pid: u32) {
+        match read_environment(pid) {
+            Ok(vars) => {
+                self.status_label.set_text(&format!(
+                    "{} variables (as of process start)",
+                    vars.len()
+                ));
+                self.table
+                    .set_rows(vars.into_iter().map(|(name, value)| vec![name, value]).collect());
+            }
+            Err(e) => {
+                self.status_label
+                    .set_text(&format!("Cannot read environment: {}", e));
+                self.table.set_rows(Vec::new());
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Mounted filesystem usage with the processes writing to each mount
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Label, LevelBar, ListBox, Orientation, ScrolledWindow};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use procular_core::filesystems::{read_filesystems, writers_on_mount, Filesystem, MountWriter};
+use procular_core::monitor::{format_bytes, format_rate, SystemMonitor};
+
+/// Show the filesystems window; `expand_mount` opens that mount's writer list
+pub fn show_filesystems_dialog(
+    parent: &impl IsA<gtk4::Window>,
+    monitor: Rc<RefCell<SystemMonitor>>,
+    threshold_percent: u32,
+    expand_mount: Option<&str>,
+) {
+    let dialog = adw::Window::builder()
+        .title("Filesystems")
+        .transient_for(parent)
+        .default_width(600)
+        .default_height(480)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+    let refresh_btn = Button::from_icon_name("view-refresh-symbolic");
+    refresh_btn.set_tooltip_text(Some("Refresh"));
+    header.pack_end(&refresh_btn);
+    main_box.append(&header);
+
+    let content = GtkBox::new(Orientation::Vertical, 8);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+    content.set_margin_start(12);
+    content.set_margin_end(12);
+
+    let list = ListBox::new();
+    list.add_css_class("boxed-list");
+    list.set_selection_mode(gtk4::SelectionMode::None);
+    let placeholder = Label::new(Some("No filesystems found"));
+    placeholder.add_css_class("dim-label");
+    placeholder.set_margin_top(24);
+    placeholder.set_margin_bottom(24);
+    list.set_placeholder(Some(&placeholder));
+
+    let scrolled = ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_child(Some(&list));
+    content.append(&scrolled);
+
+    let note = Label::new(Some(
+        "Writers are monitored processes that wrote to disk during the last refresh \
+         and have files on the mount open for writing.",
+    ));
+    note.add_css_class("dim-label");
+    note.set_halign(gtk4::Align::Start);
+    note.set_wrap(true);
+    content.append(&note);
+
+    main_box.append(&content);
+    dialog.set_content(Some(&main_box));
+
+    let expand_mount = expand_mount.map(str::to_string);
+    let populate = move || {
+        let filesystems = read_filesystems();
+        let processes = monitor.borrow().processes().to_vec();
+
+        // Keep rows the user expanded open across refreshes
+        let mut expanded: Vec<String> = expand_mount.iter().cloned().collect();
+        while let Some(row) = list.first_child() {
+            if let Some(row) = row.downcast_ref::<adw::ExpanderRow>() {
+                if row.is_expanded() {
+                    expanded.push(row.widget_name().to_string());
+                }
+            }
+            list.remove(&row);
+        }
+
+        for fs in &filesystems {
+            let writers = writers_on_mount(&filesystems, &fs.mount_point, &processes);
+            let row = create_filesystem_row(fs, &writers, threshold_percent);
+            row.set_expanded(expanded.contains(&fs.mount_point));
+            list.append(&row);
+        }
+    };
+    populate();
+    refresh_btn.connect_clicked(move |_| populate());
+
+    dialog.present();
+}
+
+/// Expander row with usage bar; expanding lists the processes writing to the mount
+fn create_filesystem_row(fs: &Filesystem, writers: &[MountWriter], threshold_percent: u32) -> adw::ExpanderRow {
+    let used_percent = fs.used_percent();
+    let row = adw::ExpanderRow::builder()
+        .title(&fs.mount_point)
+        .subtitle(format!(
+            "{} · {} · {} of {} used ({:.0}%)",
+            fs.device,
+            fs.fs_type,
+            format_bytes(fs.used_bytes),
+            format_bytes(fs.used_bytes + fs.available_bytes),
+            used_percent
+        ))
+        .build();
+    // Identifies the mount when restoring expanded rows
+    row.set_widget_name(&fs.mount_point);
+
+    let level = LevelBar::for_interval(0.0, 100.0);
+    level.set_value(used_percent);
+    level.set_width_request(120);
+    level.set_valign(gtk4::Align::Center);
+    // The default offsets color low levels as bad, which is backwards for disk usage
+    for offset in [gtk4::LEVEL_BAR_OFFSET_LOW, gtk4::LEVEL_BAR_OFFSET_HIGH, gtk4::LEVEL_BAR_OFFSET_FULL] {
+        level.remove_offset_value(Some(offset));
+    }
+    row.add_suffix(&level);
+
+    if used_percent >= threshold_percent as f64 {
+        row.add_css_class("error");
+    }
+
+    if writers.is_empty() {
+        let empty = adw::ActionRow::builder()
+            .title("No monitored process is writing here")
+            .build();
+        empty.add_css_class("dim-label");
+        row.add_row(&empty);
+    }
+    for writer in writers {
+        let writer_row = adw::ActionRow::builder()
+            .title(format!("{} (PID {})", writer.name, writer.pid))
+            .subtitle(writer.files.join("\n"))
+            .subtitle_lines(3)
+            .build();
+        let rate = Label::new(Some(&format_rate(writer.write_rate)));
+        rate.add_css_class("numeric");
+        writer_row.add_suffix(&rate);
+        row.add_row(&writer_row);
+    }
+
+    row
+}
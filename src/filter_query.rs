@@ -0,0 +1,384 @@
+//! Search box queries: plain words plus field conditions such as `cpu>50`, `mem>1g`,
+//! `user:alice` or `state:zombie`, combined with AND, OR, NOT and parentheses
+//!
+//! Words next to each other must all match (an implied AND). Keywords are only
+//! recognized in capitals so that searching for a process called "or" still works.
+
+use procular_core::process_state::ProcessState;
+use procular_core::users::uid_for_name;
+
+use crate::process_list::ProcessObject;
+
+/// Numeric column a condition compares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    /// Percent as shown in the list (per core in per-core mode)
+    Cpu,
+    Memory,
+    /// Read plus write rate in bytes per second
+    Disk,
+    /// Bytes received plus sent since the previous refresh
+    Network,
+    Gpu,
+    Pid,
+    Nice,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "cpu" => Some(Field::Cpu),
+            "mem" | "memory" => Some(Field::Memory),
+            "disk" | "io" => Some(Field::Disk),
+            "net" | "network" => Some(Field::Network),
+            "gpu" => Some(Field::Gpu),
+            "pid" => Some(Field::Pid),
+            "nice" => Some(Field::Nice),
+            _ => None,
+        }
+    }
+
+    /// Value of the field for a process; None when unknown (GPU use without a GPU, nice not read)
+    fn value(&self, proc: &ProcessObject) -> Option<f64> {
+        match self {
+            Field::Cpu => Some(proc.cpu_percent() as f64),
+            Field::Memory => Some(proc.memory_bytes() as f64),
+            Field::Disk => Some((proc.disk_read_rate() + proc.disk_write_rate()) as f64),
+            Field::Network => Some((proc.net_rx_bytes() + proc.net_tx_bytes()) as f64),
+            Field::Gpu => Some(proc.gpu_percent() as f64).filter(|gpu| *gpu >= 0.0),
+            Field::Pid => Some(proc.pid() as f64),
+            Field::Nice => proc.nice().map(|nice| nice as f64),
+        }
+    }
+
+    /// Sizes take binary suffixes (k, m, g, t), percentages an optional "%"
+    fn parse_value(&self, text: &str) -> Option<f64> {
+        let text = text.to_lowercase();
+        match self {
+            Field::Memory | Field::Disk | Field::Network => parse_size(&text),
+            Field::Cpu | Field::Gpu => text.trim_end_matches('%').parse().ok(),
+            Field::Pid | Field::Nice => text.parse().ok(),
+        }
+    }
+}
+
+/// "512m", "1.5g" or a plain number of bytes
+fn parse_size(text: &str) -> Option<f64> {
+    let text = text.trim_end_matches("ib").trim_end_matches('b');
+    let (number, factor) = match text.char_indices().last()? {
+        (i, 'k') => (&text[..i], 1024.0),
+        (i, 'm') => (&text[..i], 1024.0 * 1024.0),
+        (i, 'g') => (&text[..i], 1024.0 * 1024.0 * 1024.0),
+        (i, 't') => (&text[..i], 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        _ => (text, 1.0),
+    };
+    number.parse::<f64>().ok().map(|n| n * factor)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    fn holds(&self, a: f64, b: f64) -> bool {
+        match self {
+            Comparison::Less => a < b,
+            Comparison::LessOrEqual => a <= b,
+            Comparison::Greater => a > b,
+            Comparison::GreaterOrEqual => a >= b,
+            Comparison::Equal => a == b,
+            Comparison::NotEqual => a != b,
+        }
+    }
+}
+
+/// Operators in the order they are looked for, so ">=" is not read as ">"
+const COMPARISONS: [(&str, Comparison); 7] = [
+    (">=", Comparison::GreaterOrEqual),
+    ("<=", Comparison::LessOrEqual),
+    ("!=", Comparison::NotEqual),
+    (">", Comparison::Greater),
+    ("<", Comparison::Less),
+    ("=", Comparison::Equal),
+    (":", Comparison::Equal),
+];
+
+/// A parsed search
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    /// Lowercased text matched like the plain search (name, PID and the chosen search scope)
+    Text(String),
+    Compare(Field, Comparison, f64),
+    User(u32),
+    State(ProcessState),
+    Not(Box<Query>),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+}
+
+impl Query {
+    /// Parse a search; Ok(None) for an empty one
+    pub fn parse(text: &str) -> Result<Option<Query>, String> {
+        let tokens = tokenize(text)?;
+        if tokens.is_empty() {
+            return Ok(None);
+        }
+        let mut parser = Parser { tokens, position: 0 };
+        let query = parser.or()?;
+        match parser.peek() {
+            None => Ok(Some(query)),
+            Some(Token::Close) => Err("Unmatched \")\"".to_string()),
+            Some(token) => Err(format!("Unexpected {}", token.describe())),
+        }
+    }
+
+    /// Whether a process matches; `matches_text` handles plain words
+    pub fn matches(&self, proc: &ProcessObject, matches_text: &dyn Fn(&str) -> bool) -> bool {
+        match self {
+            Query::Text(text) => matches_text(text),
+            Query::Compare(field, comparison, value) => {
+                field.value(proc).is_some_and(|actual| comparison.holds(actual, *value))
+            }
+            Query::User(uid) => proc.uid() == Some(*uid),
+            Query::State(state) => proc.state() == *state,
+            Query::Not(query) => !query.matches(proc, matches_text),
+            Query::And(queries) => queries.iter().all(|query| query.matches(proc, matches_text)),
+            Query::Or(queries) => queries.iter().any(|query| query.matches(proc, matches_text)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Word(String),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Open => "\"(\"".to_string(),
+            Token::Close => "\")\"".to_string(),
+            Token::And => "AND".to_string(),
+            Token::Or => "OR".to_string(),
+            Token::Not => "NOT".to_string(),
+            Token::Word(word) => format!("\"{}\"", word),
+        }
+    }
+}
+
+/// Split into words, parentheses and keywords; double quotes keep spaces in a word
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if c == '(' || c == ')' {
+            chars.next();
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            continue;
+        }
+        let mut word = String::new();
+        let mut quoted = false;
+        while let Some(&c) = chars.peek() {
+            if c == '"' {
+                quoted = !quoted;
+            } else if !quoted && (c.is_whitespace() || c == '(' || c == ')') {
+                break;
+            } else {
+                word.push(c);
+            }
+            chars.next();
+        }
+        if quoted {
+            return Err("Unmatched quote".to_string());
+        }
+        match word.as_str() {
+            "AND" | "&&" => tokens.push(Token::And),
+            "OR" | "||" => tokens.push(Token::Or),
+            "NOT" => tokens.push(Token::Not),
+            _ => match word.strip_prefix('!') {
+                Some(rest) if !rest.is_empty() => {
+                    tokens.push(Token::Not);
+                    tokens.push(Token::Word(rest.to_string()));
+                }
+                _ => tokens.push(Token::Word(word)),
+            },
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Query, String> {
+        let mut terms = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            terms.push(self.and()?);
+        }
+        Ok(if terms.len() == 1 { terms.remove(0) } else { Query::Or(terms) })
+    }
+
+    fn and(&mut self) -> Result<Query, String> {
+        let mut terms = vec![self.unary()?];
+        loop {
+            match self.peek() {
+                None | Some(Token::Or) | Some(Token::Close) => break,
+                Some(Token::And) => {
+                    self.next();
+                }
+                _ => {}
+            }
+            terms.push(self.unary()?);
+        }
+        Ok(if terms.len() == 1 { terms.remove(0) } else { Query::And(terms) })
+    }
+
+    fn unary(&mut self) -> Result<Query, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Query::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let query = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(query),
+                    _ => Err("Missing \")\"".to_string()),
+                }
+            }
+            Some(Token::Word(word)) => condition(&word),
+            Some(token) => Err(format!("Unexpected {}", token.describe())),
+            None => Err("Incomplete search".to_string()),
+        }
+    }
+}
+
+/// A field condition, or plain text when the word does not start with a known field
+fn condition(word: &str) -> Result<Query, String> {
+    let Some((position, operator, comparison)) = COMPARISONS
+        .iter()
+        .filter_map(|(operator, comparison)| Some((word.find(operator)?, *operator, *comparison)))
+        .min_by_key(|(position, operator, _)| (*position, std::cmp::Reverse(operator.len())))
+    else {
+        return Ok(Query::Text(word.to_lowercase()));
+    };
+    let name = word[..position].to_lowercase();
+    let value = &word[position + operator.len()..];
+    let negate = |query: Query| match comparison {
+        Comparison::NotEqual => Ok(Query::Not(Box::new(query))),
+        Comparison::Equal => Ok(query),
+        _ => Err(format!("\"{}\" only supports \":\", \"=\" and \"!=\"", name)),
+    };
+
+    match name.as_str() {
+        "user" => {
+            let uid = value
+                .parse()
+                .ok()
+                .or_else(|| uid_for_name(value))
+                .ok_or_else(|| format!("Unknown user \"{}\"", value))?;
+            negate(Query::User(uid))
+        }
+        "state" => {
+            let value = value.to_lowercase();
+            let state = ProcessState::all()
+                .iter()
+                .copied()
+                .find(|state| {
+                    value == state.key()
+                        || value == state.as_str().to_lowercase()
+                        || value == state.letter().to_ascii_lowercase().to_string()
+                })
+                .ok_or_else(|| format!("Unknown state \"{}\"", value))?;
+            negate(Query::State(state))
+        }
+        _ => match Field::from_name(&name) {
+            Some(field) => {
+                let value = field
+                    .parse_value(value)
+                    .ok_or_else(|| format!("\"{}\" is not a valid value for {}", value, name))?;
+                Ok(Query::Compare(field, comparison, value))
+            }
+            None => Ok(Query::Text(word.to_lowercase())),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Query {
+        Query::parse(text).unwrap().unwrap()
+    }
+
+    fn text(word: &str) -> Query {
+        Query::Text(word.to_string())
+    }
+
+    #[test]
+    fn parses_field_conditions() {
+        assert_eq!(parse("cpu>50"), Query::Compare(Field::Cpu, Comparison::Greater, 50.0));
+        assert_eq!(
+            parse("mem>1.5g"),
+            Query::Compare(Field::Memory, Comparison::Greater, 1.5 * 1024.0 * 1024.0 * 1024.0)
+        );
+        assert_eq!(parse("user:0"), Query::User(0));
+        assert_eq!(parse("state:z"), Query::State(ProcessState::Zombie));
+    }
+
+    #[test]
+    fn parses_keywords_and_grouping() {
+        assert_eq!(
+            parse("NOT (a OR b) c"),
+            Query::And(vec![Query::Not(Box::new(Query::Or(vec![text("a"), text("b")]))), text("c")])
+        );
+        assert_eq!(parse("a or b"), Query::And(vec![text("a"), text("or"), text("b")]));
+        assert_eq!(Query::parse("  "), Ok(None));
+    }
+
+    #[test]
+    fn reports_malformed_searches() {
+        assert_eq!(Query::parse("(a"), Err("Missing \")\"".to_string()));
+        assert_eq!(Query::parse("a)"), Err("Unmatched \")\"".to_string()));
+        assert_eq!(Query::parse("\"a b"), Err("Unmatched quote".to_string()));
+        assert_eq!(Query::parse("a AND"), Err("Incomplete search".to_string()));
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_size("512"), Some(512.0));
+        assert_eq!(parse_size("512m"), Some(512.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_size("1.5g"), Some(1.5 * 1024.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_size("2kib"), Some(2048.0));
+        assert_eq!(parse_size("2kb"), Some(2048.0));
+        assert_eq!(parse_size("lots"), None);
+        assert_eq!(parse_size(""), None);
+    }
+}
@@ -0,0 +1,220 @@
+//! GPU page: video memory per GPU and a "free VRAM" list of the processes holding it
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Label, LevelBar, ListBox, Orientation, ScrolledWindow};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::cell::RefCell;
+
+use procular_core::monitor::{format_bytes, GpuMemory, GpuProcess};
+use procular_core::process_actions::{is_stopped, Signal};
+
+use crate::app_state::AppState;
+use crate::audit_log::send_signal_audited;
+use crate::context_menu::send_signal_confirmed;
+
+/// Usage label and bar for one GPU
+struct GpuMeter {
+    label: Label,
+    bar: LevelBar,
+}
+
+/// Video memory overview with one-click terminate/suspend for the processes using it
+pub struct GpuView {
+    pub widget: ScrolledWindow,
+    meters_box: GtkBox,
+    meters: RefCell<Vec<GpuMeter>>,
+    list: ListBox,
+    /// Rows currently shown, with whether each process was stopped; the list is only rebuilt on change
+    shown: RefCell<Vec<(GpuProcess, bool)>>,
+    state: AppState,
+}
+
+impl GpuView {
+    pub fn new(state: &AppState) -> Self {
+        let content = GtkBox::new(Orientation::Vertical, 12);
+        content.set_margin_top(12);
+        content.set_margin_bottom(12);
+        content.set_margin_start(12);
+        content.set_margin_end(12);
+
+        let meters_box = GtkBox::new(Orientation::Vertical, 12);
+        content.append(&meters_box);
+
+        let heading = Label::new(Some("Processes by Video Memory"));
+        heading.add_css_class("heading");
+        heading.set_halign(gtk4::Align::Start);
+        heading.set_margin_top(12);
+        content.append(&heading);
+
+        let list = ListBox::new();
+        list.add_css_class("boxed-list");
+        list.set_selection_mode(gtk4::SelectionMode::None);
+        let placeholder = Label::new(Some("No processes are using video memory"));
+        placeholder.add_css_class("dim-label");
+        placeholder.set_margin_top(24);
+        placeholder.set_margin_bottom(24);
+        list.set_placeholder(Some(&placeholder));
+        content.append(&list);
+
+        let note = Label::new(Some(
+            "Suspending pauses a process but keeps its video memory allocated; \
+             end it to free the memory.",
+        ));
+        note.add_css_class("dim-label");
+        note.set_halign(gtk4::Align::Start);
+        note.set_wrap(true);
+        content.append(&note);
+
+        let widget = ScrolledWindow::new();
+        widget.set_hscrollbar_policy(gtk4::PolicyType::Never);
+        widget.set_child(Some(&content));
+
+        Self {
+            widget,
+            meters_box,
+            meters: RefCell::new(Vec::new()),
+            list,
+            shown: RefCell::new(Vec::new()),
+            state: state.clone(),
+        }
+    }
+
+    /// Update the meters and the process list from the latest NVML readings
+    pub fn update(&self, gpus: &[GpuMemory], processes: &[GpuProcess]) {
+        let mut meters = self.meters.borrow_mut();
+        if meters.len() != gpus.len() {
+            while let Some(child) = self.meters_box.first_child() {
+                self.meters_box.remove(&child);
+            }
+            meters.clear();
+            for _ in gpus {
+                let label = Label::new(None);
+                label.set_halign(gtk4::Align::Start);
+                let bar = LevelBar::for_interval(0.0, 1.0);
+                // Turn the bar red well before allocations start failing
+                bar.add_offset_value(gtk4::LEVEL_BAR_OFFSET_HIGH, 0.9);
+                let meter_box = GtkBox::new(Orientation::Vertical, 6);
+                meter_box.append(&label);
+                meter_box.append(&bar);
+                self.meters_box.append(&meter_box);
+                meters.push(GpuMeter { label, bar });
+            }
+        }
+        for (meter, gpu) in meters.iter().zip(gpus) {
+            meter.label.set_text(&format!(
+                "{} · {} of {} used",
+                gpu.name,
+                format_bytes(gpu.used),
+                format_bytes(gpu.total)
+            ));
+            meter.bar.set_value(gpu.used_percent() / 100.0);
+        }
+
+        let entries: Vec<(GpuProcess, bool)> = processes.iter().map(|p| (p.clone(), is_stopped(p.pid))).collect();
+        if *self.shown.borrow() == entries {
+            return;
+        }
+        while let Some(row) = self.list.first_child() {
+            self.list.remove(&row);
+        }
+        for (process, stopped) in &entries {
+            let gpu_name = (gpus.len() > 1)
+                .then(|| gpus.iter().find(|g| g.index == process.gpu_index).map(|g| g.name.as_str()))
+                .flatten();
+            self.list.append(&self.create_row(process, *stopped, gpu_name));
+        }
+        *self.shown.borrow_mut() = entries;
+    }
+
+    /// Row with process info, its VRAM and suspend/end buttons
+    fn create_row(&self, process: &GpuProcess, stopped: bool, gpu_name: Option<&str>) -> GtkBox {
+        let row = GtkBox::new(Orientation::Horizontal, 12);
+        row.set_margin_top(6);
+        row.set_margin_bottom(6);
+        row.set_margin_start(12);
+        row.set_margin_end(12);
+
+        let info_box = GtkBox::new(Orientation::Vertical, 2);
+        info_box.set_hexpand(true);
+        let name_label = Label::new(Some(&process.name));
+        name_label.set_halign(gtk4::Align::Start);
+        name_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        info_box.append(&name_label);
+        let mut detail = format!("PID {}", process.pid);
+        if let Some(gpu_name) = gpu_name {
+            detail.push_str(&format!(" · {}", gpu_name));
+        }
+        if stopped {
+            detail.push_str(" · suspended");
+        }
+        let detail_label = Label::new(Some(&detail));
+        detail_label.add_css_class("dim-label");
+        detail_label.add_css_class("caption");
+        detail_label.set_halign(gtk4::Align::Start);
+        info_box.append(&detail_label);
+        row.append(&info_box);
+
+        let vram_label = Label::new(Some(&format_bytes(process.vram_bytes)));
+        vram_label.add_css_class("numeric");
+        row.append(&vram_label);
+
+        let buttons = GtkBox::new(Orientation::Horizontal, 6);
+        let suspend_btn = Button::with_label(if stopped { "Resume" } else { "Suspend" });
+        suspend_btn.set_valign(gtk4::Align::Center);
+        buttons.append(&suspend_btn);
+        let end_btn = Button::with_label("End");
+        end_btn.add_css_class("destructive-action");
+        end_btn.set_valign(gtk4::Align::Center);
+        buttons.append(&end_btn);
+        row.append(&buttons);
+        self.state.lockdown.bind_widget(&buttons);
+
+        let pid = process.pid;
+        let audit_log = self.state.audit_log.clone();
+        let name = process.name.clone();
+        suspend_btn.connect_clicked(move |btn| {
+            let signal = if is_stopped(pid) { Signal::Cont } else { Signal::Stop };
+            let (audit_log, targets, btn) = (audit_log.clone(), vec![(pid, name.clone())], btn.clone());
+            glib::spawn_future_local(async move {
+                match send_signal_audited(audit_log, targets, signal).await {
+                    Ok(()) => btn.set_label(if signal == Signal::Stop { "Resume" } else { "Suspend" }),
+                    Err(e) => show_error(&btn, "Failed to suspend process", &e.to_string()),
+                }
+            });
+        });
+
+        let row_weak = row.downgrade();
+        let state = self.state.clone();
+        let name = process.name.clone();
+        end_btn.connect_clicked(move |btn| {
+            let Some(window) = btn.root().and_downcast::<gtk4::Window>() else {
+                return;
+            };
+            let row_weak = row_weak.clone();
+            let btn = btn.clone();
+            send_signal_confirmed(&window, vec![(pid, name.clone())], Signal::Term, &state, move |result| match result {
+                Ok(()) => {
+                    // Remove the whole ListBoxRow wrapping our box
+                    if let Some(list_row) = row_weak.upgrade().and_then(|r| r.parent()) {
+                        if let Some(list) = list_row.parent().and_downcast::<ListBox>() {
+                            list.remove(&list_row);
+                        }
+                    }
+                }
+                Err(e) => show_error(&btn, "Failed to end process", &e.to_string()),
+            });
+        });
+
+        row
+    }
+}
+
+fn show_error(widget: &impl IsA<gtk4::Widget>, heading: &str, body: &str) {
+    let dialog = adw::MessageDialog::builder().heading(heading).body(body).build();
+    if let Some(window) = widget.root().and_downcast::<gtk4::Window>() {
+        dialog.set_transient_for(Some(&window));
+    }
+    dialog.add_response("ok", "OK");
+    dialog.present();
+}
@@ -0,0 +1,124 @@
+//! "End Gently": SIGTERM, a grace period, then SIGKILL for whatever is still running
+//!
+//! This is the usual term-wait-kill routine done by hand. Progress is shown in a toast
+//! on the main window that counts down and offers to force-kill right away.
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use adw::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use procular_core::process_actions::{get_start_time_secs, is_process_alive, Signal};
+
+use crate::app_state::AppState;
+use crate::audit_log::send_signal_audited;
+use crate::context_menu::send_signal_confirmed;
+
+/// How often the ended processes are checked
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A process being ended, identified by PID and start time so a reused PID is not killed
+#[derive(Clone)]
+struct Target {
+    pid: u32,
+    name: String,
+    start_time: f64,
+}
+
+/// "firefox" or "3 processes"
+fn describe(targets: &[Target]) -> String {
+    match targets {
+        [target] => target.name.clone(),
+        _ => format!("{} processes", targets.len()),
+    }
+}
+
+/// Toast overlay of the main window, which wraps its whole content
+fn toast_overlay(window: &gtk4::Window) -> Option<adw::ToastOverlay> {
+    window
+        .downcast_ref::<adw::ApplicationWindow>()?
+        .content()
+        .and_downcast::<adw::ToastOverlay>()
+}
+
+fn show_toast(overlay: &Option<adw::ToastOverlay>, title: &str) {
+    if let Some(overlay) = overlay {
+        overlay.add_toast(adw::Toast::new(&glib::markup_escape_text(title)));
+    }
+}
+
+/// Send SIGTERM to `targets` (after the usual confirmation) and SIGKILL to those still
+/// running once the grace period from the preferences is over
+pub fn end_gently(window: &gtk4::Window, targets: Vec<(u32, String)>, state: &AppState) {
+    // Start times are taken before signalling, so a PID reused during the wait is left alone
+    let watched: Vec<Target> = targets
+        .iter()
+        .filter_map(|(pid, name)| {
+            Some(Target {
+                pid: *pid,
+                name: name.clone(),
+                start_time: get_start_time_secs(*pid)?,
+            })
+        })
+        .collect();
+    let overlay = toast_overlay(window);
+    let grace = Duration::from_secs(state.settings.borrow().grace_period_secs as u64);
+    let audit_log = state.audit_log.clone();
+
+    send_signal_confirmed(window, targets, Signal::Term, state, move |result| {
+        if let Err(e) = result {
+            show_toast(&overlay, &format!("Failed to end {}: {}", describe(&watched), e));
+            return;
+        }
+        let subject = describe(&watched);
+        let progress = adw::Toast::builder()
+            .title(glib::markup_escape_text(&format!("Ending {}…", subject)))
+            .timeout(0)
+            .button_label("Kill Now")
+            .build();
+        let kill_now = Rc::new(Cell::new(false));
+        let kill_now_clone = kill_now.clone();
+        progress.connect_button_clicked(move |_| kill_now_clone.set(true));
+        if let Some(overlay) = &overlay {
+            overlay.add_toast(progress.clone());
+        }
+
+        let deadline = Instant::now() + grace;
+        let overlay = overlay.clone();
+        let audit_log = audit_log.clone();
+        let mut remaining = watched.clone();
+        glib::timeout_add_local(POLL_INTERVAL, move || {
+            remaining.retain(|target| is_process_alive(target.pid, target.start_time));
+            if remaining.is_empty() {
+                progress.dismiss();
+                show_toast(&overlay, &format!("{} ended", subject));
+                return glib::ControlFlow::Break;
+            }
+
+            let now = Instant::now();
+            if now < deadline && !kill_now.get() {
+                let left = deadline.duration_since(now).as_secs_f64().ceil() as u64;
+                progress.set_title(&glib::markup_escape_text(&format!(
+                    "Ending {}… force kill in {} s",
+                    subject, left
+                )));
+                return glib::ControlFlow::Continue;
+            }
+
+            progress.dismiss();
+            let stuck: Vec<(u32, String)> = remaining.iter().map(|t| (t.pid, t.name.clone())).collect();
+            let stuck_subject = describe(&remaining);
+            let (audit_log, overlay) = (audit_log.clone(), overlay.clone());
+            glib::spawn_future_local(async move {
+                match send_signal_audited(audit_log, stuck, Signal::Kill).await {
+                    Ok(()) => show_toast(&overlay, &format!("Force-killed {}", stuck_subject)),
+                    Err(e) => show_toast(&overlay, &format!("Failed to kill {}: {}", stuck_subject, e)),
+                }
+            });
+            glib::ControlFlow::Break
+        });
+    });
+}
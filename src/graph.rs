@@ -0,0 +1,759 @@
+//! Time series graph widget drawn with cairo
+
+use gtk4::prelude::*;
+use gtk4::accessible::{Property, Relation};
+use gtk4::{AccessibleRole, DrawingArea, EventControllerMotion, Label};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use procular_core::monitor::format_bytes;
+
+/// Graph configuration
+const GRAPH_LEFT_MARGIN: f64 = 55.0;  // Space for Y-axis labels
+const GRAPH_BOTTOM_MARGIN: f64 = 20.0; // Space for X-axis labels
+const GRAPH_RIGHT_MARGIN: f64 = 10.0;
+const GRAPH_TOP_MARGIN: f64 = 5.0;
+
+/// Whether X axes show clock times instead of seconds before now; a preference shared by all graphs
+static CLOCK_AXIS: AtomicBool = AtomicBool::new(false);
+
+/// Label the X axis of every graph with clock times (14:03) instead of seconds before now
+pub fn set_clock_axis(enabled: bool) {
+    CLOCK_AXIS.store(enabled, Ordering::Relaxed);
+}
+
+/// Local clock time of `time` in a glib format such as "%H:%M"
+fn format_clock(time: SystemTime, format: &str) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|since_epoch| glib::DateTime::from_unix_local(since_epoch.as_secs() as i64).ok())
+        .and_then(|time| time.format(format).ok())
+        .map(|time| time.to_string())
+        .unwrap_or_default()
+}
+
+/// Format a value for Y-axis display
+fn format_y_value(value: f64, is_percentage: bool, is_bytes: bool) -> String {
+    if is_percentage {
+        format!("{:.0}%", value)
+    } else if is_bytes {
+        format_bytes(value as u64)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+/// Format a hovered value, with more precision than the axis labels
+fn format_hover_value(value: f64, is_percentage: bool, is_bytes: bool) -> String {
+    if is_percentage {
+        format!("{:.1}%", value)
+    } else {
+        format_y_value(value, is_percentage, is_bytes)
+    }
+}
+
+/// Calculate nice Y-axis tick values. On a log scale the ticks are powers of 1024 for bytes
+/// (B, KB, MB...) and of 10 otherwise, thinned out to at most five above zero
+fn calculate_y_ticks(max_value: f64, is_percentage: bool, is_bytes: bool, log_scale: bool) -> Vec<f64> {
+    if log_scale {
+        let base: f64 = if is_bytes { 1024.0 } else { 10.0 };
+        let top = max_value.max(1.0).log(base).ceil().max(1.0) as i32;
+        let stride = (top as usize).div_ceil(5);
+        let mut powers: Vec<i32> = (0..=top).rev().step_by(stride).collect();
+        powers.reverse();
+        std::iter::once(0.0).chain(powers.into_iter().map(|power| base.powi(power))).collect()
+    } else if is_percentage {
+        // For percentages, use fixed ticks
+        let max_tick = if max_value <= 25.0 {
+            25.0
+        } else if max_value <= 50.0 {
+            50.0
+        } else if max_value <= 100.0 {
+            100.0
+        } else {
+            ((max_value / 50.0).ceil() * 50.0).max(100.0)
+        };
+        vec![0.0, max_tick * 0.25, max_tick * 0.5, max_tick * 0.75, max_tick]
+    } else {
+        // For other values, calculate nice intervals
+        if max_value <= 0.0 {
+            return vec![0.0];
+        }
+
+        let magnitude = 10_f64.powf(max_value.log10().floor());
+        let normalized = max_value / magnitude;
+
+        let nice_max = if normalized <= 1.0 {
+            magnitude
+        } else if normalized <= 2.0 {
+            2.0 * magnitude
+        } else if normalized <= 5.0 {
+            5.0 * magnitude
+        } else {
+            10.0 * magnitude
+        };
+
+        vec![0.0, nice_max * 0.25, nice_max * 0.5, nice_max * 0.75, nice_max]
+    }
+}
+
+/// Height of `value` as a fraction of the graph, on a linear or log scale up to `y_max`
+fn y_fraction(value: f64, y_max: f64, log_scale: bool) -> f64 {
+    if y_max <= 0.0 {
+        0.0
+    } else if log_scale {
+        // ln(1 + x) keeps zero at the bottom
+        (value.max(0.0).ln_1p() / y_max.ln_1p()).clamp(0.0, 1.0)
+    } else {
+        (value / y_max).clamp(0.0, 1.0)
+    }
+}
+
+/// Draw one series as a line, over a filled area if `filled`, starting a new segment after each gap.
+/// `area` is the graph rectangle (left, top, width, height); `normalized` maps a value to its height fraction
+fn draw_series(
+    cr: &gtk4::cairo::Context,
+    values: &[f64],
+    is_gap: &dyn Fn(usize) -> bool,
+    color: (f64, f64, f64),
+    area: (f64, f64, f64, f64),
+    normalized: &dyn Fn(f64) -> f64,
+    filled: bool,
+) {
+    let (graph_left, graph_top, graph_width, graph_height) = area;
+    let graph_bottom = graph_top + graph_height;
+
+    if values.len() == 1 {
+        // Single data point - draw a dot
+        let y = graph_bottom - (normalized(values[0]) * graph_height);
+        cr.set_source_rgb(color.0, color.1, color.2);
+        cr.arc(graph_left + graph_width, y, 3.0, 0.0, 2.0 * std::f64::consts::PI);
+        let _ = cr.fill();
+        return;
+    }
+    if values.len() < 2 {
+        return;
+    }
+
+    let num_points = values.len();
+    let step = graph_width / (num_points - 1) as f64;
+    let point = |i: usize| (graph_left + i as f64 * step, graph_bottom - (normalized(values[i]) * graph_height));
+
+    let mut segment_start = 0;
+    for end in 1..=num_points {
+        if end < num_points && !is_gap(end) {
+            continue;
+        }
+        let segment = segment_start..end;
+        segment_start = end;
+
+        if segment.len() == 1 {
+            // Lone sample between gaps - draw a dot
+            let (x, y) = point(segment.start);
+            cr.set_source_rgb(color.0, color.1, color.2);
+            cr.arc(x, y, 2.0, 0.0, 2.0 * std::f64::consts::PI);
+            let _ = cr.fill();
+            continue;
+        }
+
+        // Fill area under curve
+        if filled {
+            let (first_x, _) = point(segment.start);
+            let (last_x, _) = point(segment.end - 1);
+            cr.move_to(first_x, graph_bottom);
+            for i in segment.clone() {
+                let (x, y) = point(i);
+                cr.line_to(x, y);
+            }
+            cr.line_to(last_x, graph_bottom);
+            cr.close_path();
+            cr.set_source_rgba(color.0, color.1, color.2, 0.3);
+            let _ = cr.fill();
+        }
+
+        // Draw line on top
+        cr.set_source_rgb(color.0, color.1, color.2);
+        cr.set_line_width(2.0);
+        for i in segment.clone() {
+            let (x, y) = point(i);
+            if i == segment.start {
+                cr.move_to(x, y);
+            } else {
+                cr.line_to(x, y);
+            }
+        }
+        let _ = cr.stroke();
+    }
+}
+
+/// Colors of a graph's background, grid and labels, following the light or dark style
+struct GraphTheme {
+    background: (f64, f64, f64),
+    /// Theme foreground; drawn with varying transparency for grid, labels and markers
+    foreground: (f64, f64, f64),
+}
+
+impl GraphTheme {
+    /// Adwaita's view background (#ffffff light, #1e1e1e dark) and the widget's text color
+    fn of(widget: &DrawingArea) -> Self {
+        let background = if adw::StyleManager::default().is_dark() {
+            (0.118, 0.118, 0.118)
+        } else {
+            (1.0, 1.0, 1.0)
+        };
+        let color = widget.color();
+        Self {
+            background,
+            foreground: (color.red() as f64, color.green() as f64, color.blue() as f64),
+        }
+    }
+
+    fn set_background(&self, cr: &gtk4::cairo::Context) {
+        cr.set_source_rgb(self.background.0, self.background.1, self.background.2);
+    }
+
+    /// Foreground at `alpha`: about 0.2 for grid lines, 0.7 for labels
+    fn set_foreground(&self, cr: &gtk4::cairo::Context, alpha: f64) {
+        cr.set_source_rgba(self.foreground.0, self.foreground.1, self.foreground.2, alpha);
+    }
+}
+
+/// Pango markup for a square in a series color, for legends
+pub fn color_swatch(color: (f64, f64, f64)) -> String {
+    format!(
+        "<span foreground=\"#{:02x}{:02x}{:02x}\">■</span>",
+        (color.0 * 255.0) as u8,
+        (color.1 * 255.0) as u8,
+        (color.2 * 255.0) as u8,
+    )
+}
+
+/// Current, lowest, highest and average value, for screen readers
+fn summarize(values: &[f64], format: &dyn Fn(f64) -> String) -> String {
+    let Some(&current) = values.last() else {
+        return "no data".to_string();
+    };
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let average = values.iter().sum::<f64>() / values.len() as f64;
+    format!(
+        "current {}, minimum {}, maximum {}, average {}",
+        format(current),
+        format(min),
+        format(max),
+        format(average)
+    )
+}
+
+/// One line of a graph comparing several series
+#[derive(Clone)]
+pub struct GraphSeries {
+    pub name: String,
+    pub values: Vec<f64>,
+    pub color: (f64, f64, f64),
+}
+
+/// Graph data with metadata
+#[derive(Clone)]
+struct GraphData {
+    values: Vec<f64>,
+    max_value: f64,
+    is_percentage: bool,
+    is_bytes: bool,
+    num_samples: usize,
+    sample_interval_secs: u64,
+    /// Fixed Y-axis maximum instead of auto-scaling
+    fixed_max: Option<f64>,
+    /// Logarithmic Y axis, so occasional spikes do not flatten everything else
+    log_scale: bool,
+    /// Whether a gap (e.g. suspend) precedes each sample, aligned with the end of `values`
+    gaps: Vec<bool>,
+    /// Wall clock time of each sample, aligned with the end of `values` like `gaps`
+    times: Vec<SystemTime>,
+    /// Lower part of a stacked graph, as long as `values` (which hold the total)
+    lower: Vec<f64>,
+    lower_color: Option<(f64, f64, f64)>,
+    /// Named lines overlaid in one graph, all as long as `values` (which hold their maximum)
+    series: Vec<GraphSeries>,
+    /// Pointer x position while hovering the graph
+    hover_x: Option<f64>,
+    /// When the newest value arrived, to tell the time of hovered samples
+    updated_at: SystemTime,
+}
+
+impl GraphData {
+    /// Whether the sample at `index` follows a gap and must not be connected to the previous one
+    fn is_gap(&self, index: usize) -> bool {
+        let offset = self.values.len() as isize - self.gaps.len() as isize;
+        let gap_index = index as isize - offset;
+        gap_index >= 0 && self.gaps.get(gap_index as usize).copied().unwrap_or(false)
+    }
+
+    /// Sample nearest to `x` in a graph `width` pixels wide
+    fn sample_at(&self, x: f64, width: f64) -> Option<usize> {
+        let graph_width = width - GRAPH_LEFT_MARGIN - GRAPH_RIGHT_MARGIN;
+        match self.values.len() {
+            0 => None,
+            1 => Some(0),
+            len => {
+                let step = graph_width / (len - 1) as f64;
+                Some((((x - GRAPH_LEFT_MARGIN) / step).round().max(0.0) as usize).min(len - 1))
+            }
+        }
+    }
+
+    /// When the sample at `index` was taken: its recorded time, else estimated from the interval
+    fn time_of(&self, index: usize) -> Option<SystemTime> {
+        let offset = self.values.len() as isize - self.times.len() as isize;
+        let time_index = index as isize - offset;
+        if time_index >= 0 {
+            if let Some(&time) = self.times.get(time_index as usize) {
+                return Some(time);
+            }
+        }
+        let age = Duration::from_secs((self.values.len() - 1 - index) as u64 * self.sample_interval_secs);
+        self.updated_at.checked_sub(age)
+    }
+
+    /// Description read by screen readers in place of the drawing
+    fn accessible_description(&self) -> String {
+        let format = |value: f64| format_hover_value(value, self.is_percentage, self.is_bytes);
+        let span = self.values.len() as u64 * self.sample_interval_secs;
+        if self.series.is_empty() {
+            return format!("Last {} seconds: {}", span, summarize(&self.values, &format));
+        }
+        let series: Vec<String> = self
+            .series
+            .iter()
+            .map(|series| format!("{}: {}", series.name, summarize(&series.values, &format)))
+            .collect();
+        format!("Last {} seconds. {}", span, series.join("; "))
+    }
+
+    /// Tooltip for the sample at `index`: its value and when it was taken
+    fn describe_sample(&self, index: usize) -> String {
+        let sampled = self.time_of(index);
+        let age = sampled
+            .and_then(|time| self.time_of(self.values.len() - 1)?.duration_since(time).ok())
+            .unwrap_or_default();
+        let time = sampled.map(|time| format_clock(time, "%X")).unwrap_or_default();
+        let when = if age.is_zero() {
+            format!("{} (now)", time)
+        } else {
+            format!("{} ({}s ago)", time, age.as_secs())
+        };
+        if self.series.is_empty() {
+            let value = format_hover_value(self.values[index], self.is_percentage, self.is_bytes);
+            return format!("{} · {}", value, when);
+        }
+        let mut lines = vec![when];
+        for series in &self.series {
+            let value = format_hover_value(series.values[index], self.is_percentage, self.is_bytes);
+            lines.push(format!("{}: {}", series.name, value));
+        }
+        lines.join("\n")
+    }
+}
+
+impl Default for GraphData {
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            max_value: 100.0,
+            is_percentage: false,
+            is_bytes: false,
+            num_samples: 60,
+            sample_interval_secs: 2,
+            fixed_max: None,
+            log_scale: false,
+            gaps: Vec::new(),
+            times: Vec::new(),
+            lower: Vec::new(),
+            lower_color: None,
+            series: Vec::new(),
+            hover_x: None,
+            updated_at: SystemTime::now(),
+        }
+    }
+}
+
+/// A single graph widget with axis labels
+pub struct GraphWidget {
+    pub drawing_area: DrawingArea,
+    data: Rc<RefCell<GraphData>>,
+    color: (f64, f64, f64),
+}
+
+impl GraphWidget {
+    pub fn new(color: (f64, f64, f64), is_percentage: bool, is_bytes: bool) -> Self {
+        let drawing_area = DrawingArea::builder().accessible_role(AccessibleRole::Img).build();
+        drawing_area.set_size_request(-1, 120);
+        drawing_area.set_hexpand(true);
+        drawing_area.set_vexpand(true);
+
+        let data = Rc::new(RefCell::new(GraphData {
+            is_percentage,
+            is_bytes,
+            ..Default::default()
+        }));
+
+        let data_clone = data.clone();
+        let color_clone = color;
+
+        drawing_area.set_draw_func(move |widget, cr, width, height| {
+            let data = data_clone.borrow();
+            let theme = GraphTheme::of(widget);
+            let width_f = width as f64;
+            let height_f = height as f64;
+
+            // Calculate graph area
+            let graph_left = GRAPH_LEFT_MARGIN;
+            let graph_right = width_f - GRAPH_RIGHT_MARGIN;
+            let graph_top = GRAPH_TOP_MARGIN;
+            let graph_bottom = height_f - GRAPH_BOTTOM_MARGIN;
+            let graph_width = graph_right - graph_left;
+            let graph_height = graph_bottom - graph_top;
+
+            // Background
+            theme.set_background(cr);
+            let _ = cr.paint();
+
+            // Calculate Y-axis ticks
+            let y_ticks = calculate_y_ticks(data.max_value, data.is_percentage, data.is_bytes, data.log_scale);
+            let y_max = *y_ticks.last().unwrap_or(&100.0);
+            let normalized = |value: f64| y_fraction(value, y_max, data.log_scale);
+
+            // Draw grid lines and Y-axis labels
+            theme.set_foreground(cr, 0.2);
+            cr.set_line_width(1.0);
+
+            for &tick in &y_ticks {
+                let y = graph_bottom - normalized(tick) * graph_height;
+
+                // Grid line
+                cr.move_to(graph_left, y);
+                cr.line_to(graph_right, y);
+                let _ = cr.stroke();
+
+                // Y-axis label
+                theme.set_foreground(cr, 0.7);
+                let label = format_y_value(tick, data.is_percentage, data.is_bytes);
+                if let Ok(extents) = cr.text_extents(&label) {
+                    cr.move_to(graph_left - extents.width() - 5.0, y + extents.height() / 2.0);
+                    let _ = cr.show_text(&label);
+                }
+                theme.set_foreground(cr, 0.2);
+            }
+
+            // Draw X-axis labels (time)
+            let total_time_secs = data.num_samples as u64 * data.sample_interval_secs;
+            theme.set_foreground(cr, 0.7);
+
+            // Show labels at 0%, 50%, 100% of the time range: clock times of the samples there
+            // if chosen, with seconds when the graph spans only a few minutes
+            let last = data.values.len().saturating_sub(1);
+            let clock_times = [0, last / 2, last].map(|index| data.time_of(index));
+            let time_labels = match clock_times {
+                [Some(first), Some(middle), Some(newest)]
+                    if CLOCK_AXIS.load(Ordering::Relaxed) && !data.values.is_empty() =>
+                {
+                    let span = newest.duration_since(first).unwrap_or_default();
+                    let format = if span < Duration::from_secs(180) { "%H:%M:%S" } else { "%H:%M" };
+                    [
+                        (0.0, format_clock(first, format)),
+                        (0.5, format_clock(middle, format)),
+                        (1.0, format_clock(newest, format)),
+                    ]
+                }
+                _ => [
+                    (0.0, format!("{}s", total_time_secs)),
+                    (0.5, format!("{}s", total_time_secs / 2)),
+                    (1.0, "now".to_string()),
+                ],
+            };
+
+            for (pos, label) in &time_labels {
+                let x = graph_left + pos * graph_width;
+                if let Ok(extents) = cr.text_extents(label) {
+                    let x_centered = if *pos == 0.0 {
+                        x
+                    } else if *pos == 1.0 {
+                        x - extents.width()
+                    } else {
+                        x - extents.width() / 2.0
+                    };
+                    cr.move_to(x_centered, height_f - 3.0);
+                    let _ = cr.show_text(label);
+                }
+            }
+
+            // Draw the data; stacked graphs then draw their lower part over the total,
+            // comparisons draw only the lines so that none hides another
+            let area = (graph_left, graph_top, graph_width, graph_height);
+            if data.series.is_empty() {
+                draw_series(cr, &data.values, &|i| data.is_gap(i), color_clone, area, &normalized, true);
+            }
+            if let Some(lower_color) = data.lower_color {
+                draw_series(cr, &data.lower, &|i| data.is_gap(i), lower_color, area, &normalized, true);
+            }
+            for series in &data.series {
+                draw_series(cr, &series.values, &|i| data.is_gap(i), series.color, area, &normalized, false);
+            }
+
+            // Mark each gap with a dashed line between the samples it separates
+            if data.values.len() >= 2 {
+                let step = graph_width / (data.values.len() - 1) as f64;
+                theme.set_foreground(cr, 0.55);
+                cr.set_line_width(1.0);
+                cr.set_dash(&[4.0, 3.0], 0.0);
+                for i in (1..data.values.len()).filter(|&i| data.is_gap(i)) {
+                    let x = graph_left + (i as f64 - 0.5) * step;
+                    cr.move_to(x, graph_top);
+                    cr.line_to(x, graph_bottom);
+                }
+                let _ = cr.stroke();
+                cr.set_dash(&[], 0.0);
+            }
+
+            // Crosshair on the hovered sample
+            if let Some(index) = data.hover_x.and_then(|x| data.sample_at(x, width_f)) {
+                let x = if data.values.len() == 1 {
+                    graph_right
+                } else {
+                    graph_left + index as f64 * graph_width / (data.values.len() - 1) as f64
+                };
+                let y_of = |value: f64| graph_bottom - normalized(value) * graph_height;
+                theme.set_foreground(cr, 0.6);
+                cr.set_line_width(1.0);
+                cr.move_to(x, graph_top);
+                cr.line_to(x, graph_bottom);
+                if data.series.is_empty() {
+                    cr.move_to(graph_left, y_of(data.values[index]));
+                    cr.line_to(graph_right, y_of(data.values[index]));
+                }
+                let _ = cr.stroke();
+                let dots: Vec<((f64, f64, f64), f64)> = if data.series.is_empty() {
+                    vec![(color_clone, data.values[index])]
+                } else {
+                    data.series.iter().map(|series| (series.color, series.values[index])).collect()
+                };
+                for (color, value) in dots {
+                    cr.set_source_rgb(color.0, color.1, color.2);
+                    cr.arc(x, y_of(value), 3.5, 0.0, 2.0 * std::f64::consts::PI);
+                    let _ = cr.fill();
+                }
+            }
+
+            // Border around graph area
+            theme.set_foreground(cr, 0.35);
+            cr.set_line_width(1.0);
+            cr.rectangle(graph_left, graph_top, graph_width, graph_height);
+            let _ = cr.stroke();
+        });
+
+        // Redraw in the other colors when the style switches between light and dark
+        let style_manager = adw::StyleManager::default();
+        let area_weak = drawing_area.downgrade();
+        let style_handler = style_manager.connect_dark_notify(move |_| {
+            if let Some(area) = area_weak.upgrade() {
+                area.queue_draw();
+            }
+        });
+        let style_handler = RefCell::new(Some(style_handler));
+        drawing_area.connect_destroy(move |_| {
+            if let Some(handler) = style_handler.take() {
+                adw::StyleManager::default().disconnect(handler);
+            }
+        });
+
+        // Hovering shows a crosshair and the exact value and time of the nearest sample
+        let motion = EventControllerMotion::new();
+        let data_clone = data.clone();
+        let area = drawing_area.clone();
+        motion.connect_motion(move |_, x, _| {
+            data_clone.borrow_mut().hover_x = Some(x);
+            area.queue_draw();
+        });
+        let data_clone = data.clone();
+        let area = drawing_area.clone();
+        motion.connect_leave(move |_| {
+            data_clone.borrow_mut().hover_x = None;
+            area.queue_draw();
+        });
+        drawing_area.add_controller(motion);
+
+        drawing_area.set_has_tooltip(true);
+        let data_clone = data.clone();
+        drawing_area.connect_query_tooltip(move |widget, x, _, _, tooltip| {
+            let data = data_clone.borrow();
+            let Some(index) = data.sample_at(x as f64, widget.width() as f64) else {
+                return false;
+            };
+            tooltip.set_text(Some(&data.describe_sample(index)));
+            true
+        });
+
+        Self {
+            drawing_area,
+            data,
+            color,
+        }
+    }
+
+    pub fn update(&self, values: &[f64], num_samples: usize, sample_interval_secs: u64) {
+        let mut data = self.data.borrow_mut();
+        data.values = values.to_vec();
+        data.updated_at = SystemTime::now();
+        data.num_samples = num_samples;
+        data.sample_interval_secs = sample_interval_secs;
+
+        // Auto-scale: find max value with some headroom
+        let max_val = values.iter().cloned().fold(0.0_f64, f64::max);
+        // Ensure minimum of 1.0 to avoid division issues and provide meaningful scale
+        data.max_value = data.fixed_max.unwrap_or(max_val.max(1.0));
+        self.drawing_area
+            .update_property(&[Property::Description(&data.accessible_description())]);
+
+        self.drawing_area.queue_draw();
+    }
+
+    /// Draw `upper` stacked on `lower` in the lower color; the axis scales to their sum
+    pub fn update_stacked(
+        &self,
+        lower: &[f64],
+        upper: &[f64],
+        lower_color: (f64, f64, f64),
+        num_samples: usize,
+        sample_interval_secs: u64,
+    ) {
+        let totals: Vec<f64> = lower.iter().zip(upper).map(|(l, u)| l + u).collect();
+        {
+            let mut data = self.data.borrow_mut();
+            data.lower = lower[..totals.len()].to_vec();
+            data.lower_color = Some(lower_color);
+        }
+        self.update(&totals, num_samples, sample_interval_secs);
+    }
+
+    /// Draw several series as lines in one graph; they are cut to the shortest so that
+    /// their newest values line up, and the axis scales to the highest of them
+    pub fn update_series(&self, series: Vec<GraphSeries>, num_samples: usize, sample_interval_secs: u64) {
+        let len = series.iter().map(|series| series.values.len()).min().unwrap_or(0);
+        let series: Vec<GraphSeries> = series
+            .into_iter()
+            .map(|series| GraphSeries {
+                values: series.values[series.values.len() - len..].to_vec(),
+                ..series
+            })
+            .collect();
+        let maxima: Vec<f64> = (0..len)
+            .map(|i| series.iter().map(|series| series.values[i]).fold(0.0, f64::max))
+            .collect();
+        self.data.borrow_mut().series = series;
+        self.update(&maxima, num_samples, sample_interval_secs);
+    }
+
+    /// Name the graph after its heading for screen readers
+    pub fn set_title_label(&self, label: &Label) {
+        self.drawing_area.update_relation(&[Relation::LabelledBy(&[label.upcast_ref()])]);
+    }
+
+    /// Set the gap flags for the next update; the last flag belongs to the newest value
+    pub fn set_gaps(&self, gaps: &[bool]) {
+        self.data.borrow_mut().gaps = gaps.to_vec();
+    }
+
+    /// Set the sample times for the next update; the last time belongs to the newest value
+    pub fn set_times(&self, times: &[SystemTime]) {
+        self.data.borrow_mut().times = times.to_vec();
+    }
+
+    /// Button in a graph header that switches the Y axis between linear and logarithmic
+    pub fn log_scale_toggle(&self) -> gtk4::ToggleButton {
+        let button = gtk4::ToggleButton::builder()
+            .label("Log")
+            .tooltip_text("Logarithmic scale, so that spikes do not flatten the rest of the graph")
+            .valign(gtk4::Align::Center)
+            .build();
+        button.add_css_class("flat");
+        button.add_css_class("caption");
+        let data = self.data.clone();
+        let drawing_area = self.drawing_area.clone();
+        button.connect_toggled(move |button| {
+            data.borrow_mut().log_scale = button.is_active();
+            drawing_area.queue_draw();
+        });
+        button
+    }
+
+    pub fn is_percentage(&self) -> bool {
+        self.data.borrow().is_percentage
+    }
+
+    /// Use a fixed Y-axis maximum (None to auto-scale)
+    pub fn set_fixed_max(&self, max_value: Option<f64>) {
+        self.data.borrow_mut().fixed_max = max_value;
+    }
+
+    #[allow(dead_code)]
+    pub fn color(&self) -> (f64, f64, f64) {
+        self.color
+    }
+}
+
+/// Compact line graph without axes or labels, for tight spaces like the top bar
+pub struct Sparkline {
+    pub drawing_area: DrawingArea,
+    values: Rc<RefCell<Vec<f64>>>,
+}
+
+impl Sparkline {
+    /// `fixed_max` pins the scale (e.g. 100 for percentages); None scales to the largest value
+    pub fn new(color: (f64, f64, f64), fixed_max: Option<f64>) -> Self {
+        let drawing_area = DrawingArea::new();
+        drawing_area.set_size_request(60, 20);
+        let values: Rc<RefCell<Vec<f64>>> = Rc::default();
+
+        let values_clone = values.clone();
+        drawing_area.set_draw_func(move |_widget, cr, width, height| {
+            let values = values_clone.borrow();
+            if values.len() < 2 {
+                return;
+            }
+            let (width, height) = (width as f64, height as f64);
+            let max = fixed_max.unwrap_or_else(|| values.iter().cloned().fold(0.0_f64, f64::max).max(1.0));
+            let step = width / (values.len() - 1) as f64;
+            let y = |value: f64| height - (value / max).clamp(0.0, 1.0) * (height - 1.0);
+
+            cr.move_to(0.0, height);
+            for (i, &value) in values.iter().enumerate() {
+                cr.line_to(i as f64 * step, y(value));
+            }
+            cr.line_to(width, height);
+            cr.close_path();
+            cr.set_source_rgba(color.0, color.1, color.2, 0.3);
+            let _ = cr.fill();
+
+            cr.set_source_rgb(color.0, color.1, color.2);
+            cr.set_line_width(1.5);
+            for (i, &value) in values.iter().enumerate() {
+                if i == 0 {
+                    cr.move_to(0.0, y(value));
+                } else {
+                    cr.line_to(i as f64 * step, y(value));
+                }
+            }
+            let _ = cr.stroke();
+        });
+
+        Self { drawing_area, values }
+    }
+
+    pub fn update(&self, values: &[f64]) {
+        *self.values.borrow_mut() = values.to_vec();
+        self.drawing_area.queue_draw();
+    }
+}
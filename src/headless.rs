@@ -0,0 +1,191 @@
+//! Headless mode: print periodic samples to stdout without starting GTK
+//!
+//! `procular --headless [--top N] [--interval SECS] [--count N] [--json]`
+
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use procular_core::export::{format_aligned_table, json_string};
+use procular_core::monitor::{format_bytes, format_rate, ProcessInfo, SystemMonitor};
+
+const DEFAULT_TOP: usize = 10;
+const DEFAULT_INTERVAL_SECS: u64 = 2;
+
+const USAGE: &str = "\
+Usage: procular --headless [OPTIONS]
+
+Print the busiest processes periodically without starting the GUI.
+
+Options:
+  --top N          Number of processes per sample (default 10)
+  --interval SECS  Seconds between samples (default 2)
+  --count N        Stop after N samples (default: run until interrupted)
+  --json           One JSON object per sample and line instead of a table
+  --help           Show this help";
+
+struct Options {
+    top: usize,
+    interval: Duration,
+    count: Option<u64>,
+    json: bool,
+}
+
+/// Whether the command line asks for headless mode
+pub fn requested(args: &[String]) -> bool {
+    args.iter().skip(1).any(|arg| arg == "--headless")
+}
+
+/// Run headless mode until `--count` samples were printed or stdout closes
+pub fn run(args: &[String]) -> glib::ExitCode {
+    let options = match parse_args(args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return glib::ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("procular: {}\n\n{}", message, USAGE);
+            return glib::ExitCode::FAILURE;
+        }
+    };
+
+    let mut monitor = SystemMonitor::new();
+    // CPU usage and rates are deltas, so the first refresh only sets the baseline
+    monitor.refresh();
+
+    let mut stdout = io::stdout().lock();
+    let mut printed = 0;
+    loop {
+        if options.count.is_some_and(|count| printed >= count) {
+            break;
+        }
+        thread::sleep(options.interval);
+        let processes = monitor.refresh();
+        let top = &processes[..processes.len().min(options.top)];
+
+        let text = if options.json {
+            format_json_sample(top, options.interval)
+        } else {
+            format_table_sample(top, options.interval)
+        };
+        // Stop quietly when the reader goes away (e.g. piped into head)
+        if writeln!(stdout, "{}", text).and_then(|_| stdout.flush()).is_err() {
+            break;
+        }
+        printed += 1;
+    }
+
+    glib::ExitCode::SUCCESS
+}
+
+/// Parse the options; Ok(None) means help was requested
+fn parse_args(args: &[String]) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        top: DEFAULT_TOP,
+        interval: Duration::from_secs(DEFAULT_INTERVAL_SECS),
+        count: None,
+        json: false,
+    };
+
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| {
+            iter.next()
+                .ok_or_else(|| format!("{} needs a value", name))
+                .cloned()
+        };
+        match arg.as_str() {
+            "--headless" => {}
+            "--json" => options.json = true,
+            "--help" | "-h" => return Ok(None),
+            "--top" => {
+                options.top = parse_number(&value("--top")?, "--top")?;
+            }
+            "--interval" => {
+                options.interval = value("--interval")?
+                    .parse()
+                    .ok()
+                    .filter(|secs: &f64| *secs > 0.0)
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                    .ok_or("--interval needs a positive number of seconds")?;
+            }
+            "--count" => {
+                options.count = Some(parse_number(&value("--count")?, "--count")?);
+            }
+            other => return Err(format!("unknown option '{}'", other)),
+        }
+    }
+
+    Ok(Some(options))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, name: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} needs a whole number, got '{}'", name, value))
+}
+
+/// Bytes per second over one sample interval
+fn per_second(bytes: u64, interval: Duration) -> u64 {
+    (bytes as f64 / interval.as_secs_f64()) as u64
+}
+
+/// GPU memory share, None when the process (and its threads) have no GPU data
+fn gpu_percent(process: &ProcessInfo) -> Option<f32> {
+    let has_gpu = process.gpu_percent.is_some()
+        || process.children.iter().any(|c| c.gpu_percent.is_some());
+    has_gpu.then(|| process.total_gpu())
+}
+
+fn format_table_sample(processes: &[ProcessInfo], interval: Duration) -> String {
+    let headers: Vec<String> = ["PID", "NAME", "THREADS", "CPU%", "MEM", "DISK", "NET", "GPU%"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    let rows: Vec<Vec<String>> = processes
+        .iter()
+        .map(|p| {
+            vec![
+                p.pid.to_string(),
+                p.name.clone(),
+                (p.child_count() + 1).to_string(),
+                format!("{:.1}", p.total_cpu()),
+                format_bytes(p.total_memory()),
+                format_rate(p.total_disk_rate()),
+                format_rate(per_second(p.total_net_rx() + p.total_net_tx(), interval)),
+                gpu_percent(p).map_or("-".to_string(), |gpu| format!("{:.1}", gpu)),
+            ]
+        })
+        .collect();
+    let left_aligned = [false, true, false, false, false, false, false, false];
+    format_aligned_table(&headers, &rows, &left_aligned)
+}
+
+fn format_json_sample(processes: &[ProcessInfo], interval: Duration) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0);
+    let entries: Vec<String> = processes
+        .iter()
+        .map(|p| {
+            format!(
+                "{{\"pid\":{},\"name\":{},\"threads\":{},\"cpu_percent\":{:.2},\
+                 \"memory_bytes\":{},\"disk_read_bytes_per_sec\":{},\"disk_write_bytes_per_sec\":{},\
+                 \"net_rx_bytes_per_sec\":{},\"net_tx_bytes_per_sec\":{},\"gpu_percent\":{}}}",
+                p.pid,
+                json_string(&p.name),
+                p.child_count() + 1,
+                p.total_cpu(),
+                p.total_memory(),
+                p.total_disk_read_rate(),
+                p.total_disk_write_rate(),
+                per_second(p.total_net_rx(), interval),
+                per_second(p.total_net_tx(), interval),
+                gpu_percent(p).map_or("null".to_string(), |gpu| format!("{:.2}", gpu)),
+            )
+        })
+        .collect();
+    format!("{{\"timestamp\":{:.3},\"processes\":[{}]}}", timestamp, entries.join(","))
+}
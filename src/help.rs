@@ -0,0 +1,243 @@
+//! Built-in explanations of the metrics Procular shows
+//!
+//! Topics are looked up by ID: process list column IDs ("cpu", "memory", ...)
+//! plus IDs for detail sections ("pss", "state", ...). Column headers offer
+//! them from their right-click menu; sections get a small "?" button.
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Label, MenuButton, Orientation, Popover};
+
+/// One help entry
+pub struct HelpTopic {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub body: &'static str,
+}
+
+const TOPICS: &[HelpTopic] = &[
+    HelpTopic {
+        id: "name",
+        title: "Name",
+        body: "The process name, or the app ID for processes of Flatpak and Snap apps. Processes with \
+               threads show the thread count in parentheses.",
+    },
+    HelpTopic {
+        id: "pid",
+        title: "PID",
+        body: "Process ID. The kernel reuses IDs after a process exits, so Procular also tracks each \
+               process's start time to tell them apart.",
+    },
+    HelpTopic {
+        id: "cpu",
+        title: "CPU %",
+        body: "Share of CPU time used during the last refresh, including all threads. By default 100% \
+               means the whole machine; with \"CPU % Per Core\" in the main menu 100% means one core, \
+               so a busy multi-threaded process can exceed 100%.",
+    },
+    HelpTopic {
+        id: "memory",
+        title: "Memory",
+        body: "Resident set size (RSS): the process's pages currently in RAM. Shared libraries and \
+               shared memory are counted in full for every process using them, so the column does \
+               not add up to the memory in use. The optional PSS and USS columns do not have that problem.",
+    },
+    HelpTopic {
+        id: "disk",
+        title: "Disk I/O",
+        body: "Bytes read plus written per second since the last refresh, as counted by the kernel \
+               for the process. Reads served from the page cache are not included. Hover a cell for \
+               the split.",
+    },
+    HelpTopic {
+        id: "network",
+        title: "Network",
+        body: "TCP bytes received plus sent during the last refresh, attributed through the sockets \
+               the process owns. UDP traffic is not counted. Hover a cell for the split.",
+    },
+    HelpTopic {
+        id: "gpu",
+        title: "GPU %",
+        body: "Share of the GPU's video memory the process holds, as reported by the NVIDIA driver \
+               (NVML) for compute and graphics processes. It is not a measure of GPU load. Shows \
+               \"-\" for processes not on an NVIDIA GPU.",
+    },
+    HelpTopic {
+        id: "sched",
+        title: "Scheduling",
+        body: "Kernel scheduling policy, with the priority for realtime ones. OTHER is the normal \
+               policy. Realtime policies (FIFO, RR) run before all normal processes and can starve \
+               the system; BATCH and IDLE yield to interactive work.",
+    },
+    HelpTopic {
+        id: "unit",
+        title: "Unit",
+        body: "The systemd service or scope the process runs in, taken from its cgroup. Restarting \
+               or stopping a unit affects all of its processes.",
+    },
+    HelpTopic {
+        id: "wakeups",
+        title: "Wakeups/s",
+        body: "How often the process's threads wake up from sleep each second, counted as voluntary \
+               context switches. Every wakeup pulls a CPU out of its idle state; above about 100 per \
+               second the CPU rarely reaches deep sleep states, which drains laptop batteries even \
+               when CPU % stays near zero. Such values are highlighted.",
+    },
+    HelpTopic {
+        id: "cpu-trend",
+        title: "CPU Trend",
+        body: "The process's CPU use over roughly the last minute, newest on the right. The height \
+               is scaled to one fully busy core, or to the highest sample if that is more, so a flat \
+               line means the process is mostly idle. Sorts like the CPU column.",
+    },
+    HelpTopic {
+        id: "affinity",
+        title: "Affinity",
+        body: "The CPUs the process may run on, one square per CPU: filled squares are allowed, faint \
+               ones are not. A process pinned to a few CPUs cannot use the others even when they are \
+               idle. Shows the main thread; individual threads can have their own affinity. Change \
+               it with Set CPU Affinity in the context menu.",
+    },
+    HelpTopic {
+        id: "state",
+        title: "Process State",
+        body: "Running: on a CPU or waiting for one.\n\
+               Sleeping: waiting for an event such as input or a timer; most processes sleep.\n\
+               Disk Sleep (D state): waiting inside the kernel, usually for storage or a network \
+               filesystem. It cannot be interrupted, even by SIGKILL, and counts towards load \
+               average; many D-state processes point to slow or hung I/O.\n\
+               Stopped: paused by a signal or a debugger.\n\
+               Zombie: exited, but its parent has not yet collected the exit status.",
+    },
+    HelpTopic {
+        id: "pss",
+        title: "PSS",
+        body: "Proportional set size: resident memory with every shared page divided among the \
+               processes sharing it. A library page used by four processes counts a quarter for \
+               each, so PSS values add up to the memory actually in use. RSS counts shared pages \
+               in full for each process; USS counts only pages no one else uses. The PSS column is \
+               read from smaps_rollup every 10 seconds.",
+    },
+    HelpTopic {
+        id: "uss",
+        title: "USS",
+        body: "Unique set size: resident pages that only this process maps. It is roughly what ending \
+               the process gives back, and unlike RSS it is not inflated by shared libraries. Like \
+               PSS it is read from smaps_rollup every 10 seconds and shows \"-\" for processes \
+               Procular may not inspect.",
+    },
+    HelpTopic {
+        id: "gpu-graphs",
+        title: "GPU Memory and GPU Util",
+        body: "GPU Memory is the process's share of the GPU's video memory, as in the GPU % column. \
+               GPU Util is the load of the whole GPU from all processes, as reported by the NVIDIA \
+               driver; NVML does not report load per process.",
+    },
+    HelpTopic {
+        id: "swap",
+        title: "Swap Activity",
+        body: "Swap In is data read back from swap when a process touches pages that were moved out; \
+               Swap Out is RAM written to swap to make room. Sustained swap in means programs wait \
+               for the disk, which feels like stutter; adding RAM or closing the processes with the \
+               most swap helps.",
+    },
+    HelpTopic {
+        id: "page-faults",
+        title: "Page Faults",
+        body: "Faults per second of all threads, from /proc/<pid>/stat. Minor faults map a page that \
+               is already in RAM and are normal when a program allocates or starts. Major faults \
+               wait for the disk because the page was swapped out or dropped from the cache; a \
+               steady stream of them means the process, or the whole system, is short of memory.",
+    },
+    HelpTopic {
+        id: "cpu-split",
+        title: "User and Kernel CPU",
+        body: "CPU time of all threads from /proc/<pid>/stat, split into time running the program's \
+               own code (user) and time the kernel spent on its behalf (kernel): system calls, page \
+               faults and I/O setup. A large kernel share usually means many small reads or writes, \
+               busy polling or memory pressure rather than computation.",
+    },
+    HelpTopic {
+        id: "context-switches",
+        title: "Context Switches",
+        body: "Switches per second of all threads, from /proc/<tid>/status. A voluntary switch \
+               happens when a thread waits (for I/O, a timer or a lock); many of them at low CPU use \
+               point to threads blocking on each other. An involuntary switch happens when the \
+               scheduler preempts a thread that could keep running; many of them mean there are \
+               more busy threads than CPUs.",
+    },
+    HelpTopic {
+        id: "frame-rate",
+        title: "Frame Rate",
+        body: "Shown for processes with MangoHud loaded. MangoHud writes a row with the FPS and \
+               frame time to a CSV log while logging is on (Shift+F2 by default, or the button here \
+               when MangoHud runs with its control option). Procular follows the newest log in \
+               MangoHud's output folder and graphs the average FPS and the slowest frame of each \
+               refresh; spikes in frame time are stutter even when the average FPS looks fine.",
+    },
+    HelpTopic {
+        id: "perf",
+        title: "Performance Counters",
+        body: "Hardware counters read through perf_event_open. Instructions per cycle (IPC) below \
+               about 1 usually means the CPU waits for memory; cache misses show how often data \
+               had to come from RAM. Counters may be unavailable in virtual machines.",
+    },
+];
+
+/// Help entry with the given ID
+pub fn topic(id: &str) -> Option<&'static HelpTopic> {
+    TOPICS.iter().find(|topic| topic.id == id)
+}
+
+/// Popover content: the topic title and its wrapped explanation
+fn topic_content(topic: &HelpTopic) -> GtkBox {
+    let content = GtkBox::new(Orientation::Vertical, 6);
+    content.set_margin_top(6);
+    content.set_margin_bottom(6);
+    content.set_margin_start(6);
+    content.set_margin_end(6);
+
+    let title = Label::new(Some(topic.title));
+    title.add_css_class("heading");
+    title.set_halign(gtk4::Align::Start);
+    content.append(&title);
+
+    let body = Label::new(Some(topic.body));
+    body.set_wrap(true);
+    body.set_max_width_chars(50);
+    body.set_xalign(0.0);
+    content.append(&body);
+    content
+}
+
+/// Small "?" button that opens the explanation of a topic
+pub fn help_button(id: &str) -> MenuButton {
+    let button = MenuButton::new();
+    button.set_icon_name("help-about-symbolic");
+    button.add_css_class("flat");
+    button.add_css_class("circular");
+    button.set_valign(gtk4::Align::Center);
+    if let Some(topic) = topic(id) {
+        button.set_tooltip_text(Some(&format!("What is {}?", topic.title)));
+        let popover = Popover::new();
+        popover.set_child(Some(&topic_content(topic)));
+        button.set_popover(Some(&popover));
+    }
+    button
+}
+
+/// Pop up the explanation of a topic pointing at `rect` in `parent`'s coordinates
+pub fn show_help_popover(parent: &impl IsA<gtk4::Widget>, id: &str, rect: &gtk4::gdk::Rectangle) {
+    let Some(topic) = topic(id) else {
+        return;
+    };
+    let popover = Popover::new();
+    popover.set_child(Some(&topic_content(topic)));
+    popover.set_parent(parent);
+    popover.set_pointing_to(Some(rect));
+    popover.connect_closed(|popover| {
+        // Deferred so the popover finishes closing before it loses its parent
+        let popover = popover.clone();
+        glib::idle_add_local_once(move || popover.unparent());
+    });
+    popover.popup();
+}
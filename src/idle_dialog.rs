@@ -0,0 +1,203 @@
+//! "Probably idle" panel listing long-idle processes that could be terminated to free memory
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, DropDown, Label, ListBox, Orientation, ScrolledWindow, StringList};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::rc::Rc;
+use std::time::Duration;
+
+use procular_core::monitor::{format_bytes, format_duration, IdleProcess};
+use procular_core::process_actions::Signal;
+
+use crate::app_state::AppState;
+use crate::context_menu;
+
+/// Minimum idle time choices (label, seconds)
+const IDLE_THRESHOLDS: &[(&str, u64)] = &[
+    ("15 min", 15 * 60),
+    ("1 hour", 60 * 60),
+    ("3 hours", 3 * 60 * 60),
+    ("6 hours", 6 * 60 * 60),
+];
+const DEFAULT_THRESHOLD: u32 = 1; // 1 hour
+
+/// Show the idle processes window
+pub fn show_idle_dialog(parent: &impl IsA<gtk4::Window>, state: &AppState) {
+    let dialog = adw::Window::builder()
+        .title("Probably Idle Processes")
+        .transient_for(parent)
+        .default_width(520)
+        .default_height(480)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 0);
+
+    // Header bar with threshold dropdown and refresh
+    let header = adw::HeaderBar::new();
+
+    let threshold_options =
+        StringList::new(&IDLE_THRESHOLDS.iter().map(|(label, _)| *label).collect::<Vec<_>>());
+    let threshold_dropdown = DropDown::new(Some(threshold_options), gtk4::Expression::NONE);
+    threshold_dropdown.set_selected(DEFAULT_THRESHOLD);
+
+    let threshold_box = GtkBox::new(Orientation::Horizontal, 8);
+    threshold_box.append(&Label::new(Some("Idle for:")));
+    threshold_box.append(&threshold_dropdown);
+    header.pack_start(&threshold_box);
+
+    let refresh_btn = Button::from_icon_name("view-refresh-symbolic");
+    refresh_btn.set_tooltip_text(Some("Refresh"));
+    header.pack_end(&refresh_btn);
+
+    main_box.append(&header);
+
+    // Content
+    let content = GtkBox::new(Orientation::Vertical, 8);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+    content.set_margin_start(12);
+    content.set_margin_end(12);
+
+    let summary_label = Label::new(None);
+    summary_label.set_halign(gtk4::Align::Start);
+    summary_label.set_wrap(true);
+    content.append(&summary_label);
+
+    let list = ListBox::new();
+    list.add_css_class("boxed-list");
+    list.set_selection_mode(gtk4::SelectionMode::None);
+    let placeholder = Label::new(Some("No idle processes found"));
+    placeholder.add_css_class("dim-label");
+    placeholder.set_margin_top(24);
+    placeholder.set_margin_bottom(24);
+    list.set_placeholder(Some(&placeholder));
+
+    let scrolled = ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_child(Some(&list));
+    content.append(&scrolled);
+
+    let note = Label::new(Some(
+        "Processes of your user with no CPU, disk or network activity since the threshold. \
+         Idle time is only tracked while Procular is running.",
+    ));
+    note.add_css_class("dim-label");
+    note.set_halign(gtk4::Align::Start);
+    note.set_wrap(true);
+    content.append(&note);
+
+    main_box.append(&content);
+    dialog.set_content(Some(&main_box));
+
+    // Populate the list for the selected threshold
+    let dialog_weak = dialog.downgrade();
+    let populate: Rc<dyn Fn()> = {
+        let dropdown = threshold_dropdown.clone();
+        let state = state.clone();
+        Rc::new(move || {
+            let (_, secs) = IDLE_THRESHOLDS[dropdown.selected() as usize % IDLE_THRESHOLDS.len()];
+            let idle = state.monitor.borrow().idle_processes(Duration::from_secs(secs));
+
+            let total: u64 = idle.iter().map(|p| p.memory_bytes).sum();
+            summary_label.set_text(&format!(
+                "{} idle process{}, about {} could be freed",
+                idle.len(),
+                if idle.len() == 1 { "" } else { "es" },
+                format_bytes(total)
+            ));
+
+            while let Some(row) = list.first_child() {
+                list.remove(&row);
+            }
+            for process in &idle {
+                list.append(&create_idle_row(process, &dialog_weak, &state));
+            }
+        })
+    };
+    populate();
+
+    let populate_clone = populate.clone();
+    threshold_dropdown.connect_selected_notify(move |_| populate_clone());
+    refresh_btn.connect_clicked(move |_| populate());
+
+    dialog.present();
+}
+
+/// Build a list row with process info and a terminate button
+fn create_idle_row(process: &IdleProcess, dialog_weak: &glib::WeakRef<adw::Window>, state: &AppState) -> GtkBox {
+    let row = GtkBox::new(Orientation::Horizontal, 12);
+    row.set_margin_top(6);
+    row.set_margin_bottom(6);
+    row.set_margin_start(12);
+    row.set_margin_end(12);
+
+    let info_box = GtkBox::new(Orientation::Vertical, 2);
+    info_box.set_hexpand(true);
+    let name_label = Label::new(Some(&process.name));
+    name_label.set_halign(gtk4::Align::Start);
+    name_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+    info_box.append(&name_label);
+    let detail_label = Label::new(Some(&format!(
+        "PID {} · idle for {}",
+        process.pid,
+        format_duration(process.idle_for)
+    )));
+    detail_label.add_css_class("dim-label");
+    detail_label.add_css_class("caption");
+    detail_label.set_halign(gtk4::Align::Start);
+    info_box.append(&detail_label);
+    row.append(&info_box);
+
+    let memory_label = Label::new(Some(&format_bytes(process.memory_bytes)));
+    memory_label.add_css_class("numeric");
+    row.append(&memory_label);
+
+    let end_btn = Button::with_label("End");
+    end_btn.add_css_class("destructive-action");
+    end_btn.set_valign(gtk4::Align::Center);
+    row.append(&end_btn);
+    state.lockdown.bind_widget(&end_btn);
+
+    // Asks first unless quick-kill mode is on, and is recorded in the audit log
+    let target = (process.pid, process.name.clone());
+    let row_weak = row.downgrade();
+    let dialog_weak = dialog_weak.clone();
+    let state = state.clone();
+    end_btn.connect_clicked(move |_| {
+        let Some(dialog) = dialog_weak.upgrade() else {
+            return;
+        };
+        let row_weak = row_weak.clone();
+        let dialog_weak = dialog_weak.clone();
+        context_menu::send_signal_confirmed(
+            dialog.upcast_ref(),
+            vec![target.clone()],
+            Signal::Term,
+            &state,
+            move |result| match result {
+                Ok(()) => {
+                    // Remove the whole ListBoxRow wrapping our box
+                    if let Some(list_row) = row_weak.upgrade().and_then(|r| r.parent()) {
+                        if let Some(list) = list_row.parent().and_downcast::<ListBox>() {
+                            list.remove(&list_row);
+                        }
+                    }
+                }
+                Err(e) => {
+                    if let Some(dialog) = dialog_weak.upgrade() {
+                        let error = adw::MessageDialog::builder()
+                            .transient_for(&dialog)
+                            .heading("Failed to end process")
+                            .body(e.to_string())
+                            .build();
+                        error.add_response("ok", "OK");
+                        error.present();
+                    }
+                }
+            },
+        );
+    });
+
+    row
+}
@@ -0,0 +1,122 @@
+//! Suspend/idle inhibition while critical watches are active
+//!
+//! Watches register here; while at least one is active (and the preference allows
+//! it) a GtkApplication inhibit keeps the session from suspending or blanking.
+
+use gtk4::prelude::*;
+use std::cell::{Cell, RefCell};
+
+/// A registered watch and its inhibit cookie (None while inhibiting is disabled)
+struct Watch {
+    id: u32,
+    reason: String,
+    window: glib::WeakRef<gtk4::Window>,
+    cookie: Option<u32>,
+}
+
+type Listener = Box<dyn Fn(&SuspendInhibitor) -> glib::ControlFlow>;
+
+pub struct SuspendInhibitor {
+    app: gtk4::Application,
+    enabled: Cell<bool>,
+    watches: RefCell<Vec<Watch>>,
+    next_id: Cell<u32>,
+    /// Called whenever watches or the enabled state change; Break unregisters
+    listeners: RefCell<Vec<Listener>>,
+}
+
+const INHIBIT_FLAGS: gtk4::ApplicationInhibitFlags =
+    gtk4::ApplicationInhibitFlags::SUSPEND.union(gtk4::ApplicationInhibitFlags::IDLE);
+
+impl SuspendInhibitor {
+    pub fn new(app: &impl IsA<gtk4::Application>, enabled: bool) -> Self {
+        Self {
+            app: app.clone().upcast(),
+            enabled: Cell::new(enabled),
+            watches: RefCell::new(Vec::new()),
+            next_id: Cell::new(1),
+            listeners: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn take_inhibit(&self, window: &glib::WeakRef<gtk4::Window>, reason: &str) -> u32 {
+        self.app.inhibit(window.upgrade().as_ref(), INHIBIT_FLAGS, Some(reason))
+    }
+
+    /// Register a watch; returns an id for `release`
+    pub fn inhibit(&self, window: &impl IsA<gtk4::Window>, reason: &str) -> u32 {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        let window = window.upcast_ref::<gtk4::Window>().downgrade();
+        let cookie = self.enabled.get().then(|| self.take_inhibit(&window, reason));
+        self.watches.borrow_mut().push(Watch {
+            id,
+            reason: reason.to_string(),
+            window,
+            cookie,
+        });
+        self.notify();
+        id
+    }
+
+    /// Unregister a watch
+    pub fn release(&self, id: u32) {
+        let removed: Vec<Watch> = {
+            let mut watches = self.watches.borrow_mut();
+            let (removed, kept) = watches.drain(..).partition(|w| w.id == id);
+            *watches = kept;
+            removed
+        };
+        for cookie in removed.iter().filter_map(|w| w.cookie) {
+            self.app.uninhibit(cookie);
+        }
+        self.notify();
+    }
+
+    /// Allow or forbid inhibiting; active watches take or drop their inhibits
+    pub fn set_enabled(&self, enabled: bool) {
+        if self.enabled.replace(enabled) == enabled {
+            return;
+        }
+        for watch in self.watches.borrow_mut().iter_mut() {
+            if enabled {
+                watch.cookie = Some(self.take_inhibit(&watch.window, &watch.reason));
+            } else if let Some(cookie) = watch.cookie.take() {
+                self.app.uninhibit(cookie);
+            }
+        }
+        self.notify();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    /// Reasons of the watches that currently hold an inhibit
+    pub fn active_reasons(&self) -> Vec<String> {
+        self.watches
+            .borrow()
+            .iter()
+            .filter(|w| w.cookie.is_some())
+            .map(|w| w.reason.clone())
+            .collect()
+    }
+
+    /// Get notified when watches or the enabled state change
+    pub fn connect_changed<F: Fn(&SuspendInhibitor) -> glib::ControlFlow + 'static>(&self, f: F) {
+        self.listeners.borrow_mut().push(Box::new(f));
+    }
+
+    fn notify(&self) {
+        // Listeners may register watches themselves, so don't hold the borrow while calling
+        let listeners = std::mem::take(&mut *self.listeners.borrow_mut());
+        let mut kept: Vec<_> = listeners
+            .into_iter()
+            .filter(|listener| listener(self) == glib::ControlFlow::Continue)
+            .collect();
+        let mut current = self.listeners.borrow_mut();
+        kept.append(&mut current);
+        *current = kept;
+    }
+}
@@ -0,0 +1,193 @@
+//! "Limit Resources" dialog setting cgroup v2 CPU, memory and I/O limits
+
+use gtk4::prelude::*;
+use gtk4::Button;
+use libadwaita as adw;
+use adw::prelude::*;
+
+use procular_core::cgroup_limits::{apply_limits, read_process_cgroup, ResourceLimits, DEFAULT_IO_WEIGHT};
+use procular_core::process_actions::run_in_background;
+
+const MIB: u64 = 1024 * 1024;
+
+/// Show the limits dialog for one or more processes; current values come from the first
+pub fn show_limits_dialog(parent: &impl IsA<gtk4::Window>, selected: Vec<(u32, String)>) {
+    let Some((first_pid, first_name)) = selected.first().cloned() else {
+        return;
+    };
+    let dialog = adw::Window::builder()
+        .title("Limit Resources")
+        .transient_for(parent)
+        .modal(true)
+        .default_width(480)
+        .default_height(520)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    let header = adw::HeaderBar::new();
+    let cancel_btn = Button::with_label("Cancel");
+    header.pack_start(&cancel_btn);
+    let apply_btn = Button::with_label("Apply");
+    apply_btn.add_css_class("suggested-action");
+    header.pack_end(&apply_btn);
+    toolbar.add_top_bar(&header);
+    dialog.set_content(Some(&toolbar));
+
+    let dialog_weak = dialog.downgrade();
+    cancel_btn.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+
+    let cgroup = match read_process_cgroup(first_pid) {
+        Ok(cgroup) => cgroup,
+        Err(e) => {
+            apply_btn.set_sensitive(false);
+            let status = adw::StatusPage::builder()
+                .icon_name("dialog-warning-symbolic")
+                .title("Limits Not Available")
+                .description(e.to_string())
+                .build();
+            toolbar.set_content(Some(&status));
+            dialog.present();
+            return;
+        }
+    };
+
+    let page = adw::PreferencesPage::new();
+    let info_group = adw::PreferencesGroup::builder()
+        .title("Control Group")
+        .description(
+            "Limits are set on a systemd scope. A process that does not run alone in a scope \
+             is moved into a new one first.",
+        )
+        .build();
+    let path_row = adw::ActionRow::builder()
+        .title(if selected.len() == 1 {
+            format!("{} (PID {})", first_name, first_pid)
+        } else {
+            format!("{} processes; showing {} (PID {})", selected.len(), first_name, first_pid)
+        })
+        .subtitle(&cgroup.path)
+        .subtitle_selectable(true)
+        .build();
+    path_row.add_css_class("property");
+    info_group.add(&path_row);
+    page.add(&info_group);
+
+    let limits_group = adw::PreferencesGroup::builder()
+        .title("Limits")
+        .description("Turn a limit off to remove it")
+        .build();
+    let current = cgroup.limits;
+
+    let cpu_count = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let cpu_expander = limit_expander("CPU Time", "Throttles the process instead of stopping it", current.cpu_percent.is_some());
+    let cpu_row = adw::SpinRow::builder()
+        .title("Percent of One CPU")
+        .subtitle("200% allows two full CPUs")
+        .adjustment(&gtk4::Adjustment::new(
+            current.cpu_percent.unwrap_or(50) as f64,
+            1.0,
+            (cpu_count * 100) as f64,
+            5.0,
+            50.0,
+            0.0,
+        ))
+        .build();
+    cpu_expander.add_row(&cpu_row);
+    limits_group.add(&cpu_expander);
+
+    let memory_expander = limit_expander(
+        "Memory",
+        "Above the limit the kernel reclaims memory and, as a last resort, ends the process",
+        current.memory_max.is_some(),
+    );
+    let memory_row = adw::SpinRow::builder()
+        .title("Maximum (MiB)")
+        .adjustment(&gtk4::Adjustment::new(
+            current.memory_max.map_or(1024, |bytes| bytes / MIB) as f64,
+            16.0,
+            1024.0 * 1024.0,
+            64.0,
+            1024.0,
+            0.0,
+        ))
+        .build();
+    memory_expander.add_row(&memory_row);
+    limits_group.add(&memory_expander);
+
+    let io_expander = limit_expander(
+        "I/O Weight",
+        "Share of disk time while other processes compete for it",
+        current.io_weight.is_some(),
+    );
+    let io_row = adw::SpinRow::builder()
+        .title("Weight")
+        .subtitle(format!("1-10000; the default is {}", DEFAULT_IO_WEIGHT))
+        .adjustment(&gtk4::Adjustment::new(
+            current.io_weight.unwrap_or(50) as f64,
+            1.0,
+            10000.0,
+            10.0,
+            100.0,
+            0.0,
+        ))
+        .build();
+    io_expander.add_row(&io_row);
+    limits_group.add(&io_expander);
+    page.add(&limits_group);
+    toolbar.set_content(Some(&page));
+
+    let dialog_weak = dialog.downgrade();
+    apply_btn.connect_clicked(move |_| {
+        let limits = ResourceLimits {
+            cpu_percent: cpu_expander.enables_expansion().then(|| cpu_row.value() as u32),
+            memory_max: memory_expander.enables_expansion().then(|| memory_row.value() as u64 * MIB),
+            io_weight: io_expander.enables_expansion().then(|| io_row.value() as u32),
+        };
+        let (selected, dialog_weak) = (selected.clone(), dialog_weak.clone());
+        glib::spawn_future_local(async move {
+            let errors = run_in_background(move || {
+                Ok(selected
+                    .iter()
+                    .filter_map(|(pid, name)| {
+                        apply_limits(*pid, &limits)
+                            .err()
+                            .map(|e| format!("{} ({}): {}", name, pid, e))
+                    })
+                    .collect::<Vec<String>>())
+            })
+            .await
+            .unwrap_or_else(|e| vec![e.to_string()]);
+            let Some(dialog) = dialog_weak.upgrade() else {
+                return;
+            };
+            if errors.is_empty() {
+                dialog.close();
+                return;
+            }
+            let error = adw::MessageDialog::builder()
+                .transient_for(&dialog)
+                .heading("Failed to set limits")
+                .body(errors.join("\n"))
+                .build();
+            error.add_response("ok", "OK");
+            error.present();
+        });
+    });
+
+    dialog.present();
+}
+
+/// Expander with an enable switch for one kind of limit
+fn limit_expander(title: &str, subtitle: &str, enabled: bool) -> adw::ExpanderRow {
+    adw::ExpanderRow::builder()
+        .title(title)
+        .subtitle(subtitle)
+        .show_enable_switch(true)
+        .enable_expansion(enabled)
+        .expanded(enabled)
+        .build()
+}
@@ -0,0 +1,103 @@
+//! Read-only mode for shared and demo machines
+//!
+//! While read-only, everything that changes a process (signals, priority, affinity,
+//! automation rules) is hidden or disabled; monitoring keeps working. The mode is a
+//! user preference, but `--read-only` or the system lockdown file force it on.
+
+use gtk4::gio;
+use gtk4::prelude::*;
+use std::cell::{Cell, RefCell};
+
+/// Administrators set `read-only=true` in the `Lockdown` group to enforce read-only mode
+const SYSTEM_LOCKDOWN_FILE: &str = "/etc/procular/lockdown.ini";
+const LOCKDOWN_GROUP: &str = "Lockdown";
+
+/// Whether the system lockdown file enforces read-only mode
+pub fn system_enforces_read_only() -> bool {
+    let key_file = glib::KeyFile::new();
+    key_file
+        .load_from_file(SYSTEM_LOCKDOWN_FILE, glib::KeyFileFlags::NONE)
+        .is_ok()
+        && key_file.boolean(LOCKDOWN_GROUP, "read-only").unwrap_or(false)
+}
+
+type Listener = Box<dyn Fn(bool) -> glib::ControlFlow>;
+
+pub struct Lockdown {
+    read_only: Cell<bool>,
+    /// Forced by the command line or the system lockdown file; the preference cannot lift it
+    enforced: Cell<bool>,
+    /// Called with the new state when read-only mode changes; Break unregisters
+    listeners: RefCell<Vec<Listener>>,
+}
+
+impl Lockdown {
+    pub fn new(read_only: bool, enforced: bool) -> Self {
+        Self {
+            read_only: Cell::new(read_only || enforced),
+            enforced: Cell::new(enforced),
+            listeners: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.get()
+    }
+
+    pub fn is_enforced(&self) -> bool {
+        self.enforced.get()
+    }
+
+    /// Force read-only mode on for the rest of the session, as `--read-only` does
+    pub fn enforce(&self) {
+        self.enforced.set(true);
+        self.set_read_only(true);
+    }
+
+    /// Change read-only mode from the preference (ignored while enforced)
+    pub fn set_read_only(&self, read_only: bool) {
+        let read_only = read_only || self.enforced.get();
+        if self.read_only.replace(read_only) == read_only {
+            return;
+        }
+        let listeners = std::mem::take(&mut *self.listeners.borrow_mut());
+        let mut kept: Vec<_> = listeners
+            .into_iter()
+            .filter(|listener| listener(read_only) == glib::ControlFlow::Continue)
+            .collect();
+        let mut current = self.listeners.borrow_mut();
+        kept.append(&mut current);
+        *current = kept;
+    }
+
+    /// Call `f` with the current state now and whenever it changes; Break unregisters
+    pub fn connect_changed<F: Fn(bool) -> glib::ControlFlow + 'static>(&self, f: F) {
+        if f(self.is_read_only()) == glib::ControlFlow::Continue {
+            self.listeners.borrow_mut().push(Box::new(f));
+        }
+    }
+
+    /// Hide `widget` while read-only
+    pub fn bind_widget(&self, widget: &impl IsA<gtk4::Widget>) {
+        let widget = widget.upcast_ref::<gtk4::Widget>().downgrade();
+        self.connect_changed(move |read_only| match widget.upgrade() {
+            Some(widget) => {
+                widget.set_visible(!read_only);
+                glib::ControlFlow::Continue
+            }
+            None => glib::ControlFlow::Break,
+        });
+    }
+
+    /// Disable `action` while read-only
+    pub fn bind_action(&self, action: &gio::SimpleAction) {
+        let action = action.downgrade();
+        self.connect_changed(move |read_only| match action.upgrade() {
+            Some(action) => {
+                action.set_enabled(!read_only);
+                glib::ControlFlow::Continue
+            }
+            None => glib::ControlFlow::Break,
+        });
+    }
+}
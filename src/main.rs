@@ -1,17 +1,75 @@
+mod adaptive;
+mod app_state;
+mod audit_log;
+mod audit_log_dialog;
+mod automation;
+mod budgets;
+mod budgets_dialog;
+mod compare_window;
+mod config;
+mod connections_view;
 mod context_menu;
+mod cpu_view;
+mod dbus_service;
 mod detail_view;
-mod monitor;
-mod process_actions;
+mod diagnosis_dialog;
+mod disks_view;
+mod environment_view;
+mod filter_query;
+mod filesystems_dialog;
+mod gpu_view;
+mod graceful_end;
+mod graph;
+mod headless;
+mod help;
+mod idle_dialog;
+mod inhibit;
+mod limits_dialog;
+mod lockdown;
+mod memory_view;
+mod mini_window;
+mod offenders_dialog;
+mod onboarding;
+mod open_files_view;
+mod preferences;
 mod process_list;
 mod process_window;
+mod properties_dialog;
+mod rules_dialog;
+mod sensors_view;
+mod settings;
+mod shortcuts;
+mod snapshot_dialog;
+mod startup_dialog;
+mod status_icon;
+mod system_memory_view;
+mod table_view;
+mod threads_view;
+mod top_bar;
+mod unit_dialog;
+mod users_view;
 mod window;
+mod window_state;
 
 use gtk4::prelude::*;
 use libadwaita as adw;
 
+use procular_core::process_actions::is_process_running;
+
 const APP_ID: &str = "org.procular.ProcessMonitor";
 
+/// The main window, if one is open; the top bar is an application window too
+fn main_window(app: &adw::Application) -> Option<adw::ApplicationWindow> {
+    app.windows().into_iter().find_map(|window| window.downcast::<adw::ApplicationWindow>().ok())
+}
+
 fn main() -> glib::ExitCode {
+    // Headless mode never touches GTK, so it works without a display
+    let args: Vec<String> = std::env::args().collect();
+    if headless::requested(&args) {
+        return headless::run(&args);
+    }
+
     // Initialize GTK
     gtk4::init().expect("Failed to initialize GTK4");
 
@@ -24,14 +82,76 @@ fn main() -> glib::ExitCode {
         }
     }
 
-    // Create the application
+    // Heatmap shades of the process list
+    if let Some(display) = gtk4::gdk::Display::default() {
+        let provider = gtk4::CssProvider::new();
+        provider.load_from_string(process_list::HEATMAP_CSS);
+        gtk4::style_context_add_provider_for_display(&display, &provider, gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION);
+    }
+
+    // Create the application; the command line of a second launch goes to the running instance
     let app = adw::Application::builder()
         .application_id(APP_ID)
+        .flags(gtk4::gio::ApplicationFlags::HANDLES_COMMAND_LINE)
         .build();
 
-    app.connect_activate(|app| {
-        let window = window::ProcularWindow::build(app);
-        window.present();
+    app.add_main_option(
+        "read-only",
+        glib::Char::from(b'\0'),
+        glib::OptionFlags::NONE,
+        glib::OptionArg::None,
+        "Hide actions that change processes (kill, priority, affinity)",
+        None,
+    );
+    app.add_main_option(
+        "pid",
+        glib::Char::from(b'\0'),
+        glib::OptionFlags::NONE,
+        glib::OptionArg::Int,
+        "Select the process and open its process window",
+        Some("PID"),
+    );
+    app.add_main_option(
+        "search",
+        glib::Char::from(b'\0'),
+        glib::OptionFlags::NONE,
+        glib::OptionArg::String,
+        "Filter the process list, like typing into the search box",
+        Some("TEXT"),
+    );
+    app.connect_handle_local_options(|_, options| {
+        // Checked here so that the error is printed in the terminal that launched us
+        if let Ok(Some(pid)) = options.lookup::<i32>("pid") {
+            if !u32::try_from(pid).is_ok_and(is_process_running) {
+                eprintln!("No process with PID {}", pid);
+                return 1;
+            }
+        }
+        -1 // Continue with the default handling
+    });
+
+    // Launching again raises the open window instead of starting a second monitor
+    app.connect_activate(|app| match main_window(app) {
+        Some(window) => window.present(),
+        None => window::ProcularWindow::build(app).present(),
+    });
+
+    app.connect_command_line(|app, command_line| {
+        app.activate();
+        let Some(window) = main_window(app) else {
+            return 0;
+        };
+        let options = command_line.options_dict();
+        if options.contains("read-only") {
+            ActionGroupExt::activate_action(&window, "enforce-read-only", None);
+        }
+        if let Ok(Some(text)) = options.lookup::<String>("search") {
+            ActionGroupExt::activate_action(&window, "search", Some(&text.to_variant()));
+        }
+        if let Ok(Some(pid)) = options.lookup::<i32>("pid") {
+            ActionGroupExt::activate_action(&window, "show-process", Some(&(pid as u32).to_variant()));
+        }
+        0
     });
 
     app.run()
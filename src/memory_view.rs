@@ -0,0 +1,153 @@
+//! "Memory" tab of the process window: smaps based breakdown and largest mappings
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Grid, Label, Orientation};
+
+use procular_core::memory_map::{read_smaps, read_smaps_rollup, SmapsRollup};
+use procular_core::monitor::format_bytes;
+
+use crate::help;
+use crate::table_view::{ColumnKind, TableView};
+
+/// Summary grid row: (label, tooltip, value getter)
+type SummaryRow = (&'static str, &'static str, fn(&SmapsRollup) -> u64);
+
+const SUMMARY_ROWS: &[SummaryRow] = &[
+    ("Resident (RSS)", "Pages currently in RAM, including shared pages", |r| r.rss),
+    ("Proportional (PSS)", "RSS with shared pages divided among the processes sharing them", |r| r.pss),
+    ("PSS Anonymous", "Heap, stacks and other anonymous memory", |r| r.pss_anon),
+    ("PSS File", "File-backed pages (code, libraries, mapped files)", |r| r.pss_file),
+    ("PSS Shared Memory", "tmpfs and shared memory segments", |r| r.pss_shmem),
+    ("Private Clean", "Unshared pages identical to their backing file", |r| r.private_clean),
+    ("Private Dirty", "Unshared modified pages; freed when the process exits", |r| r.private_dirty),
+    ("Shared Clean", "Shared pages identical to their backing file", |r| r.shared_clean),
+    ("Shared Dirty", "Shared modified pages", |r| r.shared_dirty),
+    ("Swap", "Pages swapped out to disk", |r| r.swap),
+    ("Swap (PSS)", "Swapped out pages divided among sharing processes", |r| r.swap_pss),
+];
+
+pub struct MemoryView {
+    pub widget: GtkBox,
+    status_label: Label,
+    value_labels: Vec<Label>,
+    table: TableView,
+}
+
+impl MemoryView {
+    pub fn new() -> Self {
+        let widget = GtkBox::new(Orientation::Vertical, 8);
+        widget.set_margin_top(12);
+        widget.set_margin_bottom(12);
+        widget.set_margin_start(12);
+        widget.set_margin_end(12);
+
+        let status_label = Label::new(None);
+        status_label.set_halign(gtk4::Align::Start);
+        status_label.set_wrap(true);
+        status_label.set_visible(false);
+        widget.append(&status_label);
+
+        let summary_header = GtkBox::new(Orientation::Horizontal, 8);
+        let summary_heading = Label::new(Some("Memory Breakdown"));
+        summary_heading.add_css_class("heading");
+        summary_header.append(&summary_heading);
+        summary_header.append(&help::help_button("pss"));
+        widget.append(&summary_header);
+
+        // Two columns of label/value pairs
+        let grid = Grid::new();
+        grid.add_css_class("card");
+        grid.set_column_spacing(16);
+        grid.set_row_spacing(4);
+        grid.set_margin_bottom(8);
+        let half = SUMMARY_ROWS.len().div_ceil(2);
+        let value_labels = SUMMARY_ROWS
+            .iter()
+            .enumerate()
+            .map(|(i, (title, tooltip, _))| {
+                let (row, col) = ((i % half) as i32, (i / half) as i32 * 2);
+                let name = Label::new(Some(title));
+                name.set_halign(gtk4::Align::Start);
+                name.add_css_class("dim-label");
+                name.set_tooltip_text(Some(tooltip));
+                name.set_margin_start(8);
+                grid.attach(&name, col, row, 1, 1);
+
+                let value = Label::new(Some("-"));
+                value.set_halign(gtk4::Align::End);
+                value.add_css_class("monospace");
+                value.set_margin_end(8);
+                grid.attach(&value, col + 1, row, 1, 1);
+                value
+            })
+            .collect();
+        widget.append(&grid);
+
+        let heading = Label::new(Some("Largest Mappings"));
+        heading.add_css_class("heading");
+        heading.set_halign(gtk4::Align::Start);
+        widget.append(&heading);
+
+        let table = TableView::new(&[
+            ("Address", ColumnKind::Short),
+            ("Perms", ColumnKind::Number),
+            ("Size", ColumnKind::Bytes),
+            ("RSS", ColumnKind::Bytes),
+            ("PSS", ColumnKind::Bytes),
+            ("Swap", ColumnKind::Bytes),
+            ("Mapping", ColumnKind::Text),
+        ]);
+        widget.append(&table.widget);
+
+        Self {
+            widget,
+            status_label,
+            value_labels,
+            table,
+        }
+    }
+
+    /// Re-read smaps_rollup and smaps for the process
+    pub fn refresh(&self, pid: u32) {
+        match read_smaps_rollup(pid) {
+            Ok(rollup) => {
+                self.status_label.set_visible(false);
+                for (label, (_, _, value)) in self.value_labels.iter().zip(SUMMARY_ROWS) {
+                    label.set_label(&format_bytes(value(&rollup)));
+                }
+            }
+            Err(e) => {
+                self.status_label
+                    .set_text(&format!("Cannot read memory details: {}", e));
+                self.status_label.set_visible(true);
+                for label in &self.value_labels {
+                    label.set_label("-");
+                }
+            }
+        }
+
+        let mappings = read_smaps(pid).unwrap_or_default();
+        self.table.set_rows(
+            mappings
+                .into_iter()
+                .filter(|m| m.rss > 0 || m.swap > 0)
+                .map(|m| {
+                    let name = if m.entry.pathname.is_empty() {
+                        "[anonymous]".to_string()
+                    } else {
+                        m.entry.pathname.clone()
+                    };
+                    vec![
+                        format!("{:012x}", m.entry.start),
+                        m.entry.perms.clone(),
+                        format_bytes(m.entry.size()),
+                        format_bytes(m.rss),
+                        format_bytes(m.pss),
+                        format_bytes(m.swap),
+                        name,
+                    ]
+                })
+                .collect(),
+        );
+    }
+}
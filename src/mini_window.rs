@@ -0,0 +1,215 @@
+//! Compact always-on-top window with CPU, memory and network gauges and the busiest processes,
+//! for keeping an eye on the system while gaming or benchmarking
+//!
+//! Like the top bar it has no monitor of its own and is fed by the main window's refresh.
+
+use gtk4::prelude::*;
+use gtk4::{gio, Box as GtkBox, Button, Grid, Label, LevelBar, Orientation, WindowHandle};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::process::Command;
+use std::time::Duration;
+
+use procular_core::monitor::{format_rate, ProcessInfo, SystemMonitor};
+use procular_core::swap::MemoryUsage;
+
+use crate::graph::Sparkline;
+
+/// Window title, also used to find the window with wmctrl
+const MINI_TITLE: &str = "Procular Mini";
+const MINI_WIDTH: i32 = 240;
+/// Busiest processes listed
+const TOP_PROCESSES: usize = 3;
+/// Samples in the network sparkline (one minute at the default refresh)
+const NET_SAMPLES: usize = 30;
+
+const NET_COLOR: (f64, f64, f64) = (0.839, 0.153, 0.157); // Red, same as the top bar
+
+/// Percentage bar with its name and value
+struct Gauge {
+    value: Label,
+    level: LevelBar,
+}
+
+impl Gauge {
+    fn new(grid: &Grid, row: i32, name: &str) -> Self {
+        let name = Label::new(Some(name));
+        name.add_css_class("caption");
+        name.set_halign(gtk4::Align::Start);
+        grid.attach(&name, 0, row, 1, 1);
+
+        let level = LevelBar::for_interval(0.0, 100.0);
+        level.set_hexpand(true);
+        level.set_valign(gtk4::Align::Center);
+        // The default offsets color low levels as bad, which is backwards for usage
+        for offset in [gtk4::LEVEL_BAR_OFFSET_LOW, gtk4::LEVEL_BAR_OFFSET_HIGH, gtk4::LEVEL_BAR_OFFSET_FULL] {
+            level.remove_offset_value(Some(offset));
+        }
+        grid.attach(&level, 1, row, 1, 1);
+
+        let value = Label::new(None);
+        value.add_css_class("caption");
+        value.add_css_class("numeric");
+        value.set_width_chars(4);
+        value.set_xalign(1.0);
+        grid.attach(&value, 2, row, 1, 1);
+
+        Self { value, level }
+    }
+
+    fn set(&self, percent: f64) {
+        self.value.set_text(&format!("{:.0}%", percent));
+        self.level.set_value(percent.clamp(0.0, 100.0));
+    }
+}
+
+/// The mini window
+pub struct MiniWindow {
+    pub window: gtk4::Window,
+    cpu: Gauge,
+    memory: Gauge,
+    network_label: Label,
+    network: Sparkline,
+    network_history: RefCell<VecDeque<f64>>,
+    process_buttons: Vec<Button>,
+}
+
+impl MiniWindow {
+    /// Create the window; `main_window` provides the `win.*` actions its buttons use
+    pub fn new(app: &impl IsA<gtk4::Application>, main_window: &impl IsA<gio::ActionGroup>) -> Self {
+        let window = gtk4::Window::builder()
+            .application(app)
+            .title(MINI_TITLE)
+            .decorated(false)
+            .resizable(false)
+            .default_width(MINI_WIDTH)
+            .build();
+        window.insert_action_group("win", Some(main_window));
+
+        let content = GtkBox::new(Orientation::Vertical, 6);
+        content.set_margin_start(10);
+        content.set_margin_end(4);
+        content.set_margin_top(4);
+        content.set_margin_bottom(8);
+
+        let header = GtkBox::new(Orientation::Horizontal, 4);
+        let title = Label::new(Some("Procular"));
+        title.add_css_class("heading");
+        title.set_hexpand(true);
+        title.set_halign(gtk4::Align::Start);
+        header.append(&title);
+        let close_btn = Button::from_icon_name("window-close-symbolic");
+        close_btn.add_css_class("flat");
+        close_btn.set_tooltip_text(Some("Close Mini Window"));
+        header.append(&close_btn);
+        let window_weak = window.downgrade();
+        close_btn.connect_clicked(move |_| {
+            if let Some(window) = window_weak.upgrade() {
+                window.close();
+            }
+        });
+        content.append(&header);
+
+        let grid = Grid::new();
+        grid.set_column_spacing(8);
+        grid.set_row_spacing(4);
+        grid.set_margin_end(6);
+        let cpu = Gauge::new(&grid, 0, "CPU");
+        let memory = Gauge::new(&grid, 1, "Memory");
+
+        let network_name = Label::new(Some("Network"));
+        network_name.add_css_class("caption");
+        network_name.set_halign(gtk4::Align::Start);
+        grid.attach(&network_name, 0, 2, 1, 1);
+        let network = Sparkline::new(NET_COLOR, None);
+        network.drawing_area.set_size_request(-1, 20);
+        network.drawing_area.set_hexpand(true);
+        grid.attach(&network.drawing_area, 1, 2, 2, 1);
+        let network_label = Label::new(None);
+        network_label.add_css_class("caption");
+        network_label.add_css_class("numeric");
+        network_label.set_halign(gtk4::Align::End);
+        grid.attach(&network_label, 0, 3, 3, 1);
+        content.append(&grid);
+
+        let process_box = GtkBox::new(Orientation::Vertical, 0);
+        let process_buttons: Vec<Button> = (0..TOP_PROCESSES)
+            .map(|_| {
+                let label = Label::new(None);
+                label.add_css_class("caption");
+                label.set_halign(gtk4::Align::Start);
+                label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+                let button = Button::builder().child(&label).build();
+                button.add_css_class("flat");
+                button.set_tooltip_text(Some("Open a process window"));
+                button.set_margin_end(6);
+                process_box.append(&button);
+                button
+            })
+            .collect();
+        content.append(&process_box);
+
+        // Dragging the window moves it
+        let handle = WindowHandle::new();
+        handle.set_child(Some(&content));
+        window.set_child(Some(&handle));
+
+        window.connect_map(|_| {
+            // The window manager has to have managed the window before it can change its state
+            glib::timeout_add_local_once(Duration::from_millis(300), keep_above);
+        });
+
+        Self {
+            window,
+            cpu,
+            memory,
+            network_label,
+            network,
+            network_history: RefCell::new(VecDeque::new()),
+            process_buttons,
+        }
+    }
+
+    /// Add the latest refresh; `processes` is sorted busiest first
+    pub fn update(&self, monitor: &SystemMonitor, usage: &MemoryUsage, processes: &[ProcessInfo], cpu_scale: f32) {
+        self.cpu.set(monitor.cpu_percent());
+        self.memory.set(usage.used_percent());
+
+        let rx = monitor.net_rx_rate();
+        let tx = monitor.net_tx_rate();
+        self.network_label
+            .set_text(&format!("↓ {} ↑ {}", format_rate(rx), format_rate(tx)));
+        let mut history = self.network_history.borrow_mut();
+        history.push_back((rx + tx) as f64);
+        while history.len() > NET_SAMPLES {
+            history.pop_front();
+        }
+        self.network.update(&history.iter().copied().collect::<Vec<_>>());
+
+        for (i, button) in self.process_buttons.iter().enumerate() {
+            let label = button.child().and_downcast::<Label>();
+            match processes.get(i) {
+                Some(process) => {
+                    if let Some(label) = label {
+                        label.set_text(&format!("{:.0}%  {}", process.total_cpu() * cpu_scale, process.name));
+                    }
+                    button.set_action_name(Some("win.open-process"));
+                    button.set_action_target_value(Some(&(process.pid, process.name.clone()).to_variant()));
+                    button.set_visible(true);
+                }
+                None => {
+                    button.set_action_name(None);
+                    button.set_visible(false);
+                }
+            }
+        }
+    }
+}
+
+/// Keep the window above other windows on every workspace. GTK 4 cannot do this, so it asks the
+/// window manager through wmctrl; it has no effect on Wayland
+fn keep_above() {
+    let _ = Command::new("wmctrl")
+        .args(["-F", "-r", MINI_TITLE, "-b", "add,above,sticky"])
+        .output();
+}
@@ -0,0 +1,196 @@
+//! Top offenders window: which programs used the most CPU, memory, disk and network recently
+
+use gtk4::prelude::*;
+use gtk4::{gio, Box as GtkBox, Button, DropDown, Label, Orientation, StringList};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+use procular_core::monitor::{format_bytes, format_duration, format_rate};
+use procular_core::offenders::{reports_to_csv, Offender, OffenderMetric};
+
+use crate::app_state::AppState;
+use crate::table_view::{ColumnKind, TableView};
+
+/// Report periods (label, seconds)
+const PERIODS: &[(&str, u64)] = &[
+    ("Last 15 min", 15 * 60),
+    ("Last hour", 60 * 60),
+    ("Last 4 hours", 4 * 60 * 60),
+    ("Last day", 24 * 60 * 60),
+];
+const DEFAULT_PERIOD: u32 = 1; // Last hour
+
+/// Programs listed per metric
+const REPORT_LIMIT: usize = 25;
+
+/// Table cells of one program: average, peak, total and running time
+fn offender_row(metric: OffenderMetric, offender: &Offender) -> Vec<String> {
+    let (average, peak, total) = match metric {
+        OffenderMetric::Cpu => (
+            format!("{:.1}%", offender.average),
+            format!("{:.1}%", offender.peak),
+            offender.total.map(|secs| format!("{:.0} s", secs)),
+        ),
+        OffenderMetric::Memory => (
+            format_bytes(offender.average as u64),
+            format_bytes(offender.peak as u64),
+            None,
+        ),
+        OffenderMetric::Disk | OffenderMetric::Network => (
+            format_rate(offender.average as u64),
+            format_rate(offender.peak as u64),
+            offender.total.map(|bytes| format_bytes(bytes as u64)),
+        ),
+    };
+    vec![
+        offender.name.clone(),
+        average,
+        peak,
+        total.unwrap_or_else(|| "-".to_string()),
+        format_duration(offender.running),
+    ]
+}
+
+/// Show the top offenders report with CSV export
+pub fn show_offenders_dialog(parent: &impl IsA<gtk4::Window>, state: &AppState) {
+    let dialog = adw::Window::builder()
+        .title("Top Offenders")
+        .transient_for(parent)
+        .default_width(760)
+        .default_height(520)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+    let period_options = StringList::new(&PERIODS.iter().map(|(label, _)| *label).collect::<Vec<_>>());
+    let period_dropdown = DropDown::new(Some(period_options), gtk4::Expression::NONE);
+    period_dropdown.set_selected(DEFAULT_PERIOD);
+    header.pack_start(&period_dropdown);
+    let metric_options =
+        StringList::new(&OffenderMetric::all().iter().map(|metric| metric.as_str()).collect::<Vec<_>>());
+    let metric_dropdown = DropDown::new(Some(metric_options), gtk4::Expression::NONE);
+    header.pack_start(&metric_dropdown);
+
+    let csv_btn = Button::with_label("Export CSV…");
+    header.pack_end(&csv_btn);
+    let refresh_btn = Button::from_icon_name("view-refresh-symbolic");
+    refresh_btn.set_tooltip_text(Some("Refresh"));
+    header.pack_end(&refresh_btn);
+    main_box.append(&header);
+
+    let toast_overlay = adw::ToastOverlay::new();
+    let content = GtkBox::new(Orientation::Vertical, 8);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+    content.set_margin_start(12);
+    content.set_margin_end(12);
+
+    let summary_label = Label::new(None);
+    summary_label.set_halign(gtk4::Align::Start);
+    summary_label.set_wrap(true);
+    content.append(&summary_label);
+
+    let table = TableView::new(&[
+        ("Program", ColumnKind::Text),
+        ("Average", ColumnKind::Bytes),
+        ("Peak", ColumnKind::Bytes),
+        ("Total", ColumnKind::Bytes),
+        ("Running", ColumnKind::Short),
+    ]);
+    content.append(&table.widget);
+
+    let note = Label::new(Some(
+        "Processes are combined by program name. Averages cover the time a program was running; \
+         CPU totals are in core-seconds. Usage is only recorded while Procular runs, in five-minute \
+         steps, and only the busiest programs of each step are kept.",
+    ));
+    note.add_css_class("dim-label");
+    note.set_halign(gtk4::Align::Start);
+    note.set_wrap(true);
+    content.append(&note);
+
+    toast_overlay.set_child(Some(&content));
+    main_box.append(&toast_overlay);
+    dialog.set_content(Some(&main_box));
+
+    let selected_period = {
+        let dropdown = period_dropdown.clone();
+        move || PERIODS[dropdown.selected() as usize % PERIODS.len()]
+    };
+
+    let populate: Rc<dyn Fn()> = {
+        let state = state.clone();
+        let selected_period = selected_period.clone();
+        let metric_dropdown = metric_dropdown.clone();
+        Rc::new(move || {
+            let (label, secs) = selected_period();
+            let metric = OffenderMetric::all()[metric_dropdown.selected() as usize % OffenderMetric::all().len()];
+            let period = Duration::from_secs(secs);
+            let now = SystemTime::now();
+            let monitor = state.monitor.borrow();
+            let log = monitor.offenders();
+            let covered = log.covered(period, now);
+            summary_label.set_text(&if covered < period {
+                format!(
+                    "Top {} users of the {} recorded so far ({} requested).",
+                    metric.as_str(),
+                    format_duration(covered),
+                    label.to_lowercase()
+                )
+            } else {
+                format!("Top {} users of the {}.", metric.as_str(), label.to_lowercase())
+            });
+            let offenders = log.report(metric, period, now, REPORT_LIMIT);
+            table.set_rows(offenders.iter().map(|offender| offender_row(metric, offender)).collect());
+        })
+    };
+    populate();
+
+    let populate_clone = populate.clone();
+    period_dropdown.connect_selected_notify(move |_| populate_clone());
+    let populate_clone = populate.clone();
+    metric_dropdown.connect_selected_notify(move |_| populate_clone());
+    refresh_btn.connect_clicked(move |_| populate());
+
+    let dialog_weak = dialog.downgrade();
+    let state = state.clone();
+    csv_btn.connect_clicked(move |_| {
+        let Some(dialog) = dialog_weak.upgrade() else {
+            return;
+        };
+        let period = Duration::from_secs(selected_period().1);
+        let now = SystemTime::now();
+        let reports: Vec<(OffenderMetric, Vec<Offender>)> = {
+            let monitor = state.monitor.borrow();
+            OffenderMetric::all()
+                .iter()
+                .map(|&metric| (metric, monitor.offenders().report(metric, period, now, REPORT_LIMIT)))
+                .collect()
+        };
+        let text = reports_to_csv(&reports);
+        let toast_overlay = toast_overlay.clone();
+        let file_dialog = gtk4::FileDialog::builder()
+            .title("Export Top Offenders")
+            .initial_name("procular-top-offenders.csv")
+            .build();
+        file_dialog.save(Some(&dialog), gio::Cancellable::NONE, move |result| {
+            // Cancelled dialogs also end up here
+            let Ok(file) = result else {
+                return;
+            };
+            let Some(path) = file.path() else {
+                return;
+            };
+            let message = match std::fs::write(&path, &text) {
+                Ok(()) => format!("Exported to {}", path.display()),
+                Err(e) => format!("Export failed: {}", e),
+            };
+            toast_overlay.add_toast(adw::Toast::new(&message));
+        });
+    });
+
+    dialog.present();
+}
@@ -0,0 +1,166 @@
+//! First-run setup assistant listing missing capabilities and how to enable them
+
+use gtk4::prelude::*;
+use gtk4::gdk::Display;
+use gtk4::{Box as GtkBox, Button, Label, Orientation};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use procular_core::capabilities::{check_capabilities, Capability, CapabilityStatus};
+
+use crate::app_state::AppState;
+
+/// Show the assistant once, on the first start
+pub fn show_on_first_run(parent: &impl IsA<gtk4::Window>, state: &AppState) {
+    if !state.settings.borrow().onboarding_complete {
+        show_setup_assistant(parent, state);
+    }
+}
+
+/// Show the setup assistant
+pub fn show_setup_assistant(parent: &impl IsA<gtk4::Window>, state: &AppState) {
+    let dialog = adw::Window::builder()
+        .title("Set Up Procular")
+        .transient_for(parent)
+        .modal(true)
+        .default_width(560)
+        .default_height(600)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    let header = adw::HeaderBar::new();
+    let recheck_btn = Button::from_icon_name("view-refresh-symbolic");
+    recheck_btn.set_tooltip_text(Some("Check Again"));
+    header.pack_start(&recheck_btn);
+    let done_btn = Button::with_label("Done");
+    done_btn.add_css_class("suggested-action");
+    header.pack_end(&done_btn);
+    toolbar.add_top_bar(&header);
+
+    let page = adw::PreferencesPage::new();
+    let intro = adw::PreferencesGroup::builder()
+        .title("Welcome to Procular")
+        .description(
+            "Procular works without extra permissions, but some information and actions need \
+             system setup. Expand a missing item for the commands that enable it; they need \
+             administrator rights, so run them in a terminal.",
+        )
+        .build();
+    page.add(&intro);
+
+    let toast_overlay = adw::ToastOverlay::new();
+    toast_overlay.set_child(Some(&page));
+    toolbar.set_content(Some(&toast_overlay));
+    dialog.set_content(Some(&toolbar));
+
+    // Rebuilt on every check, since groups can't be cleared
+    let checks_group: Rc<RefCell<Option<adw::PreferencesGroup>>> = Rc::default();
+    let populate = {
+        let page = page.clone();
+        let toast_overlay = toast_overlay.clone();
+        move || {
+            if let Some(group) = checks_group.borrow_mut().take() {
+                page.remove(&group);
+            }
+            let capabilities = check_capabilities();
+            let missing = capabilities
+                .iter()
+                .filter(|c| c.status == CapabilityStatus::Missing)
+                .count();
+            let group = adw::PreferencesGroup::builder()
+                .title("Capabilities")
+                .description(match missing {
+                    0 => "Everything Procular can use is set up".to_string(),
+                    1 => "1 capability is missing".to_string(),
+                    n => format!("{} capabilities are missing", n),
+                })
+                .build();
+            for capability in &capabilities {
+                group.add(&create_capability_row(capability, &toast_overlay));
+            }
+            page.add(&group);
+            *checks_group.borrow_mut() = Some(group);
+        }
+    };
+    populate();
+    recheck_btn.connect_clicked(move |_| populate());
+
+    // Closing the assistant either way counts as having seen it
+    let state_clone = state.clone();
+    dialog.connect_close_request(move |_| {
+        let mut settings = state_clone.settings.borrow_mut();
+        if !settings.onboarding_complete {
+            settings.onboarding_complete = true;
+            if let Err(e) = settings.save() {
+                eprintln!("Failed to save preferences: {}", e);
+            }
+        }
+        glib::Propagation::Proceed
+    });
+    let dialog_weak = dialog.downgrade();
+    done_btn.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+
+    dialog.present();
+}
+
+/// Row for one capability; missing ones expand to show how to enable them
+fn create_capability_row(capability: &Capability, toast_overlay: &adw::ToastOverlay) -> gtk4::Widget {
+    let (icon, css_class) = match capability.status {
+        CapabilityStatus::Available => ("emblem-ok-symbolic", "success"),
+        CapabilityStatus::Missing => ("dialog-warning-symbolic", "warning"),
+        CapabilityStatus::NotNeeded => ("action-unavailable-symbolic", "dim-label"),
+    };
+    let status_icon = gtk4::Image::from_icon_name(icon);
+    status_icon.add_css_class(css_class);
+
+    let Some(fix) = &capability.fix else {
+        let row = adw::ActionRow::builder()
+            .title(capability.title)
+            .subtitle(&capability.detail)
+            .build();
+        row.add_prefix(&status_icon);
+        return row.upcast();
+    };
+
+    let row = adw::ExpanderRow::builder()
+        .title(capability.title)
+        .subtitle(&capability.detail)
+        .build();
+    row.add_prefix(&status_icon);
+
+    let fix_box = GtkBox::new(Orientation::Horizontal, 8);
+    fix_box.set_margin_top(8);
+    fix_box.set_margin_bottom(8);
+    fix_box.set_margin_start(12);
+    fix_box.set_margin_end(12);
+    let fix_label = Label::new(Some(fix));
+    fix_label.add_css_class("monospace");
+    fix_label.set_selectable(true);
+    fix_label.set_wrap(true);
+    fix_label.set_xalign(0.0);
+    fix_label.set_hexpand(true);
+    fix_box.append(&fix_label);
+
+    let copy_btn = Button::from_icon_name("edit-copy-symbolic");
+    copy_btn.set_tooltip_text(Some("Copy Commands"));
+    copy_btn.set_valign(gtk4::Align::Center);
+    copy_btn.add_css_class("flat");
+    let fix = fix.clone();
+    let toast_overlay = toast_overlay.clone();
+    copy_btn.connect_clicked(move |_| {
+        if let Some(display) = Display::default() {
+            display.clipboard().set_text(&fix);
+            toast_overlay.add_toast(adw::Toast::new("Commands copied to clipboard"));
+        }
+    });
+    fix_box.append(&copy_btn);
+    row.add_row(&fix_box);
+
+    row.upcast()
+}
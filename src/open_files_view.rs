@@ -0,0 +1,73 @@
+//! "Open Files" tab of the process window
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Label, Orientation};
+
+use procular_core::open_files::{read_open_files, FdKind};
+
+use crate::table_view::{ColumnKind, TableView};
+
+pub struct OpenFilesView {
+    pub widget: GtkBox,
+    summary_label: Label,
+    table: TableView,
+}
+
+impl OpenFilesView {
+    pub fn new() -> Self {
+        let widget = GtkBox::new(Orientation::Vertical, 8);
+        widget.set_margin_top(12);
+        widget.set_margin_bottom(12);
+        widget.set_margin_start(12);
+        widget.set_margin_end(12);
+
+        let summary_label = Label::new(None);
+        summary_label.set_halign(gtk4::Align::Start);
+        summary_label.set_wrap(true);
+        widget.append(&summary_label);
+
+        let table = TableView::new(&[
+            ("FD", ColumnKind::Number),
+            ("Type", ColumnKind::Short),
+            ("Flags", ColumnKind::Short),
+            ("Target", ColumnKind::Text),
+        ]);
+        widget.append(&table.widget);
+
+        Self {
+            widget,
+            summary_label,
+            table,
+        }
+    }
+
+    /// Re-read /proc/<pid>/fd and update the table
+    pub fn refresh(&self, pid: u32) {
+        let files = match read_open_files(pid) {
+            Ok(files) => files,
+            Err(e) => {
+                self.summary_label
+                    .set_text(&format!("Cannot read open files: {}", e));
+                self.table.set_rows(Vec::new());
+                return;
+            }
+        };
+
+        let count = |kind: FdKind| files.iter().filter(|f| f.kind == kind).count();
+        self.summary_label.set_text(&format!(
+            "{} open descriptors: {} files, {} sockets, {} pipes, {} other",
+            files.len(),
+            count(FdKind::File) + count(FdKind::Directory),
+            count(FdKind::Socket),
+            count(FdKind::Pipe),
+            count(FdKind::Device) + count(FdKind::AnonInode) + count(FdKind::Other),
+        ));
+
+        self.table.set_rows(
+            files
+                .into_iter()
+                .map(|f| vec![f.fd.to_string(), f.kind.label().to_string(), f.flags, f.target])
+                .collect(),
+        );
+    }
+}
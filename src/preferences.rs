@@ -0,0 +1,525 @@
+//! Preferences window
+
+use gtk4::prelude::*;
+use libadwaita as adw;
+use adw::prelude::*;
+
+use procular_core::developer_tools::{Tracer, DEFAULT_DEBUGGER_COMMAND};
+use procular_core::monitor::Collectors;
+
+use crate::app_state::AppState;
+use crate::graph;
+use crate::settings::{parse_name_list, ScreenEdge, Settings, MAX_PROCESS_LIMIT, MIN_PROCESS_LIMIT};
+
+/// Show the preferences window
+pub fn show_preferences(parent: &impl IsA<gtk4::Window>, state: &AppState) {
+    let window = adw::PreferencesWindow::builder()
+        .title("Preferences")
+        .transient_for(parent)
+        .modal(true)
+        .search_enabled(false)
+        .build();
+
+    let page = adw::PreferencesPage::new();
+
+    let power_group = adw::PreferencesGroup::builder()
+        .title("Power")
+        .description("Process windows with Keep Awake turned on count as critical watches")
+        .build();
+    let inhibit_row = adw::SwitchRow::builder()
+        .title("Prevent Sleep While Watching")
+        .subtitle("Block suspend and screen blanking while a watch is active")
+        .active(state.settings.borrow().inhibit_suspend)
+        .build();
+    power_group.add(&inhibit_row);
+    page.add(&power_group);
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    inhibit_row.connect_active_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.inhibit_suspend = row.is_active();
+        state_clone.inhibitor.set_enabled(settings.inhibit_suspend);
+        save_settings(&window_weak, &settings);
+    });
+
+    let background_group = adw::PreferencesGroup::builder()
+        .title("Background")
+        .build();
+    let background_row = adw::SwitchRow::builder()
+        .title("Run in Background")
+        .subtitle("Keep monitoring and applying automation rules after the window is closed, with a status icon to reopen it")
+        .active(state.settings.borrow().run_in_background)
+        .build();
+    background_group.add(&background_row);
+    page.add(&background_group);
+
+    let state_clone = state.clone();
+    let parent_weak = parent.as_ref().downgrade();
+    background_row.connect_active_notify(move |row| {
+        let changed = state_clone.settings.borrow().run_in_background != row.is_active();
+        if let Some(parent) = parent_weak.upgrade().filter(|_| changed) {
+            let _ = parent.activate_action("win.run-in-background", None);
+        }
+    });
+
+    let lockdown_group = adw::PreferencesGroup::builder()
+        .title("Lockdown")
+        .build();
+    let read_only_row = adw::SwitchRow::builder()
+        .title("Read-Only Mode")
+        .subtitle(if state.lockdown.is_enforced() {
+            "Enforced by the administrator or the --read-only option"
+        } else {
+            "Hide ending, pausing, priority and affinity actions and pause automation rules"
+        })
+        .active(state.lockdown.is_read_only())
+        .sensitive(!state.lockdown.is_enforced())
+        .build();
+    lockdown_group.add(&read_only_row);
+    let quick_kill_row = adw::SwitchRow::builder()
+        .title("Quick-Kill Mode")
+        .subtitle("End and kill processes without confirmation; every signal is still recorded in the audit log")
+        .active(state.settings.borrow().quick_kill)
+        .build();
+    lockdown_group.add(&quick_kill_row);
+    let grace_period_row = adw::SpinRow::builder()
+        .title("Grace Period")
+        .subtitle("Seconds End Gently waits for a process to exit before force-killing it")
+        .adjustment(&gtk4::Adjustment::new(
+            state.settings.borrow().grace_period_secs as f64,
+            1.0,
+            300.0,
+            1.0,
+            5.0,
+            0.0,
+        ))
+        .build();
+    lockdown_group.add(&grace_period_row);
+    page.add(&lockdown_group);
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    read_only_row.connect_active_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.read_only = row.is_active();
+        state_clone.lockdown.set_read_only(settings.read_only);
+        save_settings(&window_weak, &settings);
+    });
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    quick_kill_row.connect_active_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.quick_kill = row.is_active();
+        save_settings(&window_weak, &settings);
+    });
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    grace_period_row.connect_value_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.grace_period_secs = row.value() as u32;
+        save_settings(&window_weak, &settings);
+    });
+
+    let storage_group = adw::PreferencesGroup::builder()
+        .title("Storage")
+        .build();
+    let disk_alerts_row = adw::SwitchRow::builder()
+        .title("Warn When a Disk Is Nearly Full")
+        .subtitle("Show a notification when a mounted filesystem crosses the threshold")
+        .active(state.settings.borrow().disk_alerts)
+        .build();
+    storage_group.add(&disk_alerts_row);
+    let threshold_row = adw::SpinRow::builder()
+        .title("Warning Threshold")
+        .subtitle("Used space in percent")
+        .adjustment(&gtk4::Adjustment::new(
+            state.settings.borrow().disk_alert_percent as f64,
+            50.0,
+            99.0,
+            1.0,
+            5.0,
+            0.0,
+        ))
+        .build();
+    disk_alerts_row
+        .bind_property("active", &threshold_row, "sensitive")
+        .sync_create()
+        .build();
+    storage_group.add(&threshold_row);
+    page.add(&storage_group);
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    disk_alerts_row.connect_active_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.disk_alerts = row.is_active();
+        save_settings(&window_weak, &settings);
+    });
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    threshold_row.connect_value_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.disk_alert_percent = row.value() as u32;
+        save_settings(&window_weak, &settings);
+    });
+
+    let sensors_group = adw::PreferencesGroup::builder()
+        .title("Sensors")
+        .description("Set the warning temperature of each sensor on the Sensors page")
+        .build();
+    let temperature_alerts_row = adw::SwitchRow::builder()
+        .title("Warn About High Temperatures")
+        .subtitle("Show a notification when a temperature sensor reaches its threshold")
+        .active(state.settings.borrow().temperature_alerts)
+        .build();
+    sensors_group.add(&temperature_alerts_row);
+    page.add(&sensors_group);
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    temperature_alerts_row.connect_active_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.temperature_alerts = row.is_active();
+        save_settings(&window_weak, &settings);
+    });
+
+    let gpu_group = adw::PreferencesGroup::builder()
+        .title("GPU")
+        .visible(state.monitor.borrow().has_gpu())
+        .build();
+    let vram_leak_row = adw::SwitchRow::builder()
+        .title("Warn About Video Memory Leaks")
+        .subtitle("Show a notification when a process's video memory keeps growing for minutes")
+        .active(state.settings.borrow().vram_leak_alerts)
+        .build();
+    gpu_group.add(&vram_leak_row);
+    let vram_limit_row = adw::SwitchRow::builder()
+        .title("Warn About Large Video Memory Users")
+        .subtitle("Show a notification when a process uses more than the limit")
+        .active(state.settings.borrow().vram_process_limit)
+        .build();
+    gpu_group.add(&vram_limit_row);
+    let vram_limit_percent_row = adw::SpinRow::builder()
+        .title("Per-Process Limit")
+        .subtitle("Percent of the GPU's video memory")
+        .adjustment(&gtk4::Adjustment::new(
+            state.settings.borrow().vram_process_limit_percent as f64,
+            10.0,
+            100.0,
+            1.0,
+            5.0,
+            0.0,
+        ))
+        .build();
+    vram_limit_row
+        .bind_property("active", &vram_limit_percent_row, "sensitive")
+        .sync_create()
+        .build();
+    gpu_group.add(&vram_limit_percent_row);
+    page.add(&gpu_group);
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    vram_leak_row.connect_active_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.vram_leak_alerts = row.is_active();
+        save_settings(&window_weak, &settings);
+    });
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    vram_limit_row.connect_active_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.vram_process_limit = row.is_active();
+        save_settings(&window_weak, &settings);
+    });
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    vram_limit_percent_row.connect_value_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.vram_process_limit_percent = row.value() as u32;
+        save_settings(&window_weak, &settings);
+    });
+
+    let performance_group = adw::PreferencesGroup::builder()
+        .title("Performance")
+        .description("The status bar shows Procular's own CPU and memory use")
+        .build();
+    let self_limit_row = adw::SwitchRow::builder()
+        .title("Limit Own CPU Use")
+        .subtitle("Refresh less often while Procular uses more CPU than the limit")
+        .active(state.settings.borrow().self_limit)
+        .build();
+    performance_group.add(&self_limit_row);
+    let self_limit_percent_row = adw::SpinRow::builder()
+        .title("CPU Limit")
+        .subtitle("Percent of one core")
+        .adjustment(&gtk4::Adjustment::new(
+            state.settings.borrow().self_limit_percent as f64,
+            1.0,
+            100.0,
+            1.0,
+            5.0,
+            0.0,
+        ))
+        .build();
+    self_limit_row
+        .bind_property("active", &self_limit_percent_row, "sensitive")
+        .sync_create()
+        .build();
+    performance_group.add(&self_limit_percent_row);
+    page.add(&performance_group);
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    self_limit_row.connect_active_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.self_limit = row.is_active();
+        save_settings(&window_weak, &settings);
+    });
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    self_limit_percent_row.connect_value_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.self_limit_percent = row.value() as u32;
+        save_settings(&window_weak, &settings);
+    });
+
+    let list_group = adw::PreferencesGroup::builder()
+        .title("Process List")
+        .description("Pinned processes are listed first and never left out of the list")
+        .build();
+    let pinned_row = adw::EntryRow::builder()
+        .title("Pinned Process Names (comma-separated, * and ? wildcards)")
+        .text(state.settings.borrow().pinned_names.join(", "))
+        .show_apply_button(true)
+        .build();
+    list_group.add(&pinned_row);
+    let per_core_row = adw::SwitchRow::builder()
+        .title("CPU Percent of One Core")
+        .subtitle("A process using a whole core shows 100% (top's Irix mode) instead of its share of all cores")
+        .active(state.settings.borrow().cpu_per_core)
+        .build();
+    list_group.add(&per_core_row);
+    let limit_row = adw::SwitchRow::builder()
+        .title("Limit Listed Processes")
+        .subtitle("List only the busiest processes; pinned, stuck and zombie processes are always listed")
+        .active(state.settings.borrow().limit_processes)
+        .build();
+    list_group.add(&limit_row);
+    let limit_count_row = adw::SpinRow::builder()
+        .title("Maximum Processes")
+        .adjustment(&gtk4::Adjustment::new(
+            state.settings.borrow().process_limit as f64,
+            MIN_PROCESS_LIMIT as f64,
+            MAX_PROCESS_LIMIT as f64,
+            10.0,
+            50.0,
+            0.0,
+        ))
+        .build();
+    limit_row
+        .bind_property("active", &limit_count_row, "sensitive")
+        .sync_create()
+        .build();
+    list_group.add(&limit_count_row);
+    page.add(&list_group);
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    pinned_row.connect_apply(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.pinned_names = parse_name_list(&row.text());
+        state_clone.monitor.borrow_mut().set_pinned_names(settings.pinned_names.clone());
+        save_settings(&window_weak, &settings);
+    });
+
+    // The main window's action rescales the list, graphs and top bar and saves the setting
+    let state_clone = state.clone();
+    let parent_weak = parent.as_ref().downgrade();
+    per_core_row.connect_active_notify(move |row| {
+        let changed = state_clone.settings.borrow().cpu_per_core != row.is_active();
+        if let Some(parent) = parent_weak.upgrade().filter(|_| changed) {
+            let _ = parent.activate_action("win.cpu-per-core", None);
+        }
+    });
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    limit_row.connect_active_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.limit_processes = row.is_active();
+        state_clone.monitor.borrow_mut().set_process_limit(settings.process_limit());
+        save_settings(&window_weak, &settings);
+    });
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    limit_count_row.connect_value_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.process_limit = row.value() as u32;
+        state_clone.monitor.borrow_mut().set_process_limit(settings.process_limit());
+        save_settings(&window_weak, &settings);
+    });
+
+    let graphs_group = adw::PreferencesGroup::builder().title("Graphs").build();
+    let clock_axis_row = adw::SwitchRow::builder()
+        .title("Clock Times on Time Axis")
+        .subtitle("Label graphs with the time of day (14:03) instead of seconds before now")
+        .active(state.settings.borrow().clock_time_axis)
+        .build();
+    graphs_group.add(&clock_axis_row);
+    page.add(&graphs_group);
+
+    // Graphs pick the labels up when they are next drawn, at the latest on the next refresh
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    clock_axis_row.connect_active_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.clock_time_axis = row.is_active();
+        graph::set_clock_axis(settings.clock_time_axis);
+        save_settings(&window_weak, &settings);
+    });
+
+    // Collectors: each switch skips a part of every refresh
+    let collectors_group = adw::PreferencesGroup::builder()
+        .title("Data Collection")
+        .description("Turn off what you do not need for a lighter refresh; affected columns and graphs stay empty")
+        .build();
+    type CollectorSwitch = fn(&mut Collectors) -> &mut bool;
+    let collector_rows: [(&str, &str, CollectorSwitch); 5] = [
+        ("GPU", "Utilization, per-process video memory and NVIDIA temperatures", |c| &mut c.gpu),
+        ("Disk I/O", "Per-process read and write rates", |c| &mut c.disk_io),
+        ("Network", "Per-process traffic and listening ports", |c| &mut c.network),
+        (
+            "Thread Grouping",
+            "Fold threads into their process; when off, threads are left out of the list",
+            |c| &mut c.thread_grouping,
+        ),
+        (
+            "Process Details",
+            "Scheduling, nice, unit, origin, sandbox, CPU time split, page faults, context switches, \
+             affinity and PSS/USS of the listed processes",
+            |c| &mut c.process_details,
+        ),
+    ];
+    for (title, subtitle, field) in collector_rows {
+        let mut collectors = state.settings.borrow().collectors;
+        let row = adw::SwitchRow::builder()
+            .title(title)
+            .subtitle(subtitle)
+            .active(*field(&mut collectors))
+            .build();
+        collectors_group.add(&row);
+
+        let state_clone = state.clone();
+        let window_weak = window.downgrade();
+        row.connect_active_notify(move |row| {
+            let mut settings = state_clone.settings.borrow_mut();
+            *field(&mut settings.collectors) = row.is_active();
+            state_clone.monitor.borrow_mut().set_collectors(settings.collectors);
+            save_settings(&window_weak, &settings);
+        });
+    }
+    page.add(&collectors_group);
+
+    let top_bar_group = adw::PreferencesGroup::builder()
+        .title("Top Bar")
+        .description("Main menu → Top Bar shows a thin strip with live CPU, memory and network use")
+        .build();
+    let edge_names: Vec<&str> = ScreenEdge::all().iter().map(|edge| edge.as_str()).collect();
+    let edge_row = adw::ComboRow::builder()
+        .title("Screen Edge")
+        .subtitle("Docking and staying on top need X11 with wmctrl; applies when the bar opens")
+        .model(&gtk4::StringList::new(&edge_names))
+        .selected(
+            ScreenEdge::all()
+                .iter()
+                .position(|edge| *edge == state.settings.borrow().top_bar_edge)
+                .unwrap_or(0) as u32,
+        )
+        .build();
+    top_bar_group.add(&edge_row);
+    page.add(&top_bar_group);
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    edge_row.connect_selected_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.top_bar_edge = ScreenEdge::all()[row.selected() as usize];
+        save_settings(&window_weak, &settings);
+    });
+
+    let developer_group = adw::PreferencesGroup::builder()
+        .title("Developer Tools")
+        .description("Process menu → Trace in Terminal and Attach Debugger open a terminal attached to the process")
+        .build();
+    let terminal_row = adw::EntryRow::builder()
+        .title("Terminal (empty picks an installed one)")
+        .text(state.settings.borrow().terminal.as_str())
+        .show_apply_button(true)
+        .build();
+    developer_group.add(&terminal_row);
+    let tracer_names: Vec<&str> = Tracer::all().iter().map(|tracer| tracer.as_str()).collect();
+    let tracer_row = adw::ComboRow::builder()
+        .title("Tracer")
+        .model(&gtk4::StringList::new(&tracer_names))
+        .selected(
+            Tracer::all()
+                .iter()
+                .position(|tracer| *tracer == state.settings.borrow().tracer)
+                .unwrap_or(0) as u32,
+        )
+        .build();
+    developer_group.add(&tracer_row);
+    let debugger_row = adw::EntryRow::builder()
+        .title("Debugger Command ({pid} is the process ID)")
+        .text(state.settings.borrow().debugger_command.as_str())
+        .show_apply_button(true)
+        .build();
+    developer_group.add(&debugger_row);
+    page.add(&developer_group);
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    terminal_row.connect_apply(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.terminal = row.text().trim().to_string();
+        save_settings(&window_weak, &settings);
+    });
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    debugger_row.connect_apply(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        let command = row.text().trim().to_string();
+        settings.debugger_command = if command.is_empty() { DEFAULT_DEBUGGER_COMMAND.to_string() } else { command };
+        row.set_text(&settings.debugger_command);
+        save_settings(&window_weak, &settings);
+    });
+
+    let state_clone = state.clone();
+    let window_weak = window.downgrade();
+    tracer_row.connect_selected_notify(move |row| {
+        let mut settings = state_clone.settings.borrow_mut();
+        settings.tracer = Tracer::all()[row.selected() as usize];
+        save_settings(&window_weak, &settings);
+    });
+
+    window.add(&page);
+    window.present();
+}
+
+fn save_settings(window: &glib::WeakRef<adw::PreferencesWindow>, settings: &Settings) {
+    if let Err(e) = settings.save() {
+        if let Some(win) = window.upgrade() {
+            win.add_toast(adw::Toast::new(&format!("Failed to save preferences: {}", e)));
+        }
+    }
+}
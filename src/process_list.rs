@@ -1,16 +1,566 @@
 use gtk4::prelude::*;
+use gtk4::accessible::Property;
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 use gtk4::{
-    ColumnView, ColumnViewColumn, GestureClick, PopoverMenu, ScrolledWindow,
-    SignalListItemFactory, ListItem, Label, SortListModel, CustomSorter, CustomFilter,
-    FilterListModel, SingleSelection, Ordering as GtkOrdering, SortType,
+    AccessibleRole, Box as GtkBox, ColumnView, ColumnViewColumn, ColumnViewSorter, DrawingArea, GestureClick, PopoverMenu, ScrolledWindow,
+    SignalListItemFactory, ListHeader, ListItem, Label, SortListModel, CustomSorter, CustomFilter,
+    FilterListModel, MultiSelection, SortType, gio,
 };
 use glib::Object;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
+use procular_core::connections::ListeningPort;
+use procular_core::cpu_times::{format_cpu_time, CpuTimes};
+use procular_core::environment::read_environment;
+use procular_core::export::format_aligned_table;
+use procular_core::monitor::{ProcessInfo, ProcessKey, SystemMonitor, format_bytes, format_duration, format_rate};
+use procular_core::process_actions::{get_command_line, get_cpu_count, get_process_name, Scheduling, MAX_NICE, MIN_NICE};
+use procular_core::process_state::ProcessState;
+use procular_core::sandbox::{is_sandbox_helper, SandboxApp};
+use procular_core::swap::read_memory_usage;
+use procular_core::systemd_units::{ProcessOrigin, SystemdUnit};
+use procular_core::wakeups::HIGH_WAKEUP_RATE;
+
+use crate::automation::format_cpu_list;
+use crate::config;
+use crate::help;
 use crate::context_menu;
-use crate::monitor::{ProcessInfo, format_bytes};
+use crate::filter_query::Query;
+
+const COLUMNS_FILE: &str = "columns.ini";
+/// Group in the columns file for list options that are not columns
+const LIST_GROUP: &str = "list";
+/// Height of the CPU grid in the affinity column
+const AFFINITY_GRID_HEIGHT: i32 = 16;
+/// Height of the sparkline in the CPU trend column
+const SPARKLINE_HEIGHT: i32 = 16;
+/// Samples shown by the CPU trend column (one minute at the default interval)
+const SPARKLINE_SAMPLES: usize = 30;
+
+/// A process list column; the table drives creation, sorting and the header menu
+struct ColumnSpec {
+    /// Stable identifier used in columns.ini
+    id: &'static str,
+    title: &'static str,
+    /// Default fixed width in pixels; None lets the column expand
+    width: Option<i32>,
+    /// Shown until the user changes it
+    visible: bool,
+    compare: fn(&ProcessObject, &ProcessObject) -> std::cmp::Ordering,
+}
+
+const COLUMNS: &[ColumnSpec] = &[
+    ColumnSpec {
+        id: "name",
+        title: "Name",
+        width: None,
+        visible: true,
+        compare: |a, b| a.display_name().to_lowercase().cmp(&b.display_name().to_lowercase()),
+    },
+    ColumnSpec {
+        id: "pid",
+        title: "PID",
+        width: Some(80),
+        visible: true,
+        compare: |a, b| a.pid().cmp(&b.pid()),
+    },
+    ColumnSpec {
+        id: "state",
+        title: "State",
+        width: Some(60),
+        visible: false,
+        compare: |a, b| a.state().cmp(&b.state()),
+    },
+    ColumnSpec {
+        id: "started",
+        title: "Started",
+        width: Some(110),
+        visible: false,
+        compare: |a, b| a.start_time().cmp(&b.start_time()),
+    },
+    ColumnSpec {
+        id: "cpu",
+        title: "CPU %",
+        width: Some(80),
+        visible: true,
+        compare: |a, b| compare_measured(a.cpu_percent(), b.cpu_percent()),
+    },
+    ColumnSpec {
+        id: "user-time",
+        title: "User Time",
+        width: Some(90),
+        visible: false,
+        compare: |a, b| compare_cpu_time(a.cpu_times().map(|t| t.user_secs), b.cpu_times().map(|t| t.user_secs)),
+    },
+    ColumnSpec {
+        id: "system-time",
+        title: "Kernel Time",
+        width: Some(90),
+        visible: false,
+        compare: |a, b| {
+            compare_cpu_time(a.cpu_times().map(|t| t.system_secs), b.cpu_times().map(|t| t.system_secs))
+        },
+    },
+    ColumnSpec {
+        id: "memory",
+        title: "Memory",
+        width: Some(100),
+        visible: true,
+        compare: |a, b| a.memory_bytes().cmp(&b.memory_bytes()),
+    },
+    ColumnSpec {
+        id: "pss",
+        title: "PSS",
+        width: Some(100),
+        visible: false,
+        compare: |a, b| a.pss_bytes().cmp(&b.pss_bytes()),
+    },
+    ColumnSpec {
+        id: "uss",
+        title: "USS",
+        width: Some(100),
+        visible: false,
+        compare: |a, b| a.uss_bytes().cmp(&b.uss_bytes()),
+    },
+    ColumnSpec {
+        id: "disk",
+        title: "Disk I/O",
+        width: Some(100),
+        visible: true,
+        compare: |a, b| {
+            (a.disk_read_rate() + a.disk_write_rate()).cmp(&(b.disk_read_rate() + b.disk_write_rate()))
+        },
+    },
+    ColumnSpec {
+        id: "network",
+        title: "Network",
+        width: Some(100),
+        visible: true,
+        compare: |a, b| (a.net_rx_bytes() + a.net_tx_bytes()).cmp(&(b.net_rx_bytes() + b.net_tx_bytes())),
+    },
+    ColumnSpec {
+        id: "gpu",
+        title: "GPU %",
+        width: Some(80),
+        visible: true,
+        compare: |a, b| compare_measured(a.gpu_percent(), b.gpu_percent()),
+    },
+    ColumnSpec {
+        id: "nice",
+        title: "Nice",
+        width: Some(60),
+        visible: false,
+        compare: |a, b| a.nice().cmp(&b.nice()),
+    },
+    ColumnSpec {
+        id: "sched",
+        title: "Scheduling",
+        width: Some(100),
+        visible: false,
+        compare: |a, b| a.scheduling().map(|s| s.rank()).cmp(&b.scheduling().map(|s| s.rank())),
+    },
+    ColumnSpec {
+        id: "unit",
+        title: "Unit",
+        width: Some(180),
+        visible: false,
+        compare: |a, b| a.unit().map(|u| u.name).cmp(&b.unit().map(|u| u.name)),
+    },
+    ColumnSpec {
+        id: "wakeups",
+        title: "Wakeups/s",
+        width: Some(90),
+        visible: false,
+        compare: |a, b| compare_measured(a.wakeups_per_sec(), b.wakeups_per_sec()),
+    },
+    ColumnSpec {
+        id: "cpu-trend",
+        title: "CPU Trend",
+        width: Some(90),
+        visible: false,
+        compare: |a, b| a.cpu_percent().partial_cmp(&b.cpu_percent()).unwrap_or(std::cmp::Ordering::Equal),
+    },
+    ColumnSpec {
+        id: "affinity",
+        title: "Affinity",
+        width: Some(90),
+        visible: false,
+        compare: |a, b| a.allowed_cpu_count().cmp(&b.allowed_cpu_count()),
+    },
+];
+
+/// ID of the column whose header title is at `x` (in column view coordinates).
+/// The header has one title widget per column, in column order; hidden columns keep theirs hidden
+fn column_at(column_view: &ColumnView, header: &gtk4::Widget, x: f64) -> Option<String> {
+    let mut title = header.first_child();
+    for col in column_view.columns().iter::<ColumnViewColumn>().flatten() {
+        let widget = title?;
+        title = widget.next_sibling();
+        if !widget.is_visible() {
+            continue;
+        }
+        let bounds = widget.compute_bounds(column_view)?;
+        if x >= bounds.x() as f64 && x < (bounds.x() + bounds.width()) as f64 {
+            return col.id().map(|id| id.to_string());
+        }
+    }
+    None
+}
+
+/// Compare values where NaN or negative means "not available", sorting those first
+fn compare_measured(a: f32, b: f32) -> std::cmp::Ordering {
+    let valid = |v: f32| !v.is_nan() && v >= 0.0;
+    match (valid(a), valid(b)) {
+        (false, false) => std::cmp::Ordering::Equal,
+        (false, true) => std::cmp::Ordering::Less,
+        (true, false) => std::cmp::Ordering::Greater,
+        (true, true) => a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal),
+    }
+}
+
+/// Start time as a clock time today, weekday and time within a week, else the date
+fn format_start_time(start_time: u64) -> String {
+    let (Ok(started), Ok(now)) = (
+        glib::DateTime::from_unix_local(start_time as i64),
+        glib::DateTime::now_local(),
+    ) else {
+        return "-".to_string();
+    };
+    let format = if started.ymd() == now.ymd() {
+        "%X"
+    } else if now.difference(&started).as_days() < 7 {
+        "%a %H:%M"
+    } else {
+        "%x"
+    };
+    started.format(format).map(|text| text.to_string()).unwrap_or_default()
+}
+
+/// Compare cumulative CPU times, sorting unknown ones first
+fn compare_cpu_time(a: Option<f64>, b: Option<f64>) -> std::cmp::Ordering {
+    a.unwrap_or(-1.0).total_cmp(&b.unwrap_or(-1.0))
+}
+
+/// Text shown in a column for a process (also used for the plain text export)
+fn cell_text(column: &str, obj: &ProcessObject) -> String {
+    match column {
+        "name" => {
+            let child_count = obj.child_count();
+            if child_count > 0 {
+                // Show thread count in parentheses
+                format!("{} ({} threads)", obj.display_name(), child_count)
+            } else {
+                obj.display_name()
+            }
+        }
+        "pid" => obj.pid().to_string(),
+        "state" => obj.state().letter().to_string(),
+        "started" => format_start_time(obj.start_time()),
+        "cpu" => format!("{:.1}%", obj.cpu_percent()),
+        "user-time" => obj.cpu_times().map_or_else(|| "-".to_string(), |t| format_cpu_time(t.user_secs)),
+        "system-time" => obj.cpu_times().map_or_else(|| "-".to_string(), |t| format_cpu_time(t.system_secs)),
+        "memory" => format_bytes(obj.memory_bytes()),
+        "pss" => obj.pss_bytes().map_or_else(|| "-".to_string(), format_bytes),
+        "uss" => obj.uss_bytes().map_or_else(|| "-".to_string(), format_bytes),
+        "disk" => format_rate(obj.disk_read_rate() + obj.disk_write_rate()),
+        "network" => format_bytes(obj.net_rx_bytes() + obj.net_tx_bytes()),
+        "gpu" => {
+            let gpu = obj.gpu_percent();
+            if gpu < 0.0 {
+                "-".to_string()
+            } else {
+                format!("{:.1}%", gpu)
+            }
+        }
+        "nice" => obj.nice().map_or_else(|| "-".to_string(), |nice| nice.to_string()),
+        "sched" => obj.scheduling().map_or_else(|| "-".to_string(), |s| s.short()),
+        "unit" => obj.unit().map_or_else(|| "-".to_string(), |u| u.name),
+        "wakeups" => {
+            let wakeups = obj.wakeups_per_sec();
+            if wakeups < 0.0 {
+                "-".to_string()
+            } else {
+                format!("{:.0}", wakeups)
+            }
+        }
+        "affinity" => {
+            let allowed = obj.allowed_cpus();
+            let cpus: Vec<usize> = allowed.iter().enumerate().filter(|(_, &a)| a).map(|(cpu, _)| cpu).collect();
+            if allowed.is_empty() {
+                "-".to_string()
+            } else if cpus.len() == allowed.len() {
+                "all".to_string()
+            } else {
+                format_cpu_list(&cpus)
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+/// Draw recent CPU use as a filled line in the accent color. The scale starts at one busy core
+/// and grows with the largest sample, so idle processes stay flat instead of magnifying noise
+fn draw_sparkline(area: &DrawingArea, cr: &gtk4::cairo::Context, width: f64, height: f64, samples: &[f32]) {
+    if samples.len() < 2 {
+        return;
+    }
+    let one_core = 100.0 / heat_scale().cpu_count;
+    let max = samples.iter().copied().fold(one_core, f32::max) as f64;
+    let step = width / (SPARKLINE_SAMPLES - 1) as f64;
+    // Right-aligned, so a process with a short history fills in from the right
+    let left = width - step * (samples.len() - 1) as f64;
+    let y = |value: f32| height - (value as f64 / max * (height - 1.0)).clamp(0.0, height - 1.0);
+
+    let color = area.color();
+    cr.set_source_rgba(color.red() as f64, color.green() as f64, color.blue() as f64, color.alpha() as f64);
+    cr.set_line_width(1.0);
+    cr.move_to(left, y(samples[0]));
+    for (i, &value) in samples.iter().enumerate().skip(1) {
+        cr.line_to(left + step * i as f64, y(value));
+    }
+    let _ = cr.stroke_preserve();
+    cr.line_to(width, height);
+    cr.line_to(left, height);
+    cr.close_path();
+    cr.set_source_rgba(color.red() as f64, color.green() as f64, color.blue() as f64, color.alpha() as f64 * 0.3);
+    let _ = cr.fill();
+}
+
+/// Draw an affinity mask as rows of small squares in the text color, dimmed for CPUs
+/// the process may not use. Up to 16 CPUs fit in a row and at most 4 rows are drawn
+fn draw_core_grid(area: &DrawingArea, cr: &gtk4::cairo::Context, width: f64, height: f64, allowed: &[bool]) {
+    if allowed.is_empty() {
+        return;
+    }
+    const GAP: f64 = 1.0;
+    let count = allowed.len();
+    let rows = count.div_ceil(16).clamp(1, 4);
+    let cols = count.div_ceil(rows);
+    let cell = ((width - GAP * (cols - 1) as f64) / cols as f64)
+        .min((height - GAP * (rows - 1) as f64) / rows as f64)
+        .clamp(1.0, 8.0)
+        .floor();
+    let top = (height - rows as f64 * (cell + GAP) + GAP) / 2.0;
+
+    let color = area.color();
+    for (cpu, &is_allowed) in allowed.iter().enumerate() {
+        let (row, col) = (cpu / cols, cpu % cols);
+        let alpha = if is_allowed { color.alpha() } else { color.alpha() * 0.2 };
+        cr.set_source_rgba(color.red() as f64, color.green() as f64, color.blue() as f64, alpha as f64);
+        cr.rectangle(col as f64 * (cell + GAP), top + row as f64 * (cell + GAP), cell, cell);
+        let _ = cr.fill();
+    }
+}
+
+/// Header title of a column, for screen readers
+fn column_title(column: &str) -> &'static str {
+    COLUMNS.iter().find(|spec| spec.id == column).map_or("", |spec| spec.title)
+}
+
+/// Tooltip for a cell, if the column has one
+fn cell_tooltip(column: &str, obj: &ProcessObject) -> Option<String> {
+    match column {
+        "disk" => Some(format!(
+            "Read: {}\nWritten: {}",
+            format_rate(obj.disk_read_rate()),
+            format_rate(obj.disk_write_rate())
+        )),
+        "network" => Some(format!(
+            "Received: {}\nSent: {}",
+            format_bytes(obj.net_rx_bytes()),
+            format_bytes(obj.net_tx_bytes())
+        )),
+        "state" => Some(match obj.state() {
+            ProcessState::DiskSleep => "Uninterruptible sleep, usually waiting for a disk or network filesystem; \
+                                        signals (even SIGKILL) take effect only once the wait ends"
+                .to_string(),
+            ProcessState::Zombie => zombie_description(obj),
+            state => state.as_str().to_string(),
+        }),
+        "started" => {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let started = glib::DateTime::from_unix_local(obj.start_time() as i64)
+                .and_then(|time| time.format("%c"))
+                .ok()?;
+            Some(format!(
+                "Started {}\nRunning for {}",
+                started,
+                format_duration(std::time::Duration::from_secs(now.saturating_sub(obj.start_time())))
+            ))
+        }
+        "nice" => obj.nice().map(|nice| {
+            format!(
+                "Nice value {} ({} to {}, lower runs first)\nRaise or lower it from the context menu",
+                nice, MIN_NICE, MAX_NICE
+            )
+        }),
+        "sched" => obj.scheduling().filter(|s| s.policy.is_realtime()).map(|s| {
+            format!(
+                "{}, realtime priority {}\nRealtime threads run before all normal ones and can starve the system",
+                s.policy.as_str(),
+                s.rt_priority
+            )
+        }),
+        "unit" => obj.unit().map(|u| {
+            let slice = u.slice.as_deref().unwrap_or("no slice");
+            format!("{} in {}\nManaged by the {} manager", u.name, slice, u.manager_label())
+        }),
+        "affinity" => {
+            let allowed = obj.allowed_cpus();
+            let count = allowed.iter().filter(|&&a| a).count();
+            (!allowed.is_empty()).then(|| {
+                format!("Allowed CPUs: {} ({} of {})", cell_text(column, obj), count, allowed.len())
+            })
+        }
+        "wakeups" => cell_flagged(column, obj).then(|| {
+            "Frequent wakeups keep the CPU out of deep sleep states and drain the battery".to_string()
+        }),
+        _ => None,
+    }
+}
+
+/// Why a zombie is still listed, naming the parent that has not reaped it
+fn zombie_description(obj: &ProcessObject) -> String {
+    match obj.parent_pid() {
+        Some(parent) => format!(
+            "Zombie: exited, waiting for its parent PID {} ({}) to collect the exit status\n\
+             Send Signal → Signal Parent (SIGCHLD) can remind the parent",
+            parent,
+            get_process_name(parent)
+        ),
+        None => "Zombie: exited, waiting for its parent to collect the exit status".to_string(),
+    }
+}
+
+/// Whether a cell needs attention (shown in the warning color)
+fn cell_flagged(column: &str, obj: &ProcessObject) -> bool {
+    match column {
+        // Zombies get the error color for the whole row instead
+        "state" => obj.state() == ProcessState::DiskSleep,
+        "sched" => obj.scheduling().is_some_and(|s| s.policy.is_realtime()),
+        "wakeups" => obj.wakeups_per_sec() as f64 >= HIGH_WAKEUP_RATE,
+        _ => false,
+    }
+}
+
+/// Columns whose cells are shaded from green to red by value
+const HEAT_COLUMNS: [&str; 2] = ["cpu", "memory"];
+
+/// Shades of the heatmap, coolest first; only drawn while the list has the "heatmap" class
+const HEAT_CLASSES: [&str; 4] = ["heat-1", "heat-2", "heat-3", "heat-4"];
+
+/// Styles of the heatmap shades, translucent so they work with light and dark themes
+pub const HEATMAP_CSS: &str = "
+columnview.heatmap label.heat-1 { background-color: alpha(#33d17a, 0.25); border-radius: 4px; }
+columnview.heatmap label.heat-2 { background-color: alpha(#f6d32d, 0.3); border-radius: 4px; }
+columnview.heatmap label.heat-3 { background-color: alpha(#ff7800, 0.35); border-radius: 4px; }
+columnview.heatmap label.heat-4 { background-color: alpha(#e01b24, 0.4); border-radius: 4px; }
+";
+
+/// Core count and RAM size the heat thresholds are relative to; neither changes while running
+struct HeatScale {
+    cpu_count: f32,
+    total_memory: u64,
+}
+
+fn heat_scale() -> &'static HeatScale {
+    static SCALE: std::sync::OnceLock<HeatScale> = std::sync::OnceLock::new();
+    SCALE.get_or_init(|| HeatScale {
+        cpu_count: get_cpu_count() as f32,
+        total_memory: read_memory_usage().mem_total.max(1),
+    })
+}
+
+/// Heat of a cell from 0 (unshaded) to 4. CPU counts in busy cores, so a process keeping one core
+/// busy is the hottest on any machine; memory counts in share of the RAM
+fn heat_level(column: &str, obj: &ProcessObject) -> usize {
+    let scale = heat_scale();
+    let (value, thresholds) = match column {
+        "cpu" => (obj.cpu_share() / 100.0 * scale.cpu_count, [0.05, 0.2, 0.5, 1.0]),
+        "memory" => (
+            obj.memory_bytes() as f32 / scale.total_memory as f32,
+            [0.01, 0.05, 0.1, 0.25],
+        ),
+        _ => return 0,
+    };
+    thresholds.iter().filter(|&&threshold| value >= threshold).count()
+}
+
+/// Zombie rows are drawn in the error color across all text cells, so they stand out
+fn set_zombie_highlight(label: &Label, zombie: bool) {
+    if zombie {
+        label.add_css_class("error");
+    } else {
+        label.remove_css_class("error");
+    }
+}
+
+/// What the search matches besides process names and PIDs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchScope {
+    pub command_line: bool,
+    /// Reads /proc/<pid>/environ, so it is opt-in
+    pub environment: bool,
+}
+
+/// Lowercased command line and environment of a process, read the first time a search needs them.
+/// Both are fixed at exec, so they are kept until the process exits
+#[derive(Default)]
+struct SearchText {
+    command_line: Option<String>,
+    environment: Option<String>,
+}
+
+/// Whether a process matches the lowercased search `text`
+fn matches_search(
+    proc: &ProcessObject,
+    text: &str,
+    scope: SearchScope,
+    cache: &RefCell<HashMap<ProcessKey, SearchText>>,
+) -> bool {
+    if proc.display_name().to_lowercase().contains(text)
+        || proc.name().to_lowercase().contains(text)
+        || proc.pid().to_string().contains(text)
+    {
+        return true;
+    }
+    if !scope.command_line && !scope.environment {
+        return false;
+    }
+    let mut cache = cache.borrow_mut();
+    let entry = cache.entry(proc.key()).or_default();
+    if scope.command_line {
+        let command_line = entry
+            .command_line
+            .get_or_insert_with(|| get_command_line(proc.pid()).unwrap_or_default().to_lowercase());
+        if command_line.contains(text) {
+            return true;
+        }
+    }
+    if scope.environment {
+        // One NAME=value per line, so "display=:1" finds DISPLAY=:1
+        let environment = entry.environment.get_or_insert_with(|| {
+            read_environment(proc.pid())
+                .map(|vars| {
+                    vars.iter()
+                        .map(|(name, value)| format!("{}={}", name, value))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                        .to_lowercase()
+                })
+                .unwrap_or_default()
+        });
+        if environment.contains(text) {
+            return true;
+        }
+    }
+    false
+}
 
 // GObject subclass to hold process data
 mod imp {
@@ -21,12 +571,35 @@ mod imp {
     #[derive(Default)]
     pub struct ProcessObject {
         pub pid: Cell<u32>,
+        pub start_time: Cell<u64>,
         pub name: RefCell<String>,
+        pub parent_pid: Cell<Option<u32>>,
         pub cpu_percent: Cell<f32>,
+        pub cpu_share: Cell<f32>, // Percent of the whole machine, whatever the display mode
+        pub cpu_trend: RefCell<Vec<f32>>, // Recent cpu_share samples, oldest first
+        pub cpu_times: Cell<Option<CpuTimes>>,
         pub memory_bytes: Cell<u64>,
-        pub disk_read_bytes: Cell<u64>,
-        pub disk_write_bytes: Cell<u64>,
+        pub pss_bytes: Cell<Option<u64>>,
+        pub uss_bytes: Cell<Option<u64>>,
+        pub disk_read_rate: Cell<u64>,  // Bytes per second
+        pub disk_write_rate: Cell<u64>,
         pub gpu_percent: Cell<f32>, // -1.0 means N/A
+        pub net_rx_bytes: Cell<u64>,
+        pub net_tx_bytes: Cell<u64>,
+        pub tracer_pid: Cell<u32>, // 0 means not traced
+        pub listening_ports: RefCell<Vec<ListeningPort>>,
+        pub scheduling: Cell<Option<Scheduling>>,
+        pub nice: Cell<Option<i32>>,
+        pub uid: Cell<Option<u32>>,
+        pub state: Cell<ProcessState>,
+        pub kernel_thread: Cell<bool>,
+        pub pinned: Cell<bool>,
+        pub frozen: Cell<bool>,
+        pub unit: RefCell<Option<SystemdUnit>>,
+        pub origin: Cell<Option<ProcessOrigin>>,
+        pub sandbox: RefCell<Option<SandboxApp>>,
+        pub wakeups_per_sec: Cell<f32>, // -1.0 means not sampled yet
+        pub allowed_cpus: RefCell<Vec<bool>>, // Empty means unknown
         pub child_count: Cell<usize>,
         pub is_group: Cell<bool>,
         pub children: RefCell<Vec<ProcessInfo>>,
@@ -46,22 +619,45 @@ glib::wrapper! {
 }
 
 impl ProcessObject {
-    pub fn new(info: &ProcessInfo) -> Self {
+    /// `cpu_scale` converts the monitor's whole-machine CPU percentage for display
+    pub fn new(info: &ProcessInfo, cpu_scale: f32) -> Self {
         let obj: Self = Object::builder().build();
-        obj.set_from_info(info);
+        obj.set_from_info(info, cpu_scale);
         obj
     }
 
-    pub fn set_from_info(&self, info: &ProcessInfo) {
+    pub fn set_from_info(&self, info: &ProcessInfo, cpu_scale: f32) {
         let imp = self.imp();
         imp.pid.set(info.pid);
+        imp.start_time.set(info.start_time);
         imp.name.replace(info.name.clone());
+        imp.parent_pid.set(info.parent_pid);
         // For groups, show total; for individuals, show own value
-        imp.cpu_percent.set(info.total_cpu());
+        imp.cpu_percent.set(info.total_cpu() * cpu_scale);
+        imp.cpu_share.set(info.total_cpu());
+        imp.cpu_times.set(info.cpu_times);
         imp.memory_bytes.set(info.total_memory());
-        imp.disk_read_bytes.set(info.disk_read_bytes);
-        imp.disk_write_bytes.set(info.disk_write_bytes);
+        imp.pss_bytes.set(info.pss_bytes);
+        imp.uss_bytes.set(info.uss_bytes);
+        imp.disk_read_rate.set(info.disk_read_rate);
+        imp.disk_write_rate.set(info.disk_write_rate);
         imp.gpu_percent.set(info.gpu_percent.unwrap_or(-1.0));
+        imp.net_rx_bytes.set(info.total_net_rx());
+        imp.net_tx_bytes.set(info.total_net_tx());
+        imp.tracer_pid.set(info.tracer_pid.unwrap_or(0));
+        imp.listening_ports.replace(info.listening_ports.clone());
+        imp.scheduling.set(info.effective_scheduling());
+        imp.nice.set(info.nice);
+        imp.uid.set(info.uid);
+        imp.state.set(info.state);
+        imp.kernel_thread.set(info.kernel_thread);
+        imp.pinned.set(info.pinned);
+        imp.frozen.set(info.frozen);
+        imp.unit.replace(info.unit.clone());
+        imp.origin.set(info.origin);
+        imp.sandbox.replace(info.sandbox.clone());
+        imp.wakeups_per_sec.set(info.wakeups_per_sec.map_or(-1.0, |w| w as f32));
+        imp.allowed_cpus.replace(info.allowed_cpus.clone().unwrap_or_default());
         imp.child_count.set(info.children.len());
         imp.is_group.set(info.is_group);
         imp.children.replace(info.children.clone());
@@ -71,30 +667,161 @@ impl ProcessObject {
         self.imp().pid.get()
     }
 
+    pub fn key(&self) -> ProcessKey {
+        ProcessKey {
+            pid: self.pid(),
+            start_time: self.start_time(),
+        }
+    }
+
+    /// Start time in seconds since the Unix epoch
+    pub fn start_time(&self) -> u64 {
+        self.imp().start_time.get()
+    }
+
     pub fn name(&self) -> String {
         self.imp().name.borrow().clone()
     }
 
+    pub fn parent_pid(&self) -> Option<u32> {
+        self.imp().parent_pid.get()
+    }
+
     pub fn cpu_percent(&self) -> f32 {
         self.imp().cpu_percent.get()
     }
 
+    /// Cumulative user and system CPU time of all threads; None when /proc/<pid>/stat is unreadable
+    /// CPU use in percent of the whole machine, unaffected by the per-core display mode
+    pub fn cpu_share(&self) -> f32 {
+        self.imp().cpu_share.get()
+    }
+
+    /// Recent CPU use in percent of the whole machine, oldest first; empty unless the
+    /// CPU trend column is shown
+    pub fn cpu_trend(&self) -> Vec<f32> {
+        self.imp().cpu_trend.borrow().clone()
+    }
+
+    pub fn cpu_times(&self) -> Option<CpuTimes> {
+        self.imp().cpu_times.get()
+    }
+
     pub fn memory_bytes(&self) -> u64 {
         self.imp().memory_bytes.get()
     }
 
-    pub fn disk_read_bytes(&self) -> u64 {
-        self.imp().disk_read_bytes.get()
+    /// Proportional set size; None when smaps_rollup is unreadable
+    pub fn pss_bytes(&self) -> Option<u64> {
+        self.imp().pss_bytes.get()
+    }
+
+    /// Unique set size; None when smaps_rollup is unreadable
+    pub fn uss_bytes(&self) -> Option<u64> {
+        self.imp().uss_bytes.get()
+    }
+
+    pub fn disk_read_rate(&self) -> u64 {
+        self.imp().disk_read_rate.get()
     }
 
-    pub fn disk_write_bytes(&self) -> u64 {
-        self.imp().disk_write_bytes.get()
+    pub fn disk_write_rate(&self) -> u64 {
+        self.imp().disk_write_rate.get()
     }
 
     pub fn gpu_percent(&self) -> f32 {
         self.imp().gpu_percent.get()
     }
 
+    /// Wakeups per second of all threads; negative until sampled twice
+    pub fn wakeups_per_sec(&self) -> f32 {
+        self.imp().wakeups_per_sec.get()
+    }
+
+    /// Affinity mask of the main thread (index N is CPU N); empty when unknown
+    pub fn allowed_cpus(&self) -> Vec<bool> {
+        self.imp().allowed_cpus.borrow().clone()
+    }
+
+    /// Number of CPUs the process may run on
+    pub fn allowed_cpu_count(&self) -> Option<usize> {
+        let allowed = self.imp().allowed_cpus.borrow();
+        (!allowed.is_empty()).then(|| allowed.iter().filter(|&&a| a).count())
+    }
+
+    pub fn net_rx_bytes(&self) -> u64 {
+        self.imp().net_rx_bytes.get()
+    }
+
+    pub fn net_tx_bytes(&self) -> u64 {
+        self.imp().net_tx_bytes.get()
+    }
+
+    pub fn tracer_pid(&self) -> Option<u32> {
+        Some(self.imp().tracer_pid.get()).filter(|&pid| pid != 0)
+    }
+
+    pub fn listening_ports(&self) -> Vec<ListeningPort> {
+        self.imp().listening_ports.borrow().clone()
+    }
+
+    /// Own scheduling, or that of the highest priority realtime thread
+    pub fn scheduling(&self) -> Option<Scheduling> {
+        self.imp().scheduling.get()
+    }
+
+    /// Nice value of the main thread; None when /proc/<pid>/stat is unreadable
+    pub fn nice(&self) -> Option<i32> {
+        self.imp().nice.get()
+    }
+
+    /// Real user ID of the owner
+    pub fn uid(&self) -> Option<u32> {
+        self.imp().uid.get()
+    }
+
+    pub fn state(&self) -> ProcessState {
+        self.imp().state.get()
+    }
+
+    pub fn kernel_thread(&self) -> bool {
+        self.imp().kernel_thread.get()
+    }
+
+    pub fn pinned(&self) -> bool {
+        self.imp().pinned.get()
+    }
+
+    pub fn frozen(&self) -> bool {
+        self.imp().frozen.get()
+    }
+
+    /// Flatpak or Snap app the process belongs to
+    pub fn sandbox(&self) -> Option<SandboxApp> {
+        self.imp().sandbox.borrow().clone()
+    }
+
+    /// Name shown in the list: sandboxed processes lead with their app ID so an app's
+    /// processes sort together, and helpers such as bwrap show only the app ID
+    pub fn display_name(&self) -> String {
+        let name = self.name();
+        match self.sandbox() {
+            Some(app) if is_sandbox_helper(&name) => app.app_id,
+            Some(app) => format!("{} · {}", app.app_id, name),
+            None => name,
+        }
+    }
+
+    /// systemd unit owning the process
+    pub fn unit(&self) -> Option<SystemdUnit> {
+        self.imp().unit.borrow().clone()
+    }
+
+    /// Session, system service or other user
+    pub fn origin(&self) -> Option<ProcessOrigin> {
+        self.imp().origin.get()
+    }
+
     pub fn child_count(&self) -> usize {
         self.imp().child_count.get()
     }
@@ -112,17 +839,35 @@ impl ProcessObject {
 pub struct ProcessListView {
     pub widget: ScrolledWindow,
     store: gtk4::gio::ListStore,
-    #[allow(dead_code)]
     sort_model: SortListModel,
     filter_model: FilterListModel,
-    selection: SingleSelection,
+    selection: MultiSelection,
     filter_text: Rc<RefCell<String>>,
+    search_scope: Rc<Cell<SearchScope>>,
+    /// Show only processes of this origin
+    origin_filter: Rc<Cell<Option<ProcessOrigin>>>,
+    /// Show only processes in this state
+    state_filter: Rc<Cell<Option<ProcessState>>>,
+    /// Leave out kernel threads (toggled from the header menu)
+    hide_kernel_threads: Rc<Cell<bool>>,
+    /// Command lines and environments read for searching, by process
+    search_cache: Rc<RefCell<HashMap<ProcessKey, SearchText>>>,
     column_view: ColumnView,
     /// Flag to indicate we're updating programmatically (to avoid callback recursion)
     pub updating: Rc<RefCell<bool>>,
     /// Context menu popover (kept alive for right-click)
-    #[allow(dead_code)]
     context_menu: PopoverMenu,
+    /// Row objects by process, updated in place on each refresh
+    rows: RefCell<HashMap<ProcessKey, ProcessObject>>,
+    /// Column chooser shown on right-click of the header
+    #[allow(dead_code)]
+    header_menu: PopoverMenu,
+    /// Factor applied to CPU percentages (core count in per-core mode)
+    cpu_scale: Cell<f32>,
+    /// Rows are grouped under origin headers
+    group_by_origin: Cell<bool>,
+    /// Some listed process is pinned, so pinned rows get their own section at the top
+    pinned_section: Cell<bool>,
 }
 
 impl ProcessListView {
@@ -137,8 +882,8 @@ impl ProcessListView {
         // Create sort model
         let sort_model = SortListModel::new(Some(filter_model.clone()), None::<gtk4::Sorter>);
 
-        // Create selection model
-        let selection = SingleSelection::new(Some(sort_model.clone()));
+        // Create selection model; context menu actions apply to all selected rows
+        let selection = MultiSelection::new(Some(sort_model.clone()));
 
         // Create column view
         let column_view = ColumnView::new(Some(selection.clone()));
@@ -160,35 +905,73 @@ impl ProcessListView {
         // Create columns with sorters
         Self::create_columns(&column_view);
 
-        // Set default sort to CPU descending
-        if let Some(col) = column_view.columns().item(2) {
-            let col = col.downcast::<ColumnViewColumn>()
-                .expect("Column 2 should be a ColumnViewColumn");
-            column_view.sort_by_column(Some(&col), SortType::Descending);
+        // Sort like when the list was last saved, by default CPU descending
+        let list_options = config::load_key_file(COLUMNS_FILE);
+        let sort_id = list_options.string(LIST_GROUP, "sort-column").map(|id| id.to_string());
+        let sort_order = if list_options.boolean(LIST_GROUP, "sort-descending").unwrap_or(true) {
+            SortType::Descending
+        } else {
+            SortType::Ascending
+        };
+        let columns: Vec<ColumnViewColumn> = column_view.columns().iter().flatten().collect();
+        let sort_column = columns
+            .iter()
+            .find(|col| sort_id.as_deref().is_ok_and(|id| col.id().as_deref() == Some(id)))
+            .or_else(|| columns.iter().find(|col| col.id().as_deref() == Some("cpu")));
+        if let Some(col) = sort_column {
+            column_view.sort_by_column(Some(col), sort_order);
         }
 
         // Create context menu
-        let menu = context_menu::create_process_menu();
+        let menu = context_menu::create_process_menu(false);
         let context_menu = PopoverMenu::from_model(Some(&menu));
         context_menu.set_parent(&column_view);
         context_menu.set_has_arrow(false);
 
+        // Column under the last header right-click and where it was clicked, for "About This Column"
+        let header_column: Rc<RefCell<Option<(String, gtk4::gdk::Rectangle)>>> = Rc::new(RefCell::new(None));
+        let hide_kernel_threads = Rc::new(Cell::new(
+            list_options.boolean(LIST_GROUP, "hide-kernel-threads").unwrap_or(false),
+        ));
+        if list_options.boolean(LIST_GROUP, "heatmap").unwrap_or(true) {
+            column_view.add_css_class("heatmap");
+        }
+        let header_menu = Self::create_header_menu(
+            &column_view,
+            header_column.clone(),
+            hide_kernel_threads.clone(),
+            &filter_model,
+        );
+
         // Set up right-click gesture
         let gesture = GestureClick::new();
         gesture.set_button(3); // Right click
 
         let context_menu_weak = context_menu.downgrade();
+        let header_menu_weak = header_menu.downgrade();
         gesture.connect_pressed(move |gesture, _n_press, x, y| {
             gesture.set_state(gtk4::EventSequenceState::Claimed);
 
-            if let Some(menu) = context_menu_weak.upgrade() {
+            // The header is the column view's first child; clicks on it pick columns
+            let header = gesture
+                .widget()
+                .and_then(|w| w.first_child())
+                .filter(|header| y < header.height() as f64);
+            let rect = gtk4::gdk::Rectangle::new(x as i32, y as i32, 1, 1);
+            let menu = if let Some(header) = header {
+                let column = gesture
+                    .widget()
+                    .and_downcast::<ColumnView>()
+                    .and_then(|cv| column_at(&cv, &header, x));
+                *header_column.borrow_mut() = column.map(|id| (id, rect));
+                header_menu_weak.upgrade()
+            } else {
+                context_menu_weak.upgrade()
+            };
+
+            if let Some(menu) = menu {
                 // Position menu at click location
-                menu.set_pointing_to(Some(&gtk4::gdk::Rectangle::new(
-                    x as i32,
-                    y as i32,
-                    1,
-                    1,
-                )));
+                menu.set_pointing_to(Some(&rect));
                 menu.popup();
             }
         });
@@ -203,337 +986,688 @@ impl ProcessListView {
             .child(&column_view)
             .build();
 
-        Self {
+        let view = Self {
             widget: scrolled,
             store,
             sort_model,
             filter_model,
             selection,
             filter_text,
+            search_scope: Rc::new(Cell::new(SearchScope::default())),
+            origin_filter: Rc::default(),
+            state_filter: Rc::default(),
+            hide_kernel_threads,
+            search_cache: Rc::default(),
             column_view,
             updating: Rc::new(RefCell::new(false)),
             context_menu,
-        }
+            header_menu,
+            cpu_scale: Cell::new(1.0),
+            rows: RefCell::default(),
+            group_by_origin: Cell::new(false),
+            pinned_section: Cell::new(false),
+        };
+        // Apply the saved row options
+        let _ = view.set_filter("");
+        view
     }
 
     fn create_columns(column_view: &ColumnView) {
-        // Name column (flat list with thread count)
+        let layout = config::load_key_file(COLUMNS_FILE);
+
+        for spec in COLUMNS {
+            let factory = match spec.id {
+                "name" => Self::name_factory(),
+                "affinity" => Self::affinity_factory(),
+                "cpu-trend" => Self::sparkline_factory(),
+                _ => Self::label_factory(spec.id),
+            };
+
+            let compare = spec.compare;
+            let sorter = CustomSorter::new(move |a, b| {
+                let a = a.downcast_ref::<ProcessObject>()
+                    .expect("Sorter item should be a ProcessObject");
+                let b = b.downcast_ref::<ProcessObject>()
+                    .expect("Sorter item should be a ProcessObject");
+                compare(a, b).into()
+            });
+
+            let col = ColumnViewColumn::new(Some(spec.title), Some(factory));
+            col.set_id(Some(spec.id));
+            col.set_sorter(Some(&sorter));
+            col.set_resizable(true);
+            match spec.width {
+                Some(width) => col.set_fixed_width(layout.integer(spec.id, "width").unwrap_or(width)),
+                None => col.set_expand(true),
+            }
+            // The name column always stays visible so rows can be identified
+            if spec.id != "name" {
+                col.set_visible(layout.boolean(spec.id, "visible").unwrap_or(spec.visible));
+            }
+            column_view.append_column(&col);
+        }
+    }
+
+    /// Factory for the name column: name label plus "zombie", "debugged", "frozen", sandbox and "listening" badges
+    fn name_factory() -> SignalListItemFactory {
         let factory = SignalListItemFactory::new();
         factory.connect_setup(|_, item| {
             let item = item.downcast_ref::<ListItem>()
                 .expect("Factory item should be a ListItem");
+            let row = GtkBox::new(gtk4::Orientation::Horizontal, 6);
             let label = Label::new(None);
             label.set_halign(gtk4::Align::Start);
             label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
-            item.set_child(Some(&label));
+            row.append(&label);
+            // "zombie" badge for exited processes their parent has not reaped
+            let zombie_badge = Label::new(Some("zombie"));
+            zombie_badge.add_css_class("caption");
+            zombie_badge.add_css_class("error");
+            zombie_badge.set_visible(false);
+            row.append(&zombie_badge);
+            // "debugged" badge for processes under ptrace
+            let badge = Label::new(Some("debugged"));
+            badge.add_css_class("caption");
+            badge.add_css_class("warning");
+            badge.set_visible(false);
+            row.append(&badge);
+            // "frozen" badge for processes stopped by the cgroup freezer
+            let frozen_badge = Label::new(Some("frozen"));
+            frozen_badge.add_css_class("caption");
+            frozen_badge.add_css_class("accent");
+            frozen_badge.set_tooltip_text(Some("Frozen by the cgroup freezer; choose Thaw to resume"));
+            frozen_badge.set_visible(false);
+            row.append(&frozen_badge);
+            // "flatpak"/"snap" badge for sandboxed apps
+            let sandbox_badge = Label::new(None);
+            sandbox_badge.add_css_class("caption");
+            sandbox_badge.add_css_class("dim-label");
+            sandbox_badge.set_visible(false);
+            row.append(&sandbox_badge);
+            // "listening" badge for processes with open server sockets
+            let ports_badge = Label::new(Some("listening"));
+            ports_badge.add_css_class("caption");
+            ports_badge.add_css_class("accent");
+            ports_badge.set_visible(false);
+            row.append(&ports_badge);
+            item.set_child(Some(&row));
         });
         factory.connect_bind(|_, item| {
             let item = item.downcast_ref::<ListItem>()
                 .expect("Factory item should be a ListItem");
             let obj = item.item().and_downcast::<ProcessObject>()
                 .expect("Item should contain a ProcessObject");
-            let label = item.child().and_downcast::<Label>()
-                .expect("Item child should be a Label");
+            let row = item.child().and_downcast::<GtkBox>()
+                .expect("Item child should be a Box");
+            let label = row.first_child().and_downcast::<Label>()
+                .expect("First child should be the name Label");
+            let zombie_badge = label.next_sibling().and_downcast::<Label>()
+                .expect("Second child should be the zombie badge Label");
+            let badge = zombie_badge.next_sibling().and_downcast::<Label>()
+                .expect("Third child should be the debugged badge Label");
+            let frozen_badge = badge.next_sibling().and_downcast::<Label>()
+                .expect("Fourth child should be the frozen badge Label");
+            let sandbox_badge = frozen_badge.next_sibling().and_downcast::<Label>()
+                .expect("Fifth child should be the sandbox badge Label");
+            let ports_badge = row.last_child().and_downcast::<Label>()
+                .expect("Last child should be the listening badge Label");
 
-            let name = obj.name();
-            let child_count = obj.child_count();
-            if child_count > 0 {
-                // Show thread count in parentheses
-                label.set_label(&format!("{} ({} threads)", name, child_count));
-            } else {
-                label.set_label(&name);
+            let zombie = obj.state() == ProcessState::Zombie;
+            zombie_badge.set_visible(zombie);
+            if zombie {
+                zombie_badge.set_tooltip_text(Some(&zombie_description(&obj)));
             }
-        });
-        let sorter = CustomSorter::new(|a, b| {
-            let a = a.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            let b = b.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            match a.name().to_lowercase().cmp(&b.name().to_lowercase()) {
-                std::cmp::Ordering::Less => GtkOrdering::Smaller,
-                std::cmp::Ordering::Equal => GtkOrdering::Equal,
-                std::cmp::Ordering::Greater => GtkOrdering::Larger,
+            set_zombie_highlight(&label, zombie);
+
+            match obj.tracer_pid() {
+                Some(tracer) => {
+                    badge.set_visible(true);
+                    badge.set_tooltip_text(Some(&format!(
+                        "Debugged by PID {} ({})",
+                        tracer,
+                        get_process_name(tracer)
+                    )));
+                }
+                None => badge.set_visible(false),
             }
-        });
-        let col = ColumnViewColumn::new(Some("Name"), Some(factory));
-        col.set_sorter(Some(&sorter));
-        col.set_resizable(true);
-        col.set_expand(true);
-        column_view.append_column(&col);
 
-        // PID column
-        let factory = SignalListItemFactory::new();
-        factory.connect_setup(|_, item| {
-            let item = item.downcast_ref::<ListItem>()
-                .expect("Factory item should be a ListItem");
-            let label = Label::new(None);
-            label.set_halign(gtk4::Align::End);
-            item.set_child(Some(&label));
-        });
-        factory.connect_bind(|_, item| {
-            let item = item.downcast_ref::<ListItem>()
-                .expect("Factory item should be a ListItem");
-            let obj = item.item().and_downcast::<ProcessObject>()
-                .expect("Item should contain a ProcessObject");
-            let label = item.child().and_downcast::<Label>()
-                .expect("Item child should be a Label");
-            label.set_label(&obj.pid().to_string());
-        });
-        let sorter = CustomSorter::new(|a, b| {
-            let a = a.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            let b = b.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            match a.pid().cmp(&b.pid()) {
-                std::cmp::Ordering::Less => GtkOrdering::Smaller,
-                std::cmp::Ordering::Equal => GtkOrdering::Equal,
-                std::cmp::Ordering::Greater => GtkOrdering::Larger,
+            frozen_badge.set_visible(obj.frozen());
+
+            match obj.sandbox() {
+                Some(app) => {
+                    sandbox_badge.set_label(&app.kind.as_str().to_lowercase());
+                    let name = obj.name();
+                    let tooltip = if is_sandbox_helper(&name) {
+                        format!("Sandbox helper ({}) of {} app {}", name, app.kind.as_str(), app.app_id)
+                    } else {
+                        format!("{} app {}", app.kind.as_str(), app.app_id)
+                    };
+                    sandbox_badge.set_tooltip_text(Some(&tooltip));
+                    sandbox_badge.set_visible(true);
+                }
+                None => sandbox_badge.set_visible(false),
             }
+
+            let ports = obj.listening_ports();
+            ports_badge.set_visible(!ports.is_empty());
+            if !ports.is_empty() {
+                let lines: Vec<String> = ports.iter().map(|port| port.describe()).collect();
+                ports_badge.set_tooltip_text(Some(&format!("Listening on:\n{}", lines.join("\n"))));
+            }
+
+            label.set_label(&cell_text("name", &obj));
         });
-        let col = ColumnViewColumn::new(Some("PID"), Some(factory));
-        col.set_sorter(Some(&sorter));
-        col.set_resizable(true);
-        col.set_fixed_width(80);
-        column_view.append_column(&col);
+        factory
+    }
 
-        // CPU% column
+    /// Factory for a right-aligned text column
+    fn label_factory(column: &'static str) -> SignalListItemFactory {
         let factory = SignalListItemFactory::new();
-        factory.connect_setup(|_, item| {
+        factory.connect_setup(move |_, item| {
             let item = item.downcast_ref::<ListItem>()
                 .expect("Factory item should be a ListItem");
             let label = Label::new(None);
-            label.set_halign(gtk4::Align::End);
+            if HEAT_COLUMNS.contains(&column) {
+                // Fill the cell so the heat shade covers it, keeping the text right-aligned
+                label.set_halign(gtk4::Align::Fill);
+                label.set_xalign(1.0);
+            } else {
+                label.set_halign(gtk4::Align::End);
+            }
             item.set_child(Some(&label));
         });
-        factory.connect_bind(|_, item| {
+        factory.connect_bind(move |_, item| {
             let item = item.downcast_ref::<ListItem>()
                 .expect("Factory item should be a ListItem");
             let obj = item.item().and_downcast::<ProcessObject>()
                 .expect("Item should contain a ProcessObject");
             let label = item.child().and_downcast::<Label>()
                 .expect("Item child should be a Label");
-            label.set_label(&format!("{:.1}%", obj.cpu_percent()));
-        });
-        let sorter = CustomSorter::new(|a, b| {
-            let a = a.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            let b = b.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            // Handle NaN by treating it as less than any valid number
-            let a_cpu = a.cpu_percent();
-            let b_cpu = b.cpu_percent();
-            if a_cpu.is_nan() && b_cpu.is_nan() {
-                GtkOrdering::Equal
-            } else if a_cpu.is_nan() {
-                GtkOrdering::Smaller
-            } else if b_cpu.is_nan() {
-                GtkOrdering::Larger
+            let text = cell_text(column, &obj);
+            let tooltip = cell_tooltip(column, &obj);
+            label.set_label(&text);
+            label.set_tooltip_text(tooltip.as_deref());
+            // Screen readers announce the column with the value, and the tooltip details
+            label.update_property(&[
+                Property::Label(&format!("{}: {}", column_title(column), text)),
+                Property::Description(tooltip.as_deref().unwrap_or_default()),
+            ]);
+            if cell_flagged(column, &obj) {
+                label.add_css_class("warning");
             } else {
-                match a_cpu.partial_cmp(&b_cpu).unwrap_or(std::cmp::Ordering::Equal) {
-                    std::cmp::Ordering::Less => GtkOrdering::Smaller,
-                    std::cmp::Ordering::Equal => GtkOrdering::Equal,
-                    std::cmp::Ordering::Greater => GtkOrdering::Larger,
+                label.remove_css_class("warning");
+            }
+            set_zombie_highlight(&label, obj.state() == ProcessState::Zombie);
+            if HEAT_COLUMNS.contains(&column) {
+                let level = heat_level(column, &obj);
+                for (i, class) in HEAT_CLASSES.iter().enumerate() {
+                    if i + 1 == level {
+                        label.add_css_class(class);
+                    } else {
+                        label.remove_css_class(class);
+                    }
                 }
             }
         });
-        let col = ColumnViewColumn::new(Some("CPU %"), Some(factory));
-        col.set_sorter(Some(&sorter));
-        col.set_resizable(true);
-        col.set_fixed_width(80);
-        column_view.append_column(&col);
+        factory
+    }
 
-        // Memory column
+    /// Factory for the affinity column: a grid of CPUs, filled where the process may run
+    fn affinity_factory() -> SignalListItemFactory {
         let factory = SignalListItemFactory::new();
         factory.connect_setup(|_, item| {
             let item = item.downcast_ref::<ListItem>()
                 .expect("Factory item should be a ListItem");
-            let label = Label::new(None);
-            label.set_halign(gtk4::Align::End);
-            item.set_child(Some(&label));
+            let area = DrawingArea::builder().accessible_role(AccessibleRole::Img).build();
+            area.set_content_height(AFFINITY_GRID_HEIGHT);
+            area.set_hexpand(true);
+            item.set_child(Some(&area));
         });
         factory.connect_bind(|_, item| {
             let item = item.downcast_ref::<ListItem>()
                 .expect("Factory item should be a ListItem");
             let obj = item.item().and_downcast::<ProcessObject>()
                 .expect("Item should contain a ProcessObject");
-            let label = item.child().and_downcast::<Label>()
-                .expect("Item child should be a Label");
-            label.set_label(&format_bytes(obj.memory_bytes()));
+            let area = item.child().and_downcast::<DrawingArea>()
+                .expect("Item child should be a DrawingArea");
+            let allowed = obj.allowed_cpus();
+            let tooltip = cell_tooltip("affinity", &obj);
+            area.set_tooltip_text(tooltip.as_deref());
+            area.update_property(&[Property::Label(&format!(
+                "{}: {}",
+                column_title("affinity"),
+                tooltip.as_deref().unwrap_or_default()
+            ))]);
+            area.set_draw_func(move |area, cr, width, height| {
+                draw_core_grid(area, cr, width as f64, height as f64, &allowed);
+            });
         });
-        let sorter = CustomSorter::new(|a, b| {
-            let a = a.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            let b = b.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            match a.memory_bytes().cmp(&b.memory_bytes()) {
-                std::cmp::Ordering::Less => GtkOrdering::Smaller,
-                std::cmp::Ordering::Equal => GtkOrdering::Equal,
-                std::cmp::Ordering::Greater => GtkOrdering::Larger,
-            }
-        });
-        let col = ColumnViewColumn::new(Some("Memory"), Some(factory));
-        col.set_sorter(Some(&sorter));
-        col.set_resizable(true);
-        col.set_fixed_width(100);
-        column_view.append_column(&col);
+        factory
+    }
 
-        // Disk I/O column
+    /// Factory for the CPU trend column: a sparkline of the process's recent CPU use
+    fn sparkline_factory() -> SignalListItemFactory {
         let factory = SignalListItemFactory::new();
         factory.connect_setup(|_, item| {
             let item = item.downcast_ref::<ListItem>()
                 .expect("Factory item should be a ListItem");
-            let label = Label::new(None);
-            label.set_halign(gtk4::Align::End);
-            item.set_child(Some(&label));
+            let area = DrawingArea::builder().accessible_role(AccessibleRole::Img).build();
+            area.set_content_height(SPARKLINE_HEIGHT);
+            area.set_hexpand(true);
+            area.add_css_class("accent");
+            item.set_child(Some(&area));
         });
         factory.connect_bind(|_, item| {
             let item = item.downcast_ref::<ListItem>()
                 .expect("Factory item should be a ListItem");
             let obj = item.item().and_downcast::<ProcessObject>()
                 .expect("Item should contain a ProcessObject");
-            let label = item.child().and_downcast::<Label>()
-                .expect("Item child should be a Label");
-            let total = obj.disk_read_bytes() + obj.disk_write_bytes();
-            label.set_label(&format_bytes(total));
+            let area = item.child().and_downcast::<DrawingArea>()
+                .expect("Item child should be a DrawingArea");
+            let samples = obj.cpu_trend();
+            let peak = samples.iter().copied().fold(0.0, f32::max);
+            let current = samples.last().copied().unwrap_or(0.0);
+            area.update_property(&[Property::Label(&format!(
+                "{}: {:.1}% now, peak {:.1}%",
+                column_title("cpu-trend"),
+                current,
+                peak
+            ))]);
+            area.set_draw_func(move |area, cr, width, height| {
+                draw_sparkline(area, cr, width as f64, height as f64, &samples);
+            });
         });
-        let sorter = CustomSorter::new(|a, b| {
-            let a = a.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            let b = b.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            let a_total = a.disk_read_bytes() + a.disk_write_bytes();
-            let b_total = b.disk_read_bytes() + b.disk_write_bytes();
-            match a_total.cmp(&b_total) {
-                std::cmp::Ordering::Less => GtkOrdering::Smaller,
-                std::cmp::Ordering::Equal => GtkOrdering::Equal,
-                std::cmp::Ordering::Greater => GtkOrdering::Larger,
+        factory
+    }
+
+    /// Right-click menu on the column header to show or hide columns and explain the clicked one
+    fn create_header_menu(
+        column_view: &ColumnView,
+        header_column: Rc<RefCell<Option<(String, gtk4::gdk::Rectangle)>>>,
+        hide_kernel_threads: Rc<Cell<bool>>,
+        filter_model: &FilterListModel,
+    ) -> PopoverMenu {
+        let actions = gio::SimpleActionGroup::new();
+        let menu = gio::Menu::new();
+        let columns_section = gio::Menu::new();
+        for col in column_view.columns().iter::<ColumnViewColumn>().flatten() {
+            let Some(id) = col.id() else {
+                continue;
+            };
+            if id == "name" {
+                continue;
             }
-        });
-        let col = ColumnViewColumn::new(Some("Disk I/O"), Some(factory));
-        col.set_sorter(Some(&sorter));
-        col.set_resizable(true);
-        col.set_fixed_width(100);
-        column_view.append_column(&col);
+            // Stateful action mirroring the column's visible property
+            actions.add_action(&gio::PropertyAction::new(&id, &col, "visible"));
+            let title = col.title().unwrap_or_default();
+            columns_section.append(Some(&title), Some(&format!("columns.{}", id)));
+        }
+        menu.append_section(None, &columns_section);
 
-        // GPU% column
-        let factory = SignalListItemFactory::new();
-        factory.connect_setup(|_, item| {
-            let item = item.downcast_ref::<ListItem>()
-                .expect("Factory item should be a ListItem");
-            let label = Label::new(None);
-            label.set_halign(gtk4::Align::End);
-            item.set_child(Some(&label));
-        });
-        factory.connect_bind(|_, item| {
-            let item = item.downcast_ref::<ListItem>()
-                .expect("Factory item should be a ListItem");
-            let obj = item.item().and_downcast::<ProcessObject>()
-                .expect("Item should contain a ProcessObject");
-            let label = item.child().and_downcast::<Label>()
-                .expect("Item child should be a Label");
-            let gpu = obj.gpu_percent();
-            if gpu < 0.0 {
-                label.set_label("-");
-            } else {
-                label.set_label(&format!("{:.1}%", gpu));
+        let kernel_threads_action =
+            gio::SimpleAction::new_stateful("hide-kernel-threads", None, &hide_kernel_threads.get().to_variant());
+        let filter_model_weak = filter_model.downgrade();
+        kernel_threads_action.connect_change_state(move |action, value| {
+            let Some(hide) = value.and_then(|v| v.get::<bool>()) else {
+                return;
+            };
+            action.set_state(&hide.to_variant());
+            hide_kernel_threads.set(hide);
+            if let Some(filter) = filter_model_weak.upgrade().and_then(|model| model.filter()) {
+                filter.changed(gtk4::FilterChange::Different);
             }
         });
-        let sorter = CustomSorter::new(|a, b| {
-            let a = a.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            let b = b.downcast_ref::<ProcessObject>()
-                .expect("Sorter item should be a ProcessObject");
-            // Handle NaN and negative values (used for N/A)
-            let a_gpu = a.gpu_percent();
-            let b_gpu = b.gpu_percent();
-            if (a_gpu.is_nan() || a_gpu < 0.0) && (b_gpu.is_nan() || b_gpu < 0.0) {
-                GtkOrdering::Equal
-            } else if a_gpu.is_nan() || a_gpu < 0.0 {
-                GtkOrdering::Smaller
-            } else if b_gpu.is_nan() || b_gpu < 0.0 {
-                GtkOrdering::Larger
+        actions.add_action(&kernel_threads_action);
+
+        // The shades are always set on the cells; the class on the list decides whether they show
+        let heatmap_action =
+            gio::SimpleAction::new_stateful("heatmap", None, &column_view.has_css_class("heatmap").to_variant());
+        let column_view_weak = column_view.downgrade();
+        heatmap_action.connect_change_state(move |action, value| {
+            let (Some(show), Some(column_view)) = (value.and_then(|v| v.get::<bool>()), column_view_weak.upgrade())
+            else {
+                return;
+            };
+            action.set_state(&show.to_variant());
+            if show {
+                column_view.add_css_class("heatmap");
             } else {
-                match a_gpu.partial_cmp(&b_gpu).unwrap_or(std::cmp::Ordering::Equal) {
-                    std::cmp::Ordering::Less => GtkOrdering::Smaller,
-                    std::cmp::Ordering::Equal => GtkOrdering::Equal,
-                    std::cmp::Ordering::Greater => GtkOrdering::Larger,
-                }
+                column_view.remove_css_class("heatmap");
             }
         });
-        let col = ColumnViewColumn::new(Some("GPU %"), Some(factory));
-        col.set_sorter(Some(&sorter));
-        col.set_resizable(true);
-        col.set_fixed_width(80);
-        column_view.append_column(&col);
+        actions.add_action(&heatmap_action);
+        let rows_section = gio::Menu::new();
+        rows_section.append(Some("Hide Kernel Threads"), Some("columns.hide-kernel-threads"));
+        rows_section.append(Some("Color CPU and Memory by Value"), Some("columns.heatmap"));
+        menu.append_section(None, &rows_section);
+
+        let explain_action = gio::SimpleAction::new("explain", None);
+        let column_view_weak = column_view.downgrade();
+        explain_action.connect_activate(move |_, _| {
+            let (Some(column_view), Some((id, rect))) = (column_view_weak.upgrade(), header_column.borrow().clone())
+            else {
+                return;
+            };
+            help::show_help_popover(&column_view, &id, &rect);
+        });
+        actions.add_action(&explain_action);
+        let help_section = gio::Menu::new();
+        help_section.append(Some("About This Column"), Some("columns.explain"));
+        menu.append_section(None, &help_section);
+        column_view.insert_action_group("columns", Some(&actions));
+
+        let popover = PopoverMenu::from_model(Some(&menu));
+        popover.set_parent(column_view);
+        popover.set_has_arrow(false);
+        popover
+    }
+
+    /// Rebuild the context menu with or without the actions that change processes
+    pub fn set_read_only(&self, read_only: bool) {
+        let menu = context_menu::create_process_menu(read_only);
+        self.context_menu.set_menu_model(Some(&menu));
+    }
+
+    /// Save column visibility, widths and sorting to the config directory
+    pub fn save_columns(&self) -> std::io::Result<()> {
+        let layout = glib::KeyFile::new();
+        for col in self.column_view.columns().iter::<ColumnViewColumn>().flatten() {
+            let Some(id) = col.id() else {
+                continue;
+            };
+            layout.set_boolean(&id, "visible", col.is_visible());
+            if col.fixed_width() > 0 {
+                layout.set_integer(&id, "width", col.fixed_width());
+            }
+        }
+        layout.set_boolean(LIST_GROUP, "hide-kernel-threads", self.hide_kernel_threads.get());
+        layout.set_boolean(LIST_GROUP, "heatmap", self.column_view.has_css_class("heatmap"));
+        if let Some(sorter) = self.column_view.sorter().and_downcast::<ColumnViewSorter>() {
+            if let Some(id) = sorter.primary_sort_column().and_then(|col| col.id()) {
+                layout.set_string(LIST_GROUP, "sort-column", &id);
+                layout.set_boolean(LIST_GROUP, "sort-descending", sorter.primary_sort_order() == SortType::Descending);
+            }
+        }
+        config::save_key_file(COLUMNS_FILE, &layout)
+    }
+
+    /// Show CPU relative to one core (`cpu_scale` = core count) or the whole machine (1.0);
+    /// applies from the next update
+    pub fn set_cpu_scale(&self, cpu_scale: f32) {
+        self.cpu_scale.set(cpu_scale);
     }
 
     /// Update the process list with new data
-    pub fn update(&self, processes: &[ProcessInfo]) {
+    /// Replace the rows; `monitor` provides the histories for the CPU trend column
+    pub fn update(&self, processes: &[ProcessInfo], monitor: &SystemMonitor) {
         // Set updating flag to prevent selection callback from firing
         *self.updating.borrow_mut() = true;
 
         // Save current selection
-        let selected_pid = self.selection
-            .selected_item()
-            .and_then(|obj| obj.downcast::<ProcessObject>().ok())
-            .map(|p| p.pid());
+        let selected_keys = self.selected_keys();
+
+        // Forget search text of processes that exited
+        let keys: HashSet<ProcessKey> = processes.iter().map(|p| p.key()).collect();
+        self.search_cache.borrow_mut().retain(|key, _| keys.contains(key));
+
+        // Drop the rows of processes that are gone, a run of neighbouring rows at a time
+        let mut rows = self.rows.borrow_mut();
+        rows.retain(|key, _| keys.contains(key));
+        let gone = |position: u32| {
+            self.store
+                .item(position)
+                .and_downcast::<ProcessObject>()
+                .is_some_and(|obj| !rows.contains_key(&obj.key()))
+        };
+        let mut end = self.store.n_items();
+        while end > 0 {
+            let mut start = end;
+            while start > 0 && gone(start - 1) {
+                start -= 1;
+            }
+            if start < end {
+                self.store.splice(start, end - start, &[] as &[ProcessObject]);
+                end = start;
+            } else {
+                end -= 1;
+            }
+        }
 
-        // Clear and repopulate
-        self.store.remove_all();
+        // Update the remaining rows in place and add new ones. Histories are only copied
+        // while the trend column is shown
+        let show_trend = self.column_view.columns().iter::<ColumnViewColumn>().flatten()
+            .any(|col| col.id().as_deref() == Some("cpu-trend") && col.is_visible());
+        let cpu_scale = self.cpu_scale.get();
+        let mut added = Vec::new();
         for proc in processes {
-            self.store.append(&ProcessObject::new(proc));
+            let obj = match rows.get(&proc.key()) {
+                Some(obj) => {
+                    obj.set_from_info(proc, cpu_scale);
+                    obj.clone()
+                }
+                None => {
+                    let obj = ProcessObject::new(proc, cpu_scale);
+                    rows.insert(proc.key(), obj.clone());
+                    added.push(obj.clone());
+                    obj
+                }
+            };
+            let trend = monitor
+                .get_history(proc.key())
+                .filter(|_| show_trend)
+                .map(|history| {
+                    let skip = history.cpu_history.len().saturating_sub(SPARKLINE_SAMPLES);
+                    history.cpu_history.iter().skip(skip).copied().collect()
+                })
+                .unwrap_or_default();
+            obj.imp().cpu_trend.replace(trend);
         }
+        // The objects hold plain values rather than properties, so the kept rows are reported
+        // as changed to rebind their cells and be sorted and filtered again. The list never
+        // empties, so the scroll position stays
+        let kept = self.store.n_items();
+        self.store.items_changed(0, kept, kept);
+        self.store.extend_from_slice(&added);
 
-        // Restore selection if the process still exists
-        if let Some(pid) = selected_pid {
-            self.select_by_pid(pid);
+        // The pinned section only shows while something is pinned
+        let pinned = processes.iter().any(|p| p.pinned);
+        if pinned != self.pinned_section.get() {
+            self.pinned_section.set(pinned);
+            self.update_sections();
+        }
+
+        // Restore selection of the processes that still exist (not just their PIDs)
+        if !selected_keys.is_empty() {
+            self.select_keys(&selected_keys);
         }
 
         // Clear updating flag
         *self.updating.borrow_mut() = false;
     }
 
-    /// Select a process by PID
-    pub fn select_by_pid(&self, pid: u32) {
-        // Search through the model to find the item
-        let Some(model) = self.selection.model() else {
-            return; // No model available, nothing to select
-        };
-        for i in 0..model.n_items() {
-            if let Some(obj) = model.item(i) {
-                if let Some(proc) = obj.downcast_ref::<ProcessObject>() {
-                    if proc.pid() == pid {
-                        self.selection.set_selected(i);
-                        return;
-                    }
+    /// Export the filtered and sorted rows as aligned plain text (like `ps`),
+    /// including only the visible columns
+    pub fn export_text(&self) -> String {
+        let columns: Vec<ColumnViewColumn> = self
+            .column_view
+            .columns()
+            .iter::<ColumnViewColumn>()
+            .flatten()
+            .filter(|col| col.is_visible())
+            .collect();
+        let ids: Vec<String> = columns.iter().map(|col| col.id().unwrap_or_default().to_string()).collect();
+        let titles: Vec<String> = columns.iter().map(|col| col.title().unwrap_or_default().to_string()).collect();
+
+        let rows: Vec<Vec<String>> = (0..self.sort_model.n_items())
+            .filter_map(|i| self.sort_model.item(i).and_downcast::<ProcessObject>())
+            .map(|obj| ids.iter().map(|id| cell_text(id, &obj)).collect())
+            .collect();
+
+        // Only the name column is left aligned, like ps/top
+        let left_aligned: Vec<bool> = ids.iter().map(|id| id == "name").collect();
+        format_aligned_table(&titles, &rows, &left_aligned)
+    }
+
+    /// Select the processes with the given keys, replacing the current selection
+    fn select_keys(&self, keys: &[ProcessKey]) {
+        let selected = gtk4::Bitset::new_empty();
+        for i in 0..self.sort_model.n_items() {
+            if let Some(proc) = self.sort_model.item(i).and_downcast::<ProcessObject>() {
+                if keys.contains(&proc.key()) {
+                    selected.add(i);
                 }
             }
         }
-        // Process not found - clear selection
-        self.selection.set_selected(gtk4::INVALID_LIST_POSITION);
+        // Keys not found (exited processes) simply drop out of the selection
+        let mask = gtk4::Bitset::new_range(0, self.sort_model.n_items());
+        self.selection.set_selection(&selected, &mask);
+    }
+
+    /// Selected rows in display order
+    fn selected_objects(&self) -> Vec<ProcessObject> {
+        (0..self.sort_model.n_items())
+            .filter(|&i| self.selection.is_selected(i))
+            .filter_map(|i| self.sort_model.item(i).and_downcast::<ProcessObject>())
+            .collect()
+    }
+
+    /// Select the row of `pid` and scroll it into view; false when the process is not listed
+    pub fn focus_process(&self, pid: u32) -> bool {
+        let position = (0..self.sort_model.n_items()).find(|&i| {
+            self.sort_model.item(i).and_downcast::<ProcessObject>().is_some_and(|proc| proc.pid() == pid)
+        });
+        let Some(position) = position else {
+            return false;
+        };
+        self.column_view.scroll_to(
+            position,
+            None,
+            gtk4::ListScrollFlags::FOCUS | gtk4::ListScrollFlags::SELECT,
+            None,
+        );
+        true
     }
 
-    /// Set the filter text for searching
-    pub fn set_filter(&self, text: &str) {
-        *self.filter_text.borrow_mut() = text.to_lowercase();
-        let filter_text = self.filter_text.clone();
+    /// Keys of all selected processes
+    pub fn selected_keys(&self) -> Vec<ProcessKey> {
+        self.selected_objects().iter().map(|p| p.key()).collect()
+    }
+
+    /// Set the search text: words and conditions like `cpu>50`, `mem>1g`, `user:alice` or
+    /// `state:zombie`, combined with AND, OR and NOT. If the text does not parse it is
+    /// searched for as plain text and the parse error is returned
+    pub fn set_filter(&self, text: &str) -> Result<(), String> {
+        *self.filter_text.borrow_mut() = text.to_string();
+        let (query, error) = match Query::parse(text) {
+            Ok(query) => (query, None),
+            Err(error) => (Some(Query::Text(text.trim().to_lowercase())), Some(error)),
+        };
+        let search_scope = self.search_scope.clone();
+        let search_cache = self.search_cache.clone();
+        let origin_filter = self.origin_filter.clone();
+        let state_filter = self.state_filter.clone();
+        let hide_kernel_threads = self.hide_kernel_threads.clone();
 
         let filter = CustomFilter::new(move |obj| {
-            let text = filter_text.borrow();
-            if text.is_empty() {
+            let Some(proc) = obj.downcast_ref::<ProcessObject>() else {
                 return true;
+            };
+            if origin_filter.get().is_some_and(|origin| proc.origin() != Some(origin)) {
+                return false;
             }
-            if let Some(proc) = obj.downcast_ref::<ProcessObject>() {
-                return proc.name().to_lowercase().contains(text.as_str())
-                    || proc.pid().to_string().contains(text.as_str());
+            if state_filter.get().is_some_and(|state| proc.state() != state) {
+                return false;
             }
-            true
+            if hide_kernel_threads.get() && proc.kernel_thread() {
+                return false;
+            }
+            query.as_ref().is_none_or(|query| {
+                query.matches(proc, &|text| matches_search(proc, text, search_scope.get(), &search_cache))
+            })
         });
         self.filter_model.set_filter(Some(&filter));
+        error.map_or(Ok(()), Err)
+    }
+
+    /// Choose what the search matches besides names and PIDs
+    pub fn set_search_scope(&self, scope: SearchScope) {
+        self.search_scope.set(scope);
+        let text = self.filter_text.borrow().clone();
+        let _ = self.set_filter(&text);
+    }
+
+    /// Show only the session, system services or other users; None shows everything
+    pub fn set_origin_filter(&self, origin: Option<ProcessOrigin>) {
+        self.origin_filter.set(origin);
+        let text = self.filter_text.borrow().clone();
+        let _ = self.set_filter(&text);
+    }
+
+    /// Show only processes in one state, e.g. uninterruptible sleep; None shows every state
+    pub fn set_state_filter(&self, state: Option<ProcessState>) {
+        self.state_filter.set(state);
+        let text = self.filter_text.borrow().clone();
+        let _ = self.set_filter(&text);
+    }
+
+    /// Group rows under "My Session", "System Services" and "Other Users" headers,
+    /// sorting by the chosen column within each group
+    pub fn set_group_by_origin(&self, group: bool) {
+        self.group_by_origin.set(group);
+        self.update_sections();
+    }
+
+    /// Section the rows into pinned processes first, then by origin when grouping; with
+    /// neither the list has no headers
+    fn update_sections(&self) {
+        let by_origin = self.group_by_origin.get();
+        if !by_origin && !self.pinned_section.get() {
+            self.sort_model.set_section_sorter(None::<&gtk4::Sorter>);
+            self.column_view.set_header_factory(None::<&gtk4::ListItemFactory>);
+            return;
+        }
+        let sorter = CustomSorter::new(move |a, b| {
+            let section = |obj: &Object| {
+                let proc = obj.downcast_ref::<ProcessObject>();
+                match proc.filter(|p| p.pinned()) {
+                    Some(_) => (false, None),
+                    None => (true, proc.and_then(|p| p.origin()).filter(|_| by_origin)),
+                }
+            };
+            section(a).cmp(&section(b)).into()
+        });
+        self.sort_model.set_section_sorter(Some(&sorter));
+        self.column_view.set_header_factory(Some(&Self::section_header_factory(by_origin)));
+    }
+
+    fn section_header_factory(by_origin: bool) -> SignalListItemFactory {
+        let factory = SignalListItemFactory::new();
+        factory.connect_setup(|_, item| {
+            let header = item.downcast_ref::<ListHeader>()
+                .expect("Header factory item should be a ListHeader");
+            let label = Label::new(None);
+            label.set_halign(gtk4::Align::Start);
+            label.add_css_class("heading");
+            header.set_child(Some(&label));
+        });
+        factory.connect_bind(move |_, item| {
+            let header = item.downcast_ref::<ListHeader>()
+                .expect("Header factory item should be a ListHeader");
+            let label = header.child().and_downcast::<Label>()
+                .expect("Header child should be a Label");
+            let proc = header.item().and_downcast::<ProcessObject>();
+            let title = match proc {
+                Some(proc) if proc.pinned() => "Pinned",
+                _ if !by_origin => "Processes",
+                proc => proc.and_then(|proc| proc.origin()).map_or("Unknown", |origin| origin.as_str()),
+            };
+            label.set_text(&format!("{} ({})", title, header.n_items()));
+        });
+        factory
     }
 
     /// Get the selection model for connecting signals
-    pub fn selection_model(&self) -> &SingleSelection {
+    pub fn selection_model(&self) -> &MultiSelection {
         &self.selection
     }
 
@@ -542,12 +1676,9 @@ impl ProcessListView {
         &self.column_view
     }
 
-    /// Get the currently selected process (pid, name)
-    pub fn get_selected_process(&self) -> Option<(u32, String)> {
-        self.selection
-            .selected_item()
-            .and_then(|obj| obj.downcast::<ProcessObject>().ok())
-            .map(|p| (p.pid(), p.name()))
+    /// Get all selected processes (pid, name), in display order
+    pub fn get_selected_processes(&self) -> Vec<(u32, String)> {
+        self.selected_objects().iter().map(|p| (p.pid(), p.name())).collect()
     }
 
     /// Connect a callback for row activation (double-click or Enter key)
@@ -3,38 +3,87 @@
 use gtk4::prelude::*;
 use gtk4::{
     Box as GtkBox, Button, CheckButton, DropDown, Label, Orientation,
-    ScrolledWindow, Separator, StringList, Window,
+    ScrolledWindow, Separator, StringList, ToggleButton, Window,
 };
 use libadwaita as adw;
 use adw::prelude::*;
 use glib::ControlFlow;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
 use std::rc::Rc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
+
+use procular_core::mangohud::{self, FrameLog};
+#[cfg(feature = "perf")]
+use procular_core::perf_events::{HardwareCounterSampler, HardwareSample, SyscallSampler};
+use procular_core::process_actions::{
+    self, get_cpu_affinity, get_cpu_core_info, run_in_background, set_cpu_affinity,
+    set_priority, Priority, CoreType, Signal,
+};
 
+use crate::adaptive;
+use crate::app_state::AppState;
+use crate::automation::AutomationEngine;
+use crate::connections_view::ConnectionsView;
+use crate::context_menu;
 use crate::detail_view::{DetailView, ProcessDetails};
-use crate::monitor::SystemMonitor;
-use crate::process_actions::{
-    self, get_cpu_affinity, get_cpu_core_info, kill_process, set_cpu_affinity,
-    set_priority, Priority, CoreType,
-};
+use crate::environment_view::EnvironmentView;
+use crate::limits_dialog;
+use crate::memory_view::MemoryView;
+use crate::open_files_view::OpenFilesView;
+use crate::shortcuts;
+use crate::threads_view::ThreadsView;
+use crate::window_state;
 
 const UPDATE_INTERVAL_MS: u64 = 2000;
 
+/// MangoHud log of a game and the frame rate read from it
+struct FrameRateLog {
+    log: FrameLog,
+    fps: VecDeque<f64>,
+    worst_frame_times: VecDeque<f64>,
+}
+
+/// Show the frame rate section and follow MangoHud's log if the process runs with MangoHud
+fn start_frame_rate(detail_view: &DetailView, pid: u32, name: &str) -> Option<FrameRateLog> {
+    let status = mangohud::game_status(pid);
+    if !status.mangohud {
+        detail_view.hide_frame_rate();
+        return None;
+    }
+    let folder = mangohud::output_folder(pid, name);
+    let gamemode = if status.gamemode { "GameMode active · " } else { "" };
+    detail_view.show_frame_rate(
+        &format!("{}MangoHud logs to {}", gamemode, folder.display()),
+        mangohud::control_socket(pid).is_some(),
+    );
+    Some(FrameRateLog {
+        log: FrameLog::new(folder, SystemTime::now()),
+        fps: VecDeque::new(),
+        worst_frame_times: VecDeque::new(),
+    })
+}
+
 /// Create and show a window for monitoring a single process
 pub fn open_process_window(
     parent: &impl IsA<Window>,
     pid: u32,
     name: &str,
-    monitor: Rc<RefCell<SystemMonitor>>,
+    state: AppState,
 ) {
+    let monitor = state.monitor.clone();
+    let automation = state.automation.clone();
+
     let window = adw::Window::builder()
         .title(&format!("{} (PID: {}) - Procular", name, pid))
         .icon_name("procular")
-        .default_width(600)
+        .default_width(800)
         .default_height(700)
+        .width_request(adaptive::MIN_WIDTH)
+        .height_request(adaptive::MIN_HEIGHT)
         .transient_for(parent)
         .build();
+    window_state::remember_size(&window, window_state::PROCESS_WINDOW_GROUP);
 
     let main_box = GtkBox::new(Orientation::Vertical, 0);
 
@@ -57,6 +106,10 @@ pub fn open_process_window(
 
     main_box.append(&header);
 
+    // Shown when the PID is reused by a new process
+    let restart_banner = adw::Banner::new("");
+    main_box.append(&restart_banner);
+
     // Action buttons bar
     let action_bar = GtkBox::new(Orientation::Horizontal, 8);
     action_bar.set_margin_start(12);
@@ -89,32 +142,143 @@ pub fn open_process_window(
     let priority_btn = Button::with_label("Set Priority");
     action_bar.append(&priority_btn);
 
-    main_box.append(&action_bar);
+    // cgroup limits button
+    let limits_btn = Button::with_label("Limit Resources");
+    action_bar.append(&limits_btn);
+
+    // Focus priority rule toggle
+    let focus_boost_btn = ToggleButton::with_label("Boost While Focused");
+    focus_boost_btn.set_tooltip_text(Some(
+        "Run at High priority while this program's window is focused and Normal otherwise.\n\
+         Applies to every process with this name and is remembered across restarts.\n\
+         Raising priority needs CAP_SYS_NICE; only X11/XWayland windows are detected.",
+    ));
+    focus_boost_btn.set_active(automation.borrow().has_focus_rule(name));
+    action_bar.append(&focus_boost_btn);
+
+    // Critical watch: keep the machine awake while this window is watching
+    let keep_awake_btn = ToggleButton::with_label("Keep Awake");
+    action_bar.append(&keep_awake_btn);
+    update_keep_awake_sensitivity(&keep_awake_btn, state.inhibitor.is_enabled());
+
+    // Read-only mode hides everything that changes the process
+    for widget in [
+        end_btn.upcast_ref::<gtk4::Widget>(),
+        kill_btn.upcast_ref(),
+        sep.upcast_ref(),
+        affinity_btn.upcast_ref(),
+        priority_btn.upcast_ref(),
+        limits_btn.upcast_ref(),
+        focus_boost_btn.upcast_ref(),
+    ] {
+        state.lockdown.bind_widget(widget);
+    }
+
+    // Scrolls sideways instead of forcing a wide window on small screens
+    let action_scroller = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vscrollbar_policy(gtk4::PolicyType::Never)
+        .child(&action_bar)
+        .build();
+    main_box.append(&action_scroller);
 
     // Separator
     let sep = Separator::new(Orientation::Horizontal);
     main_box.append(&sep);
 
-    // Detail view
+    // Tabs: graphs/details and open files
+    let view_stack = adw::ViewStack::new();
+    view_stack.set_vexpand(true);
+
     let detail_view = DetailView::new();
-    main_box.append(&detail_view.widget);
+    view_stack.add_titled_with_icon(&detail_view.widget, Some("overview"), "Overview", "utilities-system-monitor-symbolic");
+
+    let memory_view = Rc::new(MemoryView::new());
+    view_stack.add_titled_with_icon(&memory_view.widget, Some("memory"), "Memory", "drive-harddisk-solidstate-symbolic");
+
+    let threads_view = Rc::new(ThreadsView::new());
+    view_stack.add_titled_with_icon(&threads_view.widget, Some("threads"), "Threads", "view-list-symbolic");
+
+    let open_files_view = Rc::new(OpenFilesView::new());
+    view_stack.add_titled_with_icon(&open_files_view.widget, Some("files"), "Open Files", "document-open-symbolic");
+
+    let connections_view = Rc::new(ConnectionsView::new());
+    view_stack.add_titled_with_icon(&connections_view.widget, Some("connections"), "Connections", "network-wired-symbolic");
+
+    // The environment is fixed at exec, so it is only loaded when the tab is opened
+    let environment_view = Rc::new(EnvironmentView::new());
+    view_stack.add_titled_with_icon(&environment_view.widget, Some("environment"), "Environment", "preferences-other-symbolic");
+
+    let view_switcher = adw::ViewSwitcher::builder()
+        .stack(&view_stack)
+        .policy(adw::ViewSwitcherPolicy::Wide)
+        .build();
+    header.set_title_widget(Some(&view_switcher));
+
+    main_box.append(&view_stack);
+
+    let switcher_bar = adaptive::view_switcher_bar(&view_stack);
+    main_box.append(&switcher_bar);
+    let breakpoint = adaptive::narrow_breakpoint(&header, &switcher_bar);
+    breakpoint.add_setter(&history_label, "visible", Some(&false.to_value()));
+    window.add_breakpoint(breakpoint);
 
     window.set_content(Some(&main_box));
+    shortcuts::add_close_shortcut(&window);
+
+    // The process instance being watched; a new start time means the PID was reused
+    let mut process_key = monitor.borrow().process_key(pid);
+
+    // Keep recording the watched process even while it is not among the busiest
+    let tracked_key = Rc::new(Cell::new(process_key));
+    if let Some(key) = process_key {
+        monitor.borrow_mut().track(key);
+    }
 
     // Initial update
     {
         let mon = monitor.borrow();
-        let history = mon.get_history(pid);
+        let history = process_key.and_then(|key| mon.get_history(key));
+        let gaps: Vec<bool> = mon.sample_gaps().iter().copied().collect();
+        let net_totals = process_key.and_then(|key| mon.net_totals(key));
         let process_details = ProcessDetails::from_pid(pid);
-        detail_view.update(name, pid, history, process_details.as_ref());
+        detail_view.set_cpu_scale(state.cpu_scale());
+        detail_view.set_sample_interval(state.sample_interval_secs.get());
+        detail_view.update(name, pid, history, &gaps, net_totals, process_details.as_ref());
     }
 
+    // Jump to the debugger attached to this process
+    let window_weak_clone = window.downgrade();
+    let state_clone = state.clone();
+    detail_view.connect_open_tracer(move |tracer| {
+        if let Some(win) = window_weak_clone.upgrade() {
+            open_process_window(
+                &win,
+                tracer,
+                &process_actions::get_process_name(tracer),
+                state_clone.clone(),
+            );
+        }
+    });
+
     // Store window reference for closing
     let window_weak = window.downgrade();
     let window_weak_for_timer = window.downgrade();
-    let name_owned = name.to_string();
+    let mut name_owned = name.to_string();
     let detail_view = Rc::new(detail_view);
 
+    // Games running with MangoHud: frame rate from its log, logging toggled over its control socket
+    let mut frame_rate = start_frame_rate(&detail_view, pid, name);
+    let window_weak_clone = window.downgrade();
+    detail_view.connect_toggle_frame_logging(move || {
+        let result = mangohud::control_socket(pid)
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "MangoHud's control socket is gone"))
+            .and_then(|socket| mangohud::toggle_logging(&socket));
+        if let (Err(e), Some(win)) = (result, window_weak_clone.upgrade()) {
+            show_error_dialog(&win, "Failed to toggle MangoHud logging", &e.to_string());
+        }
+    });
+
     // Connect history duration dropdown
     let monitor_clone = monitor.clone();
     history_dropdown.connect_selected_notify(move |dropdown| {
@@ -132,11 +296,66 @@ pub fn open_process_window(
             _ => 150,   // Default to 5 min
         };
         monitor_clone.borrow_mut().set_max_samples(max_samples);
+        if let Err(e) = window_state::save_history_choice(idx) {
+            eprintln!("Failed to save history length: {}", e);
+        }
     });
+    // Bring back the length last picked (changing the selection applies it)
+    let choices = history_dropdown.model().map_or(0, |model| model.n_items());
+    if let Some(choice) = window_state::history_choice().filter(|&choice| choice < choices) {
+        history_dropdown.set_selected(choice);
+    }
+
+    // Load tab contents when a tab is shown
+    let memory_clone = memory_view.clone();
+    let threads_clone = threads_view.clone();
+    let state_clone = state.clone();
+    let open_files_clone = open_files_view.clone();
+    let connections_clone = connections_view.clone();
+    let environment_clone = environment_view.clone();
+    view_stack.connect_visible_child_name_notify(move |stack| {
+        match stack.visible_child_name().as_deref() {
+            Some("memory") => memory_clone.refresh(pid),
+            Some("threads") => {
+                let cpu_count = state_clone.monitor.borrow().cpu_count();
+                threads_clone.refresh(pid, cpu_count, state_clone.cpu_scale());
+            }
+            Some("files") => open_files_clone.refresh(pid),
+            Some("connections") => connections_clone.refresh(pid),
+            Some("environment") => environment_clone.refresh(pid),
+            _ => {}
+        }
+    });
+
+    // Syscall rate sampling via perf_event (needs CAP_PERFMON or relaxed perf_event_paranoid)
+    #[cfg(feature = "perf")]
+    let mut syscall_sampler = match SyscallSampler::new(pid) {
+        Ok(sampler) => Some(sampler),
+        Err(e) => {
+            eprintln!("Syscall sampling unavailable for PID {}: {}", pid, e);
+            None
+        }
+    };
+    #[cfg(feature = "perf")]
+    let mut syscall_history: VecDeque<f64> = VecDeque::new();
+    #[cfg(feature = "perf")]
+    let mut hardware_sampler = match HardwareCounterSampler::new(pid) {
+        Ok(sampler) => Some(sampler),
+        Err(e) => {
+            eprintln!("Hardware counters unavailable for PID {}: {}", pid, e);
+            None
+        }
+    };
+    #[cfg(feature = "perf")]
+    let mut hardware_history: VecDeque<HardwareSample> = VecDeque::new();
 
     // Set up periodic refresh
     let detail_view_clone = detail_view.clone();
     let monitor_clone = monitor.clone();
+    let view_stack_clone = view_stack.clone();
+    let environment_clone = environment_view.clone();
+    let tracked_key_clone = tracked_key.clone();
+    let state_for_timer = state.clone();
 
     let source_id = glib::timeout_add_local(Duration::from_millis(UPDATE_INTERVAL_MS), move || {
         // Check if window still exists
@@ -151,69 +370,240 @@ pub fn open_process_window(
             return ControlFlow::Break;
         }
 
-        // Update detail view
+        // Move history tracking to the new process when the PID is reused
+        let current_key = monitor_clone.borrow().process_key(pid);
+        if current_key.is_some() && current_key != tracked_key_clone.get() {
+            let mut mon = monitor_clone.borrow_mut();
+            if let Some(old) = tracked_key_clone.replace(current_key) {
+                mon.untrack(old);
+            }
+            if let Some(new) = current_key {
+                mon.track(new);
+            }
+        }
+
         let mon = monitor_clone.borrow();
-        let history = mon.get_history(pid);
+
+        // PID reused: follow the new process, which starts with an empty history
+        if let (Some(old), Some(new)) = (process_key, current_key) {
+            if old != new {
+                name_owned = process_actions::get_process_name(pid);
+                win.set_title(Some(&format!("{} (PID: {}) - Procular", name_owned, pid)));
+                let started = glib::DateTime::from_unix_local(new.start_time as i64)
+                    .and_then(|time| time.format("%X"))
+                    .map(|time| format!(" at {}", time))
+                    .unwrap_or_default();
+                restart_banner.set_title(&format!(
+                    "PID {} now belongs to a new process, {}, started{}. Earlier history was discarded.",
+                    pid, name_owned, started
+                ));
+                restart_banner.set_revealed(true);
+                if view_stack_clone.visible_child_name().as_deref() == Some("environment") {
+                    environment_clone.refresh(pid);
+                }
+
+                frame_rate = start_frame_rate(&detail_view_clone, pid, &name_owned);
+
+                #[cfg(feature = "perf")]
+                {
+                    syscall_sampler = SyscallSampler::new(pid).ok();
+                    syscall_history.clear();
+                    hardware_sampler = HardwareCounterSampler::new(pid).ok();
+                    hardware_history.clear();
+                }
+            }
+        }
+        if current_key.is_some() {
+            process_key = current_key;
+        }
+
+        // Update detail view
+        let history = process_key.and_then(|key| mon.get_history(key));
+        let gaps: Vec<bool> = mon.sample_gaps().iter().copied().collect();
+        let net_totals = process_key.and_then(|key| mon.net_totals(key));
         let process_details = ProcessDetails::from_pid(pid);
-        detail_view_clone.update(&name_owned, pid, history, process_details.as_ref());
+        detail_view_clone.set_cpu_scale(state_for_timer.cpu_scale());
+        detail_view_clone.set_sample_interval(state_for_timer.sample_interval_secs.get());
+        detail_view_clone.update(&name_owned, pid, history, &gaps, net_totals, process_details.as_ref());
+
+        match view_stack_clone.visible_child_name().as_deref() {
+            Some("memory") => memory_view.refresh(pid),
+            Some("threads") => threads_view.refresh(pid, mon.cpu_count(), state_for_timer.cpu_scale()),
+            Some("files") => open_files_view.refresh(pid),
+            Some("connections") => connections_view.refresh(pid),
+            _ => {}
+        }
+
+        if let Some(frames) = frame_rate.as_mut() {
+            if let Some(stats) = frames.log.read() {
+                frames.fps.push_back(stats.average_fps);
+                frames.worst_frame_times.push_back(stats.worst_frame_time_ms);
+                while frames.fps.len() > mon.max_samples() {
+                    frames.fps.pop_front();
+                    frames.worst_frame_times.pop_front();
+                }
+                let fps: Vec<f64> = frames.fps.iter().copied().collect();
+                let worst: Vec<f64> = frames.worst_frame_times.iter().copied().collect();
+                detail_view_clone.update_frame_rate(&fps, &worst, UPDATE_INTERVAL_MS / 1000);
+            }
+        }
+
+        #[cfg(feature = "perf")]
+        if let Some(sampler) = syscall_sampler.as_mut() {
+            syscall_history.push_back(sampler.sample());
+            while syscall_history.len() > mon.max_samples() {
+                syscall_history.pop_front();
+            }
+            let rates: Vec<f64> = syscall_history.iter().copied().collect();
+            detail_view_clone.update_syscall_rate(&rates, UPDATE_INTERVAL_MS / 1000);
+        }
+
+        #[cfg(feature = "perf")]
+        if let Some(sampler) = hardware_sampler.as_mut() {
+            hardware_history.push_back(sampler.sample());
+            while hardware_history.len() > mon.max_samples() {
+                hardware_history.pop_front();
+            }
+            let samples: Vec<HardwareSample> = hardware_history.iter().copied().collect();
+            detail_view_clone.update_hardware_counters(&samples, UPDATE_INTERVAL_MS / 1000);
+        }
 
         ControlFlow::Continue
     });
 
     let source_id = Rc::new(RefCell::new(Some(source_id)));
 
-    // Connect End Process button
+    // Connect End Process button; asks first unless quick-kill mode is on, and is audited
     let window_weak_clone = window_weak.clone();
+    let state_clone = state.clone();
+    let process_name = name.to_string();
     end_btn.connect_clicked(move |_| {
-        if let Err(e) = kill_process(pid, false) {
-            if let Some(win) = window_weak_clone.upgrade() {
+        let Some(win) = window_weak_clone.upgrade() else {
+            return;
+        };
+        let window_weak = win.downgrade();
+        let targets = vec![(pid, process_name.clone())];
+        context_menu::send_signal_confirmed(win.upcast_ref(), targets, Signal::Term, &state_clone, move |result| {
+            if let (Err(e), Some(win)) = (result, window_weak.upgrade()) {
                 show_error_dialog(&win, "Failed to end process", &e.to_string());
             }
-        }
-        // Process will end, timer will close window
+            // Process will end, timer will close window
+        });
     });
 
     // Connect Force Kill button
     let window_weak_clone = window_weak.clone();
     let source_id_clone = source_id.clone();
+    let state_clone = state.clone();
+    let process_name = name.to_string();
     kill_btn.connect_clicked(move |_| {
-        if let Err(e) = kill_process(pid, true) {
-            if let Some(win) = window_weak_clone.upgrade() {
-                show_error_dialog(&win, "Failed to kill process", &e.to_string());
-            }
-        } else {
-            // Process killed, close window immediately
-            if let Some(win) = window_weak_clone.upgrade() {
-                if let Some(id) = source_id_clone.borrow_mut().take() {
-                    id.remove();
+        let Some(win) = window_weak_clone.upgrade() else {
+            return;
+        };
+        let window_weak = win.downgrade();
+        let source_id = source_id_clone.clone();
+        let targets = vec![(pid, process_name.clone())];
+        context_menu::send_signal_confirmed(win.upcast_ref(), targets, Signal::Kill, &state_clone, move |result| {
+            let Some(win) = window_weak.upgrade() else {
+                return;
+            };
+            match result {
+                Err(e) => show_error_dialog(&win, "Failed to kill process", &e.to_string()),
+                Ok(()) => {
+                    // Process killed, close window immediately
+                    if let Some(id) = source_id.borrow_mut().take() {
+                        id.remove();
+                    }
+                    win.close();
                 }
-                win.close();
             }
-        }
+        });
     });
 
     // Connect CPU Affinity button
     let window_weak_clone = window_weak.clone();
+    let automation_clone = automation.clone();
+    let process_name = name.to_string();
     affinity_btn.connect_clicked(move |_| {
         if let Some(win) = window_weak_clone.upgrade() {
-            show_affinity_dialog(&win, pid);
+            show_affinity_dialog(&win, pid, &process_name, automation_clone.clone());
         }
     });
 
     // Connect Priority button
     let window_weak_clone = window_weak.clone();
+    let automation_clone = automation.clone();
+    let process_name = name.to_string();
     priority_btn.connect_clicked(move |_| {
         if let Some(win) = window_weak_clone.upgrade() {
-            show_priority_dialog(&win, pid);
+            show_priority_dialog(&win, pid, &process_name, automation_clone.clone());
+        }
+    });
+
+    // Connect Limit Resources button
+    let window_weak_clone = window_weak.clone();
+    let process_name = name.to_string();
+    limits_btn.connect_clicked(move |_| {
+        if let Some(win) = window_weak_clone.upgrade() {
+            limits_dialog::show_limits_dialog(&win, vec![(pid, process_name.clone())]);
         }
     });
 
-    // Clean up timer on window close
+    // Connect focus priority toggle
+    let window_weak_clone = window_weak.clone();
+    let rule_name = name.to_string();
+    focus_boost_btn.connect_toggled(move |btn| {
+        let priorities = btn.is_active().then_some((Priority::High, Priority::Normal));
+        let mut engine = automation.borrow_mut();
+        engine.set_focus_rule(&rule_name, priorities);
+        if let Err(e) = engine.save() {
+            if let Some(win) = window_weak_clone.upgrade() {
+                show_error_dialog(&win, "Failed to save automation rule", &e.to_string());
+            }
+        }
+    });
+
+    // Connect keep awake toggle
+    let inhibit_id: Rc<RefCell<Option<u32>>> = Rc::new(RefCell::new(None));
+    let window_weak_clone = window_weak.clone();
+    let inhibitor = state.inhibitor.clone();
+    let inhibit_id_clone = inhibit_id.clone();
+    let reason = format!("Watching {} (PID {})", name, pid);
+    keep_awake_btn.connect_toggled(move |btn| {
+        if let Some(id) = inhibit_id_clone.borrow_mut().take() {
+            inhibitor.release(id);
+        }
+        if btn.is_active() {
+            if let Some(win) = window_weak_clone.upgrade() {
+                *inhibit_id_clone.borrow_mut() = Some(inhibitor.inhibit(&win, &reason));
+            }
+        }
+    });
+
+    // Follow the preference while the window is open
+    let keep_awake_weak = keep_awake_btn.downgrade();
+    state.inhibitor.connect_changed(move |inhibitor| {
+        let Some(btn) = keep_awake_weak.upgrade() else {
+            return ControlFlow::Break;
+        };
+        update_keep_awake_sensitivity(&btn, inhibitor.is_enabled());
+        ControlFlow::Continue
+    });
+
+    // Clean up timer, history tracking and inhibit on window close
     let source_id_clone = source_id.clone();
+    let inhibitor = state.inhibitor.clone();
+    let monitor_clone = monitor.clone();
     window.connect_close_request(move |_| {
         if let Some(id) = source_id_clone.borrow_mut().take() {
             id.remove();
         }
+        if let Some(key) = tracked_key.take() {
+            monitor_clone.borrow_mut().untrack(key);
+        }
+        if let Some(id) = inhibit_id.borrow_mut().take() {
+            inhibitor.release(id);
+        }
         glib::Propagation::Proceed
     });
 
@@ -221,7 +611,22 @@ pub fn open_process_window(
 }
 
 /// Show CPU affinity dialog with core type information
-fn show_affinity_dialog(parent: &impl IsA<Window>, pid: u32) {
+/// Explain why Keep Awake does nothing when the preference is off
+fn update_keep_awake_sensitivity(btn: &ToggleButton, enabled: bool) {
+    btn.set_sensitive(enabled);
+    btn.set_tooltip_text(Some(if enabled {
+        "Prevent suspend and screen blanking while this window is open"
+    } else {
+        "Preventing sleep is turned off in Preferences"
+    }));
+}
+
+fn show_affinity_dialog(
+    parent: &impl IsA<Window>,
+    pid: u32,
+    name: &str,
+    automation: Rc<RefCell<AutomationEngine>>,
+) {
     let core_info = get_cpu_core_info();
     let current_affinity = get_cpu_affinity(pid).unwrap_or_else(|_| vec![true; core_info.len()]);
 
@@ -385,6 +790,9 @@ fn show_affinity_dialog(parent: &impl IsA<Window>, pid: u32) {
         content.append(&type_btn_box);
     }
 
+    let inherit_check = context_menu::create_inherit_check();
+    content.append(&inherit_check);
+
     main_box.append(&content);
     dialog.set_content(Some(&main_box));
 
@@ -400,6 +808,7 @@ fn show_affinity_dialog(parent: &impl IsA<Window>, pid: u32) {
     let checkboxes_clone = checkboxes.clone();
     let parent_weak = parent.downgrade();
     let dialog_weak = dialog.downgrade();
+    let name = name.to_string();
     apply_btn.connect_clicked(move |_| {
         let selected_cpus: Vec<usize> = checkboxes_clone
             .borrow()
@@ -413,9 +822,20 @@ fn show_affinity_dialog(parent: &impl IsA<Window>, pid: u32) {
             if let Some(parent) = parent_weak.upgrade() {
                 show_error_dialog(&parent, "Invalid Selection", "You must select at least one CPU.");
             }
-        } else if let Err(e) = set_cpu_affinity(pid, &selected_cpus) {
-            if let Some(parent) = parent_weak.upgrade() {
-                show_error_dialog(&parent, "Failed to set CPU affinity", &e.to_string());
+        } else {
+            let (cpus, error_parent) = (selected_cpus.clone(), parent_weak.clone());
+            glib::spawn_future_local(async move {
+                if let (Err(e), Some(parent)) =
+                    (run_in_background(move || set_cpu_affinity(pid, &cpus)).await, error_parent.upgrade())
+                {
+                    show_error_dialog(&parent, "Failed to set CPU affinity", &e.to_string());
+                }
+            });
+            if let (true, Some(parent)) = (inherit_check.is_active(), parent_weak.upgrade()) {
+                let names = std::slice::from_ref(&name);
+                context_menu::save_inherited_rules(parent.upcast_ref(), &automation, names, |engine, name| {
+                    engine.set_inherited_affinity(name, selected_cpus.clone())
+                });
             }
         }
 
@@ -428,7 +848,7 @@ fn show_affinity_dialog(parent: &impl IsA<Window>, pid: u32) {
 }
 
 /// Create a legend showing core type colors
-fn create_core_type_legend(core_info: &[crate::process_actions::CpuCoreInfo]) -> GtkBox {
+fn create_core_type_legend(core_info: &[procular_core::process_actions::CpuCoreInfo]) -> GtkBox {
     let legend = GtkBox::new(Orientation::Horizontal, 16);
     legend.set_halign(gtk4::Align::Center);
     legend.set_margin_top(4);
@@ -460,7 +880,12 @@ fn create_core_type_legend(core_info: &[crate::process_actions::CpuCoreInfo]) ->
 }
 
 /// Show priority dialog using adw::Window
-fn show_priority_dialog(parent: &impl IsA<Window>, pid: u32) {
+fn show_priority_dialog(
+    parent: &impl IsA<Window>,
+    pid: u32,
+    name: &str,
+    automation: Rc<RefCell<AutomationEngine>>,
+) {
     let current_priority = process_actions::get_priority(pid).unwrap_or(0);
 
     let dialog = adw::Window::builder()
@@ -523,14 +948,19 @@ fn show_priority_dialog(parent: &impl IsA<Window>, pid: u32) {
 
     content.append(&priority_box);
 
-    let note = Label::new(Some(
-        "Note: Setting higher priority (lower nice value) may require root privileges.",
-    ));
+    let note = Label::new(Some(if process_actions::is_pkexec_available() {
+        "Note: Higher priority (lower nice value) will ask for administrator authorization."
+    } else {
+        "Note: Higher priority (lower nice value) may require root privileges."
+    }));
     note.add_css_class("dim-label");
     note.set_halign(gtk4::Align::Start);
     note.set_wrap(true);
     content.append(&note);
 
+    let inherit_check = context_menu::create_inherit_check();
+    content.append(&inherit_check);
+
     main_box.append(&content);
     dialog.set_content(Some(&main_box));
 
@@ -546,13 +976,23 @@ fn show_priority_dialog(parent: &impl IsA<Window>, pid: u32) {
     let buttons_clone = buttons.clone();
     let parent_weak = parent.downgrade();
     let dialog_weak = dialog.downgrade();
+    let name = name.to_string();
     apply_btn.connect_clicked(move |_| {
         for (radio, priority) in buttons_clone.borrow().iter() {
             if radio.is_active() {
-                if let Err(e) = set_priority(pid, *priority) {
-                    if let Some(parent) = parent_weak.upgrade() {
+                let (priority_value, error_parent) = (*priority, parent_weak.clone());
+                glib::spawn_future_local(async move {
+                    if let (Err(e), Some(parent)) =
+                        (run_in_background(move || set_priority(pid, priority_value)).await, error_parent.upgrade())
+                    {
                         show_error_dialog(&parent, "Failed to set priority", &e.to_string());
                     }
+                });
+                if let (true, Some(parent)) = (inherit_check.is_active(), parent_weak.upgrade()) {
+                    let names = std::slice::from_ref(&name);
+                    context_menu::save_inherited_rules(parent.upcast_ref(), &automation, names, |engine, name| {
+                        engine.set_inherited_priority(name, *priority)
+                    });
                 }
                 break;
             }
@@ -0,0 +1,222 @@
+//! Properties window listing everything /proc has on a process, with each value selectable
+//! and a button copying all of them as text
+
+use gtk4::prelude::*;
+use gtk4::gdk::Display;
+use gtk4::Button;
+use libadwaita as adw;
+use adw::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use procular_core::process_properties::{read_process_properties, ProcessProperties};
+use procular_core::users::user_name;
+
+/// Shown for values that cannot be read, usually another user's process without ptrace access
+const NOT_ACCESSIBLE: &str = "Not accessible";
+
+/// Titled groups of (label, value) pairs, used for both the rows and the copied text
+type Sections = Vec<(&'static str, Vec<(String, String)>)>;
+
+fn or_not_accessible(value: &Option<String>) -> String {
+    value.clone().unwrap_or_else(|| NOT_ACCESSIBLE.to_string())
+}
+
+/// "1000 (alice)" for each distinct ID, real first
+fn describe_ids(ids: &[u32], name: impl Fn(u32) -> Option<String>) -> String {
+    if ids.is_empty() {
+        return "None".to_string();
+    }
+    let mut distinct: Vec<u32> = Vec::new();
+    for id in ids {
+        if !distinct.contains(id) {
+            distinct.push(*id);
+        }
+    }
+    distinct
+        .iter()
+        .map(|&id| match name(id) {
+            Some(name) => format!("{} ({})", id, name),
+            None => id.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn sections(properties: &ProcessProperties) -> Sections {
+    let process = vec![
+        ("PID".to_string(), properties.pid.to_string()),
+        ("Name".to_string(), properties.name.clone()),
+        ("Parent PID".to_string(), properties.parent_pid.map_or_else(|| "None".to_string(), |ppid| ppid.to_string())),
+        ("Command".to_string(), or_not_accessible(&properties.command)),
+        ("Executable".to_string(), or_not_accessible(&properties.exe)),
+        ("Working Directory".to_string(), or_not_accessible(&properties.cwd)),
+        ("Root Directory".to_string(), or_not_accessible(&properties.root)),
+        ("Cgroup".to_string(), or_not_accessible(&properties.cgroup)),
+    ];
+
+    let identity = vec![
+        ("Users (real, effective, saved, filesystem)".to_string(), describe_ids(&properties.uids, user_name)),
+        ("Groups (real, effective, saved, filesystem)".to_string(), describe_ids(&properties.gids, |_| None)),
+        ("Supplementary Groups".to_string(), describe_ids(&properties.groups, |_| None)),
+    ];
+
+    let namespaces = if properties.namespaces.is_empty() {
+        vec![("Namespaces".to_string(), NOT_ACCESSIBLE.to_string())]
+    } else {
+        properties
+            .namespaces
+            .iter()
+            .map(|namespace| {
+                let sharing = if namespace.shared_with_self { "same as Procular" } else { "own namespace" };
+                (namespace.kind.clone(), format!("{} · {}", namespace.id, sharing))
+            })
+            .collect()
+    };
+
+    let mut security: Vec<(String, String)> = properties
+        .capabilities
+        .iter()
+        .map(|set| {
+            let value = if set.mask == 0 {
+                "None".to_string()
+            } else if set.is_full() {
+                "All capabilities".to_string()
+            } else {
+                set.names().join(", ")
+            };
+            (set.name.to_string(), value)
+        })
+        .collect();
+    security.push((
+        "No New Privileges".to_string(),
+        match properties.no_new_privs {
+            Some(true) => "Yes",
+            Some(false) => "No",
+            None => "Unknown",
+        }
+        .to_string(),
+    ));
+    security.push((
+        "Seccomp".to_string(),
+        match properties.seccomp {
+            Some(0) => "Disabled",
+            Some(1) => "Strict",
+            Some(2) => "Filter",
+            _ => "Unknown",
+        }
+        .to_string(),
+    ));
+
+    let limits = if properties.limits.is_empty() {
+        vec![("Limits".to_string(), NOT_ACCESSIBLE.to_string())]
+    } else {
+        properties
+            .limits
+            .iter()
+            .map(|limit| {
+                let units = if limit.units.is_empty() { String::new() } else { format!(" {}", limit.units) };
+                (limit.name.clone(), format!("soft {} · hard {}{}", limit.soft, limit.hard, units))
+            })
+            .collect()
+    };
+
+    vec![
+        ("Process", process),
+        ("Identity", identity),
+        ("Namespaces", namespaces),
+        ("Capabilities", security),
+        ("Resource Limits", limits),
+    ]
+}
+
+/// Plain text of all sections, for the clipboard
+fn sections_text(sections: &Sections) -> String {
+    sections
+        .iter()
+        .map(|(title, rows)| {
+            let rows: Vec<String> = rows.iter().map(|(label, value)| format!("  {}: {}", label, value)).collect();
+            format!("{}\n{}\n", title, rows.join("\n"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn build_page(sections: &Sections) -> adw::PreferencesPage {
+    let page = adw::PreferencesPage::new();
+    for (title, rows) in sections {
+        let group = adw::PreferencesGroup::builder().title(*title).build();
+        for (label, value) in rows {
+            let row = adw::ActionRow::builder()
+                .title(label)
+                .subtitle(value)
+                .subtitle_selectable(true)
+                .build();
+            row.add_css_class("property");
+            group.add(&row);
+        }
+        page.add(&group);
+    }
+    page
+}
+
+/// Show the properties of `pid`, read now; Refresh reads them again
+pub fn show_properties_dialog(parent: &impl IsA<gtk4::Window>, pid: u32, name: &str) {
+    let dialog = adw::Window::builder()
+        .title(format!("Properties of {} ({})", name, pid))
+        .transient_for(parent)
+        .default_width(640)
+        .default_height(640)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    let header = adw::HeaderBar::new();
+    let refresh_btn = Button::from_icon_name("view-refresh-symbolic");
+    refresh_btn.set_tooltip_text(Some("Refresh"));
+    header.pack_start(&refresh_btn);
+    let copy_btn = Button::with_label("Copy All");
+    copy_btn.set_tooltip_text(Some("Copy every property as text"));
+    header.pack_end(&copy_btn);
+    toolbar.add_top_bar(&header);
+
+    let toast_overlay = adw::ToastOverlay::new();
+    toolbar.set_content(Some(&toast_overlay));
+    dialog.set_content(Some(&toolbar));
+
+    let text = Rc::new(RefCell::new(String::new()));
+    let refresh: Rc<dyn Fn()> = {
+        let toast_overlay = toast_overlay.clone();
+        let copy_btn = copy_btn.clone();
+        let text = text.clone();
+        Rc::new(move || match read_process_properties(pid) {
+            Ok(properties) => {
+                let sections = sections(&properties);
+                text.replace(sections_text(&sections));
+                toast_overlay.set_child(Some(&build_page(&sections)));
+                copy_btn.set_sensitive(true);
+            }
+            Err(e) => {
+                let status = adw::StatusPage::builder()
+                    .icon_name("dialog-information-symbolic")
+                    .title("Process Not Found")
+                    .description(format!("PID {} has exited ({})", pid, e))
+                    .build();
+                toast_overlay.set_child(Some(&status));
+                copy_btn.set_sensitive(false);
+            }
+        })
+    };
+    refresh();
+
+    let refresh_clone = refresh.clone();
+    refresh_btn.connect_clicked(move |_| refresh_clone());
+
+    copy_btn.connect_clicked(move |_| {
+        if let Some(display) = Display::default() {
+            display.clipboard().set_text(&text.borrow());
+            toast_overlay.add_toast(adw::Toast::new("Copied to clipboard"));
+        }
+    });
+
+    dialog.present();
+}
@@ -0,0 +1,222 @@
+//! Editor for automation rules (renice, re-pin and memory limit rules)
+
+use gtk4::prelude::*;
+use gtk4::{Button, StringList};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use procular_core::monitor::format_bytes;
+use procular_core::process_actions::Priority;
+
+use crate::app_state::AppState;
+use crate::automation::{format_cpu_list, parse_cpu_list, Rule};
+
+/// Rule kinds offered by the "Action" row, in display order
+const ACTIONS: &[&str] = &["Set Priority", "Set CPU Affinity", "End Above Memory Limit"];
+const ACTION_PRIORITY: u32 = 0;
+const ACTION_AFFINITY: u32 = 1;
+const ACTION_MEMORY_LIMIT: u32 = 2;
+
+const MIB: u64 = 1024 * 1024;
+
+/// Show the automation rules editor
+pub fn show_rules_dialog(parent: &impl IsA<gtk4::Window>, state: &AppState) {
+    let dialog = adw::Window::builder()
+        .title("Automation Rules")
+        .transient_for(parent)
+        .modal(true)
+        .default_width(560)
+        .default_height(640)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&adw::HeaderBar::new());
+    let paused_banner = adw::Banner::new("Rules are paused in read-only mode");
+    toolbar.add_top_bar(&paused_banner);
+    let banner_weak = paused_banner.downgrade();
+    state.lockdown.connect_changed(move |read_only| match banner_weak.upgrade() {
+        Some(banner) => {
+            banner.set_revealed(read_only);
+            glib::ControlFlow::Continue
+        }
+        None => glib::ControlFlow::Break,
+    });
+
+    let page = adw::PreferencesPage::new();
+    let toast_overlay = adw::ToastOverlay::new();
+    toast_overlay.set_child(Some(&page));
+    toolbar.set_content(Some(&toast_overlay));
+    dialog.set_content(Some(&toolbar));
+
+    // New rule form
+    let add_group = adw::PreferencesGroup::builder()
+        .title("New Rule")
+        .description("Patterns match process names; * matches anything and ? a single character")
+        .build();
+    let add_btn = Button::with_label("Add");
+    add_btn.add_css_class("suggested-action");
+    add_btn.set_valign(gtk4::Align::Center);
+    add_group.set_header_suffix(Some(&add_btn));
+
+    let action_row = adw::ComboRow::builder()
+        .title("Action")
+        .model(&StringList::new(ACTIONS))
+        .build();
+    let pattern_row = adw::EntryRow::builder().title("Process Name Pattern").build();
+    let priority_names: Vec<&str> = Priority::all().iter().map(|p| p.as_str()).collect();
+    let priority_row = adw::ComboRow::builder()
+        .title("Priority")
+        .model(&StringList::new(&priority_names))
+        .selected(Priority::all().iter().position(|p| *p == Priority::Low).unwrap_or(0) as u32)
+        .build();
+    let cpus_row = adw::EntryRow::builder().title("CPUs (e.g. 0-3,6)").build();
+    let memory_row = adw::SpinRow::builder()
+        .title("Memory Limit")
+        .subtitle("MiB; processes above it are asked to terminate")
+        .adjustment(&gtk4::Adjustment::new(2048.0, 1.0, 1024.0 * 1024.0, 64.0, 1024.0, 0.0))
+        .build();
+    add_group.add(&action_row);
+    add_group.add(&pattern_row);
+    add_group.add(&priority_row);
+    add_group.add(&cpus_row);
+    add_group.add(&memory_row);
+    let children_row = adw::SwitchRow::builder()
+        .title("Include Child Processes")
+        .subtitle("Also apply to processes they start, such as build or browser workers")
+        .build();
+    add_group.add(&children_row);
+    page.add(&add_group);
+
+    let update_fields = {
+        let priority_row = priority_row.clone();
+        let cpus_row = cpus_row.clone();
+        let memory_row = memory_row.clone();
+        let children_row = children_row.clone();
+        move |action: u32| {
+            priority_row.set_visible(action == ACTION_PRIORITY);
+            cpus_row.set_visible(action == ACTION_AFFINITY);
+            memory_row.set_visible(action == ACTION_MEMORY_LIMIT);
+            children_row.set_visible(action != ACTION_MEMORY_LIMIT);
+        }
+    };
+    update_fields(action_row.selected());
+    action_row.connect_selected_notify(move |row| update_fields(row.selected()));
+
+    // Configured rules, rebuilt when one is added since groups can't be cleared
+    let rules_group: Rc<RefCell<Option<adw::PreferencesGroup>>> = Rc::default();
+    let populate = {
+        let page = page.clone();
+        let state = state.clone();
+        let toast_overlay = toast_overlay.clone();
+        move || {
+            if let Some(group) = rules_group.borrow_mut().take() {
+                page.remove(&group);
+            }
+            let rules = state.automation.borrow().rules().to_vec();
+            let group = adw::PreferencesGroup::builder()
+                .title("Rules")
+                .description(if rules.is_empty() {
+                    "No rules yet"
+                } else {
+                    "Applied on every refresh, without asking for authorization"
+                })
+                .build();
+            for rule in rules {
+                let row = adw::ActionRow::builder()
+                    .title(describe_rule(&rule))
+                    .subtitle(rule.process_name())
+                    .build();
+                let remove_btn = Button::from_icon_name("user-trash-symbolic");
+                remove_btn.set_tooltip_text(Some("Remove Rule"));
+                remove_btn.set_valign(gtk4::Align::Center);
+                remove_btn.add_css_class("flat");
+                let state = state.clone();
+                let toast_overlay = toast_overlay.clone();
+                let group_weak = group.downgrade();
+                let row_weak = row.downgrade();
+                remove_btn.connect_clicked(move |_| {
+                    let mut rules = state.automation.borrow().rules().to_vec();
+                    if let Some(index) = rules.iter().position(|r| *r == rule) {
+                        rules.remove(index);
+                    }
+                    let now_empty = rules.is_empty();
+                    save_rules(&state, rules, &toast_overlay);
+                    if let (Some(group), Some(row)) = (group_weak.upgrade(), row_weak.upgrade()) {
+                        group.remove(&row);
+                        if now_empty {
+                            group.set_description(Some("No rules yet"));
+                        }
+                    }
+                });
+                row.add_suffix(&remove_btn);
+                group.add(&row);
+            }
+            page.add(&group);
+            *rules_group.borrow_mut() = Some(group);
+        }
+    };
+    populate();
+
+    let state_clone = state.clone();
+    let toast_overlay_clone = toast_overlay.clone();
+    add_btn.connect_clicked(move |_| {
+        let pattern = pattern_row.text().trim().to_string();
+        if pattern.is_empty() {
+            toast_overlay_clone.add_toast(adw::Toast::new("Enter a process name pattern"));
+            return;
+        }
+        let include_children = children_row.is_active();
+        let rule = match action_row.selected() {
+            ACTION_PRIORITY => {
+                let priority = Priority::all()[priority_row.selected() as usize];
+                Rule::Priority { pattern, priority, include_children }
+            }
+            ACTION_AFFINITY => match parse_cpu_list(&cpus_row.text()) {
+                Some(cpus) => Rule::Affinity { pattern, cpus, include_children },
+                None => {
+                    toast_overlay_clone.add_toast(adw::Toast::new("Enter CPUs as a list such as 0-3,6"));
+                    return;
+                }
+            },
+            _ => Rule::MemoryLimit {
+                pattern,
+                limit_bytes: memory_row.value() as u64 * MIB,
+            },
+        };
+        let mut rules = state_clone.automation.borrow().rules().to_vec();
+        rules.push(rule);
+        save_rules(&state_clone, rules, &toast_overlay_clone);
+        pattern_row.set_text("");
+        cpus_row.set_text("");
+        populate();
+    });
+
+    dialog.present();
+}
+
+/// Apply and persist a new rule list
+fn save_rules(state: &AppState, rules: Vec<Rule>, toast_overlay: &adw::ToastOverlay) {
+    let mut engine = state.automation.borrow_mut();
+    engine.set_rules(rules);
+    if let Err(e) = engine.save() {
+        toast_overlay.add_toast(adw::Toast::new(&format!("Failed to save rules: {}", e)));
+    }
+}
+
+fn describe_rule(rule: &Rule) -> String {
+    let description = match rule {
+        Rule::FocusPriority { focused, unfocused, .. } => {
+            format!("{} while focused, {} otherwise", focused.as_str(), unfocused.as_str())
+        }
+        Rule::Priority { priority, .. } => format!("Set priority to {}", priority.as_str()),
+        Rule::Affinity { cpus, .. } => format!("Pin to CPUs {}", format_cpu_list(cpus)),
+        Rule::MemoryLimit { limit_bytes, .. } => format!("End above {}", format_bytes(*limit_bytes)),
+    };
+    if rule.includes_children() {
+        format!("{}, including child processes", description)
+    } else {
+        description
+    }
+}
@@ -0,0 +1,232 @@
+//! Sensors page of the main window: temperature graphs with per-sensor warning thresholds
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Label, Orientation, ScrolledWindow, SpinButton};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use procular_core::monitor::SystemMonitor;
+use procular_core::sensors::{Sensor, SensorKind};
+
+use crate::config;
+use crate::graph::GraphWidget;
+
+const THRESHOLDS_FILE: &str = "sensors.ini";
+const SENSOR_GRAPH_HEIGHT: i32 = 80;
+
+const CPU_COLOR: (f64, f64, f64) = (0.204, 0.396, 0.643); // Blue, same as CPU graphs
+const GPU_COLOR: (f64, f64, f64) = (0.459, 0.314, 0.482); // Purple
+const STORAGE_COLOR: (f64, f64, f64) = (0.180, 0.545, 0.341); // Green
+const OTHER_COLOR: (f64, f64, f64) = (0.902, 0.494, 0.133); // Orange
+
+/// Warning thresholds the user set per sensor ID, saved to sensors.ini; other sensors use their default
+#[derive(Default)]
+pub struct SensorThresholds {
+    overrides: HashMap<String, f64>,
+}
+
+impl SensorThresholds {
+    pub fn load() -> Self {
+        let key_file = config::load_key_file(THRESHOLDS_FILE);
+        let overrides = key_file
+            .groups()
+            .iter()
+            .filter_map(|id| {
+                let warning = key_file.double(id, "warning").ok()?;
+                Some((id.to_string(), warning))
+            })
+            .collect();
+        Self { overrides }
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let key_file = glib::KeyFile::new();
+        for (id, warning) in &self.overrides {
+            key_file.set_double(id, "warning", *warning);
+        }
+        config::save_key_file(THRESHOLDS_FILE, &key_file)
+    }
+
+    /// Temperature at which `sensor` warns
+    pub fn warning(&self, sensor: &Sensor) -> f64 {
+        self.overrides.get(&sensor.id).copied().unwrap_or_else(|| sensor.default_warning())
+    }
+
+    /// Change the threshold of a sensor; setting its default removes the override
+    fn set(&mut self, sensor: &Sensor, warning: f64) -> io::Result<()> {
+        if warning == sensor.default_warning() {
+            self.overrides.remove(&sensor.id);
+        } else {
+            self.overrides.insert(sensor.id.clone(), warning);
+        }
+        self.save()
+    }
+}
+
+/// Graph, reading and threshold of one sensor
+struct SensorRow {
+    id: String,
+    value_label: Label,
+    graph: GraphWidget,
+    /// Latest reading, for the threshold handler
+    sensor: Rc<RefCell<Sensor>>,
+}
+
+/// Temperature overview grouped by CPU, GPU, storage and motherboard
+pub struct SensorsView {
+    pub widget: ScrolledWindow,
+    content: GtkBox,
+    rows: RefCell<Vec<SensorRow>>,
+    thresholds: Rc<RefCell<SensorThresholds>>,
+}
+
+impl SensorsView {
+    pub fn new(thresholds: Rc<RefCell<SensorThresholds>>) -> Self {
+        let content = GtkBox::new(Orientation::Vertical, 12);
+        content.set_margin_top(12);
+        content.set_margin_bottom(12);
+        content.set_margin_start(12);
+        content.set_margin_end(12);
+
+        let widget = ScrolledWindow::new();
+        widget.set_child(Some(&content));
+
+        Self {
+            widget,
+            content,
+            rows: RefCell::new(Vec::new()),
+            thresholds,
+        }
+    }
+
+    /// Show the latest readings; the page is rebuilt only when sensors appear or disappear
+    pub fn update(&self, monitor: &SystemMonitor, sample_interval_secs: u64) {
+        let sensors = monitor.sensors();
+        let unchanged = {
+            let rows = self.rows.borrow();
+            rows.len() == sensors.len() && rows.iter().zip(sensors).all(|(row, sensor)| row.id == sensor.id)
+        };
+        if !unchanged {
+            self.rebuild(sensors);
+        }
+
+        let gaps: Vec<bool> = monitor.sample_gaps().iter().copied().collect();
+        let times: Vec<SystemTime> = monitor.sample_times().iter().copied().collect();
+        let thresholds = self.thresholds.borrow();
+        for (row, sensor) in self.rows.borrow().iter().zip(sensors) {
+            *row.sensor.borrow_mut() = sensor.clone();
+            row.value_label.set_text(&format!("{:.0} °C", sensor.celsius));
+            if sensor.celsius >= thresholds.warning(sensor) {
+                row.value_label.add_css_class("error");
+            } else {
+                row.value_label.remove_css_class("error");
+            }
+            let values: Vec<f64> = monitor.sensor_history(&sensor.id).into_iter().flatten().copied().collect();
+            row.graph.set_gaps(&gaps);
+            row.graph.set_times(&times);
+            row.graph.update(&values, monitor.max_samples(), sample_interval_secs);
+        }
+    }
+
+    fn rebuild(&self, sensors: &[Sensor]) {
+        while let Some(child) = self.content.first_child() {
+            self.content.remove(&child);
+        }
+        let mut rows = self.rows.borrow_mut();
+        rows.clear();
+
+        if sensors.is_empty() {
+            let placeholder = Label::new(Some(
+                "No temperature sensors found in /sys/class/hwmon. Virtual machines and containers usually have none.",
+            ));
+            placeholder.add_css_class("dim-label");
+            placeholder.set_wrap(true);
+            placeholder.set_margin_top(24);
+            self.content.append(&placeholder);
+            return;
+        }
+
+        let mut current_kind = None;
+        for sensor in sensors {
+            if current_kind != Some(sensor.kind) {
+                current_kind = Some(sensor.kind);
+                let heading = Label::new(Some(sensor.kind.as_str()));
+                heading.add_css_class("heading");
+                heading.set_halign(gtk4::Align::Start);
+                self.content.append(&heading);
+            }
+            rows.push(self.create_row(sensor));
+        }
+    }
+
+    fn create_row(&self, sensor: &Sensor) -> SensorRow {
+        let card = GtkBox::new(Orientation::Vertical, 4);
+        card.add_css_class("card");
+
+        let header = GtkBox::new(Orientation::Horizontal, 8);
+        header.set_margin_top(8);
+        header.set_margin_start(12);
+        header.set_margin_end(12);
+        let title = Label::new(Some(&sensor.label));
+        title.set_halign(gtk4::Align::Start);
+        header.append(&title);
+        let chip = Label::new(Some(&sensor.chip));
+        chip.add_css_class("dim-label");
+        chip.add_css_class("caption");
+        chip.set_halign(gtk4::Align::Start);
+        chip.set_hexpand(true);
+        header.append(&chip);
+
+        let value_label = Label::new(None);
+        value_label.add_css_class("numeric");
+        header.append(&value_label);
+
+        let warn_label = Label::new(Some("Warn at"));
+        warn_label.add_css_class("dim-label");
+        warn_label.set_margin_start(12);
+        header.append(&warn_label);
+        let threshold = SpinButton::with_range(30.0, 120.0, 1.0);
+        threshold.set_value(self.thresholds.borrow().warning(sensor));
+        threshold.set_valign(gtk4::Align::Center);
+        threshold.set_tooltip_text(Some(&format!(
+            "Warn when this sensor reaches the temperature (default {:.0} °C)",
+            sensor.default_warning()
+        )));
+        header.append(&threshold);
+        header.append(&Label::new(Some("°C")));
+        card.append(&header);
+
+        let color = match sensor.kind {
+            SensorKind::Cpu => CPU_COLOR,
+            SensorKind::Gpu => GPU_COLOR,
+            SensorKind::Storage => STORAGE_COLOR,
+            SensorKind::Board | SensorKind::Other => OTHER_COLOR,
+        };
+        let graph = GraphWidget::new(color, false, false);
+        graph.set_title_label(&title);
+        graph.drawing_area.set_size_request(-1, SENSOR_GRAPH_HEIGHT);
+        graph.drawing_area.set_vexpand(false);
+        card.append(&graph.drawing_area);
+        self.content.append(&card);
+
+        let sensor = Rc::new(RefCell::new(sensor.clone()));
+        let sensor_clone = sensor.clone();
+        let thresholds = self.thresholds.clone();
+        threshold.connect_value_changed(move |spin| {
+            if let Err(e) = thresholds.borrow_mut().set(&sensor_clone.borrow(), spin.value()) {
+                eprintln!("Failed to save sensor thresholds: {}", e);
+            }
+        });
+
+        let id = sensor.borrow().id.clone();
+        SensorRow {
+            id,
+            value_label,
+            graph,
+            sensor,
+        }
+    }
+}
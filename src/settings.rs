@@ -0,0 +1,349 @@
+//! User preferences stored in ~/.config/procular/settings.ini
+
+use std::io;
+
+use procular_core::developer_tools::{Tracer, DEFAULT_DEBUGGER_COMMAND};
+use procular_core::monitor::{Collectors, DEFAULT_PROCESS_LIMIT};
+use procular_core::systemd_units::ProcessOrigin;
+
+use crate::config;
+
+const SETTINGS_FILE: &str = "settings.ini";
+const GENERAL_GROUP: &str = "General";
+const STORAGE_GROUP: &str = "Storage";
+const VIEW_GROUP: &str = "View";
+const SENSORS_GROUP: &str = "Sensors";
+const PERFORMANCE_GROUP: &str = "Performance";
+const GPU_GROUP: &str = "GPU";
+const COLLECTORS_GROUP: &str = "Collectors";
+const DEVELOPER_GROUP: &str = "Developer";
+
+/// Range of the process list limit offered in Preferences
+pub const MIN_PROCESS_LIMIT: u32 = 10;
+pub const MAX_PROCESS_LIMIT: u32 = 10000;
+
+/// Screen edge the top bar docks to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenEdge {
+    Top,
+    Bottom,
+}
+
+impl ScreenEdge {
+    pub fn all() -> &'static [ScreenEdge] {
+        &[ScreenEdge::Top, ScreenEdge::Bottom]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScreenEdge::Top => "Top",
+            ScreenEdge::Bottom => "Bottom",
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            ScreenEdge::Top => "top",
+            ScreenEdge::Bottom => "bottom",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|edge| edge.key() == key)
+    }
+}
+
+/// Persistent user preferences
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// Let "Keep Awake" watches inhibit suspend and screen blanking
+    pub inhibit_suspend: bool,
+    /// Hide actions that change processes (see `Lockdown`)
+    pub read_only: bool,
+    /// End and kill processes without asking first; signals are still recorded in the audit log
+    pub quick_kill: bool,
+    /// Seconds "End Gently" waits after SIGTERM before sending SIGKILL
+    pub grace_period_secs: u32,
+    /// The first-run setup assistant has been shown
+    pub onboarding_complete: bool,
+    /// Hide the window on close and keep monitoring, with a status icon to reopen it
+    pub run_in_background: bool,
+    /// Warn when a filesystem is nearly full
+    pub disk_alerts: bool,
+    /// Used percentage at which a filesystem counts as nearly full
+    pub disk_alert_percent: u32,
+    /// Show process CPU as percent of one core (like top's Irix mode) instead of the whole machine
+    pub cpu_per_core: bool,
+    /// Label graph time axes with clock times instead of seconds before now
+    pub clock_time_axis: bool,
+    /// Show the top bar companion window
+    pub top_bar: bool,
+    pub top_bar_edge: ScreenEdge,
+    /// Show the compact always-on-top mini window
+    pub mini_window: bool,
+    /// Warn when a temperature sensor reaches its threshold
+    pub temperature_alerts: bool,
+    /// Let the search match full command lines
+    pub search_command_line: bool,
+    /// Let the search match environment variables (reads /proc/<pid>/environ)
+    pub search_environment: bool,
+    /// Show only the session, system services or other users; None shows all processes
+    pub show_processes: Option<ProcessOrigin>,
+    /// Group the process list by origin
+    pub group_by_origin: bool,
+    /// Name patterns of processes pinned to the top of the list
+    pub pinned_names: Vec<String>,
+    /// List only the `process_limit` busiest processes
+    pub limit_processes: bool,
+    pub process_limit: u32,
+    /// Refresh less often while Procular's own CPU use is above `self_limit_percent`
+    pub self_limit: bool,
+    /// Percent of one core Procular may use before it refreshes less often
+    pub self_limit_percent: u32,
+    /// Warn when a process's video memory keeps growing
+    pub vram_leak_alerts: bool,
+    /// Warn when a process uses more than `vram_process_limit_percent` of its GPU's memory
+    pub vram_process_limit: bool,
+    pub vram_process_limit_percent: u32,
+    /// Optional monitor work; switched-off collectors are skipped on every refresh
+    pub collectors: Collectors,
+    /// Terminal emulator command for developer tools; empty picks an installed one
+    pub terminal: String,
+    /// Tracer started by "Trace in Terminal"
+    pub tracer: Tracer,
+    /// Command started by "Attach Debugger", with `{pid}` for the process ID
+    pub debugger_command: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            inhibit_suspend: true,
+            read_only: false,
+            quick_kill: false,
+            grace_period_secs: 10,
+            onboarding_complete: false,
+            run_in_background: false,
+            disk_alerts: true,
+            disk_alert_percent: 90,
+            cpu_per_core: false,
+            clock_time_axis: false,
+            top_bar: false,
+            mini_window: false,
+            top_bar_edge: ScreenEdge::Top,
+            temperature_alerts: true,
+            search_command_line: false,
+            search_environment: false,
+            show_processes: None,
+            group_by_origin: false,
+            pinned_names: Vec::new(),
+            limit_processes: true,
+            process_limit: DEFAULT_PROCESS_LIMIT as u32,
+            self_limit: false,
+            self_limit_percent: 10,
+            vram_leak_alerts: true,
+            vram_process_limit: false,
+            vram_process_limit_percent: 75,
+            collectors: Collectors::default(),
+            terminal: String::new(),
+            tracer: Tracer::Strace,
+            debugger_command: DEFAULT_DEBUGGER_COMMAND.to_string(),
+        }
+    }
+}
+
+impl Settings {
+    /// Number of busiest processes to list; None lists every process
+    pub fn process_limit(&self) -> Option<usize> {
+        self.limit_processes.then_some(self.process_limit as usize)
+    }
+
+    /// Load settings, falling back to defaults for missing keys
+    pub fn load() -> Self {
+        let key_file = config::load_key_file(SETTINGS_FILE);
+        let defaults = Self::default();
+        Self {
+            inhibit_suspend: key_file
+                .boolean(GENERAL_GROUP, "inhibit-suspend")
+                .unwrap_or(defaults.inhibit_suspend),
+            read_only: key_file
+                .boolean(GENERAL_GROUP, "read-only")
+                .unwrap_or(defaults.read_only),
+            quick_kill: key_file
+                .boolean(GENERAL_GROUP, "quick-kill")
+                .unwrap_or(defaults.quick_kill),
+            grace_period_secs: key_file
+                .integer(GENERAL_GROUP, "grace-period-secs")
+                .ok()
+                .and_then(|secs| u32::try_from(secs).ok())
+                .filter(|secs| (1..=300).contains(secs))
+                .unwrap_or(defaults.grace_period_secs),
+            onboarding_complete: key_file
+                .boolean(GENERAL_GROUP, "onboarding-complete")
+                .unwrap_or(defaults.onboarding_complete),
+            run_in_background: key_file
+                .boolean(GENERAL_GROUP, "run-in-background")
+                .unwrap_or(defaults.run_in_background),
+            disk_alerts: key_file
+                .boolean(STORAGE_GROUP, "disk-alerts")
+                .unwrap_or(defaults.disk_alerts),
+            disk_alert_percent: key_file
+                .integer(STORAGE_GROUP, "disk-alert-percent")
+                .ok()
+                .and_then(|percent| u32::try_from(percent).ok())
+                .filter(|percent| (50..=99).contains(percent))
+                .unwrap_or(defaults.disk_alert_percent),
+            cpu_per_core: key_file
+                .boolean(VIEW_GROUP, "cpu-per-core")
+                .unwrap_or(defaults.cpu_per_core),
+            clock_time_axis: key_file
+                .boolean(VIEW_GROUP, "clock-time-axis")
+                .unwrap_or(defaults.clock_time_axis),
+            top_bar: key_file
+                .boolean(VIEW_GROUP, "top-bar")
+                .unwrap_or(defaults.top_bar),
+            mini_window: key_file
+                .boolean(VIEW_GROUP, "mini-window")
+                .unwrap_or(defaults.mini_window),
+            top_bar_edge: key_file
+                .string(VIEW_GROUP, "top-bar-edge")
+                .ok()
+                .and_then(|key| ScreenEdge::from_key(&key))
+                .unwrap_or(defaults.top_bar_edge),
+            temperature_alerts: key_file
+                .boolean(SENSORS_GROUP, "temperature-alerts")
+                .unwrap_or(defaults.temperature_alerts),
+            search_command_line: key_file
+                .boolean(VIEW_GROUP, "search-command-line")
+                .unwrap_or(defaults.search_command_line),
+            search_environment: key_file
+                .boolean(VIEW_GROUP, "search-environment")
+                .unwrap_or(defaults.search_environment),
+            show_processes: key_file
+                .string(VIEW_GROUP, "show-processes")
+                .ok()
+                .and_then(|key| ProcessOrigin::from_key(&key)),
+            group_by_origin: key_file
+                .boolean(VIEW_GROUP, "group-by-origin")
+                .unwrap_or(defaults.group_by_origin),
+            pinned_names: key_file
+                .string(VIEW_GROUP, "pinned-names")
+                .map(|names| parse_name_list(&names))
+                .unwrap_or(defaults.pinned_names),
+            limit_processes: key_file
+                .boolean(VIEW_GROUP, "limit-processes")
+                .unwrap_or(defaults.limit_processes),
+            process_limit: key_file
+                .integer(VIEW_GROUP, "process-limit")
+                .ok()
+                .and_then(|limit| u32::try_from(limit).ok())
+                .filter(|limit| (MIN_PROCESS_LIMIT..=MAX_PROCESS_LIMIT).contains(limit))
+                .unwrap_or(defaults.process_limit),
+            self_limit: key_file
+                .boolean(PERFORMANCE_GROUP, "self-limit")
+                .unwrap_or(defaults.self_limit),
+            self_limit_percent: key_file
+                .integer(PERFORMANCE_GROUP, "self-limit-percent")
+                .ok()
+                .and_then(|percent| u32::try_from(percent).ok())
+                .filter(|percent| (1..=100).contains(percent))
+                .unwrap_or(defaults.self_limit_percent),
+            vram_leak_alerts: key_file
+                .boolean(GPU_GROUP, "vram-leak-alerts")
+                .unwrap_or(defaults.vram_leak_alerts),
+            vram_process_limit: key_file
+                .boolean(GPU_GROUP, "vram-process-limit")
+                .unwrap_or(defaults.vram_process_limit),
+            vram_process_limit_percent: key_file
+                .integer(GPU_GROUP, "vram-process-limit-percent")
+                .ok()
+                .and_then(|percent| u32::try_from(percent).ok())
+                .filter(|percent| (10..=100).contains(percent))
+                .unwrap_or(defaults.vram_process_limit_percent),
+            collectors: Collectors {
+                gpu: key_file
+                    .boolean(COLLECTORS_GROUP, "gpu")
+                    .unwrap_or(defaults.collectors.gpu),
+                disk_io: key_file
+                    .boolean(COLLECTORS_GROUP, "disk-io")
+                    .unwrap_or(defaults.collectors.disk_io),
+                network: key_file
+                    .boolean(COLLECTORS_GROUP, "network")
+                    .unwrap_or(defaults.collectors.network),
+                thread_grouping: key_file
+                    .boolean(COLLECTORS_GROUP, "thread-grouping")
+                    .unwrap_or(defaults.collectors.thread_grouping),
+                process_details: key_file
+                    .boolean(COLLECTORS_GROUP, "process-details")
+                    .unwrap_or(defaults.collectors.process_details),
+            },
+            terminal: key_file
+                .string(DEVELOPER_GROUP, "terminal")
+                .map(|terminal| terminal.to_string())
+                .unwrap_or(defaults.terminal),
+            tracer: key_file
+                .string(DEVELOPER_GROUP, "tracer")
+                .ok()
+                .and_then(|key| Tracer::from_key(&key))
+                .unwrap_or(defaults.tracer),
+            debugger_command: key_file
+                .string(DEVELOPER_GROUP, "debugger-command")
+                .map(|command| command.to_string())
+                .ok()
+                .filter(|command| !command.trim().is_empty())
+                .unwrap_or(defaults.debugger_command),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let key_file = glib::KeyFile::new();
+        key_file.set_boolean(GENERAL_GROUP, "inhibit-suspend", self.inhibit_suspend);
+        key_file.set_boolean(GENERAL_GROUP, "read-only", self.read_only);
+        key_file.set_boolean(GENERAL_GROUP, "quick-kill", self.quick_kill);
+        key_file.set_integer(GENERAL_GROUP, "grace-period-secs", self.grace_period_secs as i32);
+        key_file.set_boolean(GENERAL_GROUP, "onboarding-complete", self.onboarding_complete);
+        key_file.set_boolean(GENERAL_GROUP, "run-in-background", self.run_in_background);
+        key_file.set_boolean(STORAGE_GROUP, "disk-alerts", self.disk_alerts);
+        key_file.set_integer(STORAGE_GROUP, "disk-alert-percent", self.disk_alert_percent as i32);
+        key_file.set_boolean(VIEW_GROUP, "cpu-per-core", self.cpu_per_core);
+        key_file.set_boolean(VIEW_GROUP, "clock-time-axis", self.clock_time_axis);
+        key_file.set_boolean(VIEW_GROUP, "top-bar", self.top_bar);
+        key_file.set_string(VIEW_GROUP, "top-bar-edge", self.top_bar_edge.key());
+        key_file.set_boolean(VIEW_GROUP, "mini-window", self.mini_window);
+        key_file.set_boolean(SENSORS_GROUP, "temperature-alerts", self.temperature_alerts);
+        key_file.set_boolean(VIEW_GROUP, "search-command-line", self.search_command_line);
+        key_file.set_boolean(VIEW_GROUP, "search-environment", self.search_environment);
+        key_file.set_string(
+            VIEW_GROUP,
+            "show-processes",
+            self.show_processes.map_or("all", |origin| origin.key()),
+        );
+        key_file.set_boolean(VIEW_GROUP, "group-by-origin", self.group_by_origin);
+        key_file.set_string(VIEW_GROUP, "pinned-names", &self.pinned_names.join(", "));
+        key_file.set_boolean(VIEW_GROUP, "limit-processes", self.limit_processes);
+        key_file.set_integer(VIEW_GROUP, "process-limit", self.process_limit as i32);
+        key_file.set_boolean(PERFORMANCE_GROUP, "self-limit", self.self_limit);
+        key_file.set_integer(PERFORMANCE_GROUP, "self-limit-percent", self.self_limit_percent as i32);
+        key_file.set_boolean(GPU_GROUP, "vram-leak-alerts", self.vram_leak_alerts);
+        key_file.set_boolean(GPU_GROUP, "vram-process-limit", self.vram_process_limit);
+        key_file.set_integer(GPU_GROUP, "vram-process-limit-percent", self.vram_process_limit_percent as i32);
+        key_file.set_boolean(COLLECTORS_GROUP, "gpu", self.collectors.gpu);
+        key_file.set_boolean(COLLECTORS_GROUP, "disk-io", self.collectors.disk_io);
+        key_file.set_boolean(COLLECTORS_GROUP, "network", self.collectors.network);
+        key_file.set_boolean(COLLECTORS_GROUP, "thread-grouping", self.collectors.thread_grouping);
+        key_file.set_boolean(COLLECTORS_GROUP, "process-details", self.collectors.process_details);
+        key_file.set_string(DEVELOPER_GROUP, "terminal", &self.terminal);
+        key_file.set_string(DEVELOPER_GROUP, "tracer", self.tracer.key());
+        key_file.set_string(DEVELOPER_GROUP, "debugger-command", &self.debugger_command);
+        config::save_key_file(SETTINGS_FILE, &key_file)
+    }
+}
+
+/// Split a comma-separated list of name patterns, dropping empty entries
+pub fn parse_name_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
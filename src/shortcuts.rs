@@ -0,0 +1,93 @@
+//! Keyboard shortcuts: the Keyboard Shortcuts window and shortcuts shared by several windows
+
+use gtk4::prelude::*;
+use gtk4::{NamedAction, Shortcut, ShortcutController, ShortcutTrigger};
+
+/// Shortcuts window listing every shortcut; keep in sync with the accelerators in window.rs,
+/// `context_menu::setup_process_actions` and `add_close_shortcut`
+const SHORTCUTS_UI: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<interface>
+  <object class="GtkShortcutsWindow" id="shortcuts">
+    <property name="modal">true</property>
+    <child>
+      <object class="GtkShortcutsSection">
+        <property name="section-name">main</property>
+        <child>
+          <object class="GtkShortcutsGroup">
+            <property name="title">General</property>
+            <child>
+              <object class="GtkShortcutsShortcut">
+                <property name="title">Search Processes</property>
+                <property name="accelerator">&lt;Ctrl&gt;f</property>
+              </object>
+            </child>
+            <child>
+              <object class="GtkShortcutsShortcut">
+                <property name="title">Refresh Now</property>
+                <property name="accelerator">F5</property>
+              </object>
+            </child>
+            <child>
+              <object class="GtkShortcutsShortcut">
+                <property name="title">CPU % Per Core</property>
+                <property name="accelerator">&lt;Ctrl&gt;i</property>
+              </object>
+            </child>
+            <child>
+              <object class="GtkShortcutsShortcut">
+                <property name="title">Keyboard Shortcuts</property>
+                <property name="accelerator">&lt;Ctrl&gt;question</property>
+              </object>
+            </child>
+          </object>
+        </child>
+        <child>
+          <object class="GtkShortcutsGroup">
+            <property name="title">Process List</property>
+            <child>
+              <object class="GtkShortcutsShortcut">
+                <property name="title">End Selected Processes (SIGTERM)</property>
+                <property name="accelerator">Delete</property>
+              </object>
+            </child>
+            <child>
+              <object class="GtkShortcutsShortcut">
+                <property name="title">Force Kill Selected Processes (SIGKILL)</property>
+                <property name="accelerator">&lt;Shift&gt;Delete</property>
+              </object>
+            </child>
+          </object>
+        </child>
+        <child>
+          <object class="GtkShortcutsGroup">
+            <property name="title">Process Window</property>
+            <child>
+              <object class="GtkShortcutsShortcut">
+                <property name="title">Close Window</property>
+                <property name="accelerator">&lt;Ctrl&gt;w</property>
+              </object>
+            </child>
+          </object>
+        </child>
+      </object>
+    </child>
+  </object>
+</interface>
+"#;
+
+/// Keyboard Shortcuts window, shown as the main window's help overlay
+pub fn shortcuts_window() -> gtk4::ShortcutsWindow {
+    gtk4::Builder::from_string(SHORTCUTS_UI)
+        .object("shortcuts")
+        .expect("shortcuts window is defined in SHORTCUTS_UI")
+}
+
+/// Let Ctrl+W close a secondary window such as a process window
+pub fn add_close_shortcut(window: &impl IsA<gtk4::Window>) {
+    let controller = ShortcutController::new();
+    controller.add_shortcut(Shortcut::new(
+        ShortcutTrigger::parse_string("<Ctrl>w"),
+        Some(NamedAction::new("window.close")),
+    ));
+    window.as_ref().add_controller(controller);
+}
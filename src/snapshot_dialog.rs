@@ -0,0 +1,139 @@
+//! Snapshot comparison window: which processes started, stopped or changed since a snapshot
+
+use gtk4::prelude::*;
+use gtk4::{gio, Box as GtkBox, Button, Label, Orientation};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::rc::Rc;
+use std::time::Duration;
+
+use procular_core::monitor::{format_bytes, format_duration};
+use procular_core::snapshot::{format_signed_bytes, SnapshotDiff};
+
+use crate::table_view::{ColumnKind, TableView};
+
+/// Show the differences between two snapshots with JSON and CSV export
+pub fn show_snapshot_diff(parent: &impl IsA<gtk4::Window>, diff: SnapshotDiff) {
+    let dialog = adw::Window::builder()
+        .title("Changes Since Snapshot")
+        .transient_for(parent)
+        .default_width(900)
+        .default_height(500)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+    let csv_btn = Button::with_label("Export CSV…");
+    header.pack_end(&csv_btn);
+    let json_btn = Button::with_label("Export JSON…");
+    header.pack_end(&json_btn);
+    main_box.append(&header);
+
+    let toast_overlay = adw::ToastOverlay::new();
+    let content = GtkBox::new(Orientation::Vertical, 8);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+    content.set_margin_start(12);
+    content.set_margin_end(12);
+
+    let (started, stopped) = diff.counts();
+    let summary_label = Label::new(Some(&format!(
+        "{} started, {} stopped and {} changed in the {} since the snapshot.",
+        started,
+        stopped,
+        diff.changes.len() - started - stopped,
+        format_duration(Duration::from_secs(diff.to.saturating_sub(diff.from)))
+    )));
+    summary_label.set_halign(gtk4::Align::Start);
+    summary_label.set_wrap(true);
+    content.append(&summary_label);
+
+    let table = TableView::new(&[
+        ("Change", ColumnKind::Short),
+        ("PID", ColumnKind::Number),
+        ("Name", ColumnKind::Text),
+        ("Memory Δ", ColumnKind::Bytes),
+        ("CPU Time (s)", ColumnKind::Number),
+        ("Disk Read", ColumnKind::Bytes),
+        ("Disk Written", ColumnKind::Bytes),
+        ("Received", ColumnKind::Bytes),
+        ("Sent", ColumnKind::Bytes),
+    ]);
+    table.set_rows(
+        diff.changes
+            .iter()
+            .map(|c| {
+                vec![
+                    c.kind.as_str().to_string(),
+                    c.key.pid.to_string(),
+                    c.name.clone(),
+                    format_signed_bytes(c.memory_delta),
+                    format!("{:.1}", c.cpu_time_delta),
+                    format_bytes(c.disk_read_delta),
+                    format_bytes(c.disk_written_delta),
+                    format_bytes(c.net_received_delta),
+                    format_bytes(c.net_sent_delta),
+                ]
+            })
+            .collect(),
+    );
+    content.append(&table.widget);
+
+    let note = Label::new(Some(
+        "Running processes are listed only if they used CPU, memory, disk or network in between. \
+         Disk and network totals count only traffic seen while Procular was running.",
+    ));
+    note.add_css_class("dim-label");
+    note.set_halign(gtk4::Align::Start);
+    note.set_wrap(true);
+    content.append(&note);
+
+    toast_overlay.set_child(Some(&content));
+    main_box.append(&toast_overlay);
+    dialog.set_content(Some(&main_box));
+
+    let diff = Rc::new(diff);
+    connect_export(&json_btn, &dialog, &toast_overlay, diff.clone(), "snapshot-diff.json", SnapshotDiff::to_json);
+    connect_export(&csv_btn, &dialog, &toast_overlay, diff, "snapshot-diff.csv", SnapshotDiff::to_csv);
+
+    dialog.present();
+}
+
+/// Save the diff rendered by `render` to a file chosen when `button` is clicked
+fn connect_export(
+    button: &Button,
+    dialog: &adw::Window,
+    toast_overlay: &adw::ToastOverlay,
+    diff: Rc<SnapshotDiff>,
+    initial_name: &'static str,
+    render: fn(&SnapshotDiff) -> String,
+) {
+    let dialog_weak = dialog.downgrade();
+    let toast_overlay = toast_overlay.clone();
+    button.connect_clicked(move |_| {
+        let Some(dialog) = dialog_weak.upgrade() else {
+            return;
+        };
+        let text = render(&diff);
+        let toast_overlay = toast_overlay.clone();
+        let file_dialog = gtk4::FileDialog::builder()
+            .title("Export Snapshot Changes")
+            .initial_name(initial_name)
+            .build();
+        file_dialog.save(Some(&dialog), gio::Cancellable::NONE, move |result| {
+            // Cancelled dialogs also end up here
+            let Ok(file) = result else {
+                return;
+            };
+            let Some(path) = file.path() else {
+                return;
+            };
+            let message = match std::fs::write(&path, &text) {
+                Ok(()) => format!("Exported to {}", path.display()),
+                Err(e) => format!("Export failed: {}", e),
+            };
+            toast_overlay.add_toast(adw::Toast::new(&message));
+        });
+    });
+}
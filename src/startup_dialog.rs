@@ -0,0 +1,136 @@
+//! Startup impact window: which session applications weigh on login
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, DropDown, Label, Orientation, StringList};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::rc::Rc;
+use std::time::Duration;
+
+use procular_core::monitor::{format_bytes, format_duration};
+use procular_core::startup_impact::read_startup_impact;
+
+use crate::table_view::{ColumnKind, TableView};
+
+/// Window after login to attribute to startup (label, seconds)
+const STARTUP_WINDOWS: &[(&str, u64)] = &[
+    ("1 min", 60),
+    ("2 min", 2 * 60),
+    ("5 min", 5 * 60),
+    ("10 min", 10 * 60),
+];
+const DEFAULT_WINDOW: u32 = 1; // 2 minutes
+
+/// Show the startup impact window
+pub fn show_startup_dialog(parent: &impl IsA<gtk4::Window>) {
+    let dialog = adw::Window::builder()
+        .title("Startup Impact")
+        .transient_for(parent)
+        .default_width(800)
+        .default_height(500)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 0);
+
+    // Header bar with window dropdown and refresh
+    let header = adw::HeaderBar::new();
+
+    let window_options =
+        StringList::new(&STARTUP_WINDOWS.iter().map(|(label, _)| *label).collect::<Vec<_>>());
+    let window_dropdown = DropDown::new(Some(window_options), gtk4::Expression::NONE);
+    window_dropdown.set_selected(DEFAULT_WINDOW);
+
+    let window_box = GtkBox::new(Orientation::Horizontal, 8);
+    window_box.append(&Label::new(Some("Started within:")));
+    window_box.append(&window_dropdown);
+    header.pack_start(&window_box);
+
+    let refresh_btn = Button::from_icon_name("view-refresh-symbolic");
+    refresh_btn.set_tooltip_text(Some("Refresh"));
+    header.pack_end(&refresh_btn);
+
+    main_box.append(&header);
+
+    // Content
+    let content = GtkBox::new(Orientation::Vertical, 8);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+    content.set_margin_start(12);
+    content.set_margin_end(12);
+
+    let summary_label = Label::new(None);
+    summary_label.set_halign(gtk4::Align::Start);
+    summary_label.set_wrap(true);
+    content.append(&summary_label);
+
+    let table = TableView::new(&[
+        ("Application", ColumnKind::Short),
+        ("Unit", ColumnKind::Text),
+        ("Started (s)", ColumnKind::Number),
+        ("CPU Time (s)", ColumnKind::Number),
+        ("Read", ColumnKind::Bytes),
+        ("Written", ColumnKind::Bytes),
+        ("Processes", ColumnKind::Number),
+    ]);
+    content.append(&table.widget);
+
+    let note = Label::new(Some(
+        "Applications and services of your session (systemd user units, including autostart \
+         entries) that started within the chosen time after login. CPU and I/O are totals since \
+         each unit started, so they reflect startup cost best right after logging in.",
+    ));
+    note.add_css_class("dim-label");
+    note.set_halign(gtk4::Align::Start);
+    note.set_wrap(true);
+    content.append(&note);
+
+    main_box.append(&content);
+    dialog.set_content(Some(&main_box));
+
+    let populate: Rc<dyn Fn()> = {
+        let dropdown = window_dropdown.clone();
+        Rc::new(move || {
+            let (label, secs) = STARTUP_WINDOWS[dropdown.selected() as usize % STARTUP_WINDOWS.len()];
+            match read_startup_impact(Duration::from_secs(secs)) {
+                Ok(report) => {
+                    let total_cpu: Duration = report.units.iter().map(|u| u.cpu_time).sum();
+                    summary_label.set_text(&format!(
+                        "Logged in {} ago. {} units started within {} of login, using {:.1} s of CPU time.",
+                        format_duration(report.session_age),
+                        report.units.len(),
+                        label,
+                        total_cpu.as_secs_f64()
+                    ));
+                    table.set_rows(
+                        report
+                            .units
+                            .into_iter()
+                            .map(|u| {
+                                vec![
+                                    u.process_name,
+                                    u.unit,
+                                    format!("{:.1}", u.started_after_login.as_secs_f64()),
+                                    format!("{:.1}", u.cpu_time.as_secs_f64()),
+                                    format_bytes(u.read_bytes),
+                                    format_bytes(u.write_bytes),
+                                    u.process_count.to_string(),
+                                ]
+                            })
+                            .collect(),
+                    );
+                }
+                Err(e) => {
+                    summary_label.set_text(&format!("Startup impact unavailable: {}", e));
+                    table.set_rows(Vec::new());
+                }
+            }
+        })
+    };
+    populate();
+
+    let populate_clone = populate.clone();
+    window_dropdown.connect_selected_notify(move |_| populate_clone());
+    refresh_btn.connect_clicked(move |_| populate());
+
+    dialog.present();
+}
@@ -0,0 +1,353 @@
+//! Status icon for running in the background, exported as a StatusNotifierItem
+//!
+//! The item lives at `/StatusNotifierItem` on the application's bus connection with a minimal
+//! `com.canonical.dbusmenu` menu next to it, and is announced to the panel's
+//! `org.kde.StatusNotifierWatcher` whenever one is running. Panels without StatusNotifierItem
+//! support (stock GNOME without an extension) simply show nothing.
+
+use gtk4::gio;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use procular_core::monitor::SystemMonitor;
+use procular_core::swap::MemoryUsage;
+
+const ITEM_PATH: &str = "/StatusNotifierItem";
+const ITEM_INTERFACE: &str = "org.kde.StatusNotifierItem";
+const MENU_PATH: &str = "/StatusNotifierItem/Menu";
+const MENU_INTERFACE: &str = "com.canonical.dbusmenu";
+const WATCHER_NAME: &str = "org.kde.StatusNotifierWatcher";
+const WATCHER_PATH: &str = "/StatusNotifierWatcher";
+
+/// Menu item IDs; 0 is the root
+const MENU_SHOW: i32 = 1;
+const MENU_QUIT: i32 = 2;
+
+/// Icon pixmaps, matching the `a(iiay)` signature; empty since the icon comes from the theme
+type Pixmaps = Vec<(i32, i32, Vec<u8>)>;
+
+/// One dbusmenu layout node, matching the `(ia{sv}av)` signature
+type LayoutNode = (i32, HashMap<String, glib::Variant>, Vec<glib::Variant>);
+
+const INTROSPECTION_XML: &str = r#"
+<node>
+  <interface name="org.kde.StatusNotifierItem">
+    <property name="Category" type="s" access="read"/>
+    <property name="Id" type="s" access="read"/>
+    <property name="Title" type="s" access="read"/>
+    <property name="Status" type="s" access="read"/>
+    <property name="IconName" type="s" access="read"/>
+    <property name="IconPixmap" type="a(iiay)" access="read"/>
+    <property name="ToolTip" type="(sa(iiay)ss)" access="read"/>
+    <property name="ItemIsMenu" type="b" access="read"/>
+    <property name="Menu" type="o" access="read"/>
+    <method name="ContextMenu">
+      <arg name="x" type="i" direction="in"/>
+      <arg name="y" type="i" direction="in"/>
+    </method>
+    <method name="Activate">
+      <arg name="x" type="i" direction="in"/>
+      <arg name="y" type="i" direction="in"/>
+    </method>
+    <method name="SecondaryActivate">
+      <arg name="x" type="i" direction="in"/>
+      <arg name="y" type="i" direction="in"/>
+    </method>
+    <method name="Scroll">
+      <arg name="delta" type="i" direction="in"/>
+      <arg name="orientation" type="s" direction="in"/>
+    </method>
+    <signal name="NewToolTip"/>
+  </interface>
+  <interface name="com.canonical.dbusmenu">
+    <property name="Version" type="u" access="read"/>
+    <property name="TextDirection" type="s" access="read"/>
+    <property name="Status" type="s" access="read"/>
+    <property name="IconThemePath" type="as" access="read"/>
+    <method name="GetLayout">
+      <arg name="parentId" type="i" direction="in"/>
+      <arg name="recursionDepth" type="i" direction="in"/>
+      <arg name="propertyNames" type="as" direction="in"/>
+      <arg name="revision" type="u" direction="out"/>
+      <arg name="layout" type="(ia{sv}av)" direction="out"/>
+    </method>
+    <method name="GetGroupProperties">
+      <arg name="ids" type="ai" direction="in"/>
+      <arg name="propertyNames" type="as" direction="in"/>
+      <arg name="properties" type="a(ia{sv})" direction="out"/>
+    </method>
+    <method name="GetProperty">
+      <arg name="id" type="i" direction="in"/>
+      <arg name="name" type="s" direction="in"/>
+      <arg name="value" type="v" direction="out"/>
+    </method>
+    <method name="Event">
+      <arg name="id" type="i" direction="in"/>
+      <arg name="eventId" type="s" direction="in"/>
+      <arg name="data" type="v" direction="in"/>
+      <arg name="timestamp" type="u" direction="in"/>
+    </method>
+    <method name="EventGroup">
+      <arg name="events" type="a(isvu)" direction="in"/>
+      <arg name="idErrors" type="ai" direction="out"/>
+    </method>
+    <method name="AboutToShow">
+      <arg name="id" type="i" direction="in"/>
+      <arg name="needUpdate" type="b" direction="out"/>
+    </method>
+    <method name="AboutToShowGroup">
+      <arg name="ids" type="ai" direction="in"/>
+      <arg name="updatesNeeded" type="ai" direction="out"/>
+      <arg name="idErrors" type="ai" direction="out"/>
+    </method>
+    <signal name="LayoutUpdated">
+      <arg name="revision" type="u"/>
+      <arg name="parent" type="i"/>
+    </signal>
+  </interface>
+</node>
+"#;
+
+/// Exported status icon; removed from the panel when dropped
+pub struct StatusIcon {
+    connection: gio::DBusConnection,
+    registrations: Vec<gio::RegistrationId>,
+    /// Stops watching for the panel (gio's dbus WatcherId is not nameable from outside the crate)
+    unwatch: Option<Box<dyn FnOnce()>>,
+    /// Tooltip text with the aggregate CPU and memory use
+    tooltip: Rc<RefCell<String>>,
+}
+
+impl StatusIcon {
+    /// Export the item and its menu for `window`.
+    /// Returns None when the application is not on the session bus.
+    pub fn register(window: &adw::ApplicationWindow) -> Option<Self> {
+        let app = window.application()?;
+        let connection = app.dbus_connection()?;
+        let node = gio::DBusNodeInfo::for_xml(INTROSPECTION_XML).ok()?;
+        let item_interface = node.lookup_interface(ITEM_INTERFACE)?;
+        let menu_interface = node.lookup_interface(MENU_INTERFACE)?;
+        let tooltip = Rc::new(RefCell::new(String::new()));
+
+        let window_weak = window.downgrade();
+        let tooltip_clone = tooltip.clone();
+        let item = connection
+            .register_object(ITEM_PATH, &item_interface)
+            .method_call(move |_, _, _, _, method, _, invocation| {
+                if matches!(method, "Activate" | "SecondaryActivate") {
+                    if let Some(window) = window_weak.upgrade() {
+                        window.present();
+                    }
+                }
+                invocation.return_value(None);
+            })
+            .property(move |_, _, _, _, property| item_property(property, &tooltip_clone.borrow()))
+            .build();
+
+        let window_weak = window.downgrade();
+        let menu = connection
+            .register_object(MENU_PATH, &menu_interface)
+            .method_call(move |_, _, _, _, method, params, invocation| {
+                handle_menu_call(&window_weak, method, &params, invocation);
+            })
+            .property(|_, _, _, _, property| menu_property(property))
+            .build();
+
+        let registrations = match (item, menu) {
+            (Ok(item), Ok(menu)) => vec![item, menu],
+            (item, menu) => {
+                // Another window of this instance already exports the item
+                for registration in [item, menu] {
+                    match registration {
+                        Ok(registration) => {
+                            let _ = connection.unregister_object(registration);
+                        }
+                        Err(e) => eprintln!("Failed to register status icon: {}", e),
+                    }
+                }
+                return None;
+            }
+        };
+
+        // Announce the item to the panel now and whenever the panel restarts
+        let watcher = gio::bus_watch_name_on_connection(
+            &connection,
+            WATCHER_NAME,
+            gio::BusNameWatcherFlags::NONE,
+            |connection, _, _| {
+                let Some(name) = connection.unique_name() else {
+                    return;
+                };
+                connection.call(
+                    Some(WATCHER_NAME),
+                    WATCHER_PATH,
+                    WATCHER_NAME,
+                    "RegisterStatusNotifierItem",
+                    Some(&(name.as_str(),).to_variant()),
+                    None,
+                    gio::DBusCallFlags::NONE,
+                    -1,
+                    gio::Cancellable::NONE,
+                    |result| {
+                        if let Err(e) = result {
+                            eprintln!("Failed to register status icon with the panel: {}", e);
+                        }
+                    },
+                );
+            },
+            |_, _| {},
+        );
+
+        Some(Self {
+            connection,
+            registrations,
+            unwatch: Some(Box::new(move || gio::bus_unwatch_name(watcher))),
+            tooltip,
+        })
+    }
+
+    /// Show the aggregate CPU and memory use of the last refresh in the tooltip
+    pub fn update(&self, monitor: &SystemMonitor, usage: &MemoryUsage) {
+        let text = format!("CPU {:.0}% · Memory {:.0}%", monitor.cpu_percent(), usage.used_percent());
+        if *self.tooltip.borrow() == text {
+            return;
+        }
+        self.tooltip.replace(text);
+        let result = self.connection.emit_signal(None, ITEM_PATH, ITEM_INTERFACE, "NewToolTip", None);
+        if let Err(e) = result {
+            eprintln!("Failed to emit NewToolTip: {}", e);
+        }
+    }
+}
+
+impl Drop for StatusIcon {
+    fn drop(&mut self) {
+        if let Some(unwatch) = self.unwatch.take() {
+            unwatch();
+        }
+        for registration in self.registrations.drain(..) {
+            let _ = self.connection.unregister_object(registration);
+        }
+    }
+}
+
+fn item_property(property: &str, tooltip: &str) -> glib::Variant {
+    match property {
+        "Category" => "SystemServices".to_variant(),
+        "Id" => "procular".to_variant(),
+        "Title" => "Procular".to_variant(),
+        "Status" => "Active".to_variant(),
+        "IconName" => "procular".to_variant(),
+        "IconPixmap" => Pixmaps::new().to_variant(),
+        "ToolTip" => ("procular", Pixmaps::new(), "Procular", tooltip).to_variant(),
+        "ItemIsMenu" => false.to_variant(),
+        "Menu" => glib::variant::ObjectPath::try_from(MENU_PATH)
+            .expect("MENU_PATH is a valid object path")
+            .to_variant(),
+        _ => "".to_variant(),
+    }
+}
+
+fn menu_property(property: &str) -> glib::Variant {
+    match property {
+        "Version" => 3u32.to_variant(),
+        "TextDirection" => "ltr".to_variant(),
+        "Status" => "normal".to_variant(),
+        "IconThemePath" => Vec::<String>::new().to_variant(),
+        _ => "".to_variant(),
+    }
+}
+
+/// Properties of a menu item; the root only has children
+fn menu_item_properties(id: i32) -> HashMap<String, glib::Variant> {
+    let mut properties = HashMap::new();
+    match id {
+        MENU_SHOW => {
+            properties.insert("label".to_string(), "Show Procular".to_variant());
+        }
+        MENU_QUIT => {
+            properties.insert("label".to_string(), "Quit".to_variant());
+        }
+        _ => {
+            properties.insert("children-display".to_string(), "submenu".to_variant());
+        }
+    }
+    properties
+}
+
+fn menu_layout() -> LayoutNode {
+    let children = [MENU_SHOW, MENU_QUIT]
+        .into_iter()
+        .map(|id| (id, menu_item_properties(id), Vec::<glib::Variant>::new()).to_variant())
+        .collect();
+    (0, menu_item_properties(0), children)
+}
+
+fn handle_menu_call(
+    window_weak: &glib::WeakRef<adw::ApplicationWindow>,
+    method: &str,
+    params: &glib::Variant,
+    invocation: gio::DBusMethodInvocation,
+) {
+    match method {
+        "GetLayout" => invocation.return_value(Some(&(1u32, menu_layout()).to_variant())),
+        "GetGroupProperties" => {
+            let ids = params.child_value(0).get::<Vec<i32>>().unwrap_or_default();
+            let properties: Vec<(i32, HashMap<String, glib::Variant>)> =
+                ids.into_iter().map(|id| (id, menu_item_properties(id))).collect();
+            invocation.return_value(Some(&(properties,).to_variant()));
+        }
+        "GetProperty" => {
+            let Some((id, name)) = params.get::<(i32, String)>() else {
+                return invocation.return_dbus_error("org.freedesktop.DBus.Error.InvalidArgs", "Invalid arguments");
+            };
+            let value = menu_item_properties(id)
+                .remove(&name)
+                .unwrap_or_else(|| "".to_variant());
+            invocation.return_value(Some(&(value,).to_variant()));
+        }
+        "Event" => {
+            let id = params.child_value(0).get::<i32>().unwrap_or(0);
+            let event = params.child_value(1).get::<String>().unwrap_or_default();
+            if event == "clicked" {
+                activate_menu_item(window_weak, id);
+            }
+            invocation.return_value(None);
+        }
+        "EventGroup" => {
+            let events = params.child_value(0);
+            for event in events.iter() {
+                if event.child_value(1).get::<String>().as_deref() == Some("clicked") {
+                    activate_menu_item(window_weak, event.child_value(0).get::<i32>().unwrap_or(0));
+                }
+            }
+            invocation.return_value(Some(&(Vec::<i32>::new(),).to_variant()));
+        }
+        "AboutToShow" => invocation.return_value(Some(&(false,).to_variant())),
+        "AboutToShowGroup" => {
+            invocation.return_value(Some(&(Vec::<i32>::new(), Vec::<i32>::new()).to_variant()));
+        }
+        _ => invocation.return_dbus_error(
+            "org.freedesktop.DBus.Error.UnknownMethod",
+            &format!("Unknown method {}", method),
+        ),
+    }
+}
+
+fn activate_menu_item(window_weak: &glib::WeakRef<adw::ApplicationWindow>, id: i32) {
+    let Some(window) = window_weak.upgrade() else {
+        return;
+    };
+    match id {
+        MENU_SHOW => window.present(),
+        MENU_QUIT => {
+            if let Some(app) = window.application() {
+                app.quit();
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,219 @@
+//! Memory page of the main window: RAM and swap usage, swap-in/out rate graphs and the processes holding swap
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Label, LevelBar, ListBox, Orientation, ScrolledWindow};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use procular_core::monitor::{format_bytes, format_rate, SystemMonitor};
+use procular_core::swap::{MemoryUsage, SwapUser};
+
+use crate::graph::GraphWidget;
+use crate::help;
+
+const SWAP_IN_COLOR: (f64, f64, f64) = (0.180, 0.545, 0.341); // Green, same as disk read graphs
+const SWAP_OUT_COLOR: (f64, f64, f64) = (0.902, 0.494, 0.133); // Orange, same as disk write graphs
+const SWAP_GRAPH_HEIGHT: i32 = 100;
+
+/// Label and bar for RAM or swap
+struct UsageMeter {
+    label: Label,
+    bar: LevelBar,
+}
+
+impl UsageMeter {
+    fn new(container: &GtkBox) -> Self {
+        let label = Label::new(None);
+        label.set_halign(gtk4::Align::Start);
+        let bar = LevelBar::for_interval(0.0, 1.0);
+        bar.add_offset_value(gtk4::LEVEL_BAR_OFFSET_HIGH, 0.9);
+        container.append(&label);
+        container.append(&bar);
+        Self { label, bar }
+    }
+
+    fn set(&self, title: &str, used: u64, total: u64) {
+        self.label
+            .set_text(&format!("{} · {} of {} used", title, format_bytes(used), format_bytes(total)));
+        self.bar
+            .set_value(if total > 0 { used as f64 / total as f64 } else { 0.0 });
+    }
+}
+
+/// Graph with a heading and current-rate label
+struct RateGraph {
+    graph: GraphWidget,
+    rate_label: Label,
+}
+
+impl RateGraph {
+    fn new(container: &GtkBox, title: &str, color: (f64, f64, f64)) -> Self {
+        let header = GtkBox::new(Orientation::Horizontal, 8);
+        let title_label = Label::new(Some(title));
+        title_label.add_css_class("heading");
+        title_label.set_halign(gtk4::Align::Start);
+        title_label.set_hexpand(true);
+        header.append(&title_label);
+        let rate_label = Label::new(Some("0 B/s"));
+        rate_label.add_css_class("numeric");
+        header.append(&rate_label);
+        header.append(&help::help_button("swap"));
+        container.append(&header);
+
+        let graph = GraphWidget::new(color, false, true);
+        graph.set_title_label(&title_label);
+        graph.drawing_area.set_size_request(200, SWAP_GRAPH_HEIGHT);
+        container.append(&graph.drawing_area);
+        Self { graph, rate_label }
+    }
+
+    fn update(
+        &self,
+        history: &VecDeque<f64>,
+        gaps: &[bool],
+        times: &[SystemTime],
+        max_samples: usize,
+        sample_interval_secs: u64,
+    ) {
+        let values: Vec<f64> = history.iter().copied().collect();
+        self.graph.set_gaps(gaps);
+        self.graph.set_times(times);
+        self.graph.update(&values, max_samples, sample_interval_secs);
+        self.rate_label
+            .set_text(&format_rate(values.last().copied().unwrap_or(0.0) as u64));
+    }
+}
+
+/// Memory overview focused on swap activity
+pub struct SystemMemoryView {
+    pub widget: ScrolledWindow,
+    ram: UsageMeter,
+    swap: UsageMeter,
+    swap_in: RateGraph,
+    swap_out: RateGraph,
+    list: ListBox,
+    /// Rows currently shown; the list is only rebuilt on change
+    shown: RefCell<Vec<SwapUser>>,
+}
+
+impl SystemMemoryView {
+    pub fn new() -> Self {
+        let content = GtkBox::new(Orientation::Vertical, 12);
+        content.set_margin_top(12);
+        content.set_margin_bottom(12);
+        content.set_margin_start(12);
+        content.set_margin_end(12);
+
+        let meters_box = GtkBox::new(Orientation::Vertical, 6);
+        let ram = UsageMeter::new(&meters_box);
+        let swap = UsageMeter::new(&meters_box);
+        content.append(&meters_box);
+
+        let graphs_box = GtkBox::new(Orientation::Horizontal, 12);
+        graphs_box.set_homogeneous(true);
+        let swap_in_box = GtkBox::new(Orientation::Vertical, 4);
+        let swap_in = RateGraph::new(&swap_in_box, "Swap In", SWAP_IN_COLOR);
+        graphs_box.append(&swap_in_box);
+        let swap_out_box = GtkBox::new(Orientation::Vertical, 4);
+        let swap_out = RateGraph::new(&swap_out_box, "Swap Out", SWAP_OUT_COLOR);
+        graphs_box.append(&swap_out_box);
+        content.append(&graphs_box);
+
+        let heading = Label::new(Some("Processes by Swap Use"));
+        heading.add_css_class("heading");
+        heading.set_halign(gtk4::Align::Start);
+        heading.set_margin_top(12);
+        content.append(&heading);
+
+        let list = ListBox::new();
+        list.add_css_class("boxed-list");
+        list.set_selection_mode(gtk4::SelectionMode::None);
+        let placeholder = Label::new(Some("No processes have memory in swap"));
+        placeholder.add_css_class("dim-label");
+        placeholder.set_margin_top(24);
+        placeholder.set_margin_bottom(24);
+        list.set_placeholder(Some(&placeholder));
+        content.append(&list);
+
+        let widget = ScrolledWindow::new();
+        widget.set_hscrollbar_policy(gtk4::PolicyType::Never);
+        widget.set_vexpand(true);
+        widget.set_child(Some(&content));
+
+        Self {
+            widget,
+            ram,
+            swap,
+            swap_in,
+            swap_out,
+            list,
+            shown: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Update the meters and the swap graphs from the monitor's history
+    pub fn update(&self, usage: &MemoryUsage, monitor: &SystemMonitor, sample_interval_secs: u64) {
+        self.ram.set("Memory", usage.mem_used, usage.mem_total);
+        if usage.swap_total > 0 {
+            self.swap.set("Swap", usage.swap_used, usage.swap_total);
+        } else {
+            self.swap.label.set_text("Swap · not configured");
+            self.swap.bar.set_value(0.0);
+        }
+
+        let gaps: Vec<bool> = monitor.sample_gaps().iter().copied().collect();
+        let times: Vec<SystemTime> = monitor.sample_times().iter().copied().collect();
+        let max_samples = monitor.max_samples();
+        self.swap_in.update(monitor.swap_in_history(), &gaps, &times, max_samples, sample_interval_secs);
+        self.swap_out.update(monitor.swap_out_history(), &gaps, &times, max_samples, sample_interval_secs);
+    }
+
+    /// Show the processes with the most memory in swap
+    pub fn update_swap_users(&self, users: &[SwapUser]) {
+        if *self.shown.borrow() == users {
+            return;
+        }
+        while let Some(row) = self.list.first_child() {
+            self.list.remove(&row);
+        }
+        for user in users {
+            self.list.append(&create_row(user));
+        }
+        *self.shown.borrow_mut() = users.to_vec();
+    }
+}
+
+/// Row with process info, its swap use and a button opening the process window
+fn create_row(user: &SwapUser) -> GtkBox {
+    let row = GtkBox::new(Orientation::Horizontal, 12);
+    row.set_margin_top(6);
+    row.set_margin_bottom(6);
+    row.set_margin_start(12);
+    row.set_margin_end(12);
+
+    let info_box = GtkBox::new(Orientation::Vertical, 2);
+    info_box.set_hexpand(true);
+    let name_label = Label::new(Some(&user.name));
+    name_label.set_halign(gtk4::Align::Start);
+    name_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+    info_box.append(&name_label);
+    let pid_label = Label::new(Some(&format!("PID {}", user.pid)));
+    pid_label.add_css_class("dim-label");
+    pid_label.add_css_class("caption");
+    pid_label.set_halign(gtk4::Align::Start);
+    info_box.append(&pid_label);
+    row.append(&info_box);
+
+    let swap_label = Label::new(Some(&format_bytes(user.swap_bytes)));
+    swap_label.add_css_class("numeric");
+    row.append(&swap_label);
+
+    let show_btn = Button::with_label("Show");
+    show_btn.set_valign(gtk4::Align::Center);
+    show_btn.set_action_name(Some("win.open-process"));
+    show_btn.set_action_target_value(Some(&(user.pid, user.name.clone()).to_variant()));
+    row.append(&show_btn);
+
+    row
+}
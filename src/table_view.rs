@@ -0,0 +1,203 @@
+//! Generic read-only, sortable table of text columns
+//!
+//! Used by the process window tabs that list /proc data (open files, ...).
+//! Supports a substring filter and single row selection.
+//! Each row is a `Vec<String>` wrapped in a `BoxedAnyObject`.
+
+use gtk4::prelude::*;
+use gtk4::{
+    ColumnView, ColumnViewColumn, CustomFilter, CustomSorter, FilterListModel, Label, ListItem,
+    Ordering as GtkOrdering, ScrolledWindow, SignalListItemFactory, SingleSelection, SortListModel,
+};
+use glib::BoxedAnyObject;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// How a column is displayed and sorted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    /// Left aligned, ellipsized, sorted case-insensitively; expands to fill
+    Text,
+    /// Right aligned, sorted by the leading number
+    Number,
+    /// Left aligned, sorted case-insensitively; fixed width
+    Short,
+    /// Right aligned, sorted by size (cells formatted with `format_bytes` or `format_rate`)
+    Bytes,
+}
+
+/// Parse the leading number of a cell for numeric sorting ("12.5 MB" -> 12.5)
+fn leading_number(cell: &str) -> f64 {
+    let end = cell
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(cell.len());
+    cell[..end].parse().unwrap_or(0.0)
+}
+
+/// Sort key of a `format_bytes` or `format_rate` cell ("1.5 GB" -> bytes, "2 MB/s" -> bytes per second)
+fn byte_size(cell: &str) -> f64 {
+    let multiplier = match cell.rsplit(' ').next().map(|unit| unit.trim_end_matches("/s")) {
+        Some("KB") => 1024.0,
+        Some("MB") => 1024.0 * 1024.0,
+        Some("GB") => 1024.0 * 1024.0 * 1024.0,
+        _ => 1.0,
+    };
+    leading_number(cell) * multiplier
+}
+
+fn cell(obj: &glib::Object, column: usize) -> String {
+    obj.downcast_ref::<BoxedAnyObject>()
+        .expect("Table item should be a BoxedAnyObject")
+        .borrow::<Vec<String>>()
+        .get(column)
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub struct TableView {
+    pub widget: ScrolledWindow,
+    store: gtk4::gio::ListStore,
+    filter: CustomFilter,
+    /// Lowercase filter text; rows match if any cell contains it
+    filter_text: Rc<RefCell<String>>,
+    selection: SingleSelection,
+}
+
+impl TableView {
+    pub fn new(columns: &[(&str, ColumnKind)]) -> Self {
+        let store = gtk4::gio::ListStore::new::<BoxedAnyObject>();
+
+        let filter_text = Rc::new(RefCell::new(String::new()));
+        let filter_text_clone = filter_text.clone();
+        let filter = CustomFilter::new(move |obj| {
+            let text = filter_text_clone.borrow();
+            text.is_empty()
+                || obj
+                    .downcast_ref::<BoxedAnyObject>()
+                    .expect("Table item should be a BoxedAnyObject")
+                    .borrow::<Vec<String>>()
+                    .iter()
+                    .any(|cell| cell.to_lowercase().contains(text.as_str()))
+        });
+        let filter_model = FilterListModel::new(Some(store.clone()), Some(filter.clone()));
+
+        let sort_model = SortListModel::new(Some(filter_model), None::<gtk4::Sorter>);
+        let selection = SingleSelection::new(Some(sort_model.clone()));
+        selection.set_autoselect(false);
+        selection.set_can_unselect(true);
+
+        let column_view = ColumnView::new(Some(selection.clone()));
+        column_view.set_show_column_separators(true);
+        column_view.set_show_row_separators(true);
+        column_view.set_reorderable(false);
+        sort_model.set_sorter(column_view.sorter().as_ref());
+
+        for (index, &(title, kind)) in columns.iter().enumerate() {
+            let factory = SignalListItemFactory::new();
+            factory.connect_setup(move |_, item| {
+                let item = item.downcast_ref::<ListItem>()
+                    .expect("Factory item should be a ListItem");
+                let label = Label::new(None);
+                if matches!(kind, ColumnKind::Number | ColumnKind::Bytes) {
+                    label.set_halign(gtk4::Align::End);
+                    label.add_css_class("numeric");
+                } else {
+                    label.set_halign(gtk4::Align::Start);
+                    label.set_ellipsize(gtk4::pango::EllipsizeMode::Middle);
+                }
+                item.set_child(Some(&label));
+            });
+            factory.connect_bind(move |_, item| {
+                let item = item.downcast_ref::<ListItem>()
+                    .expect("Factory item should be a ListItem");
+                let label = item.child().and_downcast::<Label>()
+                    .expect("Item child should be a Label");
+                if let Some(obj) = item.item() {
+                    let text = cell(&obj, index);
+                    // Long paths are ellipsized; keep the full text reachable
+                    if kind == ColumnKind::Text {
+                        label.set_tooltip_text(Some(&text));
+                    }
+                    label.set_label(&text);
+                }
+            });
+
+            let sorter = CustomSorter::new(move |a, b| {
+                let (a, b) = (cell(a, index), cell(b, index));
+                let ordering = match kind {
+                    ColumnKind::Number => leading_number(&a).total_cmp(&leading_number(&b)),
+                    ColumnKind::Bytes => byte_size(&a).total_cmp(&byte_size(&b)),
+                    ColumnKind::Text | ColumnKind::Short => a.to_lowercase().cmp(&b.to_lowercase()),
+                };
+                GtkOrdering::from(ordering)
+            });
+
+            let col = ColumnViewColumn::new(Some(title), Some(factory));
+            col.set_sorter(Some(&sorter));
+            col.set_resizable(true);
+            match kind {
+                ColumnKind::Text => col.set_expand(true),
+                ColumnKind::Number => col.set_fixed_width(80),
+                ColumnKind::Bytes => col.set_fixed_width(100),
+                ColumnKind::Short => col.set_fixed_width(140),
+            }
+            column_view.append_column(&col);
+        }
+
+        let widget = ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vexpand(true)
+            .hexpand(true)
+            .child(&column_view)
+            .build();
+
+        Self {
+            widget,
+            store,
+            filter,
+            filter_text,
+            selection,
+        }
+    }
+
+    /// Show only rows where some cell contains `text` (case-insensitive)
+    pub fn set_filter(&self, text: &str) {
+        *self.filter_text.borrow_mut() = text.to_lowercase();
+        self.filter.changed(gtk4::FilterChange::Different);
+    }
+
+    /// Cells of the selected row
+    pub fn selected_row(&self) -> Option<Vec<String>> {
+        let obj = self.selection.selected_item()?;
+        let row = obj.downcast_ref::<BoxedAnyObject>()?.borrow::<Vec<String>>().clone();
+        Some(row)
+    }
+
+    /// Cells of all rows passing the filter, in display order
+    pub fn visible_rows(&self) -> Vec<Vec<String>> {
+        (0..self.selection.n_items())
+            .filter_map(|i| self.selection.item(i).and_downcast::<BoxedAnyObject>())
+            .map(|obj| obj.borrow::<Vec<String>>().clone())
+            .collect()
+    }
+
+    /// Replace all rows
+    pub fn set_rows(&self, rows: Vec<Vec<String>>) {
+        let objects: Vec<BoxedAnyObject> = rows.into_iter().map(BoxedAnyObject::new).collect();
+        self.store.splice(0, self.store.n_items(), &objects);
+    }
+
+    /// Select the first visible row whose cells match
+    pub fn select_row(&self, matches: impl Fn(&[String]) -> bool) {
+        let position = (0..self.selection.n_items()).find(|&i| {
+            self.selection
+                .item(i)
+                .and_downcast::<BoxedAnyObject>()
+                .is_some_and(|obj| matches(&obj.borrow::<Vec<String>>()))
+        });
+        if let Some(position) = position {
+            self.selection.set_selected(position);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! "Threads" tab of the process window: every thread with its state, the core it last ran
+//! on and its share of the CPU since the previous refresh
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Label, Orientation};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Instant;
+
+use procular_core::process_actions::get_thread_cpu_info;
+use procular_core::process_state::ProcessState;
+
+use crate::table_view::{ColumnKind, TableView};
+
+/// CPU time of each thread at the previous refresh
+struct LastReading {
+    pid: u32,
+    at: Instant,
+    cpu_time_secs: HashMap<u32, f64>,
+}
+
+pub struct ThreadsView {
+    pub widget: GtkBox,
+    summary_label: Label,
+    table: TableView,
+    last: RefCell<Option<LastReading>>,
+}
+
+impl ThreadsView {
+    pub fn new() -> Self {
+        let widget = GtkBox::new(Orientation::Vertical, 8);
+        widget.set_margin_top(12);
+        widget.set_margin_bottom(12);
+        widget.set_margin_start(12);
+        widget.set_margin_end(12);
+
+        let summary_label = Label::new(None);
+        summary_label.set_halign(gtk4::Align::Start);
+        summary_label.set_wrap(true);
+        widget.append(&summary_label);
+
+        let table = TableView::new(&[
+            ("TID", ColumnKind::Number),
+            ("Name", ColumnKind::Text),
+            ("State", ColumnKind::Short),
+            ("Core", ColumnKind::Number),
+            ("CPU", ColumnKind::Number),
+        ]);
+        widget.append(&table.widget);
+
+        Self {
+            widget,
+            summary_label,
+            table,
+            last: RefCell::new(None),
+        }
+    }
+
+    /// Re-read /proc/<pid>/task and update the table. CPU use is measured against the previous
+    /// refresh, so it shows from the second one; `cpu_scale` is the core count in per-core mode,
+    /// else 1, as in the process list
+    pub fn refresh(&self, pid: u32, cpu_count: usize, cpu_scale: f32) {
+        let threads = get_thread_cpu_info(pid);
+        if threads.is_empty() {
+            self.summary_label.set_text("Cannot read the threads of this process");
+            self.table.set_rows(Vec::new());
+            self.last.replace(None);
+            return;
+        }
+
+        let now = Instant::now();
+        let last = self.last.borrow_mut().take().filter(|last| last.pid == pid);
+        let elapsed = last.as_ref().map(|last| now.duration_since(last.at).as_secs_f64());
+        let percent_of_machine = |tid: u32, cpu_time_secs: f64| -> Option<f64> {
+            let elapsed = elapsed.filter(|&secs| secs > 0.0)?;
+            let before = last.as_ref()?.cpu_time_secs.get(&tid)?;
+            Some((cpu_time_secs - before).max(0.0) / elapsed * 100.0 / cpu_count.max(1) as f64)
+        };
+
+        let running = threads.iter().filter(|t| t.state == ProcessState::Running).count();
+        self.summary_label.set_text(&format!("{} threads, {} running", threads.len(), running));
+
+        let rows = threads
+            .iter()
+            .map(|thread| {
+                vec![
+                    thread.tid.to_string(),
+                    thread.name.clone(),
+                    thread.state.as_str().to_string(),
+                    thread.current_cpu.map_or_else(|| "-".to_string(), |cpu| cpu.to_string()),
+                    percent_of_machine(thread.tid, thread.cpu_time_secs)
+                        .map_or_else(|| "-".to_string(), |percent| format!("{:.1}%", percent * cpu_scale as f64)),
+                ]
+            })
+            .collect();
+        self.table.set_rows(rows);
+
+        self.last.replace(Some(LastReading {
+            pid,
+            at: now,
+            cpu_time_secs: threads.iter().map(|t| (t.tid, t.cpu_time_secs)).collect(),
+        }));
+    }
+}
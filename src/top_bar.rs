@@ -0,0 +1,198 @@
+//! Thin always-on-top strip with CPU, memory and network sparklines and the busiest process
+//!
+//! The bar has no monitor of its own; the main window's refresh feeds it, so it
+//! costs only a redraw per refresh.
+
+use gtk4::prelude::*;
+use gtk4::{gdk, gio, Box as GtkBox, Button, Label, Orientation, WindowHandle};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::process::Command;
+use std::time::Duration;
+
+use procular_core::monitor::{format_rate, ProcessInfo, SystemMonitor};
+use procular_core::swap::MemoryUsage;
+
+use crate::graph::Sparkline;
+use crate::settings::ScreenEdge;
+
+/// Window title, also used to find the window with wmctrl
+const TOP_BAR_TITLE: &str = "Procular Top Bar";
+const TOP_BAR_HEIGHT: i32 = 28;
+/// Samples per sparkline (one minute at the default refresh)
+const TOP_BAR_SAMPLES: usize = 30;
+
+const CPU_COLOR: (f64, f64, f64) = (0.204, 0.396, 0.643); // Blue
+const MEMORY_COLOR: (f64, f64, f64) = (0.584, 0.345, 0.698); // Purple
+const NET_COLOR: (f64, f64, f64) = (0.839, 0.153, 0.157); // Red
+
+/// Value label with a sparkline of its recent history
+struct Meter {
+    label: Label,
+    sparkline: Sparkline,
+    history: RefCell<VecDeque<f64>>,
+}
+
+impl Meter {
+    fn new(container: &GtkBox, color: (f64, f64, f64), fixed_max: Option<f64>) -> Self {
+        let label = Label::new(None);
+        label.add_css_class("caption");
+        label.add_css_class("numeric");
+        container.append(&label);
+        let sparkline = Sparkline::new(color, fixed_max);
+        sparkline.drawing_area.set_size_request(80, TOP_BAR_HEIGHT - 8);
+        sparkline.drawing_area.set_valign(gtk4::Align::Center);
+        container.append(&sparkline.drawing_area);
+        Self {
+            label,
+            sparkline,
+            history: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    fn push(&self, text: &str, value: f64) {
+        self.label.set_text(text);
+        let mut history = self.history.borrow_mut();
+        history.push_back(value);
+        while history.len() > TOP_BAR_SAMPLES {
+            history.pop_front();
+        }
+        self.sparkline.update(&history.iter().copied().collect::<Vec<_>>());
+    }
+}
+
+/// The top bar companion window
+pub struct TopBar {
+    pub window: gtk4::Window,
+    cpu: Meter,
+    memory: Meter,
+    network: Meter,
+    top_button: Button,
+}
+
+impl TopBar {
+    /// Create the bar; `main_window` provides the `win.*` actions its buttons use
+    pub fn new(app: &impl IsA<gtk4::Application>, main_window: &impl IsA<gio::ActionGroup>, edge: ScreenEdge) -> Self {
+        let window = gtk4::Window::builder()
+            .application(app)
+            .title(TOP_BAR_TITLE)
+            .decorated(false)
+            .resizable(false)
+            .default_height(TOP_BAR_HEIGHT)
+            .build();
+        window.insert_action_group("win", Some(main_window));
+
+        let content = GtkBox::new(Orientation::Horizontal, 8);
+        content.set_margin_start(8);
+        content.set_margin_end(4);
+        let cpu = Meter::new(&content, CPU_COLOR, Some(100.0));
+        let memory = Meter::new(&content, MEMORY_COLOR, Some(100.0));
+        let network = Meter::new(&content, NET_COLOR, None);
+
+        let top_button = Button::new();
+        top_button.add_css_class("flat");
+        top_button.set_tooltip_text(Some("Busiest process; click to open it"));
+        top_button.set_hexpand(true);
+        top_button.set_halign(gtk4::Align::Start);
+        content.append(&top_button);
+
+        let close_btn = Button::from_icon_name("window-close-symbolic");
+        close_btn.add_css_class("flat");
+        close_btn.set_tooltip_text(Some("Close Top Bar"));
+        content.append(&close_btn);
+        let window_weak = window.downgrade();
+        close_btn.connect_clicked(move |_| {
+            if let Some(window) = window_weak.upgrade() {
+                window.close();
+            }
+        });
+
+        // Dragging the bar moves it where the window manager allows
+        let handle = WindowHandle::new();
+        handle.set_child(Some(&content));
+        window.set_child(Some(&handle));
+
+        // Span the primary monitor
+        let geometry = primary_monitor_geometry();
+        if let Some(geometry) = geometry {
+            window.set_default_width(geometry.width());
+        }
+        window.connect_map(move |_| {
+            let Some(geometry) = geometry else {
+                return;
+            };
+            // The window manager has to have managed the window before it can be moved
+            glib::timeout_add_local_once(Duration::from_millis(300), move || dock(geometry, edge));
+        });
+
+        Self {
+            window,
+            cpu,
+            memory,
+            network,
+            top_button,
+        }
+    }
+
+    /// Add the latest refresh; `processes` is sorted busiest first
+    pub fn update(&self, monitor: &SystemMonitor, usage: &MemoryUsage, processes: &[ProcessInfo], cpu_scale: f32) {
+        let cpu = monitor.cpu_percent();
+        self.cpu.push(&format!("CPU {:.0}%", cpu), cpu);
+
+        let memory = usage.used_percent();
+        self.memory.push(&format!("Mem {:.0}%", memory), memory);
+
+        let rx = monitor.net_rx_rate();
+        let tx = monitor.net_tx_rate();
+        self.network.push(
+            &format!("↓ {} ↑ {}", format_rate(rx), format_rate(tx)),
+            (rx + tx) as f64,
+        );
+
+        match processes.first() {
+            Some(top) => {
+                self.top_button
+                    .set_label(&format!("{} {:.0}%", top.name, top.total_cpu() * cpu_scale));
+                self.top_button.set_action_name(Some("win.open-process"));
+                self.top_button
+                    .set_action_target_value(Some(&(top.pid, top.name.clone()).to_variant()));
+            }
+            None => {
+                self.top_button.set_label("");
+                self.top_button.set_action_name(None);
+            }
+        }
+    }
+}
+
+/// Geometry of the first monitor
+fn primary_monitor_geometry() -> Option<gdk::Rectangle> {
+    let display = gdk::Display::default()?;
+    let monitor = display.monitors().item(0).and_downcast::<gdk::Monitor>()?;
+    Some(monitor.geometry())
+}
+
+/// Move the bar to a screen edge and keep it above other windows on every workspace.
+/// GTK 4 cannot do either, so this asks the window manager through wmctrl; it has no
+/// effect on Wayland, where the compositor places windows
+fn dock(geometry: gdk::Rectangle, edge: ScreenEdge) {
+    let y = match edge {
+        ScreenEdge::Top => geometry.y(),
+        ScreenEdge::Bottom => geometry.y() + geometry.height() - TOP_BAR_HEIGHT,
+    };
+    let commands = [
+        vec!["-b".to_string(), "add,above,sticky".to_string()],
+        vec!["-b".to_string(), "add,skip_taskbar,skip_pager".to_string()],
+        vec![
+            "-e".to_string(),
+            format!("0,{},{},{},{}", geometry.x(), y, geometry.width(), TOP_BAR_HEIGHT),
+        ],
+    ];
+    for args in commands {
+        let result = Command::new("wmctrl").args(["-F", "-r", TOP_BAR_TITLE]).args(&args).output();
+        if result.is_err() {
+            // wmctrl is not installed
+            return;
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Status window and restart/stop confirmation for the systemd unit owning a process
+
+use gtk4::prelude::*;
+use gtk4::{Button, Label, ScrolledWindow};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::rc::Rc;
+
+use procular_core::process_actions::run_in_background;
+use procular_core::systemd_units::{run_unit_action, unit_status, SystemdUnit, UnitAction};
+
+use crate::lockdown::Lockdown;
+
+/// Show `systemctl status` of a unit with Restart and Stop buttons
+pub fn show_unit_dialog(parent: &impl IsA<gtk4::Window>, unit: SystemdUnit, lockdown: Rc<Lockdown>) {
+    let dialog = adw::Window::builder()
+        .title(&unit.name)
+        .transient_for(parent)
+        .default_width(720)
+        .default_height(480)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    let header = adw::HeaderBar::new();
+    let refresh_btn = Button::from_icon_name("view-refresh-symbolic");
+    refresh_btn.set_tooltip_text(Some("Refresh"));
+    header.pack_start(&refresh_btn);
+    let stop_btn = Button::with_label("Stop");
+    stop_btn.add_css_class("destructive-action");
+    header.pack_end(&stop_btn);
+    let restart_btn = Button::with_label("Restart");
+    header.pack_end(&restart_btn);
+    lockdown.bind_widget(&stop_btn);
+    lockdown.bind_widget(&restart_btn);
+    toolbar.add_top_bar(&header);
+
+    let status_label = Label::new(None);
+    status_label.add_css_class("monospace");
+    status_label.set_selectable(true);
+    status_label.set_xalign(0.0);
+    status_label.set_yalign(0.0);
+    status_label.set_margin_top(12);
+    status_label.set_margin_bottom(12);
+    status_label.set_margin_start(12);
+    status_label.set_margin_end(12);
+    let scrolled = ScrolledWindow::new();
+    scrolled.set_child(Some(&status_label));
+    toolbar.set_content(Some(&scrolled));
+    dialog.set_content(Some(&toolbar));
+
+    let refresh: Rc<dyn Fn()> = {
+        let unit = unit.clone();
+        Rc::new(move || {
+            let text = unit_status(&unit).unwrap_or_else(|e| format!("Cannot read unit status: {}", e));
+            status_label.set_text(&text);
+        })
+    };
+    refresh();
+
+    let refresh_clone = refresh.clone();
+    refresh_btn.connect_clicked(move |_| refresh_clone());
+
+    for (button, action) in [(&restart_btn, UnitAction::Restart), (&stop_btn, UnitAction::Stop)] {
+        let unit = unit.clone();
+        let refresh = refresh.clone();
+        let dialog_weak = dialog.downgrade();
+        button.connect_clicked(move |_| {
+            if let Some(dialog) = dialog_weak.upgrade() {
+                let refresh = refresh.clone();
+                confirm_unit_action(&dialog, vec![unit.clone()], action, move || refresh());
+            }
+        });
+    }
+
+    dialog.present();
+}
+
+/// Ask before restarting or stopping units, since that affects every process in them.
+/// `on_done` runs after the action was attempted
+pub fn confirm_unit_action(
+    parent: &impl IsA<gtk4::Window>,
+    units: Vec<SystemdUnit>,
+    action: UnitAction,
+    on_done: impl Fn() + 'static,
+) {
+    let on_done = Rc::new(on_done);
+    if units.is_empty() {
+        return;
+    }
+    let names: Vec<&str> = units.iter().map(|u| u.name.as_str()).collect();
+    let heading = if units.len() == 1 {
+        format!("{} {}?", action.as_str(), names[0])
+    } else {
+        format!("{} {} Units?", action.as_str(), units.len())
+    };
+    let body = format!(
+        "All processes of {} will be affected, not only the selected ones.",
+        names.join(", ")
+    );
+    let confirm = adw::MessageDialog::builder()
+        .transient_for(parent)
+        .heading(heading)
+        .body(body)
+        .build();
+    confirm.add_response("cancel", "Cancel");
+    confirm.add_response("confirm", action.as_str());
+    confirm.set_response_appearance("confirm", adw::ResponseAppearance::Destructive);
+    confirm.set_default_response(Some("cancel"));
+    confirm.set_close_response("cancel");
+
+    confirm.connect_response(None, move |confirm, response| {
+        if response != "confirm" {
+            return;
+        }
+        let (units, on_done, parent) = (units.clone(), on_done.clone(), confirm.transient_for());
+        glib::spawn_future_local(async move {
+            let errors = run_in_background(move || {
+                Ok(units
+                    .iter()
+                    .filter_map(|unit| run_unit_action(unit, action).err().map(|e| e.to_string()))
+                    .collect::<Vec<String>>())
+            })
+            .await
+            .unwrap_or_else(|e| vec![e.to_string()]);
+            on_done();
+            if errors.is_empty() {
+                return;
+            }
+            let error = adw::MessageDialog::builder()
+                .heading(format!("Failed to {} unit", action.as_str().to_lowercase()))
+                .body(errors.join("\n"))
+                .build();
+            if let Some(parent) = parent {
+                error.set_transient_for(Some(&parent));
+            }
+            error.add_response("ok", "OK");
+            error.present();
+        });
+    });
+    confirm.present();
+}
@@ -0,0 +1,90 @@
+//! Users page of the main window: CPU, memory, process count and I/O summed per account,
+//! to see who is using a shared machine
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Label, Orientation};
+use std::rc::Rc;
+
+use procular_core::monitor::{format_bytes, format_rate};
+use procular_core::user_usage::UserUsage;
+use procular_core::users::user_name;
+
+use crate::table_view::{ColumnKind, TableView};
+
+const USER_COLUMN: usize = 0;
+
+/// Table of per-user totals with a button listing the selected user's processes
+pub struct UsersView {
+    pub widget: GtkBox,
+    table: Rc<TableView>,
+}
+
+impl UsersView {
+    pub fn new() -> Self {
+        let widget = GtkBox::new(Orientation::Vertical, 0);
+
+        let table = Rc::new(TableView::new(&[
+            ("User", ColumnKind::Text),
+            ("Processes", ColumnKind::Number),
+            ("CPU", ColumnKind::Number),
+            ("Memory", ColumnKind::Bytes),
+            ("Disk Read", ColumnKind::Bytes),
+            ("Disk Write", ColumnKind::Bytes),
+            ("Received", ColumnKind::Bytes),
+            ("Sent", ColumnKind::Bytes),
+        ]));
+        widget.append(&table.widget);
+
+        let footer = GtkBox::new(Orientation::Horizontal, 8);
+        footer.set_margin_top(6);
+        footer.set_margin_bottom(6);
+        footer.set_margin_start(12);
+        footer.set_margin_end(12);
+        let note = Label::new(Some("Memory adds up each process's resident memory, so shared memory counts more than once"));
+        note.add_css_class("dim-label");
+        note.add_css_class("caption");
+        note.set_wrap(true);
+        note.set_xalign(0.0);
+        note.set_hexpand(true);
+        footer.append(&note);
+        let show_btn = Button::with_label("Show Processes");
+        show_btn.set_tooltip_text(Some("List the selected user's processes"));
+        footer.append(&show_btn);
+        widget.append(&footer);
+
+        let table_clone = table.clone();
+        show_btn.connect_clicked(move |btn| {
+            if let Some(row) = table_clone.selected_row() {
+                let query = format!("user:{}", row[USER_COLUMN]);
+                let _ = btn.activate_action("win.search", Some(&query.to_variant()));
+            }
+        });
+
+        Self { widget, table }
+    }
+
+    /// Show the totals of the last refresh; `cpu_scale` is the core count in per-core mode, else 1
+    pub fn update(&self, users: &[UserUsage], cpu_scale: f32) {
+        let selected = self.table.selected_row().map(|row| row[USER_COLUMN].clone());
+        let rows = users
+            .iter()
+            .map(|usage| {
+                vec![
+                    user_name(usage.uid).unwrap_or_else(|| usage.uid.to_string()),
+                    usage.processes.to_string(),
+                    format!("{:.1}%", usage.cpu_percent * cpu_scale),
+                    format_bytes(usage.memory_bytes),
+                    format_rate(usage.disk_read_rate),
+                    format_rate(usage.disk_write_rate),
+                    format_rate(usage.net_rx_rate),
+                    format_rate(usage.net_tx_rate),
+                ]
+            })
+            .collect();
+        self.table.set_rows(rows);
+        // Replacing the rows clears the selection
+        if let Some(selected) = selected {
+            self.table.select_row(|row| row[USER_COLUMN] == selected);
+        }
+    }
+}
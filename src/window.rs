@@ -1,18 +1,67 @@
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Orientation, SearchEntry};
+use gtk4::{gio, Box as GtkBox, Orientation, SearchEntry};
 use libadwaita as adw;
 use adw::prelude::*;
+use futures_channel::oneshot;
 use glib::ControlFlow;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use procular_core::filesystems::{read_filesystems, DiskFullDetector};
+use procular_core::history_export;
+use procular_core::monitor::{format_bytes, format_duration, ProcessKey, SystemMonitor, VramFullDetector};
+use procular_core::process_actions;
+use procular_core::process_state::ProcessState;
+use procular_core::self_usage::{RefreshThrottle, SelfUsage};
+use procular_core::sensors::OverheatDetector;
+use procular_core::snapshot::{ProcessSnapshot, SnapshotDiff};
+use procular_core::swap::{read_memory_usage, top_swap_users, SwappingDetector};
+use procular_core::systemd_units::ProcessOrigin;
+use procular_core::vram_leaks::{VramAlertKind, VramLeakDetector};
+
+use crate::adaptive;
+use crate::app_state::AppState;
+use crate::audit_log::AuditLog;
+use crate::audit_log_dialog;
+use crate::automation::AutomationEngine;
+use crate::budgets::BudgetMonitor;
+use crate::budgets_dialog;
 use crate::context_menu;
-use crate::monitor::SystemMonitor;
-use crate::process_list::{ProcessListView, ProcessObject};
+use crate::cpu_view::CpuView;
+use crate::dbus_service::DbusService;
+use crate::diagnosis_dialog;
+use crate::disks_view::DisksView;
+use crate::filesystems_dialog;
+use crate::gpu_view::GpuView;
+use crate::graph;
+use crate::idle_dialog;
+use crate::inhibit::SuspendInhibitor;
+use crate::lockdown::{self, Lockdown};
+use crate::mini_window::MiniWindow;
+use crate::onboarding;
+use crate::offenders_dialog;
+use crate::preferences;
+use crate::process_list::{ProcessListView, SearchScope};
 use crate::process_window;
+use crate::rules_dialog;
+use crate::sensors_view::{SensorThresholds, SensorsView};
+use crate::settings::Settings;
+use crate::shortcuts;
+use crate::snapshot_dialog;
+use crate::startup_dialog;
+use crate::status_icon::StatusIcon;
+use crate::system_memory_view::SystemMemoryView;
+use crate::top_bar::TopBar;
+use crate::users_view::UsersView;
+use crate::window_state;
 
 const UPDATE_INTERVAL_MS: u64 = 2000; // 2 seconds
+const DISK_CHECK_TICKS: u32 = 15; // Check filesystem usage every 30 seconds
+const VRAM_ALERT_PERCENT: f64 = 95.0; // Video memory use at which a GPU counts as nearly full
+const SWAP_ALERT_BYTES_PER_SEC: f64 = 1024.0 * 1024.0; // Combined swap-in/out rate that counts as swapping
+const SWAP_ALERT_TICKS: usize = 15; // Swapping must last 30 seconds before it alerts
+const SWAP_USERS_SHOWN: usize = 10;
 
 pub struct ProcularWindow;
 
@@ -26,47 +75,439 @@ impl ProcularWindow {
             .icon_name("procular")
             .default_width(1200)
             .default_height(700)
+            .width_request(adaptive::MIN_WIDTH)
+            .height_request(adaptive::MIN_HEIGHT)
             .build();
+        // Connected before the close handler below, which may only hide the window
+        window_state::remember_size(&window, window_state::MAIN_WINDOW_GROUP);
 
         // Main layout
         let main_box = GtkBox::new(Orientation::Vertical, 0);
 
         // Header bar with search
-        let (header_bar, search_entry) = Self::create_header_bar();
+        let (header_bar, search_entry, idle_btn) = Self::create_header_bar();
         main_box.append(&header_bar);
 
         // Create the monitor
         let monitor = Rc::new(RefCell::new(SystemMonitor::new()));
 
+        // Load automation rules
+        let automation = Rc::new(RefCell::new(AutomationEngine::load()));
+
+        // Load disk and network budgets
+        let budgets = Rc::new(RefCell::new(BudgetMonitor::load()));
+
+        // Preferences and suspend inhibition for critical watches
+        let settings = Settings::load();
+        monitor.borrow_mut().set_collectors(settings.collectors);
+        monitor.borrow_mut().set_pinned_names(settings.pinned_names.clone());
+        monitor.borrow_mut().set_process_limit(settings.process_limit());
+        graph::set_clock_axis(settings.clock_time_axis);
+        let inhibitor = Rc::new(SuspendInhibitor::new(app, settings.inhibit_suspend));
+        let lockdown = Rc::new(Lockdown::new(settings.read_only, lockdown::system_enforces_read_only()));
+
+        let state = AppState {
+            monitor: monitor.clone(),
+            automation: automation.clone(),
+            budgets: budgets.clone(),
+            settings: Rc::new(RefCell::new(settings)),
+            inhibitor: inhibitor.clone(),
+            lockdown: lockdown.clone(),
+            audit_log: Rc::new(RefCell::new(AuditLog::load())),
+            sample_interval_secs: Rc::new(Cell::new(UPDATE_INTERVAL_MS / 1000)),
+        };
+
+        // Scripting interface on the session bus
+        let dbus_service = DbusService::register(app, &state);
+
         // Create process list view
         let process_list = Rc::new(ProcessListView::new());
+        let process_list_weak = Rc::downgrade(&process_list);
+        lockdown.connect_changed(move |read_only| match process_list_weak.upgrade() {
+            Some(process_list) => {
+                process_list.set_read_only(read_only);
+                ControlFlow::Continue
+            }
+            None => ControlFlow::Break,
+        });
 
         // Set up context menu actions for process list
         let process_list_clone = process_list.clone();
         let window_clone = window.clone();
-        let monitor_clone = monitor.clone();
         context_menu::setup_process_actions(
             process_list.column_view(),
-            move || process_list_clone.get_selected_process(),
+            move || process_list_clone.get_selected_processes(),
             move || Some(window_clone.clone().upcast::<gtk4::Window>()),
-            monitor_clone,
+            state.clone(),
         );
 
         // Set up double-click to open process window
         let window_clone = window.clone();
-        let monitor_clone = monitor.clone();
+        let state_clone = state.clone();
         process_list.connect_double_click(move |pid, name| {
             process_window::open_process_window(
                 &window_clone,
                 pid,
                 &name,
-                monitor_clone.clone(),
+                state_clone.clone(),
             );
         });
 
-        // Add process list directly (no paned view)
+        // Idle processes panel
+        let window_clone = window.clone();
+        let state_clone = state.clone();
+        idle_btn.connect_clicked(move |_| {
+            idle_dialog::show_idle_dialog(&window_clone, &state_clone);
+        });
+
+        // Toasts for short confirmations
+        let toast_overlay = adw::ToastOverlay::new();
+
+        // Main menu actions
+        let process_list_clone = process_list.clone();
+        let window_weak = window.downgrade();
+        let toast_overlay_clone = toast_overlay.clone();
+        let copy_action = gio::SimpleAction::new("copy-process-list", None);
+        copy_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                win.clipboard().set_text(&process_list_clone.export_text());
+                toast_overlay_clone.add_toast(adw::Toast::new("Process list copied to clipboard"));
+            }
+        });
+        window.add_action(&copy_action);
+
+        let process_list_clone = process_list.clone();
+        let window_weak = window.downgrade();
+        let toast_overlay_clone = toast_overlay.clone();
+        let export_action = gio::SimpleAction::new("export-process-list", None);
+        export_action.connect_activate(move |_, _| {
+            let Some(win) = window_weak.upgrade() else {
+                return;
+            };
+            // Snapshot now so the file matches what was on screen
+            let text = process_list_clone.export_text();
+            let toast_overlay = toast_overlay_clone.clone();
+            let dialog = gtk4::FileDialog::builder()
+                .title("Export Process List")
+                .initial_name("processes.txt")
+                .build();
+            dialog.save(Some(&win), gio::Cancellable::NONE, move |result| {
+                // Cancelled dialogs also end up here
+                let Ok(file) = result else {
+                    return;
+                };
+                let Some(path) = file.path() else {
+                    return;
+                };
+                let message = match std::fs::write(&path, &text) {
+                    Ok(()) => format!("Exported to {}", path.display()),
+                    Err(e) => format!("Export failed: {}", e),
+                };
+                toast_overlay.add_toast(adw::Toast::new(&message));
+            });
+        });
+        window.add_action(&export_action);
+
+        let state_clone = state.clone();
+        let window_weak = window.downgrade();
+        let toast_overlay_clone = toast_overlay.clone();
+        let export_all_action = gio::SimpleAction::new("export-all-data", None);
+        export_all_action.connect_activate(move |_, _| {
+            let Some(win) = window_weak.upgrade() else {
+                return;
+            };
+            let text = history_export::export_json(&state_clone.monitor.borrow(), state_clone.sample_interval_secs.get());
+            let toast_overlay = toast_overlay_clone.clone();
+            let dialog = gtk4::FileDialog::builder()
+                .title("Export All Data")
+                .initial_name("procular-history.json")
+                .build();
+            dialog.save(Some(&win), gio::Cancellable::NONE, move |result| {
+                let Ok(file) = result else {
+                    return;
+                };
+                let Some(path) = file.path() else {
+                    return;
+                };
+                let message = match std::fs::write(&path, &text) {
+                    Ok(()) => format!("Exported to {}", path.display()),
+                    Err(e) => format!("Export failed: {}", e),
+                };
+                toast_overlay.add_toast(adw::Toast::new(&message));
+            });
+        });
+        window.add_action(&export_all_action);
+
+        // Baseline for "what changed since", e.g. the start of a maintenance window
+        let snapshot: Rc<RefCell<Option<ProcessSnapshot>>> = Rc::new(RefCell::new(None));
+        let state_clone = state.clone();
+        let snapshot_clone = snapshot.clone();
+        let toast_overlay_clone = toast_overlay.clone();
+        let take_snapshot_action = gio::SimpleAction::new("take-snapshot", None);
+        take_snapshot_action.connect_activate(move |_, _| {
+            let taken = state_clone.monitor.borrow().snapshot();
+            let message = format!("Snapshot of {} processes taken", taken.entries.len());
+            *snapshot_clone.borrow_mut() = Some(taken);
+            toast_overlay_clone.add_toast(adw::Toast::new(&message));
+        });
+        window.add_action(&take_snapshot_action);
+
+        let state_clone = state.clone();
+        let window_weak = window.downgrade();
+        let toast_overlay_clone = toast_overlay.clone();
+        let compare_action = gio::SimpleAction::new("compare-snapshot", None);
+        compare_action.connect_activate(move |_, _| {
+            let Some(win) = window_weak.upgrade() else {
+                return;
+            };
+            let Some(before) = snapshot.borrow().clone() else {
+                toast_overlay_clone.add_toast(adw::Toast::new("Take a snapshot first"));
+                return;
+            };
+            let after = state_clone.monitor.borrow().snapshot();
+            snapshot_dialog::show_snapshot_diff(&win, SnapshotDiff::between(&before, &after));
+        });
+        window.add_action(&compare_action);
+
+        let offenders_action = gio::SimpleAction::new("top-offenders", None);
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        offenders_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                offenders_dialog::show_offenders_dialog(&win, &state_clone);
+            }
+        });
+        window.add_action(&offenders_action);
+
+        let audit_log_action = gio::SimpleAction::new("audit-log", None);
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        audit_log_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                audit_log_dialog::show_audit_log_dialog(&win, &state_clone);
+            }
+        });
+        window.add_action(&audit_log_action);
+
+        let startup_action = gio::SimpleAction::new("startup-impact", None);
+        let window_weak = window.downgrade();
+        startup_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                startup_dialog::show_startup_dialog(&win);
+            }
+        });
+        window.add_action(&startup_action);
+
+        let filesystems_action = gio::SimpleAction::new("filesystems", None);
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        filesystems_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                let percent = state_clone.settings.borrow().disk_alert_percent;
+                filesystems_dialog::show_filesystems_dialog(&win, state_clone.monitor.clone(), percent, None);
+            }
+        });
+        window.add_action(&filesystems_action);
+
+        let diagnose_action = gio::SimpleAction::new("diagnose", None);
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        diagnose_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                diagnosis_dialog::show_diagnosis_dialog(&win, &state_clone);
+            }
+        });
+        window.add_action(&diagnose_action);
+
+        let rules_action = gio::SimpleAction::new("automation-rules", None);
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        rules_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                rules_dialog::show_rules_dialog(&win, &state_clone);
+            }
+        });
+        window.add_action(&rules_action);
+
+        let budgets_action = gio::SimpleAction::new("io-budgets", None);
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        budgets_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                budgets_dialog::show_budgets_dialog(&win, &state_clone);
+            }
+        });
+        window.add_action(&budgets_action);
+
+        // Opens a process window by PID and name (used by budget toasts)
+        let open_process_action =
+            gio::SimpleAction::new("open-process", Some(&<(u32, String)>::static_variant_type()));
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        open_process_action.connect_activate(move |_, param| {
+            let Some((pid, name)) = param.and_then(|p| p.get::<(u32, String)>()) else {
+                return;
+            };
+            if let Some(win) = window_weak.upgrade() {
+                process_window::open_process_window(&win, pid, &name, state_clone.clone());
+            }
+        });
+        window.add_action(&open_process_action);
+
+        // Selects a process in the list and opens its window by PID alone (used by --pid)
+        let show_process_action = gio::SimpleAction::new("show-process", Some(glib::VariantTy::UINT32));
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        let process_list_clone = process_list.clone();
+        show_process_action.connect_activate(move |_, param| {
+            let Some(pid) = param.and_then(|p| p.get::<u32>()) else {
+                return;
+            };
+            let Some(win) = window_weak.upgrade() else {
+                return;
+            };
+            process_list_clone.focus_process(pid);
+            let name = process_actions::get_process_name(pid);
+            process_window::open_process_window(&win, pid, &name, state_clone.clone());
+        });
+        window.add_action(&show_process_action);
+
+        // Forces read-only mode on for the rest of the session (used by --read-only)
+        let enforce_read_only_action = gio::SimpleAction::new("enforce-read-only", None);
+        let lockdown_clone = lockdown.clone();
+        enforce_read_only_action.connect_activate(move |_, _| lockdown_clone.enforce());
+        window.add_action(&enforce_read_only_action);
+
+        // Opens the filesystems window with one mount expanded (used by disk full toasts)
+        let details_action = gio::SimpleAction::new("filesystem-details", Some(glib::VariantTy::STRING));
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        details_action.connect_activate(move |_, param| {
+            let mount_point = param.and_then(|p| p.get::<String>());
+            if let Some(win) = window_weak.upgrade() {
+                let percent = state_clone.settings.borrow().disk_alert_percent;
+                filesystems_dialog::show_filesystems_dialog(
+                    &win,
+                    state_clone.monitor.clone(),
+                    percent,
+                    mount_point.as_deref(),
+                );
+            }
+        });
+        window.add_action(&details_action);
+
+        let preferences_action = gio::SimpleAction::new("preferences", None);
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        preferences_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                preferences::show_preferences(&win, &state_clone);
+            }
+        });
+        window.add_action(&preferences_action);
+
+        let setup_action = gio::SimpleAction::new("setup-assistant", None);
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        setup_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                onboarding::show_setup_assistant(&win, &state_clone);
+            }
+        });
+        window.add_action(&setup_action);
+
+        // Offer the setup assistant once the window is shown
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        glib::idle_add_local_once(move || {
+            if let Some(win) = window_weak.upgrade() {
+                onboarding::show_on_first_run(&win, &state_clone);
+            }
+        });
+
+        // Pages: process list, per-core CPU graphs, memory/swap activity, disks, users and temperatures
+        let view_stack = adw::ViewStack::new();
+        view_stack.set_vexpand(true);
+
         process_list.widget.set_vexpand(true);
-        main_box.append(&process_list.widget);
+        view_stack.add_titled_with_icon(&process_list.widget, Some("processes"), "Processes", "view-list-symbolic");
+
+        let cpu_view = Rc::new(CpuView::new());
+        view_stack.add_titled_with_icon(&cpu_view.widget, Some("cpu"), "CPU", "computer-symbolic");
+
+        let memory_view = Rc::new(SystemMemoryView::new());
+        view_stack.add_titled_with_icon(
+            &memory_view.widget,
+            Some("memory"),
+            "Memory",
+            "drive-harddisk-solidstate-symbolic",
+        );
+
+        let disks_view = Rc::new(DisksView::new());
+        view_stack.add_titled_with_icon(&disks_view.widget, Some("disks"), "Disks", "drive-harddisk-symbolic");
+
+        let users_view = Rc::new(UsersView::new());
+        view_stack.add_titled_with_icon(&users_view.widget, Some("users"), "Users", "system-users-symbolic");
+
+        let sensor_thresholds = Rc::new(RefCell::new(SensorThresholds::load()));
+        let sensors_view = Rc::new(SensorsView::new(sensor_thresholds.clone()));
+        view_stack.add_titled_with_icon(
+            &sensors_view.widget,
+            Some("sensors"),
+            "Sensors",
+            "weather-clear-symbolic",
+        );
+
+        // GPU page only when NVML found an NVIDIA GPU
+        let gpu_view = monitor.borrow().has_gpu().then(|| Rc::new(GpuView::new(&state)));
+        if let Some(gpu_view) = &gpu_view {
+            view_stack.add_titled_with_icon(&gpu_view.widget, Some("gpu"), "GPU", "video-display-symbolic");
+        }
+
+        // Switches to the GPU page (used by VRAM full toasts)
+        let show_gpu_action = gio::SimpleAction::new("show-gpu", None);
+        let view_stack_weak = view_stack.downgrade();
+        show_gpu_action.connect_activate(move |_, _| {
+            if let Some(view_stack) = view_stack_weak.upgrade() {
+                view_stack.set_visible_child_name("gpu");
+            }
+        });
+        window.add_action(&show_gpu_action);
+
+        // Switches to the memory page (used by swapping toasts)
+        let show_memory_action = gio::SimpleAction::new("show-memory", None);
+        let view_stack_weak = view_stack.downgrade();
+        show_memory_action.connect_activate(move |_, _| {
+            if let Some(view_stack) = view_stack_weak.upgrade() {
+                view_stack.set_visible_child_name("memory");
+            }
+        });
+        window.add_action(&show_memory_action);
+
+        // Switches to the sensors page (used by temperature toasts)
+        let show_sensors_action = gio::SimpleAction::new("show-sensors", None);
+        let view_stack_weak = view_stack.downgrade();
+        show_sensors_action.connect_activate(move |_, _| {
+            if let Some(view_stack) = view_stack_weak.upgrade() {
+                view_stack.set_visible_child_name("sensors");
+            }
+        });
+        window.add_action(&show_sensors_action);
+
+        let view_switcher = adw::ViewSwitcher::builder()
+            .stack(&view_stack)
+            .policy(adw::ViewSwitcherPolicy::Wide)
+            .build();
+        header_bar.set_title_widget(Some(&view_switcher));
+
+        main_box.append(&view_stack);
+
+        // Narrow windows switch pages from the bottom and shrink the search entry
+        let switcher_bar = adaptive::view_switcher_bar(&view_stack);
+        main_box.append(&switcher_bar);
+        let breakpoint = adaptive::narrow_breakpoint(&header_bar, &switcher_bar);
+        breakpoint.add_setter(&search_entry, "width-chars", Some(&10.to_value()));
+        window.add_breakpoint(breakpoint);
 
         // Status bar
         let status_bar = GtkBox::new(Orientation::Horizontal, 8);
@@ -77,81 +518,711 @@ impl ProcularWindow {
         let status_label = gtk4::Label::new(Some("Monitoring processes..."));
         status_label.set_halign(gtk4::Align::Start);
         status_bar.append(&status_label);
+
+        let status_label_weak = status_label.downgrade();
+        lockdown.connect_changed(move |read_only| {
+            let Some(status_label) = status_label_weak.upgrade() else {
+                return ControlFlow::Break;
+            };
+            status_label.set_text(if read_only {
+                "Monitoring processes (read-only)"
+            } else {
+                "Monitoring processes..."
+            });
+            ControlFlow::Continue
+        });
+
+        // Shown when the process limit left processes out of the list
+        let hidden_label = gtk4::Label::new(None);
+        hidden_label.add_css_class("dim-label");
+        hidden_label.set_tooltip_text(Some(
+            "Only the busiest processes are listed; the limit can be changed or turned off in Preferences",
+        ));
+        hidden_label.set_visible(false);
+        status_bar.append(&hidden_label);
+
+        // Shown while a watch keeps the system awake
+        let inhibit_indicator = GtkBox::new(Orientation::Horizontal, 4);
+        inhibit_indicator.set_hexpand(true);
+        inhibit_indicator.set_halign(gtk4::Align::End);
+        inhibit_indicator.append(&gtk4::Image::from_icon_name("system-suspend-symbolic"));
+        let inhibit_label = gtk4::Label::new(None);
+        inhibit_label.add_css_class("dim-label");
+        inhibit_indicator.append(&inhibit_label);
+        inhibit_indicator.set_visible(false);
+        status_bar.append(&inhibit_indicator);
+
+        // Procular's own footprint, updated after each refresh
+        let self_usage_label = gtk4::Label::new(None);
+        self_usage_label.add_css_class("dim-label");
+        self_usage_label.add_css_class("numeric");
+        status_bar.append(&self_usage_label);
         main_box.append(&status_bar);
 
-        window.set_content(Some(&main_box));
+        let indicator_weak = inhibit_indicator.downgrade();
+        inhibitor.connect_changed(move |inhibitor| {
+            let Some(indicator) = indicator_weak.upgrade() else {
+                return ControlFlow::Break;
+            };
+            let reasons = inhibitor.active_reasons();
+            indicator.set_visible(!reasons.is_empty());
+            inhibit_label.set_text(&match reasons.len() {
+                1 => "Sleep blocked by 1 watch".to_string(),
+                n => format!("Sleep blocked by {} watches", n),
+            });
+            indicator.set_tooltip_text(Some(&reasons.join("\n")));
+            ControlFlow::Continue
+        });
+
+        toast_overlay.set_child(Some(&main_box));
+        window.set_content(Some(&toast_overlay));
 
         // Track selected process
-        let selected_pid: Rc<RefCell<Option<u32>>> = Rc::new(RefCell::new(None));
+        let selected_keys: Rc<RefCell<Vec<ProcessKey>>> = Rc::new(RefCell::new(Vec::new()));
 
         // Connect search
         let process_list_clone = process_list.clone();
         search_entry.connect_search_changed(move |entry| {
             let text = entry.text();
-            process_list_clone.set_filter(&text);
+            // An unfinished query still searches as plain text; the entry shows what is wrong
+            match process_list_clone.set_filter(&text) {
+                Ok(()) => {
+                    entry.remove_css_class("error");
+                    entry.set_tooltip_text(None);
+                }
+                Err(error) => {
+                    entry.add_css_class("error");
+                    entry.set_tooltip_text(Some(&error));
+                }
+            }
         });
 
-        // Connect selection change to track selected PID
-        let selected_pid_clone = selected_pid.clone();
+        // Ctrl+F jumps to the search box
+        let find_action = gio::SimpleAction::new("find", None);
+        let search_entry_clone = search_entry.clone();
+        find_action.connect_activate(move |_, _| {
+            search_entry_clone.grab_focus();
+        });
+        window.add_action(&find_action);
+        app.set_accels_for_action("win.find", &["<Ctrl>f"]);
+
+        // Fills in the search box (used by --search)
+        let search_action = gio::SimpleAction::new("search", Some(glib::VariantTy::STRING));
+        let search_entry_clone = search_entry.clone();
+        let view_stack_weak = view_stack.downgrade();
+        search_action.connect_activate(move |_, param| {
+            if let Some(text) = param.and_then(|p| p.get::<String>()) {
+                if let Some(view_stack) = view_stack_weak.upgrade() {
+                    view_stack.set_visible_child_name("processes");
+                }
+                search_entry_clone.set_text(&text);
+                search_entry_clone.grab_focus();
+            }
+        });
+        window.add_action(&search_action);
+
+        // Optionally let the search match command lines and environment variables
+        let search_scope = |settings: &Settings| SearchScope {
+            command_line: settings.search_command_line,
+            environment: settings.search_environment,
+        };
+        process_list.set_search_scope(search_scope(&state.settings.borrow()));
+        let search_options = [
+            ("search-command-line", state.settings.borrow().search_command_line),
+            ("search-environment", state.settings.borrow().search_environment),
+        ];
+        for (name, enabled) in search_options {
+            let action = gio::SimpleAction::new_stateful(name, None, &enabled.to_variant());
+            let process_list_clone = process_list.clone();
+            let state_clone = state.clone();
+            let toast_overlay_clone = toast_overlay.clone();
+            action.connect_change_state(move |action, value| {
+                let Some(on) = value.and_then(|v| v.get::<bool>()) else {
+                    return;
+                };
+                action.set_state(&on.to_variant());
+                let mut settings = state_clone.settings.borrow_mut();
+                match name {
+                    "search-command-line" => settings.search_command_line = on,
+                    _ => settings.search_environment = on,
+                }
+                if let Err(e) = settings.save() {
+                    toast_overlay_clone.add_toast(adw::Toast::new(&format!("Failed to save preferences: {}", e)));
+                }
+                process_list_clone.set_search_scope(search_scope(&settings));
+            });
+            window.add_action(&action);
+        }
+
+        // Separate the desktop session from system services and other users
+        let show_processes = state.settings.borrow().show_processes;
+        process_list.set_origin_filter(show_processes);
+        let show_action = gio::SimpleAction::new_stateful(
+            "show-processes",
+            Some(glib::VariantTy::STRING),
+            &show_processes.map_or("all", |origin| origin.key()).to_variant(),
+        );
+        let process_list_clone = process_list.clone();
+        let state_clone = state.clone();
+        let toast_overlay_clone = toast_overlay.clone();
+        show_action.connect_change_state(move |action, value| {
+            let Some(key) = value.and_then(|v| v.get::<String>()) else {
+                return;
+            };
+            action.set_state(&key.to_variant());
+            let origin = ProcessOrigin::from_key(&key);
+            let mut settings = state_clone.settings.borrow_mut();
+            settings.show_processes = origin;
+            if let Err(e) = settings.save() {
+                toast_overlay_clone.add_toast(adw::Toast::new(&format!("Failed to save preferences: {}", e)));
+            }
+            process_list_clone.set_origin_filter(origin);
+        });
+        window.add_action(&show_action);
+
+        // Filter by scheduler state, e.g. to find processes stuck in uninterruptible sleep
+        let state_action = gio::SimpleAction::new_stateful(
+            "show-state",
+            Some(glib::VariantTy::STRING),
+            &"any".to_variant(),
+        );
+        let process_list_clone = process_list.clone();
+        state_action.connect_change_state(move |action, value| {
+            let Some(key) = value.and_then(|v| v.get::<String>()) else {
+                return;
+            };
+            action.set_state(&key.to_variant());
+            process_list_clone.set_state_filter(ProcessState::from_key(&key));
+        });
+        window.add_action(&state_action);
+
+        let group_by_origin = state.settings.borrow().group_by_origin;
+        process_list.set_group_by_origin(group_by_origin);
+        let group_action = gio::SimpleAction::new_stateful("group-by-origin", None, &group_by_origin.to_variant());
+        let process_list_clone = process_list.clone();
+        let state_clone = state.clone();
+        let toast_overlay_clone = toast_overlay.clone();
+        group_action.connect_change_state(move |action, value| {
+            let Some(group) = value.and_then(|v| v.get::<bool>()) else {
+                return;
+            };
+            action.set_state(&group.to_variant());
+            let mut settings = state_clone.settings.borrow_mut();
+            settings.group_by_origin = group;
+            if let Err(e) = settings.save() {
+                toast_overlay_clone.add_toast(adw::Toast::new(&format!("Failed to save preferences: {}", e)));
+            }
+            process_list_clone.set_group_by_origin(group);
+        });
+        window.add_action(&group_action);
+
+        // Connect selection change to track the selected processes; their history is kept
+        // even if they drop out of the top list
+        let selected_keys_clone = selected_keys.clone();
+        let monitor_clone = monitor.clone();
+        let process_list_clone = process_list.clone();
         let updating_flag = process_list.updating.clone();
-        process_list.selection_model().connect_selection_changed(move |selection, _, _| {
+        process_list.selection_model().connect_selection_changed(move |_, _, _| {
             // Skip if we're in the middle of a programmatic update
             if *updating_flag.borrow() {
                 return;
             }
 
-            if let Some(obj) = selection.selected_item() {
-                if let Some(proc_obj) = obj.downcast_ref::<ProcessObject>() {
-                    *selected_pid_clone.borrow_mut() = Some(proc_obj.pid());
+            let keys = process_list_clone.selected_keys();
+            let previous = selected_keys_clone.replace(keys.clone());
+            let mut mon = monitor_clone.borrow_mut();
+            for key in previous.iter().filter(|key| !keys.contains(key)) {
+                mon.untrack(*key);
+            }
+            for key in keys.iter().filter(|key| !previous.contains(key)) {
+                mon.track(*key);
+            }
+        });
+
+        // Toggle between % of the whole machine and % of one core (top's Irix mode)
+        process_list.set_cpu_scale(state.cpu_scale());
+        let cpu_mode_action = gio::SimpleAction::new_stateful(
+            "cpu-per-core",
+            None,
+            &state.settings.borrow().cpu_per_core.to_variant(),
+        );
+        let process_list_clone = process_list.clone();
+        let state_clone = state.clone();
+        let toast_overlay_clone = toast_overlay.clone();
+        cpu_mode_action.connect_change_state(move |action, value| {
+            let Some(per_core) = value.and_then(|v| v.get::<bool>()) else {
+                return;
+            };
+            action.set_state(&per_core.to_variant());
+            {
+                let mut settings = state_clone.settings.borrow_mut();
+                settings.cpu_per_core = per_core;
+                if let Err(e) = settings.save() {
+                    toast_overlay_clone.add_toast(adw::Toast::new(&format!("Failed to save preferences: {}", e)));
                 }
-            } else {
-                *selected_pid_clone.borrow_mut() = None;
+            }
+            process_list_clone.set_cpu_scale(state_clone.cpu_scale());
+            let monitor = state_clone.monitor.borrow();
+            process_list_clone.update(monitor.processes(), &monitor);
+        });
+        window.add_action(&cpu_mode_action);
+        app.set_accels_for_action("win.cpu-per-core", &["<Ctrl>i"]);
+
+        // GtkApplicationWindow adds the win.show-help-overlay action for the shortcuts window
+        window.set_help_overlay(Some(&shortcuts::shortcuts_window()));
+        app.set_accels_for_action("win.show-help-overlay", &["<Ctrl>question"]);
+
+        // F5 ends the wait for the next refresh early
+        let refresh_now: Rc<RefCell<Option<oneshot::Sender<()>>>> = Rc::default();
+        let refresh_action = gio::SimpleAction::new("refresh", None);
+        let refresh_now_clone = refresh_now.clone();
+        refresh_action.connect_activate(move |_, _| {
+            if let Some(wake) = refresh_now_clone.take() {
+                let _ = wake.send(());
             }
         });
+        window.add_action(&refresh_action);
+        app.set_accels_for_action("win.refresh", &["F5"]);
+
+        // Top bar companion window, fed by the refresh below
+        let top_bar: Rc<RefCell<Option<TopBar>>> = Rc::default();
+        let top_bar_action = gio::SimpleAction::new_stateful("top-bar", None, &false.to_variant());
+        let top_bar_clone = top_bar.clone();
+        let app_clone = app.clone();
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        let toast_overlay_clone = toast_overlay.clone();
+        top_bar_action.connect_change_state(move |action, value| {
+            let Some(show) = value.and_then(|v| v.get::<bool>()) else {
+                return;
+            };
+            action.set_state(&show.to_variant());
+            if !show {
+                if let Some(bar) = top_bar_clone.borrow_mut().take() {
+                    bar.window.destroy();
+                }
+            } else if top_bar_clone.borrow().is_none() {
+                let Some(window) = window_weak.upgrade() else {
+                    return;
+                };
+                let bar = TopBar::new(&app_clone, &window, state_clone.settings.borrow().top_bar_edge);
+                // Closing the bar itself turns the action off
+                let holder_weak = Rc::downgrade(&top_bar_clone);
+                let action_weak = action.downgrade();
+                bar.window.connect_close_request(move |_| {
+                    if let Some(holder) = holder_weak.upgrade() {
+                        holder.borrow_mut().take();
+                    }
+                    if let Some(action) = action_weak.upgrade() {
+                        action.change_state(&false.to_variant());
+                    }
+                    glib::Propagation::Proceed
+                });
+                bar.window.present();
+                *top_bar_clone.borrow_mut() = Some(bar);
+            }
+            let mut settings = state_clone.settings.borrow_mut();
+            if settings.top_bar != show {
+                settings.top_bar = show;
+                if let Err(e) = settings.save() {
+                    toast_overlay_clone.add_toast(adw::Toast::new(&format!("Failed to save preferences: {}", e)));
+                }
+            }
+        });
+        window.add_action(&top_bar_action);
+        if state.settings.borrow().top_bar {
+            top_bar_action.change_state(&true.to_variant());
+        }
+
+        // Mini window, also fed by the refresh below
+        let mini_window: Rc<RefCell<Option<MiniWindow>>> = Rc::default();
+        let mini_action = gio::SimpleAction::new_stateful("mini-window", None, &false.to_variant());
+        let mini_window_clone = mini_window.clone();
+        let app_clone = app.clone();
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        let toast_overlay_clone = toast_overlay.clone();
+        mini_action.connect_change_state(move |action, value| {
+            let Some(show) = value.and_then(|v| v.get::<bool>()) else {
+                return;
+            };
+            action.set_state(&show.to_variant());
+            if !show {
+                if let Some(mini) = mini_window_clone.borrow_mut().take() {
+                    mini.window.destroy();
+                }
+            } else if mini_window_clone.borrow().is_none() {
+                let Some(window) = window_weak.upgrade() else {
+                    return;
+                };
+                let mini = MiniWindow::new(&app_clone, &window);
+                // Closing the mini window itself turns the action off
+                let holder_weak = Rc::downgrade(&mini_window_clone);
+                let action_weak = action.downgrade();
+                mini.window.connect_close_request(move |_| {
+                    if let Some(holder) = holder_weak.upgrade() {
+                        holder.borrow_mut().take();
+                    }
+                    if let Some(action) = action_weak.upgrade() {
+                        action.change_state(&false.to_variant());
+                    }
+                    glib::Propagation::Proceed
+                });
+                mini.window.present();
+                *mini_window_clone.borrow_mut() = Some(mini);
+            }
+            let mut settings = state_clone.settings.borrow_mut();
+            if settings.mini_window != show {
+                settings.mini_window = show;
+                if let Err(e) = settings.save() {
+                    toast_overlay_clone.add_toast(adw::Toast::new(&format!("Failed to save preferences: {}", e)));
+                }
+            }
+        });
+        window.add_action(&mini_action);
+        if state.settings.borrow().mini_window {
+            mini_action.change_state(&true.to_variant());
+        }
+
+        // Running in the background: closing hides the window and a status icon reopens it
+        let status_icon: Rc<RefCell<Option<StatusIcon>>> = Rc::default();
+        let background_action = gio::SimpleAction::new_stateful("run-in-background", None, &false.to_variant());
+        let status_icon_clone = status_icon.clone();
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        let toast_overlay_clone = toast_overlay.clone();
+        background_action.connect_change_state(move |action, value| {
+            let Some(background) = value.and_then(|v| v.get::<bool>()) else {
+                return;
+            };
+            action.set_state(&background.to_variant());
+            if !background {
+                status_icon_clone.borrow_mut().take();
+            } else if status_icon_clone.borrow().is_none() {
+                if let Some(window) = window_weak.upgrade() {
+                    *status_icon_clone.borrow_mut() = StatusIcon::register(&window);
+                }
+            }
+            let mut settings = state_clone.settings.borrow_mut();
+            if settings.run_in_background != background {
+                settings.run_in_background = background;
+                if let Err(e) = settings.save() {
+                    toast_overlay_clone.add_toast(adw::Toast::new(&format!("Failed to save preferences: {}", e)));
+                }
+            }
+        });
+        window.add_action(&background_action);
+        if state.settings.borrow().run_in_background {
+            background_action.change_state(&true.to_variant());
+        }
 
         // Initial data load
         {
             let mut mon = monitor.borrow_mut();
             let processes = mon.refresh();
-            process_list.update(&processes);
+            process_list.update(&processes, &mon);
         }
 
         // Set up periodic refresh using glib::timeout_add_local
         let process_list_clone = process_list.clone();
         let monitor_clone = monitor.clone();
-        let selected_pid_clone = selected_pid.clone();
+        let automation_clone = automation.clone();
+        let audit_log_clone = state.audit_log.clone();
+        let budgets_clone = budgets.clone();
+        let cpu_view_clone = cpu_view.clone();
+        let gpu_view_clone = gpu_view.clone();
+        let memory_view_clone = memory_view.clone();
+        let disks_view_clone = disks_view.clone();
+        let users_view_clone = users_view.clone();
+        let sensors_view_clone = sensors_view.clone();
+        let top_bar_clone = top_bar.clone();
+        let mini_window_clone = mini_window.clone();
+        let status_icon_clone = status_icon.clone();
+        let view_stack_clone = view_stack.clone();
+        let lockdown_clone = lockdown.clone();
+        let selected_keys_clone = selected_keys.clone();
+        let settings_clone = state.settings.clone();
+        let toast_overlay_clone = toast_overlay.clone();
+        let mut disk_detector = DiskFullDetector::new();
+        let mut ticks_until_disk_check = 0;
+        let mut vram_detector = VramFullDetector::new();
+        let mut vram_leak_detector = VramLeakDetector::new();
+        let mut swapping_detector = SwappingDetector::new(SWAP_ALERT_TICKS);
+        let mut overheat_detector = OverheatDetector::new();
+        let mut self_usage = SelfUsage::new();
+        let mut refresh_throttle = RefreshThrottle::new();
+        let mut ticks_until_refresh = 0;
+        let sample_interval_clone = state.sample_interval_secs.clone();
         let window_weak = window.downgrade();
 
-        let source_id = glib::timeout_add_local(Duration::from_millis(UPDATE_INTERVAL_MS), move || {
-            // Check if window still exists
-            if window_weak.upgrade().is_none() {
-                return ControlFlow::Break;
-            }
+        // The system is read on a worker thread so that thousands of processes do not stall the UI
+        let refresh_loop = glib::spawn_future_local(async move {
+            loop {
+                let (wake, woken) = oneshot::channel();
+                refresh_now.replace(Some(wake));
+                let forced = matches!(
+                    glib::future_with_timeout(Duration::from_millis(UPDATE_INTERVAL_MS), woken).await,
+                    Ok(Ok(()))
+                );
+                // Check if window still exists
+                if window_weak.upgrade().is_none() {
+                    break;
+                }
 
-            // Refresh process data
-            let mut mon = monitor_clone.borrow_mut();
-            let processes = mon.refresh();
-            process_list_clone.update(&processes);
+                // The self-limit skips ticks to refresh less often, unless asked to refresh now
+                if !forced && ticks_until_refresh > 1 {
+                    ticks_until_refresh -= 1;
+                    continue;
+                }
+                let sample_interval = sample_interval_clone.get();
+
+                // Refresh process data
+                let pending = monitor_clone.borrow().start_refresh();
+                let Ok(sample) = pending.await else {
+                    continue;
+                };
+                let mut mon = monitor_clone.borrow_mut();
+                let processes = mon.finish_refresh(sample);
+                process_list_clone.update(&processes, &mon);
+                let hidden = mon.hidden_processes();
+                hidden_label.set_visible(hidden > 0);
+                hidden_label.set_text(&format!(
+                    "{} more process{} not listed",
+                    hidden,
+                    if hidden == 1 { "" } else { "es" }
+                ));
+
+                cpu_view_clone.update(&mon, sample_interval);
 
-            // Clear selected PID if process no longer exists
-            let current_pid = *selected_pid_clone.borrow();
-            if let Some(pid) = current_pid {
-                if !processes.iter().any(|p| p.pid == pid) {
-                    *selected_pid_clone.borrow_mut() = None;
+                // Memory page; scanning every process for swap use only while it is shown
+                let usage = read_memory_usage();
+                memory_view_clone.update(&usage, &mon, sample_interval);
+                if view_stack_clone.visible_child_name().as_deref() == Some("memory") {
+                    memory_view_clone.update_swap_users(&top_swap_users(SWAP_USERS_SHOWN));
                 }
-            }
 
-            ControlFlow::Continue
-        });
+                let cpu_scale = if settings_clone.borrow().cpu_per_core { mon.cpu_count() as f32 } else { 1.0 };
+                if let Some(bar) = top_bar_clone.borrow().as_ref() {
+                    bar.update(&mon, &usage, &processes, cpu_scale);
+                }
+                if let Some(mini) = mini_window_clone.borrow().as_ref() {
+                    mini.update(&mon, &usage, &processes, cpu_scale);
+                }
+                if let Some(icon) = status_icon_clone.borrow().as_ref() {
+                    icon.update(&mon, &usage);
+                }
+
+                // Warn once when the system keeps swapping
+                let swap_rate = mon.swap_in_history().back().copied().unwrap_or(0.0)
+                    + mon.swap_out_history().back().copied().unwrap_or(0.0);
+                if swapping_detector.check(swap_rate, SWAP_ALERT_BYTES_PER_SEC) {
+                    let toast = adw::Toast::builder()
+                        .title(format!(
+                            "The system is swapping {}/s, {} of swap in use",
+                            format_bytes(swap_rate as u64),
+                            format_bytes(usage.swap_used)
+                        ))
+                        .button_label("Show")
+                        .action_name("win.show-memory")
+                        .timeout(0)
+                        .build();
+                    toast_overlay_clone.add_toast(toast);
+                    if let Some(service) = &dbus_service {
+                        service.emit_threshold_alert("swapping", "swap", swap_rate);
+                    }
+                }
+
+                disks_view_clone.update(&mon, sample_interval);
+                users_view_clone.update(mon.users(), cpu_scale);
+
+                // Temperatures; warn once when a sensor reaches its threshold
+                sensors_view_clone.update(&mon, sample_interval);
+                if settings_clone.borrow().temperature_alerts {
+                    let thresholds = sensor_thresholds.borrow();
+                    for sensor in overheat_detector.check(mon.sensors(), |s| thresholds.warning(s)) {
+                        let toast = adw::Toast::builder()
+                            .title(format!("{} “{}” is at {:.0} °C", sensor.kind.as_str(), sensor.label, sensor.celsius))
+                            .button_label("Show")
+                            .action_name("win.show-sensors")
+                            .timeout(0)
+                            .build();
+                        toast_overlay_clone.add_toast(toast);
+                        if let Some(service) = &dbus_service {
+                            service.emit_threshold_alert("overheating", &sensor.label, sensor.celsius);
+                        }
+                    }
+                }
+
+                // Refresh the GPU page and warn once when a GPU is nearly out of video memory
+                if let Some(gpu_view) = &gpu_view_clone {
+                    let gpus = mon.gpu_memory();
+                    let gpu_processes = mon.gpu_processes();
+                    gpu_view.update(&gpus, &gpu_processes);
+                    for gpu in vram_detector.check(&gpus, VRAM_ALERT_PERCENT) {
+                        let toast = adw::Toast::builder()
+                            .title(format!("“{}” video memory is {:.0}% full", gpu.name, gpu.used_percent()))
+                            .button_label("Free VRAM")
+                            .action_name("win.show-gpu")
+                            .timeout(0)
+                            .build();
+                        toast_overlay_clone.add_toast(toast);
+                        if let Some(service) = &dbus_service {
+                            service.emit_threshold_alert("vram-full", &gpu.name, gpu.used_percent());
+                        }
+                    }
+
+                    // Warn once per process whose video memory keeps growing or passes the limit
+                    let settings = settings_clone.borrow();
+                    let limit = settings.vram_process_limit.then_some(settings.vram_process_limit_percent as f64);
+                    let alerts = vram_leak_detector.check(&gpu_processes, &gpus, limit, Instant::now());
+                    for alert in alerts {
+                        let (title, kind, value) = match alert.kind {
+                            VramAlertKind::Growing { grown_bytes, duration } => {
+                                if !settings.vram_leak_alerts {
+                                    continue;
+                                }
+                                (
+                                    format!(
+                                        "“{}” video memory grew by {} in {} and is still growing",
+                                        alert.process.name,
+                                        format_bytes(grown_bytes),
+                                        format_duration(duration)
+                                    ),
+                                    "vram-leak",
+                                    grown_bytes as f64,
+                                )
+                            }
+                            VramAlertKind::AboveLimit { percent } => (
+                                format!(
+                                    "“{}” uses {} ({:.0}%) of “{}” video memory",
+                                    alert.process.name,
+                                    format_bytes(alert.process.vram_bytes),
+                                    percent,
+                                    alert.gpu_name
+                                ),
+                                "vram-process",
+                                percent,
+                            ),
+                        };
+                        let toast = adw::Toast::builder()
+                            .title(title)
+                            .button_label("Show")
+                            .action_name("win.open-process")
+                            .action_target(&(alert.process.pid, alert.process.name.clone()).to_variant())
+                            .timeout(0)
+                            .build();
+                        toast_overlay_clone.add_toast(toast);
+                        if let Some(service) = &dbus_service {
+                            service.emit_threshold_alert(kind, &alert.process.name, value);
+                        }
+                    }
+                }
+
+                // Apply automation rules (they change priorities, so not in read-only mode)
+                if !lockdown_clone.is_read_only() {
+                    automation_clone.borrow_mut().evaluate(&processes, &mut audit_log_clone.borrow_mut());
+                }
+
+                // Warn once a day per process that goes over a disk or network budget
+                for alert in budgets_clone.borrow_mut().check(&mon.process_totals()) {
+                    let toast = adw::Toast::builder()
+                        .title(format!(
+                            "“{}” {} {} today",
+                            alert.name,
+                            alert.budget.metric.verb(),
+                            format_bytes(alert.used)
+                        ))
+                        .button_label("Show")
+                        .action_name("win.open-process")
+                        .action_target(&(alert.pid, alert.name.clone()).to_variant())
+                        .timeout(0)
+                        .build();
+                    toast_overlay_clone.add_toast(toast);
+                    if let Some(service) = &dbus_service {
+                        service.emit_threshold_alert("io-budget", &alert.name, alert.used as f64);
+                    }
+                }
+
+                // Drop selected processes that no longer exist (a reused PID is a new process)
+                selected_keys_clone.borrow_mut().retain(|key| {
+                    let exists = processes.iter().any(|p| p.key() == *key);
+                    if !exists {
+                        mon.untrack(*key);
+                    }
+                    exists
+                });
+
+                // Warn once when a filesystem crosses the nearly full threshold
+                if ticks_until_disk_check == 0 {
+                    ticks_until_disk_check = DISK_CHECK_TICKS;
+                    let settings = settings_clone.borrow();
+                    if settings.disk_alerts {
+                        let filesystems = read_filesystems();
+                        for fs in disk_detector.check(&filesystems, settings.disk_alert_percent as f64) {
+                            let toast = adw::Toast::builder()
+                                .title(format!("“{}” is {:.0}% full", fs.mount_point, fs.used_percent()))
+                                .button_label("Details")
+                                .action_name("win.filesystem-details")
+                                .action_target(&fs.mount_point.to_variant())
+                                .timeout(0)
+                                .build();
+                            toast_overlay_clone.add_toast(toast);
+                            if let Some(service) = &dbus_service {
+                                service.emit_threshold_alert("disk-full", &fs.mount_point, fs.used_percent());
+                            }
+                        }
+                    }
+                }
+                ticks_until_disk_check -= 1;
+
+                // Show Procular's own use and refresh less often while it is above the limit
+                let own = self_usage.sample();
+                let (self_limit, limit_percent) = {
+                    let settings = settings_clone.borrow();
+                    (settings.self_limit, settings.self_limit_percent as f64)
+                };
+                if let Some(cpu_percent) = own.cpu_percent {
+                    if self_limit {
+                        refresh_throttle.update(cpu_percent, limit_percent);
+                    } else {
+                        refresh_throttle.reset();
+                    }
+                    let slowdown = refresh_throttle.slowdown();
+                    ticks_until_refresh = slowdown;
+                    sample_interval_clone.set(UPDATE_INTERVAL_MS / 1000 * slowdown as u64);
 
-        // Store source ID for cleanup
-        let source_id = Rc::new(RefCell::new(Some(source_id)));
+                    let mut text = format!("Procular: {:.1}% of a core · {}", cpu_percent, format_bytes(own.memory_bytes));
+                    if slowdown > 1 {
+                        text.push_str(&format!(" · refreshing every {} s", sample_interval_clone.get()));
+                    }
+                    self_usage_label.set_text(&text);
+                    self_usage_label.set_tooltip_text(Some(if slowdown > 1 {
+                        "Procular refreshes less often because its own CPU use is above the limit set in Preferences"
+                    } else {
+                        "CPU and memory used by Procular itself"
+                    }));
+                }
+            }
+        });
 
-        // Clean up timeout on window close
-        let source_id_clone = source_id.clone();
-        window.connect_close_request(move |_| {
-            if let Some(id) = source_id_clone.borrow_mut().take() {
-                id.remove();
+        // Stop refreshing and remember the column layout on window close; when running in the
+        // background only hide the window, so monitoring and automation rules carry on
+        let process_list_clone = process_list.clone();
+        window.connect_close_request(move |window| {
+            if let Err(e) = process_list_clone.save_columns() {
+                eprintln!("Failed to save column layout: {}", e);
+            }
+            if status_icon.borrow().is_some() {
+                window.set_visible(false);
+                return glib::Propagation::Stop;
+            }
+            refresh_loop.abort();
+            // Leave the settings alone so the bar and mini window come back with the next window
+            if let Some(bar) = top_bar.borrow_mut().take() {
+                bar.window.destroy();
+            }
+            if let Some(mini) = mini_window.borrow_mut().take() {
+                mini.window.destroy();
             }
             glib::Propagation::Proceed
         });
@@ -159,7 +1230,7 @@ impl ProcularWindow {
         window
     }
 
-    fn create_header_bar() -> (adw::HeaderBar, SearchEntry) {
+    fn create_header_bar() -> (adw::HeaderBar, SearchEntry, gtk4::Button) {
         let header = adw::HeaderBar::new();
 
         // Search entry
@@ -168,6 +1239,75 @@ impl ProcularWindow {
         search_entry.set_width_chars(30);
         header.pack_start(&search_entry);
 
-        (header, search_entry)
+        let search_menu = gio::Menu::new();
+        search_menu.append(Some("Match Command Lines"), Some("win.search-command-line"));
+        search_menu.append(Some("Match Environment Variables (Slower)"), Some("win.search-environment"));
+        let search_menu_btn = gtk4::MenuButton::builder()
+            .icon_name("system-search-symbolic")
+            .menu_model(&search_menu)
+            .tooltip_text("Search Options")
+            .build();
+        header.pack_start(&search_menu_btn);
+
+        // Main menu
+        let menu = gio::Menu::new();
+        let view_section = gio::Menu::new();
+        view_section.append(Some("CPU % Per Core"), Some("win.cpu-per-core"));
+        view_section.append(Some("Top Bar"), Some("win.top-bar"));
+        view_section.append(Some("Mini Window"), Some("win.mini-window"));
+        view_section.append(Some("Run in Background"), Some("win.run-in-background"));
+        let show_menu = gio::Menu::new();
+        show_menu.append(Some("All Processes"), Some("win.show-processes::all"));
+        for origin in ProcessOrigin::all() {
+            show_menu.append(Some(origin.as_str()), Some(&format!("win.show-processes::{}", origin.key())));
+        }
+        let group_section = gio::Menu::new();
+        group_section.append(Some("Group by Origin"), Some("win.group-by-origin"));
+        show_menu.append_section(None, &group_section);
+        view_section.append_submenu(Some("Show Processes"), &show_menu);
+        let state_menu = gio::Menu::new();
+        state_menu.append(Some("Any State"), Some("win.show-state::any"));
+        for process_state in ProcessState::all() {
+            state_menu.append(
+                Some(&format!("{} ({})", process_state.as_str(), process_state.letter())),
+                Some(&format!("win.show-state::{}", process_state.key())),
+            );
+        }
+        view_section.append_submenu(Some("Filter by State"), &state_menu);
+        menu.append_section(None, &view_section);
+        let export_section = gio::Menu::new();
+        export_section.append(Some("Copy Process List as Text"), Some("win.copy-process-list"));
+        export_section.append(Some("Export Process List…"), Some("win.export-process-list"));
+        export_section.append(Some("Export All Data…"), Some("win.export-all-data"));
+        export_section.append(Some("Take Snapshot"), Some("win.take-snapshot"));
+        export_section.append(Some("Compare with Snapshot…"), Some("win.compare-snapshot"));
+        menu.append_section(None, &export_section);
+        let tools_section = gio::Menu::new();
+        tools_section.append(Some("Why Is My System Slow?"), Some("win.diagnose"));
+        tools_section.append(Some("Top Offenders"), Some("win.top-offenders"));
+        tools_section.append(Some("Startup Impact"), Some("win.startup-impact"));
+        tools_section.append(Some("Filesystems"), Some("win.filesystems"));
+        tools_section.append(Some("Automation Rules"), Some("win.automation-rules"));
+        tools_section.append(Some("I/O Budgets"), Some("win.io-budgets"));
+        tools_section.append(Some("Audit Log"), Some("win.audit-log"));
+        menu.append_section(None, &tools_section);
+        let app_section = gio::Menu::new();
+        app_section.append(Some("Preferences"), Some("win.preferences"));
+        app_section.append(Some("Keyboard Shortcuts"), Some("win.show-help-overlay"));
+        app_section.append(Some("Setup Assistant"), Some("win.setup-assistant"));
+        menu.append_section(None, &app_section);
+        let menu_btn = gtk4::MenuButton::builder()
+            .icon_name("open-menu-symbolic")
+            .menu_model(&menu)
+            .tooltip_text("Main Menu")
+            .build();
+        header.pack_end(&menu_btn);
+
+        // Probably idle processes
+        let idle_btn = gtk4::Button::from_icon_name("weather-clear-night-symbolic");
+        idle_btn.set_tooltip_text(Some("Probably Idle Processes"));
+        header.pack_end(&idle_btn);
+
+        (header, search_entry, idle_btn)
     }
 }
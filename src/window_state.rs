@@ -0,0 +1,59 @@
+//! Window sizes and view choices remembered between sessions, stored in
+//! ~/.config/procular/window-state.ini
+//!
+//! Column layout and sorting live with the process list in columns.ini.
+
+use gtk4::prelude::*;
+use std::io;
+
+use crate::config;
+
+const STATE_FILE: &str = "window-state.ini";
+pub const MAIN_WINDOW_GROUP: &str = "Main Window";
+pub const PROCESS_WINDOW_GROUP: &str = "Process Window";
+
+/// Give `window` the size it had when a window of its kind was last closed, and save its size
+/// whenever it closes
+pub fn remember_size(window: &impl IsA<gtk4::Window>, group: &'static str) {
+    let window = window.as_ref();
+    let state = config::load_key_file(STATE_FILE);
+    if let (Ok(width), Ok(height)) = (state.integer(group, "width"), state.integer(group, "height")) {
+        if width > 0 && height > 0 {
+            window.set_default_size(width, height);
+        }
+    }
+    if state.boolean(group, "maximized").unwrap_or(false) {
+        window.maximize();
+    }
+
+    window.connect_close_request(move |window| {
+        if let Err(e) = save_size(window, group) {
+            eprintln!("Failed to save window size: {}", e);
+        }
+        glib::Propagation::Proceed
+    });
+}
+
+/// GTK keeps the default size at the last unmaximized size, so it is what to restore
+fn save_size(window: &gtk4::Window, group: &str) -> io::Result<()> {
+    let state = config::load_key_file(STATE_FILE);
+    let (width, height) = window.default_size();
+    state.set_integer(group, "width", width);
+    state.set_integer(group, "height", height);
+    state.set_boolean(group, "maximized", window.is_maximized());
+    config::save_key_file(STATE_FILE, &state)
+}
+
+/// Position of the process window's history length last picked
+pub fn history_choice() -> Option<u32> {
+    config::load_key_file(STATE_FILE)
+        .integer(PROCESS_WINDOW_GROUP, "history")
+        .ok()
+        .and_then(|choice| u32::try_from(choice).ok())
+}
+
+pub fn save_history_choice(choice: u32) -> io::Result<()> {
+    let state = config::load_key_file(STATE_FILE);
+    state.set_integer(PROCESS_WINDOW_GROUP, "history", choice as i32);
+    config::save_key_file(STATE_FILE, &state)
+}
@@ -6,6 +6,7 @@ BINDIR="${PREFIX}/bin"
 ICONDIR="${PREFIX}/share/icons/hicolor/256x256/apps"
 DESKTOPDIR="${PREFIX}/share/applications"
 PIXMAPDIR="${PREFIX}/share/pixmaps"
+POLKITDIR="/usr/share/polkit-1/actions"
 
 echo "Uninstalling Procular..."
 
@@ -42,6 +43,11 @@ if [ -f "$DESKTOPDIR/procular.desktop" ]; then
     echo "Removed $DESKTOPDIR/procular.desktop"
 fi
 
+if [ "$EUID" -eq 0 ] && [ -f "$POLKITDIR/org.procular.ProcessMonitor.policy" ]; then
+    rm -f "$POLKITDIR/org.procular.ProcessMonitor.policy"
+    echo "Removed $POLKITDIR/org.procular.ProcessMonitor.policy"
+fi
+
 # Update icon cache if available
 if command -v gtk-update-icon-cache &> /dev/null; then
     gtk-update-icon-cache -f -t "${PREFIX}/share/icons/hicolor" 2>/dev/null || true